@@ -104,7 +104,12 @@ pub unsafe extern "C" fn icl_verify(text: *const c_char) -> IclResult {
         Err(e) => return IclResult::err(e.to_string()),
     };
 
-    let result = icl_core::verifier::verify(&ast);
+    // Honour the contract's icl-allow comments
+    let options = icl_core::verifier::VerifyOptions {
+        suppressions: icl_core::lint::Suppressions::from_source(text),
+        ..Default::default()
+    };
+    let result = icl_core::verifier::verify_with_options(&ast, &options);
 
     let errors: Vec<serde_json::Value> = result
         .errors()
@@ -113,6 +118,8 @@ pub unsafe extern "C" fn icl_verify(text: *const c_char) -> IclResult {
             serde_json::json!({
                 "severity": "error",
                 "kind": d.kind.to_string(),
+                "code": d.rule.code,
+                "rule": d.rule.name,
                 "message": d.message,
             })
         })
@@ -125,6 +132,8 @@ pub unsafe extern "C" fn icl_verify(text: *const c_char) -> IclResult {
             serde_json::json!({
                 "severity": "warning",
                 "kind": d.kind.to_string(),
+                "code": d.rule.code,
+                "rule": d.rule.name,
                 "message": d.message,
             })
         })
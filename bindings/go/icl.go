@@ -41,7 +41,8 @@ func handleResult(r C.struct_IclResult) (string, error) {
 
 // ParseContract parses ICL contract text and returns a JSON string of the parsed Contract.
 //
-// Returns an error if the contract text has syntax or semantic errors.
+// Returns an error if the contract text has syntax or semantic errors. Its
+// message carries the stable diagnostic code, e.g. "Parse error: [ICL-P005] ...".
 func ParseContract(text string) (string, error) {
 	cText := C.CString(text)
 	defer C.free(unsafe.Pointer(cText))
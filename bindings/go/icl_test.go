@@ -12,7 +12,7 @@ const helloWorld = `Contract {
     version: 1,
     created_timestamp: 2026-02-08T00:00:00Z,
     owner: "test",
-    semantic_hash: "abc123"
+    semantic_hash: "f62f855a71739b383631e35f72d34b7b9152d39acf4fc39c120175359a800043"
   }
 
   PurposeStatement {
@@ -87,6 +87,9 @@ func TestParseContractInvalid(t *testing.T) {
 	if err == nil {
 		t.Fatal("Expected error for invalid contract")
 	}
+	if !strings.Contains(err.Error(), "[ICL-P005]") {
+		t.Errorf("Expected the error to carry code ICL-P005, got %q", err)
+	}
 }
 
 func TestParseContractEmpty(t *testing.T) {
@@ -3,16 +3,20 @@
  *
  * All functions are thin wrappers around the canonical Rust implementation
  * compiled to WebAssembly. Deterministic: same input always produces identical output.
+ *
+ * Contracts that `import` modules take the module sources as `modules`, a JSON
+ * object keyed by import path: `JSON.stringify({ "common/types.icl": "Types { ... }" })`.
  */
 
 /**
  * Parse ICL contract text and return a JSON string of the parsed Contract.
  *
  * @param text - ICL contract source text
+ * @param modules - Optional JSON object of imported module sources, keyed by import path
  * @returns JSON string representation of the parsed Contract
  * @throws Error if the contract text has syntax or semantic errors
  */
-export function parseContract(text: string): string;
+export function parseContract(text: string, modules?: string): string;
 
 /**
  * Normalize ICL contract text to canonical form.
@@ -20,10 +24,11 @@ export function parseContract(text: string): string;
  * Deterministic and idempotent: normalize(normalize(x)) === normalize(x)
  *
  * @param text - ICL contract source text
+ * @param modules - Optional JSON object of imported module sources, keyed by import path
  * @returns Canonical normalized ICL text
  * @throws Error if the contract text cannot be parsed
  */
-export function normalize(text: string): string;
+export function normalize(text: string, modules?: string): string;
 
 /**
  * Verify an ICL contract for correctness.
@@ -31,26 +36,29 @@ export function normalize(text: string): string;
  * Returns JSON with verification result including errors and warnings.
  *
  * @param text - ICL contract source text
+ * @param modules - Optional JSON object of imported module sources, keyed by import path
  * @returns JSON string: { valid: boolean, errors: Array, warnings: Array }
  * @throws Error if the contract text cannot be parsed
  */
-export function verify(text: string): string;
+export function verify(text: string, modules?: string): string;
 
 /**
  * Execute an ICL contract with the given inputs.
  *
  * @param text - ICL contract source text
  * @param inputs - JSON string with execution inputs
+ * @param modules - Optional JSON object of imported module sources, keyed by import path
  * @returns JSON string with execution result including provenance log
  * @throws Error if the contract cannot be parsed, verified, or executed
  */
-export function execute(text: string, inputs: string): string;
+export function execute(text: string, inputs: string, modules?: string): string;
 
 /**
  * Compute the SHA-256 semantic hash of a contract.
  *
  * @param text - ICL contract source text
+ * @param modules - Optional JSON object of imported module sources, keyed by import path
  * @returns Hex-encoded SHA-256 hash string
  * @throws Error if the contract text cannot be parsed
  */
-export function semanticHash(text: string): string;
+export function semanticHash(text: string, modules?: string): string;
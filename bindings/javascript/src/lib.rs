@@ -5,14 +5,29 @@
 
 use wasm_bindgen::prelude::*;
 
+/// Parse contract text and merge the modules it imports. `modules` is a
+/// JSON object mapping import paths to module sources. A failure's message
+/// carries the stable diagnostic code, e.g. `Parse error: [ICL-P005] ...`.
+fn load(text: &str, modules: Option<String>) -> Result<icl_core::ContractNode, JsError> {
+    let modules: std::collections::BTreeMap<String, String> = match modules {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| JsError::new(&format!("Invalid modules JSON: {}", e)))?,
+        None => Default::default(),
+    };
+    let loader: icl_core::loader::MemoryLoader = modules.into_iter().collect();
+    icl_core::loader::load_source("", text, &loader)
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
 /// Parse ICL contract text and return a JSON string of the parsed Contract.
 ///
 /// @param text - ICL contract source text
+/// @param modules - Optional JSON object of imported module sources, keyed by import path
 /// @returns JSON string representation of the parsed Contract
-/// @throws Error if the contract text has syntax or semantic errors
+/// @throws Error if the contract text has syntax or semantic errors, or an import cannot be resolved
 #[wasm_bindgen(js_name = "parseContract")]
-pub fn parse_contract(text: &str) -> Result<String, JsError> {
-    let contract = icl_core::parser::parse_contract(text)
+pub fn parse_contract(text: &str, modules: Option<String>) -> Result<String, JsError> {
+    let contract = icl_core::parser::lower_contract(&load(text, modules)?)
         .map_err(|e| JsError::new(&e.to_string()))?;
 
     serde_json::to_string_pretty(&contract)
@@ -27,12 +42,13 @@ pub fn parse_contract(text: &str) -> Result<String, JsError> {
 ///   - Semantic preserving: meaning is unchanged
 ///
 /// @param text - ICL contract source text
-/// @returns Canonical normalized ICL text
-/// @throws Error if the contract text cannot be parsed
+/// @param modules - Optional JSON object of imported module sources, keyed by import path
+/// @returns Canonical normalized ICL text, with imports merged
+/// @throws Error if the contract text cannot be parsed or an import cannot be resolved
 #[wasm_bindgen]
-pub fn normalize(text: &str) -> Result<String, JsError> {
-    icl_core::normalizer::normalize(text)
-        .map_err(|e| JsError::new(&e.to_string()))
+pub fn normalize(text: &str, modules: Option<String>) -> Result<String, JsError> {
+    let normalized = icl_core::normalizer::normalize_ast(load(text, modules)?);
+    Ok(icl_core::normalizer::serialize_canonical(&normalized))
 }
 
 /// Verify an ICL contract for correctness.
@@ -44,14 +60,19 @@ pub fn normalize(text: &str) -> Result<String, JsError> {
 ///   - Coherence verification
 ///
 /// @param text - ICL contract source text
+/// @param modules - Optional JSON object of imported module sources, keyed by import path
 /// @returns JSON string: { valid: boolean, errors: [...], warnings: [...] }
-/// @throws Error if the contract text cannot be parsed
+/// @throws Error if the contract text cannot be parsed or an import cannot be resolved
 #[wasm_bindgen]
-pub fn verify(text: &str) -> Result<String, JsError> {
-    let ast = icl_core::parser::parse(text)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+pub fn verify(text: &str, modules: Option<String>) -> Result<String, JsError> {
+    let ast = load(text, modules)?;
 
-    let result = icl_core::verifier::verify(&ast);
+    // Honour the contract's icl-allow comments
+    let options = icl_core::verifier::VerifyOptions {
+        suppressions: icl_core::lint::Suppressions::from_source(text),
+        ..Default::default()
+    };
+    let result = icl_core::verifier::verify_with_options(&ast, &options);
 
     let errors: Vec<serde_json::Value> = result
         .errors()
@@ -60,6 +81,8 @@ pub fn verify(text: &str) -> Result<String, JsError> {
             serde_json::json!({
                 "severity": "error",
                 "kind": d.kind.to_string(),
+                "code": d.rule.code,
+                "rule": d.rule.name,
                 "message": d.message,
             })
         })
@@ -72,6 +95,8 @@ pub fn verify(text: &str) -> Result<String, JsError> {
             serde_json::json!({
                 "severity": "warning",
                 "kind": d.kind.to_string(),
+                "code": d.rule.code,
+                "rule": d.rule.name,
                 "message": d.message,
             })
         })
@@ -91,11 +116,12 @@ pub fn verify(text: &str) -> Result<String, JsError> {
 ///
 /// @param text - ICL contract source text
 /// @param inputs - JSON string with execution inputs
+/// @param modules - Optional JSON object of imported module sources, keyed by import path
 /// @returns JSON string with execution result including provenance log
 /// @throws Error if the contract cannot be parsed, verified, or executed
 #[wasm_bindgen]
-pub fn execute(text: &str, inputs: &str) -> Result<String, JsError> {
-    let contract = icl_core::parser::parse_contract(text)
+pub fn execute(text: &str, inputs: &str, modules: Option<String>) -> Result<String, JsError> {
+    let contract = icl_core::parser::lower_contract(&load(text, modules)?)
         .map_err(|e| JsError::new(&format!("Parse error: {}", e)))?;
 
     icl_core::executor::execute_contract(&contract, inputs)
@@ -104,13 +130,15 @@ pub fn execute(text: &str, inputs: &str) -> Result<String, JsError> {
 
 /// Compute the SHA-256 semantic hash of a contract.
 ///
+/// Imported modules are part of the hash.
+///
 /// @param text - ICL contract source text
+/// @param modules - Optional JSON object of imported module sources, keyed by import path
 /// @returns Hex-encoded SHA-256 hash string
-/// @throws Error if the contract text cannot be parsed
+/// @throws Error if the contract text cannot be parsed or an import cannot be resolved
 #[wasm_bindgen(js_name = "semanticHash")]
-pub fn semantic_hash(text: &str) -> Result<String, JsError> {
-    let ast = icl_core::parser::parse(text)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+pub fn semantic_hash(text: &str, modules: Option<String>) -> Result<String, JsError> {
+    let ast = load(text, modules)?;
 
     let normalized = icl_core::normalizer::normalize_ast(ast);
     Ok(icl_core::normalizer::compute_semantic_hash(&normalized))
@@ -13,7 +13,7 @@ const HELLO_WORLD = `Contract {
     version: 1,
     created_timestamp: 2026-02-08T00:00:00Z,
     owner: "test",
-    semantic_hash: "abc123"
+    semantic_hash: "f62f855a71739b383631e35f72d34b7b9152d39acf4fc39c120175359a800043"
   }
 
   PurposeStatement {
@@ -77,14 +77,19 @@ function assert(condition, message) {
   }
 }
 
-function assertThrows(fn, message) {
+function assertThrows(fn, message, pattern) {
   try {
     fn();
     console.error(`  FAIL: ${message} (expected error, got none)`);
     failed++;
   } catch (e) {
-    console.log(`  PASS: ${message}`);
-    passed++;
+    if (pattern && !pattern.test(e.message)) {
+      console.error(`  FAIL: ${message} (error '${e.message}' does not match ${pattern})`);
+      failed++;
+    } else {
+      console.log(`  PASS: ${message}`);
+      passed++;
+    }
   }
 }
 
@@ -99,6 +104,7 @@ assert(parsed.behavioral_semantics.operations.length === 1, 'parse: operations c
 assert(parsed.behavioral_semantics.operations[0].name === 'greet', 'parse: operation name');
 assertThrows(() => parseContract('invalid'), 'parse: invalid input throws');
 assertThrows(() => parseContract(''), 'parse: empty input throws');
+assertThrows(() => parseContract('invalid'), 'parse: error carries its code', /^Parse error: \[ICL-P005\] /);
 
 // ── normalize tests ──────────────────────────────────────
 console.log('\n=== normalize ===');
@@ -161,6 +167,24 @@ for (let i = 0; i < 100; i++) {
 }
 assert(allSame, 'hash: deterministic (100 iterations)');
 
+// ── imports ──────────────────────────────────────────────
+console.log('\n=== imports ===');
+
+// The import changes the contract, and so its semantic hash
+const WITH_IMPORT = 'import "common/messages.icl"\n' +
+  HELLO_WORLD.replace('message: String = "hello"', 'message: Message = "hello"')
+    .replace('f62f855a71739b383631e35f72d34b7b9152d39acf4fc39c120175359a800043',
+      '4426520778633de2dedd143c634358a4b43b17a1d2ac7f1191a05fc905410a5f');
+const modules = JSON.stringify({
+  'common/messages.icl': 'Types { Message: String }\nInvariants ["message is not empty"]',
+});
+assert(JSON.parse(verify(WITH_IMPORT, modules)).valid === true, 'imports: verify with modules');
+assert(normalize(WITH_IMPORT, modules).includes('Message: String'), 'imports: types merged');
+assertThrows(() => verify(WITH_IMPORT), 'imports: missing module throws', /\[ICL-M001\] cannot read/);
+const changed = JSON.stringify({ 'common/messages.icl': 'Types { Message: String }' });
+assert(semanticHash(WITH_IMPORT, modules) !== semanticHash(WITH_IMPORT, changed),
+  'imports: hash covers modules');
+
 // ── Full pipeline ────────────────────────────────────────
 console.log('\n=== Full Pipeline ===');
 
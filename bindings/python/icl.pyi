@@ -3,13 +3,23 @@ ICL (Intent Contract Language) — Python bindings for the canonical Rust runtim
 
 All functions are thin wrappers around the Rust implementation.
 Deterministic: same input always produces identical output.
+
+Contracts that `import` modules take the module sources as `modules`, a
+dict keyed by import path.
+
+A ValueError raised for a contract that does not parse or whose imports
+cannot be resolved has a `code` attribute holding the stable diagnostic
+code (e.g. "ICL-P005"); the message starts with the same code in brackets.
 """
 
-def parse_contract(text: str) -> str:
+from typing import Mapping, Optional
+
+def parse_contract(text: str, modules: Optional[Mapping[str, str]] = None) -> str:
     """Parse ICL contract text and return a JSON string of the parsed Contract.
 
     Args:
         text: ICL contract source text
+        modules: Imported module sources, keyed by import path
 
     Returns:
         JSON string representation of the parsed Contract
@@ -19,13 +29,14 @@ def parse_contract(text: str) -> str:
     """
     ...
 
-def normalize(text: str) -> str:
+def normalize(text: str, modules: Optional[Mapping[str, str]] = None) -> str:
     """Normalize ICL contract text to canonical form.
 
     Deterministic and idempotent: normalize(normalize(x)) == normalize(x)
 
     Args:
         text: ICL contract source text
+        modules: Imported module sources, keyed by import path
 
     Returns:
         Canonical normalized ICL text
@@ -35,13 +46,14 @@ def normalize(text: str) -> str:
     """
     ...
 
-def verify(text: str) -> str:
+def verify(text: str, modules: Optional[Mapping[str, str]] = None) -> str:
     """Verify an ICL contract for correctness.
 
     Returns JSON with verification result including errors and warnings.
 
     Args:
         text: ICL contract source text
+        modules: Imported module sources, keyed by import path
 
     Returns:
         JSON string: {"valid": bool, "errors": [...], "warnings": [...]}
@@ -51,12 +63,15 @@ def verify(text: str) -> str:
     """
     ...
 
-def execute(text: str, inputs: str) -> str:
+def execute(
+    text: str, inputs: str, modules: Optional[Mapping[str, str]] = None
+) -> str:
     """Execute an ICL contract with the given inputs.
 
     Args:
         text: ICL contract source text
         inputs: JSON string with execution inputs
+        modules: Imported module sources, keyed by import path
 
     Returns:
         JSON string with execution result including provenance log
@@ -66,11 +81,12 @@ def execute(text: str, inputs: str) -> str:
     """
     ...
 
-def semantic_hash(text: str) -> str:
+def semantic_hash(text: str, modules: Optional[Mapping[str, str]] = None) -> str:
     """Compute the SHA-256 semantic hash of a contract.
 
     Args:
         text: ICL contract source text
+        modules: Imported module sources, keyed by import path
 
     Returns:
         Hex-encoded SHA-256 hash string
@@ -3,23 +3,45 @@
 //! Thin wrapper around `icl-core` — ZERO logic here.
 //! All behavior comes from the canonical Rust implementation.
 
+use std::collections::HashMap;
+
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+/// Parse contract text and merge the modules it imports from `modules`
+/// (import path → module source)
+fn load(text: &str, modules: Option<HashMap<String, String>>) -> PyResult<icl_core::ContractNode> {
+    let loader: icl_core::loader::MemoryLoader = modules.unwrap_or_default().into_iter().collect();
+    icl_core::loader::load_source("", text, &loader).map_err(load_error)
+}
+
+/// A ValueError carrying the message and, when the core error has one,
+/// its stable diagnostic code as a `code` attribute
+fn load_error(e: icl_core::Error) -> PyErr {
+    let err = PyValueError::new_err(e.to_string());
+    Python::with_gil(|py| match err.value(py).setattr("code", e.code()) {
+        Ok(()) => err,
+        Err(setattr_err) => setattr_err,
+    })
+}
+
 /// Parse ICL contract text and return a JSON string of the parsed Contract.
 ///
 /// Args:
 ///     text: ICL contract source text
+///     modules: Optional dict of imported module sources, keyed by import path
 ///
 /// Returns:
 ///     JSON string representation of the parsed Contract
 ///
 /// Raises:
-///     ValueError: If the contract text has syntax or semantic errors
+///     ValueError: If the contract text has syntax or semantic errors,
+///         or an import cannot be resolved. Its `code` attribute holds the
+///         stable diagnostic code (e.g. "ICL-P005"), or None.
 #[pyfunction]
-fn parse_contract(text: &str) -> PyResult<String> {
-    let contract =
-        icl_core::parser::parse_contract(text).map_err(|e| PyValueError::new_err(e.to_string()))?;
+#[pyo3(signature = (text, modules=None))]
+fn parse_contract(text: &str, modules: Option<HashMap<String, String>>) -> PyResult<String> {
+    let contract = icl_core::parser::lower_contract(&load(text, modules)?).map_err(load_error)?;
 
     serde_json::to_string_pretty(&contract)
         .map_err(|e| PyValueError::new_err(format!("Serialization error: {}", e)))
@@ -34,15 +56,19 @@ fn parse_contract(text: &str) -> PyResult<String> {
 ///
 /// Args:
 ///     text: ICL contract source text
+///     modules: Optional dict of imported module sources, keyed by import path
 ///
 /// Returns:
-///     Canonical normalized ICL text
+///     Canonical normalized ICL text, with imports merged
 ///
 /// Raises:
-///     ValueError: If the contract text cannot be parsed
+///     ValueError: If the contract text cannot be parsed or an import
+///         cannot be resolved
 #[pyfunction]
-fn normalize(text: &str) -> PyResult<String> {
-    icl_core::normalizer::normalize(text).map_err(|e| PyValueError::new_err(e.to_string()))
+#[pyo3(signature = (text, modules=None))]
+fn normalize(text: &str, modules: Option<HashMap<String, String>>) -> PyResult<String> {
+    let normalized = icl_core::normalizer::normalize_ast(load(text, modules)?);
+    Ok(icl_core::normalizer::serialize_canonical(&normalized))
 }
 
 /// Verify an ICL contract for correctness.
@@ -55,22 +81,32 @@ fn normalize(text: &str) -> PyResult<String> {
 ///
 /// Args:
 ///     text: ICL contract source text
+///     modules: Optional dict of imported module sources, keyed by import path
 ///
 /// Returns:
 ///     JSON string with verification result:
 ///     {
 ///         "valid": bool,
-///         "errors": [{"severity": "error", "kind": "...", "message": "..."}],
-///         "warnings": [{"severity": "warning", "kind": "...", "message": "..."}]
+///         "errors": [{"severity": "error", "kind": "...", "code": "ICL-...",
+///                     "rule": "...", "message": "..."}],
+///         "warnings": [{"severity": "warning", "kind": "...", "code": "ICL-...",
+///                       "rule": "...", "message": "..."}]
 ///     }
 ///
 /// Raises:
-///     ValueError: If the contract text cannot be parsed
+///     ValueError: If the contract text cannot be parsed or an import
+///         cannot be resolved
 #[pyfunction]
-fn verify(text: &str) -> PyResult<String> {
-    let ast = icl_core::parser::parse(text).map_err(|e| PyValueError::new_err(e.to_string()))?;
+#[pyo3(signature = (text, modules=None))]
+fn verify(text: &str, modules: Option<HashMap<String, String>>) -> PyResult<String> {
+    let ast = load(text, modules)?;
 
-    let result = icl_core::verifier::verify(&ast);
+    // Honour the contract's icl-allow comments
+    let options = icl_core::verifier::VerifyOptions {
+        suppressions: icl_core::lint::Suppressions::from_source(text),
+        ..Default::default()
+    };
+    let result = icl_core::verifier::verify_with_options(&ast, &options);
 
     // Convert to JSON-serializable structure
     let errors: Vec<serde_json::Value> = result
@@ -80,6 +116,8 @@ fn verify(text: &str) -> PyResult<String> {
             serde_json::json!({
                 "severity": "error",
                 "kind": d.kind.to_string(),
+                "code": d.rule.code,
+                "rule": d.rule.name,
                 "message": d.message,
             })
         })
@@ -92,6 +130,8 @@ fn verify(text: &str) -> PyResult<String> {
             serde_json::json!({
                 "severity": "warning",
                 "kind": d.kind.to_string(),
+                "code": d.rule.code,
+                "rule": d.rule.name,
                 "message": d.message,
             })
         })
@@ -120,6 +160,7 @@ fn verify(text: &str) -> PyResult<String> {
 ///     inputs: JSON string with execution inputs
 ///         Single request: {"operation": "name", "inputs": {...}}
 ///         Multiple: [{"operation": "name", "inputs": {...}}, ...]
+///     modules: Optional dict of imported module sources, keyed by import path
 ///
 /// Returns:
 ///     JSON string with execution result including provenance log
@@ -127,8 +168,9 @@ fn verify(text: &str) -> PyResult<String> {
 /// Raises:
 ///     ValueError: If the contract cannot be parsed, verified, or executed
 #[pyfunction]
-fn execute(text: &str, inputs: &str) -> PyResult<String> {
-    let contract = icl_core::parser::parse_contract(text)
+#[pyo3(signature = (text, inputs, modules=None))]
+fn execute(text: &str, inputs: &str, modules: Option<HashMap<String, String>>) -> PyResult<String> {
+    let contract = icl_core::parser::lower_contract(&load(text, modules)?)
         .map_err(|e| PyValueError::new_err(format!("Parse error: {}", e)))?;
 
     icl_core::executor::execute_contract(&contract, inputs)
@@ -140,17 +182,23 @@ fn execute(text: &str, inputs: &str) -> PyResult<String> {
 /// The hash is computed from the normalized (canonical) form,
 /// so semantically equivalent contracts produce the same hash.
 ///
+/// Imported modules are part of the hash: changing a module changes the
+/// hash of every contract importing it.
+///
 /// Args:
 ///     text: ICL contract source text
+///     modules: Optional dict of imported module sources, keyed by import path
 ///
 /// Returns:
 ///     Hex-encoded SHA-256 hash string
 ///
 /// Raises:
-///     ValueError: If the contract text cannot be parsed
+///     ValueError: If the contract text cannot be parsed or an import
+///         cannot be resolved
 #[pyfunction]
-fn semantic_hash(text: &str) -> PyResult<String> {
-    let ast = icl_core::parser::parse(text).map_err(|e| PyValueError::new_err(e.to_string()))?;
+#[pyo3(signature = (text, modules=None))]
+fn semantic_hash(text: &str, modules: Option<HashMap<String, String>>) -> PyResult<String> {
+    let ast = load(text, modules)?;
 
     let normalized = icl_core::normalizer::normalize_ast(ast);
     Ok(icl_core::normalizer::compute_semantic_hash(&normalized))
@@ -16,7 +16,7 @@ Contract {
     version: 1,
     created_timestamp: 2026-02-08T00:00:00Z,
     owner: "test",
-    semantic_hash: "abc123"
+    semantic_hash: "f62f855a71739b383631e35f72d34b7b9152d39acf4fc39c120175359a800043"
   }
 
   PurposeStatement {
@@ -97,6 +97,11 @@ class TestParseContract:
         with pytest.raises(ValueError):
             icl.parse_contract("")
 
+    def test_error_carries_code(self):
+        with pytest.raises(ValueError, match=r"\[ICL-P007\]") as excinfo:
+            icl.parse_contract(INVALID_CONTRACT)
+        assert excinfo.value.code == "ICL-P007"
+
 
 # ── normalize tests ──────────────────────────────────────
 
@@ -188,6 +193,48 @@ class TestSemanticHash:
             icl.semantic_hash("not valid icl")
 
 
+# ── imports ──────────────────────────────────────────────
+
+MODULES = {
+    "common/messages.icl": 'Types { Message: String }\nInvariants ["message is not empty"]',
+}
+
+# The import changes the contract, and so its semantic hash
+WITH_IMPORT = 'import "common/messages.icl"\n' + HELLO_WORLD.replace(
+    'message: String = "hello"', 'message: Message = "hello"'
+).replace(
+    "f62f855a71739b383631e35f72d34b7b9152d39acf4fc39c120175359a800043",
+    "4426520778633de2dedd143c634358a4b43b17a1d2ac7f1191a05fc905410a5f",
+)
+
+
+class TestImports:
+    def test_modules_resolve_imports(self):
+        result = json.loads(icl.verify(WITH_IMPORT, modules=MODULES))
+        assert result["valid"] is True
+        assert "Message: String" in icl.normalize(WITH_IMPORT, modules=MODULES)
+
+    def test_missing_module_raises(self):
+        with pytest.raises(ValueError, match="cannot read 'common/messages.icl'") as excinfo:
+            icl.verify(WITH_IMPORT)
+        assert excinfo.value.code == "ICL-M001"
+
+    def test_hash_covers_modules(self):
+        changed = {"common/messages.icl": "Types { Message: String }"}
+        assert icl.semantic_hash(WITH_IMPORT, modules=MODULES) != icl.semantic_hash(
+            WITH_IMPORT, modules=changed
+        )
+
+    def test_import_cycle_raises(self):
+        cycle = {
+            "common/messages.icl": 'import "other.icl"',
+            "common/other.icl": 'import "messages.icl"',
+        }
+        with pytest.raises(ValueError, match="import cycle") as excinfo:
+            icl.semantic_hash(WITH_IMPORT, modules=cycle)
+        assert excinfo.value.code == "ICL-M003"
+
+
 # ── Cross-binding consistency ────────────────────────────
 
 class TestConsistency:
@@ -1,6 +1,6 @@
 use clap::{Parser, Subcommand};
 use colored::Colorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 
 /// ICL — Intent Contract Language CLI
@@ -26,6 +26,9 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Reject conditions that are not machine-checkable
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Normalize a contract to canonical form
@@ -41,9 +44,12 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Reject conditions that are not machine-checkable
+        #[arg(long)]
+        strict: bool,
     },
 
-    /// Format a contract to standard style
+    /// Fix whitespace and indentation, keeping comments and field order
     Fmt {
         /// Path to .icl file
         file: PathBuf,
@@ -52,6 +58,15 @@ enum Commands {
         write: bool,
     },
 
+    /// Apply the safe fixes verification suggests, keeping formatting
+    Fix {
+        /// Path to .icl file
+        file: PathBuf,
+        /// Print the fixes without writing the file
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Compute semantic hash (SHA-256) of a contract
     Hash {
         /// Path to .icl file
@@ -82,6 +97,19 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Reject conditions that are not machine-checkable, and fail
+        /// operations whose conditions cannot be evaluated
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Explain a diagnostic code, or list every code
+    Explain {
+        /// Diagnostic code (e.g. ICL-C004) or rule name
+        code: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show version information
@@ -101,14 +129,21 @@ fn main() {
     let quiet = cli.quiet;
 
     let exit_code = match cli.command {
-        Commands::Validate { file, json } => cmd_validate(&file, json, quiet),
+        Commands::Validate { file, json, strict } => cmd_validate(&file, json, strict, quiet),
         Commands::Normalize { file } => cmd_normalize(&file, quiet),
-        Commands::Verify { file, json } => cmd_verify(&file, json, quiet),
+        Commands::Verify { file, json, strict } => cmd_verify(&file, json, strict, quiet),
         Commands::Fmt { file, write } => cmd_fmt(&file, write, quiet),
+        Commands::Fix { file, dry_run } => cmd_fix(&file, dry_run, quiet),
         Commands::Hash { file } => cmd_hash(&file, quiet),
         Commands::Diff { file_a, file_b } => cmd_diff(&file_a, &file_b, quiet),
         Commands::Init { name } => cmd_init(name.as_deref(), quiet),
-        Commands::Execute { file, input, json } => cmd_execute(&file, &input, json, quiet),
+        Commands::Execute {
+            file,
+            input,
+            json,
+            strict,
+        } => cmd_execute(&file, &input, json, strict, quiet),
+        Commands::Explain { code, json } => cmd_explain(code.as_deref(), json),
         Commands::Version => cmd_version(),
     };
 
@@ -118,35 +153,34 @@ fn main() {
 // ── Command Implementations ──────────────────────────────
 
 /// `icl validate <file>` — parse + verify (types, invariants, determinism, coherence)
-fn cmd_validate(file: &PathBuf, json: bool, quiet: bool) -> i32 {
+fn cmd_validate(file: &PathBuf, json: bool, strict: bool, quiet: bool) -> i32 {
     let source = match read_icl_file(file) {
         Ok(s) => s,
         Err(code) => return code,
     };
 
-    // Parse
-    let ast = match icl_core::parser::parse(&source) {
+    // Parse, collecting every syntax error rather than stopping at the first
+    let parsed = icl_core::parser::parse_with_diagnostics(&source);
+    if !parsed.is_valid() {
+        report_parse_failure("valid", file, &source, &parsed.diagnostics, json);
+        return EXIT_VALIDATION_FAILURE;
+    }
+
+    // Merge imported modules
+    let ast = match resolve_imports(file, parsed.contract) {
         Ok(ast) => ast,
         Err(e) => {
-            if json {
-                let output = serde_json::json!({
-                    "valid": false,
-                    "error": format!("{}", e),
-                    "phase": "parse"
-                });
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&output).unwrap_or_default()
-                );
-            } else {
-                eprintln!("{} {}", "error:".red().bold(), e);
-            }
+            report_import_failure("valid", file, &source, e, json);
             return EXIT_VALIDATION_FAILURE;
         }
     };
 
     // Verify
-    let result = icl_core::verifier::verify(&ast);
+    let options = match verify_options(file, &source, strict) {
+        Ok(options) => options,
+        Err(code) => return code,
+    };
+    let result = icl_core::verifier::verify_with_options(&ast, &options);
 
     if json {
         let diagnostics: Vec<serde_json::Value> = result
@@ -156,6 +190,8 @@ fn cmd_validate(file: &PathBuf, json: bool, quiet: bool) -> i32 {
                 serde_json::json!({
                     "severity": format!("{:?}", d.severity),
                     "kind": format!("{}", d.kind),
+                    "code": d.rule.code,
+                    "rule": d.rule.name,
                     "message": d.message,
                 })
             })
@@ -175,20 +211,11 @@ fn cmd_validate(file: &PathBuf, json: bool, quiet: bool) -> i32 {
     } else if result.is_valid() {
         if !quiet {
             println!("{} {} is valid", "✓".green().bold(), file.display());
-            let warning_count = result.warnings().len();
-            if warning_count > 0 {
-                for w in result.warnings() {
-                    eprintln!("  {} {}", "warning:".yellow(), w.message);
-                }
-            }
+            print_diagnostics(&source, file, &result.warnings());
         }
     } else {
-        for e in result.errors() {
-            eprintln!("  {} {}", "error:".red().bold(), e.message);
-        }
-        for w in result.warnings() {
-            eprintln!("  {} {}", "warning:".yellow(), w.message);
-        }
+        print_diagnostics(&source, file, &result.errors());
+        print_diagnostics(&source, file, &result.warnings());
         eprintln!(
             "{} {} ({} error(s), {} warning(s))",
             "✗".red().bold(),
@@ -212,9 +239,10 @@ fn cmd_normalize(file: &PathBuf, _quiet: bool) -> i32 {
         Err(code) => return code,
     };
 
-    match icl_core::normalizer::normalize(&source) {
-        Ok(canonical) => {
-            print!("{}", canonical);
+    match load_contract(file, &source) {
+        Ok(ast) => {
+            let normalized = icl_core::normalizer::normalize_ast(ast);
+            print!("{}", icl_core::normalizer::serialize_canonical(&normalized));
             EXIT_SUCCESS
         }
         Err(e) => {
@@ -225,33 +253,30 @@ fn cmd_normalize(file: &PathBuf, _quiet: bool) -> i32 {
 }
 
 /// `icl verify <file>` — full verification with detailed output
-fn cmd_verify(file: &PathBuf, json: bool, quiet: bool) -> i32 {
+fn cmd_verify(file: &PathBuf, json: bool, strict: bool, quiet: bool) -> i32 {
     let source = match read_icl_file(file) {
         Ok(s) => s,
         Err(code) => return code,
     };
 
-    let ast = match icl_core::parser::parse(&source) {
+    let parsed = icl_core::parser::parse_with_diagnostics(&source);
+    if !parsed.is_valid() {
+        report_parse_failure("verified", file, &source, &parsed.diagnostics, json);
+        return EXIT_VALIDATION_FAILURE;
+    }
+    let ast = match resolve_imports(file, parsed.contract) {
         Ok(ast) => ast,
         Err(e) => {
-            if json {
-                let output = serde_json::json!({
-                    "verified": false,
-                    "error": format!("{}", e),
-                    "phase": "parse"
-                });
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&output).unwrap_or_default()
-                );
-            } else {
-                eprintln!("{} {}", "error:".red().bold(), e);
-            }
+            report_import_failure("verified", file, &source, e, json);
             return EXIT_VALIDATION_FAILURE;
         }
     };
 
-    let result = icl_core::verifier::verify(&ast);
+    let options = match verify_options(file, &source, strict) {
+        Ok(options) => options,
+        Err(code) => return code,
+    };
+    let result = icl_core::verifier::verify_with_options(&ast, &options);
 
     if json {
         let diagnostics: Vec<serde_json::Value> = result
@@ -261,17 +286,44 @@ fn cmd_verify(file: &PathBuf, json: bool, quiet: bool) -> i32 {
                 serde_json::json!({
                     "severity": format!("{:?}", d.severity),
                     "kind": format!("{}", d.kind),
+                    "code": d.rule.code,
+                    "rule": d.rule.name,
                     "message": d.message,
                 })
             })
             .collect();
 
+        let preservation: Vec<serde_json::Value> = result
+            .preservation
+            .iter()
+            .map(|p| {
+                use icl_core::verifier::Preservation;
+                match &p.preservation {
+                    Preservation::Proved => serde_json::json!({
+                        "operation": p.operation,
+                        "status": "proved",
+                    }),
+                    Preservation::Refuted { invariant, input } => serde_json::json!({
+                        "operation": p.operation,
+                        "status": "refuted",
+                        "invariant": invariant,
+                        "input": input,
+                    }),
+                    Preservation::Unknown { invariants } => serde_json::json!({
+                        "operation": p.operation,
+                        "status": "unknown",
+                        "invariants": invariants,
+                    }),
+                }
+            })
+            .collect();
         let output = serde_json::json!({
             "verified": result.is_valid(),
             "file": file.display().to_string(),
             "errors": result.errors().len(),
             "warnings": result.warnings().len(),
             "diagnostics": diagnostics,
+            "preservation": preservation,
         });
         println!(
             "{}",
@@ -284,12 +336,13 @@ fn cmd_verify(file: &PathBuf, json: bool, quiet: bool) -> i32 {
                 "✓".green().bold(),
                 file.display()
             );
-            let warning_count = result.warnings().len();
-            if warning_count > 0 {
-                for w in result.warnings() {
-                    eprintln!("  {} {}", "warning:".yellow(), w.message);
+            if !result.preservation.is_empty() {
+                println!("  Invariant preservation:");
+                for p in &result.preservation {
+                    println!("    {}: {}", p.operation, p.preservation);
                 }
             }
+            print_diagnostics(&source, file, &result.warnings());
         }
     } else {
         eprintln!(
@@ -297,12 +350,8 @@ fn cmd_verify(file: &PathBuf, json: bool, quiet: bool) -> i32 {
             "✗".red().bold(),
             file.display()
         );
-        for e in result.errors() {
-            eprintln!("  {} [{}] {}", "error:".red().bold(), e.kind, e.message);
-        }
-        for w in result.warnings() {
-            eprintln!("  {} [{}] {}", "warning:".yellow(), w.kind, w.message);
-        }
+        print_diagnostics(&source, file, &result.errors());
+        print_diagnostics(&source, file, &result.warnings());
     }
 
     if result.is_valid() {
@@ -312,14 +361,14 @@ fn cmd_verify(file: &PathBuf, json: bool, quiet: bool) -> i32 {
     }
 }
 
-/// `icl fmt <file>` — format to standard style (normalize without hash update)
+/// `icl fmt <file>` — fix whitespace and indentation, keeping comments and order
 fn cmd_fmt(file: &PathBuf, write: bool, quiet: bool) -> i32 {
     let source = match read_icl_file(file) {
         Ok(s) => s,
         Err(code) => return code,
     };
 
-    match icl_core::normalizer::normalize(&source) {
+    match icl_core::formatter::format(&source) {
         Ok(formatted) => {
             if write {
                 match std::fs::write(file, &formatted) {
@@ -351,6 +400,94 @@ fn cmd_fmt(file: &PathBuf, write: bool, quiet: bool) -> i32 {
     }
 }
 
+/// `icl fix <file>` — apply safe fixes in place, listing the suggestions left
+fn cmd_fix(file: &PathBuf, dry_run: bool, quiet: bool) -> i32 {
+    let source = match read_icl_file(file) {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    let options = match verify_options(file, &source, false) {
+        Ok(options) => options,
+        Err(code) => return code,
+    };
+
+    // Import paths use `/`, which every platform accepts
+    let path = file.to_string_lossy().replace('\\', "/");
+    let fixed =
+        match icl_core::fix::fix_source(&path, &source, &icl_core::loader::FileLoader, &options) {
+            Ok(fixed) => fixed,
+            Err(e) => {
+                eprintln!("{} {}", "error:".red().bold(), e);
+                return EXIT_ERROR;
+            }
+        };
+
+    if !quiet {
+        let verb = if dry_run { "would fix" } else { "fixed" };
+        for d in &fixed.applied {
+            let at = d
+                .span
+                .as_ref()
+                .map(|s| format!("{}:{}: ", s.line, s.column))
+                .unwrap_or_default();
+            let message = d.fix.as_ref().map_or("", |f| f.message.as_str());
+            println!(
+                "{} {}{} ({})",
+                format!("{}[{}]", verb, d.rule.code).green(),
+                at,
+                message,
+                d.message
+            );
+        }
+        // Remaining suggestions, and errors no fix could resolve
+        let remaining = fixed
+            .result
+            .diagnostics
+            .iter()
+            .filter(|d| d.fix.is_some() || d.severity == icl_core::verifier::Severity::Error)
+            .collect::<Vec<_>>();
+        print_diagnostics(&fixed.source, file, &remaining);
+    }
+
+    if !dry_run && fixed.source != source {
+        if let Err(e) = std::fs::write(file, &fixed.source) {
+            eprintln!(
+                "{} failed to write {}: {}",
+                "error:".red().bold(),
+                file.display(),
+                e
+            );
+            return EXIT_ERROR;
+        }
+    }
+    if !quiet {
+        println!(
+            "{} {} fix(es) {} {}",
+            "✓".green().bold(),
+            fixed.applied.len(),
+            if dry_run {
+                "available for"
+            } else {
+                "applied to"
+            },
+            file.display()
+        );
+    }
+    let errors = fixed.result.errors().len();
+    if errors > 0 {
+        if !quiet {
+            eprintln!(
+                "{} {} error(s) remain in {}",
+                "✗".red().bold(),
+                errors,
+                file.display()
+            );
+        }
+        return EXIT_VALIDATION_FAILURE;
+    }
+    EXIT_SUCCESS
+}
+
 /// `icl hash <file>` — compute and print semantic hash
 fn cmd_hash(file: &PathBuf, _quiet: bool) -> i32 {
     let source = match read_icl_file(file) {
@@ -358,7 +495,7 @@ fn cmd_hash(file: &PathBuf, _quiet: bool) -> i32 {
         Err(code) => return code,
     };
 
-    match icl_core::parser::parse(&source) {
+    match load_contract(file, &source) {
         Ok(ast) => {
             let normalized = icl_core::normalizer::normalize_ast(ast);
             let hash = icl_core::normalizer::compute_semantic_hash(&normalized);
@@ -383,15 +520,19 @@ fn cmd_diff(file_a: &PathBuf, file_b: &PathBuf, _quiet: bool) -> i32 {
         Err(code) => return code,
     };
 
-    let canonical_a = match icl_core::normalizer::normalize(&source_a) {
-        Ok(c) => c,
+    let canonical_a = match load_contract(file_a, &source_a) {
+        Ok(ast) => {
+            icl_core::normalizer::serialize_canonical(&icl_core::normalizer::normalize_ast(ast))
+        }
         Err(e) => {
             eprintln!("{} {} — {}", "error:".red().bold(), file_a.display(), e);
             return EXIT_ERROR;
         }
     };
-    let canonical_b = match icl_core::normalizer::normalize(&source_b) {
-        Ok(c) => c,
+    let canonical_b = match load_contract(file_b, &source_b) {
+        Ok(ast) => {
+            icl_core::normalizer::serialize_canonical(&icl_core::normalizer::normalize_ast(ast))
+        }
         Err(e) => {
             eprintln!("{} {} — {}", "error:".red().bold(), file_b.display(), e);
             return EXIT_ERROR;
@@ -505,6 +646,17 @@ fn cmd_init(name: Option<&str>, quiet: bool) -> i32 {
         contract_name
     );
 
+    // Stamp the scaffold with its real hash so it verifies as written
+    let placeholder = "0".repeat(64);
+    let template = match icl_core::parser::parse(&template) {
+        Ok(ast) => {
+            let normalized = icl_core::normalizer::normalize_ast(ast);
+            let hash = icl_core::normalizer::compute_semantic_hash(&normalized);
+            template.replacen(&placeholder, &hash, 1)
+        }
+        Err(_) => template,
+    };
+
     match std::fs::write(&filename, &template) {
         Ok(_) => {
             if !quiet {
@@ -525,19 +677,19 @@ fn cmd_init(name: Option<&str>, quiet: bool) -> i32 {
 }
 
 /// `icl execute <file>` — execute a contract with JSON inputs
-fn cmd_execute(file: &PathBuf, input: &str, json: bool, quiet: bool) -> i32 {
+fn cmd_execute(file: &PathBuf, input: &str, json: bool, strict: bool, quiet: bool) -> i32 {
     let source = match read_icl_file(file) {
         Ok(s) => s,
         Err(code) => return code,
     };
 
-    // Parse
-    let contract = match icl_core::parser::parse(&source) {
+    // Parse and merge imports
+    let contract = match load_contract(file, &source) {
         Ok(ast) => ast,
         Err(e) => {
             if !quiet {
                 eprintln!(
-                    "{} {} has parse errors: {}",
+                    "{} {} failed to load: {}",
                     "error:".red().bold(),
                     file.display(),
                     e
@@ -548,7 +700,11 @@ fn cmd_execute(file: &PathBuf, input: &str, json: bool, quiet: bool) -> i32 {
     };
 
     // Verify first
-    let verification = icl_core::verifier::verify(&contract);
+    let options = match verify_options(file, &source, strict) {
+        Ok(options) => options,
+        Err(code) => return code,
+    };
+    let verification = icl_core::verifier::verify_with_options(&contract, &options);
     if !verification.is_valid() {
         if !quiet {
             eprintln!(
@@ -557,7 +713,7 @@ fn cmd_execute(file: &PathBuf, input: &str, json: bool, quiet: bool) -> i32 {
                 file.display()
             );
             for e in &verification.errors() {
-                eprintln!("  {}", e.message);
+                eprintln!("  {}: {}", e.rule.code, e.message);
             }
         }
         return EXIT_VALIDATION_FAILURE;
@@ -575,7 +731,10 @@ fn cmd_execute(file: &PathBuf, input: &str, json: bool, quiet: bool) -> i32 {
     };
 
     // Execute
-    match icl_core::executor::execute_contract(&runtime_contract, input) {
+    let options = icl_core::executor::ExecuteOptions {
+        strict_conditions: strict,
+    };
+    match icl_core::executor::execute_contract_with_options(&runtime_contract, input, options) {
         Ok(result) => {
             if json {
                 println!("{}", result);
@@ -597,14 +756,22 @@ fn cmd_execute(file: &PathBuf, input: &str, json: bool, quiet: bool) -> i32 {
                         .map(|a| a.len())
                         .unwrap_or(0);
                     if !quiet {
+                        let entries = result_json["provenance"]["entries"]
+                            .as_array()
+                            .cloned()
+                            .unwrap_or_default();
+                        let checks: Vec<&serde_json::Value> = entries
+                            .iter()
+                            .filter_map(|e| e["conditions"].as_array())
+                            .flatten()
+                            .collect();
+                        let evaluated = checks
+                            .iter()
+                            .filter(|c| c["evaluated"].as_bool() == Some(true))
+                            .count();
                         println!("  Operations: {}", ops);
-                        println!(
-                            "  Provenance entries: {}",
-                            result_json["provenance"]["entries"]
-                                .as_array()
-                                .map(|a| a.len())
-                                .unwrap_or(0)
-                        );
+                        println!("  Provenance entries: {}", entries.len());
+                        println!("  Conditions evaluated: {} of {}", evaluated, checks.len());
                     }
                 } else {
                     if !quiet {
@@ -634,6 +801,79 @@ fn cmd_execute(file: &PathBuf, input: &str, json: bool, quiet: bool) -> i32 {
     }
 }
 
+/// `icl explain [code]` — long explanation of a diagnostic code
+fn cmd_explain(code: Option<&str>, json: bool) -> i32 {
+    use icl_core::explain::{explain, Explanation, EXPLANATIONS};
+
+    let to_json = |e: &Explanation| {
+        let rule = icl_core::lint::lookup(e.code);
+        serde_json::json!({
+            "code": e.code,
+            "name": e.name,
+            "kind": rule.map(|r| r.kind.to_string()),
+            "severity": rule.map(|r| icl_core::lint::Level::from(r.default_severity).to_string()),
+            "summary": e.summary,
+            "explanation": e.explanation,
+            "broken": e.broken,
+            "fixed": e.fixed,
+        })
+    };
+
+    let Some(code) = code else {
+        if json {
+            let all: Vec<serde_json::Value> = EXPLANATIONS.iter().map(to_json).collect();
+            println!("{}", serde_json::to_string_pretty(&all).unwrap_or_default());
+        } else {
+            for e in EXPLANATIONS {
+                println!("{}  {:<34} {}", e.code.bold(), e.name, e.summary);
+            }
+        }
+        return EXIT_SUCCESS;
+    };
+
+    let Some(e) = explain(code) else {
+        eprintln!(
+            "{} unknown diagnostic code '{}' (run `icl explain` to list codes)",
+            "error:".red().bold(),
+            code
+        );
+        return EXIT_ERROR;
+    };
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&to_json(e)).unwrap_or_default()
+        );
+        return EXIT_SUCCESS;
+    }
+
+    println!("{} {}", e.code.bold(), e.name);
+    if let Some(rule) = icl_core::lint::lookup(e.code) {
+        println!(
+            "{} [{}], default severity {}",
+            "lint rule".dimmed(),
+            rule.kind,
+            icl_core::lint::Level::from(rule.default_severity)
+        );
+    }
+    println!();
+    println!("{}", e.summary);
+    println!();
+    println!("{}", e.explanation);
+    println!();
+    println!("{}", "Broken:".red().bold());
+    for line in e.broken.lines() {
+        println!("    {}", line);
+    }
+    println!();
+    println!("{}", "Fixed:".green().bold());
+    for line in e.fixed.lines() {
+        println!("    {}", line);
+    }
+    EXIT_SUCCESS
+}
+
 /// `icl version` — show version information
 fn cmd_version() -> i32 {
     println!(
@@ -647,6 +887,159 @@ fn cmd_version() -> i32 {
 
 // ── Helpers ───────────────────────────────────────────────
 
+/// Print a diagnostic rendered by the library, coloring its
+/// `error[CODE]:` or `warning[CODE]:` label
+fn print_rendered(rendered: &str) {
+    let colored = match rendered.split_once("]:") {
+        Some((label, rest)) if label.starts_with("error[") => {
+            format!("{}{}", format!("{}]:", label).red().bold(), rest)
+        }
+        Some((label, rest)) if label.starts_with("warning[") => {
+            format!("{}{}", format!("{}]:", label).yellow(), rest)
+        }
+        _ => rendered.to_string(),
+    };
+    eprintln!("{}", colored);
+}
+
+/// Print verifier diagnostics with their snippets and fix notes
+fn print_diagnostics(source: &str, file: &Path, diagnostics: &[&icl_core::verifier::Diagnostic]) {
+    let origin = file.display().to_string();
+    for d in diagnostics {
+        print_rendered(&d.render(source, &origin));
+    }
+}
+
+/// Report a contract that does not parse. With `--json` this prints the
+/// same shape as a verification result, with `status_key` (`valid` or
+/// `verified`) false and one entry per parse diagnostic.
+fn report_parse_failure(
+    status_key: &str,
+    file: &Path,
+    source: &str,
+    parse_diagnostics: &[icl_core::parser::ParseDiagnostic],
+    json: bool,
+) {
+    if json {
+        let diagnostics: Vec<serde_json::Value> = parse_diagnostics
+            .iter()
+            .map(|d| {
+                serde_json::json!({
+                    "severity": "Error",
+                    "kind": match d.kind {
+                        icl_core::parser::ParseDiagnosticKind::Syntax => "syntax",
+                        icl_core::parser::ParseDiagnosticKind::Validation => "validation",
+                    },
+                    "code": d.code,
+                    "message": d.message,
+                    "line": d.span.line,
+                    "column": d.span.column,
+                })
+            })
+            .collect();
+        let first = parse_diagnostics
+            .first()
+            .cloned()
+            .map(icl_core::Error::from);
+        print_failure_json(status_key, file, "parse", first, diagnostics);
+    } else {
+        for d in parse_diagnostics {
+            print_rendered(&d.render(source, &file.display().to_string()));
+        }
+        if parse_diagnostics.len() > 1 {
+            eprintln!(
+                "{} {} ({} parse error(s))",
+                "✗".red().bold(),
+                file.display(),
+                parse_diagnostics.len()
+            );
+        }
+    }
+}
+
+/// Report an import that could not be resolved, in the same shape as
+/// [`report_parse_failure`]
+fn report_import_failure(
+    status_key: &str,
+    file: &Path,
+    source: &str,
+    e: icl_core::loader::ImportError,
+    json: bool,
+) {
+    if json {
+        let diagnostics = vec![serde_json::json!({
+            "severity": "Error",
+            "kind": "import",
+            "code": e.code,
+            "message": e.message,
+            "line": e.span.line,
+            "column": e.span.column,
+        })];
+        print_failure_json(status_key, file, "import", Some(e.into()), diagnostics);
+    } else {
+        print_rendered(&e.render(source, &file.display().to_string()));
+    }
+}
+
+fn print_failure_json(
+    status_key: &str,
+    file: &Path,
+    phase: &str,
+    first: Option<icl_core::Error>,
+    diagnostics: Vec<serde_json::Value>,
+) {
+    let mut output = serde_json::json!({
+        "file": file.display().to_string(),
+        "phase": phase,
+        "error": first.map(|e| e.to_string()),
+        "errors": diagnostics.len(),
+        "warnings": 0,
+        "diagnostics": diagnostics,
+    });
+    output[status_key] = serde_json::Value::Bool(false);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).unwrap_or_default()
+    );
+}
+
+/// Parse a contract and merge the modules it imports
+fn load_contract(file: &Path, source: &str) -> icl_core::Result<icl_core::ContractNode> {
+    let ast = icl_core::parser::parse(source)?;
+    Ok(resolve_imports(file, ast)?)
+}
+
+/// Merge imported modules, resolving import paths relative to `file`
+fn resolve_imports(
+    file: &Path,
+    ast: icl_core::ContractNode,
+) -> std::result::Result<icl_core::ContractNode, icl_core::loader::ImportError> {
+    // Import paths use `/`, which every platform accepts
+    let path = file.to_string_lossy().replace('\\', "/");
+    icl_core::loader::resolve_imports(ast, &path, &icl_core::loader::FileLoader)
+}
+
+/// Verification policy: the `--strict` flag, the project's lint levels
+/// from the nearest `icl.json`, and the file's `icl-allow` comments
+fn verify_options(
+    file: &Path,
+    source: &str,
+    strict: bool,
+) -> std::result::Result<icl_core::verifier::VerifyOptions, i32> {
+    let lint = match icl_core::lint::LintConfig::for_file(file) {
+        Ok(lint) => lint,
+        Err(e) => {
+            eprintln!("{} {}", "error:".red().bold(), e);
+            return Err(EXIT_ERROR);
+        }
+    };
+    Ok(icl_core::verifier::VerifyOptions {
+        strict_conditions: strict,
+        lint,
+        suppressions: icl_core::lint::Suppressions::from_source(source),
+    })
+}
+
 /// Read an ICL file, printing error and returning exit code on failure
 fn read_icl_file(file: &PathBuf) -> std::result::Result<String, i32> {
     match std::fs::read_to_string(file) {
@@ -39,6 +39,21 @@ fn run_icl(args: &[&str]) -> std::process::Output {
         .expect("failed to execute icl-cli")
 }
 
+/// Set the semantic_hash of the contract at `file` to the one `icl hash`
+/// computes, after the test has edited it
+fn stamp_hash(file: &std::path::Path) {
+    let output = run_icl(&["hash", file.to_str().unwrap()]);
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let source = std::fs::read_to_string(file).unwrap();
+    let start = source.find("semantic_hash: \"").unwrap() + "semantic_hash: \"".len();
+    let end = start + source[start..].find('"').unwrap();
+    std::fs::write(
+        file,
+        format!("{}{}{}", &source[..start], hash, &source[end..]),
+    )
+    .unwrap();
+}
+
 // ── Version ───────────────────────────────────────────────
 
 #[test]
@@ -128,6 +143,47 @@ fn test_validate_json_invalid() {
     assert_eq!(json["valid"], false);
 }
 
+#[test]
+fn test_validate_reports_all_parse_errors() {
+    let source = std::fs::read_to_string(fixture_valid("minimal-contract.icl"))
+        .expect("read fixture")
+        .replacen("version: 1,", "version: \"one\",", 1)
+        .replacen("confidence_level: 1.0", "confidence_level: 2.5", 1);
+    let temp = std::env::temp_dir().join("icl_test_multiple_parse_errors.icl");
+    std::fs::write(&temp, &source).expect("write temp");
+
+    let output = run_icl(&["validate", temp.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Expected integer literal"), "{}", stderr);
+    assert!(stderr.contains("confidence_level"), "{}", stderr);
+    // Each error shows its source line with carets under the offending token
+    assert!(stderr.contains("8 |     version: \"one\","), "{}", stderr);
+    assert!(stderr.contains("|              ^^^^^"), "{}", stderr);
+
+    let output = run_icl(&["validate", "--json", temp.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("should be valid JSON");
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["phase"], "parse");
+    assert_eq!(json["errors"], 2);
+    assert_eq!(json["diagnostics"][0]["kind"], "syntax");
+    assert_eq!(json["diagnostics"][0]["code"], "ICL-P005");
+    assert_eq!(json["diagnostics"][1]["kind"], "validation");
+    assert_eq!(json["diagnostics"][1]["code"], "ICL-P008");
+    assert!(json["diagnostics"][0]["line"].as_u64().unwrap() > 0);
+
+    // verify reports every parse error in the same shape
+    let output = run_icl(&["verify", "--json", temp.to_str().unwrap()]);
+    let _ = std::fs::remove_file(&temp);
+    assert_eq!(output.status.code(), Some(1));
+    let verified: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("should be valid JSON");
+    assert_eq!(verified["verified"], false);
+    assert_eq!(verified["diagnostics"], json["diagnostics"]);
+    assert_eq!(verified["errors"], 2);
+}
+
 #[test]
 fn test_validate_quiet_valid() {
     let output = run_icl(&[
@@ -213,6 +269,105 @@ fn test_verify_json_output() {
     assert_eq!(json["verified"], true);
 }
 
+#[test]
+fn test_verify_reports_invariant_preservation() {
+    let file = fixture_valid("postconditions-old.icl");
+    let output = run_icl(&["verify", file.to_str().unwrap()]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout
+            .contains("Invariant preservation:\n    increment: proved\n    halve_balance: proved"),
+        "stdout={}",
+        stdout
+    );
+
+    let output = run_icl(&["verify", "--json", file.to_str().unwrap()]);
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("should be valid JSON");
+    assert_eq!(
+        json["preservation"],
+        serde_json::json!([
+            { "operation": "increment", "status": "proved" },
+            { "operation": "halve_balance", "status": "proved" },
+        ])
+    );
+}
+
+#[test]
+fn test_verify_strict_rejects_prose_conditions() {
+    let file = fixture_valid("all-primitive-types.icl");
+    let output = run_icl(&["verify", file.to_str().unwrap()]);
+    assert!(output.status.success(), "prose is accepted by default");
+
+    let output = run_icl(&["verify", "--strict", file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("postcondition of 'update_count' 'count updated' is not machine-checkable"),
+        "stderr={}",
+        stderr
+    );
+
+    let output = run_icl(&[
+        "verify",
+        "--strict",
+        fixture_valid("postconditions-old.icl").to_str().unwrap(),
+    ]);
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_verify_applies_project_lint_levels() {
+    let dir = std::env::temp_dir().join("icl_test_lint_levels");
+    let _ = std::fs::create_dir_all(&dir);
+    let file = dir.join("contract.icl");
+    let source = std::fs::read_to_string(fixture_valid("minimal-contract.icl")).unwrap();
+    std::fs::write(&file, source.replace("[\"manual\"]", "[\"cron\"]")).unwrap();
+    stamp_hash(&file);
+    let config = dir.join("icl.json");
+    let _ = std::fs::remove_file(&config);
+
+    let output = run_icl(&["verify", "--json", file.to_str().unwrap()]);
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("should be valid JSON");
+    assert_eq!(json["diagnostics"][0]["code"], "ICL-C004");
+    assert_eq!(json["diagnostics"][0]["rule"], "unknown-trigger-type");
+
+    std::fs::write(&config, r#"{"lint": {"unknown-trigger-type": "error"}}"#).unwrap();
+    let output = run_icl(&["verify", file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unrecognized trigger_type 'cron'") && stderr.contains("error[ICL-C004]"),
+        "stderr={}",
+        stderr
+    );
+
+    std::fs::write(&config, r#"{"lint": {"unknown-trigger-type": "off"}}"#).unwrap();
+    let output = run_icl(&["validate", "--json", file.to_str().unwrap()]);
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("should be valid JSON");
+    assert_eq!(json["warnings"], 0);
+
+    std::fs::write(&config, r#"{"lint": {"no-such-rule": "off"}}"#).unwrap();
+    let output = run_icl(&["validate", file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unknown lint rule 'no-such-rule'"),
+        "stderr={}",
+        stderr
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 // ── Hash ──────────────────────────────────────────────────
 
 #[test]
@@ -245,6 +400,78 @@ fn test_hash_determinism() {
     }
 }
 
+// ── Imports ───────────────────────────────────────────────
+
+fn fixture_imports(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("../../tests/fixtures/imports/{}", name))
+}
+
+#[test]
+fn test_validate_resolves_imports() {
+    let output = run_icl(&["validate", fixture_imports("ledger.icl").to_str().unwrap()]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Imported types and invariants end up in the canonical form
+    let output = run_icl(&["normalize", fixture_imports("ledger.icl").to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Contract {\n"));
+    assert!(stdout.contains("    Entry: Object {"));
+    assert!(stdout.contains("\"total >= 0\""));
+}
+
+#[test]
+fn test_validate_import_cycle_fails() {
+    let path = fixture_imports("cycle.icl");
+    let output = run_icl(&["validate", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("import cycle: "), "{}", stderr);
+    assert!(stderr.contains("import \"cycle/a.icl\""), "{}", stderr);
+
+    let output = run_icl(&["validate", "--json", path.to_str().unwrap()]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["phase"], "import");
+    assert_eq!(json["errors"], 1);
+    assert_eq!(json["diagnostics"][0]["kind"], "import");
+    assert_eq!(json["diagnostics"][0]["code"], "ICL-M003");
+
+    let output = run_icl(&["verify", "--json", path.to_str().unwrap()]);
+    let verified: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(verified["verified"], false);
+    assert_eq!(verified["diagnostics"], json["diagnostics"]);
+}
+
+#[test]
+fn test_hash_covers_imported_modules() {
+    let dir = std::env::temp_dir().join("icl_test_hash_imports");
+    std::fs::create_dir_all(dir.join("common")).unwrap();
+    for name in ["ledger.icl", "common/types.icl", "common/invariants.icl"] {
+        std::fs::copy(fixture_imports(name), dir.join(name)).unwrap();
+    }
+    let contract = dir.join("ledger.icl");
+    let hash = || {
+        let output = run_icl(&["hash", contract.to_str().unwrap()]);
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    let before = hash();
+    let types = std::fs::read_to_string(dir.join("common/types.icl")).unwrap();
+    std::fs::write(
+        dir.join("common/types.icl"),
+        types.replace("Amount: Integer", "Amount: Float"),
+    )
+    .unwrap();
+    assert_ne!(before, hash(), "changing a module must change the hash");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 // ── Fmt ───────────────────────────────────────────────────
 
 #[test]
@@ -278,6 +505,110 @@ fn test_fmt_write_flag() {
     let _ = std::fs::remove_file(&temp);
 }
 
+#[test]
+fn test_fmt_keeps_comments_and_order() {
+    let output = run_icl(&[
+        "fmt",
+        fixture_valid("comments-everywhere.icl").to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "fmt should exit 0");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("// Conformance: valid/comments-everywhere.icl\n"));
+    assert!(stdout.contains("    // Comment for stable_id\n    stable_id:"));
+    // Author order is kept (normalize would sort `value` after `count`)
+    let value = stdout.find("value: String").unwrap();
+    let count = stdout.find("count: Integer").unwrap();
+    assert!(value < count);
+}
+
+#[test]
+fn test_verify_checks_semantic_hash() {
+    let dir = std::env::temp_dir().join("icl_test_semantic_hash");
+    let _ = std::fs::create_dir_all(&dir);
+    let file = dir.join("contract.icl");
+    let source = std::fs::read_to_string(fixture_valid("minimal-contract.icl")).unwrap();
+    std::fs::write(&file, source.replace("value: String", "value: Integer")).unwrap();
+    let config = dir.join("icl.json");
+    let _ = std::fs::remove_file(&config);
+
+    // The edit changed the meaning, so the declared hash is stale
+    let hash = run_icl(&["hash", file.to_str().unwrap()]);
+    let hash = String::from_utf8_lossy(&hash.stdout).trim().to_string();
+    let output = run_icl(&["verify", file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error[ICL-C012]"), "stderr={}", stderr);
+    assert!(stderr.contains(&format!("expected '{}'", hash)));
+
+    // Drafts may downgrade it
+    std::fs::write(
+        &config,
+        r#"{"lint": {"semantic-hash-mismatch": "warning"}}"#,
+    )
+    .unwrap();
+    let output = run_icl(&["verify", file.to_str().unwrap()]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("warning[ICL-C012]"), "stderr={}", stderr);
+
+    // `icl fix` writes the computed hash
+    let output = run_icl(&["fix", file.to_str().unwrap()]);
+    assert!(output.status.success());
+    let fixed = std::fs::read_to_string(&file).unwrap();
+    assert!(fixed.contains(&format!("semantic_hash: \"{}\"", hash)));
+    let _ = std::fs::remove_file(&config);
+    let output = run_icl(&["verify", file.to_str().unwrap()]);
+    assert!(output.status.success());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ── Fix ───────────────────────────────────────────────────
+
+#[test]
+fn test_fix_applies_safe_fixes_in_place() {
+    let dir = std::env::temp_dir().join("icl_test_fix");
+    let _ = std::fs::create_dir_all(&dir);
+    let temp = dir.join("contract.icl");
+    let source = std::fs::read_to_string(fixture_valid("minimal-contract.icl"))
+        .expect("read fixture")
+        .replace("value: String", "value: Optional<Optional<String>>")
+        .replace("[\"manual\"]", "[\"manaul\"]");
+    std::fs::write(&temp, &source).expect("write temp");
+
+    // --dry-run reports the fix but leaves the file alone
+    let output = run_icl(&["fix", "--dry-run", temp.to_str().unwrap()]);
+    assert!(output.status.success(), "fix --dry-run should exit 0");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("would fix[ICL-T008]"), "{}", stdout);
+    assert_eq!(std::fs::read_to_string(&temp).unwrap(), source);
+
+    let output = run_icl(&["fix", temp.to_str().unwrap()]);
+    assert!(output.status.success(), "fix should exit 0");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("fixed[ICL-T008]"), "{}", stdout);
+    // Suggestions are listed, not applied
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("help: replace 'manaul' with 'manual'"));
+
+    let fixed = std::fs::read_to_string(&temp).unwrap();
+    assert!(fixed.starts_with("// Conformance: valid/minimal-contract.icl\n"));
+    assert!(fixed.contains("      value: Optional<String>\n"));
+    assert!(fixed.contains("[\"manaul\"]"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_fix_fails_when_errors_remain() {
+    let path = fixture_invalid("comparison-type-mismatch.icl");
+    let output = run_icl(&["fix", "--dry-run", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1), "errors remain after fixing");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error(s) remain in"), "{}", stderr);
+    assert!(stderr.contains("error[ICL-T"), "{}", stderr);
+}
+
 // ── Diff ──────────────────────────────────────────────────
 
 #[test]
@@ -420,6 +751,48 @@ fn test_execute_json_output() {
     assert!(!json["provenance"]["entries"].as_array().unwrap().is_empty());
 }
 
+#[test]
+fn test_execute_reports_evaluated_conditions() {
+    let file = fixture_valid("all-primitive-types.icl");
+    let input = r#"{"operation": "update_count", "inputs": {"new_count": 10, "label": "test"}}"#;
+    let output = run_icl(&[
+        "execute",
+        file.to_str().unwrap(),
+        "--input",
+        input,
+        "--json",
+    ]);
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_str(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    let conditions = json["provenance"]["entries"][0]["conditions"]
+        .as_array()
+        .unwrap();
+    assert_eq!(conditions[0]["role"], "precondition");
+    assert_eq!(conditions[0]["evaluated"], true);
+    assert_eq!(conditions[1]["role"], "postcondition");
+    assert_eq!(conditions[1]["condition"], "count updated");
+    assert_eq!(conditions[1]["evaluated"], false);
+
+    let output = run_icl(&["execute", file.to_str().unwrap(), "--input", input]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Conditions evaluated:"),
+        "stdout={}",
+        stdout
+    );
+
+    // Strict mode refuses the prose postcondition before running anything
+    let output = run_icl(&[
+        "execute",
+        "--strict",
+        file.to_str().unwrap(),
+        "--input",
+        input,
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
 #[test]
 fn test_execute_unknown_operation() {
     let output = run_icl(&[
@@ -435,6 +808,39 @@ fn test_execute_unknown_operation() {
     );
 }
 
+// ── Explain ───────────────────────────────────────────────
+
+#[test]
+fn test_explain_code() {
+    let output = run_icl(&["explain", "ICL-C004"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("unknown-trigger-type"), "{}", stdout);
+    assert!(stdout.contains("default severity warning"), "{}", stdout);
+    assert!(stdout.contains("Broken:") && stdout.contains("Fixed:"));
+
+    // Rule names work too, and JSON carries the same entry
+    let output = run_icl(&["explain", "--json", "import-cycle"]);
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("should be valid JSON");
+    assert_eq!(json["code"], "ICL-M003");
+    assert!(json["severity"].is_null());
+}
+
+#[test]
+fn test_explain_lists_and_rejects_codes() {
+    let output = run_icl(&["explain"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("ICL-P001") && stdout.contains("ICL-C011"));
+
+    let output = run_icl(&["explain", "ICL-X999"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown diagnostic code 'ICL-X999'"));
+}
+
 // ── All valid conformance fixtures ────────────────────────
 
 #[test]
@@ -479,6 +885,27 @@ fn test_all_invalid_conformance_fixtures_fail() {
     }
 }
 
+#[test]
+fn test_undeclared_identifier_fixtures_fail_with_t019() {
+    for name in [
+        "operations-empty-parameters.icl",
+        "operations-multiple-varied.icl",
+        "operations-not-idempotent.icl",
+    ] {
+        let path = fixture_invalid(name);
+        let output = run_icl(&["validate", path.to_str().unwrap()]);
+        assert!(!output.status.success(), "{} should fail validation", name);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("error[ICL-T019]: [type] unknown identifier 'max_int'"),
+            "{}: {}",
+            name,
+            stderr
+        );
+        assert!(stderr.contains("(1 error(s)"), "{}: {}", name, stderr);
+    }
+}
+
 // ── Determinism: CLI output ───────────────────────────────
 
 #[test]
@@ -0,0 +1,635 @@
+//! Built-in functions — the pure function library of the condition language
+//!
+//! Conditions may call a fixed set of functions, e.g. `len(items) <= 100`,
+//! `contains(tags, "urgent")` or `matches(email, "^[^@]+@[^@]+$")`. Each
+//! built-in has a typed signature the verifier checks against declared
+//! state and parameter types, and an implementation the executor applies
+//! to runtime values.
+//!
+//! # Functions
+//!
+//! | Function     | Signature                                              |
+//! |--------------|--------------------------------------------------------|
+//! | `len`        | `len(String \| Array<T> \| Map<K, V>) -> Integer`      |
+//! | `contains`   | `contains(String \| Array<T> \| Map<K, V>, T) -> Boolean` |
+//! | `starts_with`| `starts_with(String, String) -> Boolean`               |
+//! | `ends_with`  | `ends_with(String, String) -> Boolean`                 |
+//! | `matches`    | `matches(String, String) -> Boolean`                   |
+//! | `lower`      | `lower(String) -> String`                              |
+//! | `upper`      | `upper(String) -> String`                              |
+//! | `sum`        | `sum(Array<Integer \| Float>) -> Integer \| Float`     |
+//! | `min`        | `min(Array<Integer \| Float>) -> Integer \| Float`     |
+//! | `max`        | `max(Array<Integer \| Float>) -> Integer \| Float`     |
+//! | `abs`        | `abs(Integer \| Float) -> Integer \| Float`            |
+//! | `keys`       | `keys(Map<K, V>) -> Array<K>`                          |
+//! | `values`     | `values(Map<K, V>) -> Array<V>`                        |
+//! | `is_uuid`    | `is_uuid(String) -> Boolean`                           |
+//! | `is_iso8601` | `is_iso8601(String) -> Boolean`                        |
+//!
+//! `length` is accepted as an alias of `len`; the normalizer writes it as
+//! `len`.
+//!
+//! # Guarantees
+//!
+//! - **Deterministic**: no clock, no randomness, no environment — the same
+//!   arguments always give the same result
+//! - **Total**: a call outside its domain (a runtime value of the wrong
+//!   type, `min` of an empty array, integer overflow) has no value, which
+//!   makes the condition opaque instead of failing
+
+use regex::Regex;
+
+use crate::executor::{ExpressionEvaluator, Value};
+use crate::formats::{is_uuid, parse_timestamp};
+use crate::schema::Type;
+use crate::verifier::equatable;
+
+/// Checks argument types (`None` when unknown) and returns the result type
+type Signature = fn(&[Option<&Type>]) -> Result<Option<Type>, String>;
+
+/// A built-in function of the condition language
+pub struct Builtin {
+    /// Name used to call the function in a condition
+    pub name: &'static str,
+    /// Human-readable signature
+    pub signature: &'static str,
+    /// One-line description
+    pub description: &'static str,
+    /// Number of arguments
+    pub arity: usize,
+    /// Return type for the given argument types (`None` when unknown)
+    check: Signature,
+    /// Result for the given argument values (`None` outside the domain)
+    eval: fn(&[Value]) -> Option<Value>,
+}
+
+/// Every built-in function, in alphabetical order
+pub static BUILTINS: &[Builtin] = &[
+    Builtin {
+        name: "abs",
+        signature: "abs(Integer | Float) -> Integer | Float",
+        description: "Absolute value of a number",
+        arity: 1,
+        check: check_abs,
+        eval: eval_abs,
+    },
+    Builtin {
+        name: "contains",
+        signature: "contains(String | Array<T> | Map<K, V>, T) -> Boolean",
+        description: "Whether a string has a substring, an array an element, or a map a key",
+        arity: 2,
+        check: check_contains,
+        eval: eval_contains,
+    },
+    Builtin {
+        name: "ends_with",
+        signature: "ends_with(String, String) -> Boolean",
+        description: "Whether a string ends with a suffix",
+        arity: 2,
+        check: check_string_predicate,
+        eval: |args| string_predicate(args, |s, suffix| s.ends_with(suffix)),
+    },
+    Builtin {
+        name: "is_iso8601",
+        signature: "is_iso8601(String) -> Boolean",
+        description: "Whether a string is an RFC 3339 (ISO8601) timestamp",
+        arity: 1,
+        check: check_string_predicate,
+        eval: |args| match &args[0] {
+            Value::String(s) => Some(Value::Boolean(parse_timestamp(s).is_some())),
+            _ => None,
+        },
+    },
+    Builtin {
+        name: "is_uuid",
+        signature: "is_uuid(String) -> Boolean",
+        description: "Whether a string is an RFC 4122 UUID",
+        arity: 1,
+        check: check_string_predicate,
+        eval: |args| match &args[0] {
+            Value::String(s) => Some(Value::Boolean(is_uuid(s))),
+            _ => None,
+        },
+    },
+    Builtin {
+        name: "keys",
+        signature: "keys(Map<K, V>) -> Array<K>",
+        description: "Keys of a map in ascending order",
+        arity: 1,
+        check: |args| check_map(args, |key, _| key.clone()),
+        eval: |args| match &args[0] {
+            Value::Object(map) => Some(Value::Array(
+                map.keys().cloned().map(Value::String).collect(),
+            )),
+            _ => None,
+        },
+    },
+    Builtin {
+        name: "len",
+        signature: "len(String | Array<T> | Map<K, V>) -> Integer",
+        description: "Number of characters in a string, elements in an array or entries in a map",
+        arity: 1,
+        check: check_len,
+        eval: eval_len,
+    },
+    Builtin {
+        name: "lower",
+        signature: "lower(String) -> String",
+        description: "A string in lowercase",
+        arity: 1,
+        check: check_string_function,
+        eval: |args| match &args[0] {
+            Value::String(s) => Some(Value::String(s.to_lowercase())),
+            _ => None,
+        },
+    },
+    Builtin {
+        name: "matches",
+        signature: "matches(String, String) -> Boolean",
+        description: "Whether a regular expression matches anywhere in a string",
+        arity: 2,
+        check: check_string_predicate,
+        eval: |args| match args {
+            [Value::String(s), Value::String(pattern)] => {
+                Some(Value::Boolean(Regex::new(pattern).ok()?.is_match(s)))
+            }
+            _ => None,
+        },
+    },
+    Builtin {
+        name: "max",
+        signature: "max(Array<Integer | Float>) -> Integer | Float",
+        description: "Largest element of a non-empty array of numbers",
+        arity: 1,
+        check: check_numbers,
+        eval: |args| extreme(&args[0], std::cmp::Ordering::Greater),
+    },
+    Builtin {
+        name: "min",
+        signature: "min(Array<Integer | Float>) -> Integer | Float",
+        description: "Smallest element of a non-empty array of numbers",
+        arity: 1,
+        check: check_numbers,
+        eval: |args| extreme(&args[0], std::cmp::Ordering::Less),
+    },
+    Builtin {
+        name: "starts_with",
+        signature: "starts_with(String, String) -> Boolean",
+        description: "Whether a string starts with a prefix",
+        arity: 2,
+        check: check_string_predicate,
+        eval: |args| string_predicate(args, |s, prefix| s.starts_with(prefix)),
+    },
+    Builtin {
+        name: "sum",
+        signature: "sum(Array<Integer | Float>) -> Integer | Float",
+        description: "Sum of an array of numbers, 0 when empty",
+        arity: 1,
+        check: check_numbers,
+        eval: eval_sum,
+    },
+    Builtin {
+        name: "upper",
+        signature: "upper(String) -> String",
+        description: "A string in uppercase",
+        arity: 1,
+        check: check_string_function,
+        eval: |args| match &args[0] {
+            Value::String(s) => Some(Value::String(s.to_uppercase())),
+            _ => None,
+        },
+    },
+    Builtin {
+        name: "values",
+        signature: "values(Map<K, V>) -> Array<V>",
+        description: "Values of a map in key order",
+        arity: 1,
+        check: |args| check_map(args, |_, value| value.clone()),
+        eval: |args| match &args[0] {
+            Value::Object(map) => Some(Value::Array(map.values().cloned().collect())),
+            _ => None,
+        },
+    },
+];
+
+/// Other names built-ins can be called by, with the built-in they stand for
+const ALIASES: &[(&str, &str)] = &[("length", "len")];
+
+/// Find a built-in function by name or alias
+pub fn lookup(name: &str) -> Option<&'static Builtin> {
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name, |(_, target)| target);
+    BUILTINS.iter().find(|b| b.name == name)
+}
+
+impl Builtin {
+    /// Check a call against the declared argument types (`None` when a
+    /// type is unknown) and return the result type
+    pub fn check(&self, args: &[Option<Type>]) -> Result<Option<Type>, String> {
+        if args.len() != self.arity {
+            return Err(format!(
+                "'{}' takes {} argument{}, found {}",
+                self.name,
+                self.arity,
+                if self.arity == 1 { "" } else { "s" },
+                args.len()
+            ));
+        }
+        // Optional arguments are checked as their inner type: a null at
+        // runtime leaves the call without a value
+        let args: Vec<Option<&Type>> = args
+            .iter()
+            .map(|ty| {
+                ty.as_ref()
+                    .map(Type::unwrapped)
+                    .filter(|ty| !matches!(ty, Type::Named { .. }))
+            })
+            .collect();
+        (self.check)(&args).map_err(|reason| format!("'{}' {}", self.name, reason))
+    }
+
+    /// Apply the function — `None` if the arguments are outside its domain
+    pub fn call(&self, args: &[Value]) -> Option<Value> {
+        if args.len() != self.arity {
+            return None;
+        }
+        (self.eval)(args)
+    }
+}
+
+// ── Signatures ────────────────────────────────────────────
+
+fn is_string(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::String | Type::Uuid | Type::Iso8601 | Type::Enum { .. }
+    )
+}
+
+fn is_number(ty: &Type) -> bool {
+    matches!(ty, Type::Integer | Type::Float)
+}
+
+fn check_len(args: &[Option<&Type>]) -> Result<Option<Type>, String> {
+    match args[0] {
+        Some(ty) if !is_string(ty) && !matches!(ty, Type::Array { .. } | Type::Map { .. }) => {
+            Err(format!("needs a String, Array or Map, found {}", ty))
+        }
+        _ => Ok(Some(Type::Integer)),
+    }
+}
+
+fn check_contains(args: &[Option<&Type>]) -> Result<Option<Type>, String> {
+    let (Some(haystack), needle) = (args[0], args[1]) else {
+        return Ok(Some(Type::Boolean));
+    };
+    let element = match haystack {
+        ty if is_string(ty) => &Type::String,
+        Type::Array { items } => items.unwrapped(),
+        Type::Map { key, .. } => key.unwrapped(),
+        other => {
+            return Err(format!(
+                "needs a String, Array or Map to search, found {}",
+                other
+            ))
+        }
+    };
+    match needle {
+        Some(needle) if !matches!(element, Type::Named { .. }) && !equatable(element, needle) => {
+            Err(format!(
+                "cannot look for {} in {}",
+                needle,
+                args[0].expect("haystack type is known")
+            ))
+        }
+        _ => Ok(Some(Type::Boolean)),
+    }
+}
+
+fn check_strings(args: &[Option<&Type>]) -> Result<(), String> {
+    match args.iter().flatten().find(|ty| !is_string(ty)) {
+        Some(ty) => Err(format!("needs String arguments, found {}", ty)),
+        None => Ok(()),
+    }
+}
+
+fn check_string_predicate(args: &[Option<&Type>]) -> Result<Option<Type>, String> {
+    check_strings(args).map(|_| Some(Type::Boolean))
+}
+
+fn check_string_function(args: &[Option<&Type>]) -> Result<Option<Type>, String> {
+    check_strings(args).map(|_| Some(Type::String))
+}
+
+/// `sum`, `min` and `max`: an Array of numbers, returning the element type
+fn check_numbers(args: &[Option<&Type>]) -> Result<Option<Type>, String> {
+    let Some(ty) = args[0] else {
+        return Ok(None);
+    };
+    match ty {
+        Type::Array { items } => match items.unwrapped() {
+            Type::Named { .. } => Ok(None),
+            Type::Integer => Ok(Some(Type::Integer)),
+            Type::Float => Ok(Some(Type::Float)),
+            _ => Err(format!("needs an Array of numbers, found {}", ty)),
+        },
+        other => Err(format!("needs an Array of numbers, found {}", other)),
+    }
+}
+
+fn check_abs(args: &[Option<&Type>]) -> Result<Option<Type>, String> {
+    match args[0] {
+        Some(ty) if is_number(ty) => Ok(Some(ty.clone())),
+        Some(ty) => Err(format!("needs a number, found {}", ty)),
+        None => Ok(None),
+    }
+}
+
+fn check_map(
+    args: &[Option<&Type>],
+    part: fn(&Type, &Type) -> Type,
+) -> Result<Option<Type>, String> {
+    match args[0] {
+        Some(Type::Map { key, value }) => Ok(Some(Type::Array {
+            items: Box::new(part(key, value)),
+        })),
+        Some(other) => Err(format!("needs a Map, found {}", other)),
+        None => Ok(None),
+    }
+}
+
+// ── Implementations ───────────────────────────────────────
+
+fn eval_len(args: &[Value]) -> Option<Value> {
+    let len = match &args[0] {
+        Value::String(s) => s.chars().count(),
+        Value::Array(items) => items.len(),
+        Value::Object(map) => map.len(),
+        _ => return None,
+    };
+    i64::try_from(len).ok().map(Value::Integer)
+}
+
+fn eval_contains(args: &[Value]) -> Option<Value> {
+    let found = match (&args[0], &args[1]) {
+        (Value::String(s), Value::String(part)) => s.contains(part.as_str()),
+        (Value::Array(items), needle) => items
+            .iter()
+            .any(|item| ExpressionEvaluator::values_equal(item, needle)),
+        (Value::Object(map), Value::String(key)) => map.contains_key(key),
+        _ => return None,
+    };
+    Some(Value::Boolean(found))
+}
+
+fn string_predicate(args: &[Value], test: fn(&str, &str) -> bool) -> Option<Value> {
+    match args {
+        [Value::String(s), Value::String(part)] => Some(Value::Boolean(test(s, part))),
+        _ => None,
+    }
+}
+
+fn eval_sum(args: &[Value]) -> Option<Value> {
+    let Value::Array(items) = &args[0] else {
+        return None;
+    };
+    if items.iter().all(|v| matches!(v, Value::Integer(_))) {
+        return items
+            .iter()
+            .try_fold(0i64, |total, v| match v {
+                Value::Integer(n) => total.checked_add(*n),
+                _ => None,
+            })
+            .map(Value::Integer);
+    }
+    let total = items.iter().try_fold(0.0, |total, v| match v {
+        Value::Integer(n) => Some(total + *n as f64),
+        Value::Float(f) => Some(total + f),
+        _ => None,
+    })?;
+    total.is_finite().then_some(Value::Float(total))
+}
+
+fn eval_abs(args: &[Value]) -> Option<Value> {
+    match &args[0] {
+        Value::Integer(n) => n.checked_abs().map(Value::Integer),
+        Value::Float(f) => Some(Value::Float(f.abs())),
+        _ => None,
+    }
+}
+
+/// The first element that no other element is `toward` of — the minimum
+/// for `Less`, the maximum for `Greater`
+fn extreme(value: &Value, toward: std::cmp::Ordering) -> Option<Value> {
+    let Value::Array(items) = value else {
+        return None;
+    };
+    let number = |v: &Value| match v {
+        Value::Integer(n) => Some(*n as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    };
+    let mut best = items.first()?;
+    for item in items {
+        if number(item)?.partial_cmp(&number(best)?)? == toward {
+            best = item;
+        }
+    }
+    Some(best.clone())
+}
+
+// ── Tests ─────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn call(name: &str, args: &[Value]) -> Option<Value> {
+        lookup(name).unwrap().call(args)
+    }
+
+    fn check(name: &str, args: &[Type]) -> Result<Option<Type>, String> {
+        let args: Vec<Option<Type>> = args.iter().cloned().map(Some).collect();
+        lookup(name).unwrap().check(&args)
+    }
+
+    fn s(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    fn ints(items: &[i64]) -> Value {
+        Value::Array(items.iter().map(|n| Value::Integer(*n)).collect())
+    }
+
+    fn array(items: Type) -> Type {
+        Type::Array {
+            items: Box::new(items),
+        }
+    }
+
+    fn map(key: Type, value: Type) -> Type {
+        Type::Map {
+            key: Box::new(key),
+            value: Box::new(value),
+        }
+    }
+
+    #[test]
+    fn test_builtins_sorted_and_unique() {
+        let names: Vec<&str> = BUILTINS.iter().map(|b| b.name).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+        for builtin in BUILTINS {
+            assert!(builtin.signature.starts_with(&format!("{}(", builtin.name)));
+        }
+    }
+
+    #[test]
+    fn test_eval_collections() {
+        let counters = Value::Object(BTreeMap::from([
+            ("b".to_string(), Value::Integer(2)),
+            ("a".to_string(), Value::Integer(1)),
+        ]));
+        assert_eq!(call("len", &[s("héllo")]), Some(Value::Integer(5)));
+        assert_eq!(call("len", &[ints(&[1, 2, 3])]), Some(Value::Integer(3)));
+        assert_eq!(
+            call("len", std::slice::from_ref(&counters)),
+            Some(Value::Integer(2))
+        );
+        assert_eq!(call("len", &[Value::Integer(3)]), None);
+        assert_eq!(
+            call("keys", std::slice::from_ref(&counters)),
+            Some(Value::Array(vec![s("a"), s("b")]))
+        );
+        assert_eq!(
+            call("values", std::slice::from_ref(&counters)),
+            Some(ints(&[1, 2]))
+        );
+        assert_eq!(
+            call("contains", &[ints(&[1, 2]), Value::Float(2.0)]),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            call("contains", &[counters, s("c")]),
+            Some(Value::Boolean(false))
+        );
+        assert_eq!(
+            call("contains", &[s("release-notes"), s("notes")]),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_eval_numbers() {
+        assert_eq!(call("sum", &[ints(&[])]), Some(Value::Integer(0)));
+        assert_eq!(call("sum", &[ints(&[3, -1, 4])]), Some(Value::Integer(6)));
+        assert_eq!(call("sum", &[ints(&[i64::MAX, 1])]), None);
+        assert_eq!(
+            call(
+                "sum",
+                &[Value::Array(vec![Value::Integer(1), Value::Float(0.5)])]
+            ),
+            Some(Value::Float(1.5))
+        );
+        assert_eq!(call("min", &[ints(&[3, -1, 4])]), Some(Value::Integer(-1)));
+        assert_eq!(call("max", &[ints(&[3, -1, 4])]), Some(Value::Integer(4)));
+        assert_eq!(call("max", &[ints(&[])]), None);
+        assert_eq!(call("abs", &[Value::Integer(-7)]), Some(Value::Integer(7)));
+        assert_eq!(call("abs", &[Value::Integer(i64::MIN)]), None);
+    }
+
+    #[test]
+    fn test_eval_strings() {
+        assert_eq!(
+            call("matches", &[s("ada@example.com"), s("^[^@]+@[^@]+$")]),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(call("matches", &[s("x"), s("(")]), None);
+        assert_eq!(
+            call("starts_with", &[s("order-17"), s("order-")]),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            call("ends_with", &[s("order-17"), s("-18")]),
+            Some(Value::Boolean(false))
+        );
+        assert_eq!(call("lower", &[s("MiXeD")]), Some(s("mixed")));
+        assert_eq!(call("upper", &[s("MiXeD")]), Some(s("MIXED")));
+        assert_eq!(
+            call("is_uuid", &[s("123e4567-e89b-12d3-a456-426614174000")]),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            call("is_uuid", &[s("123e4567e89b12d3a456426614174000")]),
+            Some(Value::Boolean(false))
+        );
+        // Version 0 and the reserved variant are not RFC 4122
+        for text in [
+            "123e4567-e89b-02d3-a456-426614174000",
+            "123e4567-e89b-12d3-c456-426614174000",
+        ] {
+            assert_eq!(call("is_uuid", &[s(text)]), Some(Value::Boolean(false)));
+        }
+        assert_eq!(
+            call("is_uuid", &[s("00000000-0000-0000-0000-000000000000")]),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            call("is_iso8601", &[s("2026-02-01T10:00:00Z")]),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            call("is_iso8601", &[s("yesterday")]),
+            Some(Value::Boolean(false))
+        );
+    }
+
+    #[test]
+    fn test_check_signatures() {
+        assert_eq!(check("len", &[Type::String]), Ok(Some(Type::Integer)));
+        assert_eq!(
+            check("len", &[Type::Integer]),
+            Err("'len' needs a String, Array or Map, found Integer".to_string())
+        );
+        assert_eq!(
+            check("len", &[Type::String, Type::String]),
+            Err("'len' takes 1 argument, found 2".to_string())
+        );
+        assert_eq!(
+            check("contains", &[array(Type::String), Type::Integer]),
+            Err("'contains' cannot look for Integer in Array<String>".to_string())
+        );
+        assert_eq!(
+            check(
+                "contains",
+                &[map(Type::String, Type::Integer), Type::String]
+            ),
+            Ok(Some(Type::Boolean))
+        );
+        assert_eq!(check("sum", &[array(Type::Float)]), Ok(Some(Type::Float)));
+        assert_eq!(
+            check("sum", &[array(Type::String)]),
+            Err("'sum' needs an Array of numbers, found Array<String>".to_string())
+        );
+        assert_eq!(
+            check("keys", &[map(Type::String, Type::Integer)]),
+            Ok(Some(array(Type::String)))
+        );
+        assert_eq!(
+            check("matches", &[Type::Integer, Type::String]),
+            Err("'matches' needs String arguments, found Integer".to_string())
+        );
+        // Optional arguments are checked as their inner type
+        let nickname = Type::Optional {
+            inner: Box::new(Type::String),
+        };
+        assert_eq!(check("upper", &[nickname]), Ok(Some(Type::String)));
+        // Unknown argument types are not reported
+        assert_eq!(lookup("abs").unwrap().check(&[None]), Ok(None));
+    }
+}
@@ -31,6 +31,12 @@ pub enum Error {
 
     /// Normalization failure
     NormalizationError(String),
+
+    /// Import could not be read or forms a cycle
+    ImportError(String),
+
+    /// Project configuration could not be read or is invalid
+    ConfigError(String),
 }
 
 impl fmt::Display for Error {
@@ -50,11 +56,43 @@ impl fmt::Display for Error {
             Error::ValidationError(msg) => write!(f, "Validation error: {}", msg),
             Error::ExecutionError(msg) => write!(f, "Execution error: {}", msg),
             Error::NormalizationError(msg) => write!(f, "Normalization error: {}", msg),
+            Error::ImportError(msg) => write!(f, "Import error: {}", msg),
+            Error::ConfigError(msg) => write!(f, "Configuration error: {}", msg),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Stable diagnostic code (e.g. `ICL-P005`) of an error converted
+    /// from a parse diagnostic or an import error, explained in
+    /// [`crate::explain`]
+    pub fn code(&self) -> Option<&str> {
+        let msg = match self {
+            Error::ParseError(msg) | Error::ValidationError(msg) | Error::ImportError(msg) => msg,
+            _ => return None,
+        };
+        let (code, _) = msg.strip_prefix('[')?.split_once(']')?;
+        code.starts_with("ICL-").then_some(code)
+    }
+}
+
 /// Result type alias for ICL operations
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_of_converted_diagnostics() {
+        let err = crate::parser::parse("Contract {").unwrap_err();
+        let code = err.code().expect("parse errors carry a code");
+        assert!(crate::explain::explain(code).is_some(), "{}", code);
+        assert!(err.to_string().contains(&format!("[{}]", code)), "{}", err);
+
+        assert_eq!(Error::ParseError("no code here".into()).code(), None);
+        assert_eq!(Error::ExecutionError("[ICL-P001] x".into()).code(), None);
+    }
+}
@@ -11,9 +11,9 @@
 //!
 //! 1. Maintains typed state matching DataSemantics.state
 //! 2. Validates inputs against operation parameter types
-//! 3. Evaluates simple condition patterns against state
+//! 3. Evaluates parsed condition expressions against state
 //! 4. Applies state transitions (parameter values → state fields)
-//! 5. Verifies postconditions and invariants hold
+//! 5. Verifies type refinements, postconditions and invariants hold
 //! 6. Enforces resource limits (memory, timeout)
 //! 7. Logs every transition in an immutable provenance log
 //!
@@ -22,11 +22,19 @@
 //! The executor is pure — no I/O, no randomness, no system time.
 //! All operations are deterministic: same state + same inputs = same result.
 
+use std::cell::Cell;
 use std::collections::BTreeMap;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
 
-use crate::{Contract, Error, Result};
+use regex::Regex;
+
+use crate::builtins;
+use crate::formats::{is_uuid, parse_timestamp};
+use crate::parser::ast::{quote, BinaryOp, Expr, LiteralValue, PrimitiveType, Quantifier, UnaryOp};
+use crate::parser::condition::parse_condition;
+use crate::schema::{Field, Refinement, Type};
+use crate::{Condition, Contract, Error, Result};
 
 // ── Core Types ────────────────────────────────────────────
 
@@ -137,6 +145,20 @@ impl Value {
         }
     }
 
+    /// Convert from a literal written in ICL source (defaults, conditions)
+    pub fn from_literal(lit: &LiteralValue) -> Self {
+        match lit {
+            LiteralValue::String(s, _) => Value::String(s.clone()),
+            LiteralValue::Integer(i, _) => Value::Integer(*i),
+            LiteralValue::Float(f, _) => Value::Float(*f),
+            LiteralValue::Boolean(b, _) => Value::Boolean(*b),
+            LiteralValue::Null(_) => Value::Null,
+            LiteralValue::Array(items, _) => {
+                Value::Array(items.iter().map(Value::from_literal).collect())
+            }
+        }
+    }
+
     /// Convert to serde_json::Value
     pub fn to_json(&self) -> serde_json::Value {
         match self {
@@ -170,57 +192,58 @@ pub struct ExecutionState {
 impl ExecutionState {
     /// Create initial state from contract's DataSemantics
     pub fn from_contract(contract: &Contract) -> Self {
-        let fields = if let serde_json::Value::Object(map) = &contract.data_semantics.state {
-            let mut btree = BTreeMap::new();
-            for (key, type_info) in map.iter() {
-                // Extract default value if present, otherwise use type-appropriate default
-                let value = Self::default_for_type(type_info);
-                btree.insert(key.clone(), value);
-            }
-            btree
-        } else {
-            BTreeMap::new()
-        };
-        ExecutionState { fields }
-    }
-
-    /// Derive a default value from a type descriptor
-    fn default_for_type(type_info: &serde_json::Value) -> Value {
-        match type_info {
-            serde_json::Value::String(type_name) => match type_name.as_str() {
-                "Integer" => Value::Integer(0),
-                "Float" => Value::Float(0.0),
-                "String" | "ISO8601" | "UUID" => Value::String(String::new()),
-                "Boolean" => Value::Boolean(false),
-                _ => Value::Null,
-            },
-            serde_json::Value::Object(obj) => {
-                if let Some(serde_json::Value::String(t)) = obj.get("type") {
-                    match t.as_str() {
-                        "Integer" | "Float" | "String" | "Boolean" | "ISO8601" | "UUID" => {
-                            // Check for explicit default value
-                            if let Some(default) = obj.get("default") {
-                                Value::from_json(default)
-                            } else {
-                                Self::default_for_type(&serde_json::Value::String(t.clone()))
-                            }
-                        }
-                        _ => Value::Null,
-                    }
-                } else {
-                    // Nested object — recurse
-                    let mut btree = BTreeMap::new();
-                    for (k, v) in obj {
-                        btree.insert(k.clone(), Self::default_for_type(v));
-                    }
-                    Value::Object(btree)
-                }
-            }
-            serde_json::Value::Array(_) => Value::Array(Vec::new()),
-            _ => Value::Null,
+        Self::from_fields(&contract.data_semantics.state)
+    }
+
+    /// Create initial state from declared state fields
+    pub fn from_fields(fields: &[Field]) -> Self {
+        ExecutionState {
+            fields: Self::initial_values(fields),
         }
     }
 
+    /// Declared defaults, or type-appropriate defaults for fields without
+    /// one. Fields whose type has no default are left unset.
+    fn initial_values(fields: &[Field]) -> BTreeMap<String, Value> {
+        fields
+            .iter()
+            .filter_map(|field| {
+                let value = match field.default {
+                    Some(ref default) => Value::from_json(default),
+                    None => Self::default_for_type(&field.ty)?,
+                };
+                Some((field.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Derive a default value from a type
+    ///
+    /// Numbers start at zero, strings empty, collections empty, `Enum`s at
+    /// their first variant and `Object`s with each field's default.
+    /// `Optional<T>` fields start as `Null` (intentionally empty), as do
+    /// fields of an unresolved type. `UUID` and `ISO8601` have no value
+    /// that is both valid and neutral, so they have no default.
+    pub fn default_for_type(ty: &Type) -> Option<Value> {
+        let value = match ty {
+            Type::Integer => Value::Integer(0),
+            Type::Float => Value::Float(0.0),
+            Type::String => Value::String(String::new()),
+            Type::Iso8601 | Type::Uuid => return None,
+            Type::Boolean => Value::Boolean(false),
+            Type::Array { .. } => Value::Array(Vec::new()),
+            Type::Map { .. } => Value::Object(BTreeMap::new()),
+            Type::Object { fields } => Value::Object(Self::initial_values(fields)),
+            Type::Enum { variants } => variants
+                .first()
+                .map_or(Value::Null, |v| Value::String(v.clone())),
+            // Refinements such as `Integer(min: 0)` don't change the base default
+            Type::Refined { base, .. } => return Self::default_for_type(base),
+            Type::Optional { .. } | Type::Named { .. } => Value::Null,
+        };
+        Some(value)
+    }
+
     /// Get a field value by name
     pub fn get(&self, field: &str) -> Option<&Value> {
         self.fields.get(field)
@@ -263,112 +286,454 @@ impl ExecutionState {
 
 // ── Expression Evaluator ──────────────────────────────────
 
-/// Evaluates simple condition patterns against execution state.
+/// Evaluates condition expressions against execution state.
+///
+/// Conditions are parsed with the condition grammar
+/// (`parser::condition`) and evaluated on the resulting `Expr` tree:
+/// - `and` / `or` / `not` — three-valued: an unknown operand only matters
+///   if it can change the result
+/// - `==`, `!=` — equality (Integer and Float compare numerically)
+/// - `<`, `<=`, `>`, `>=` — ordering of numbers, and of ISO8601 timestamps
+///   by the instant they denote
+/// - `x in [..]` — membership in an array, or key of a map
+/// - A bare name that is not a field, compared with `==`, `!=` or `in`
+///   against a string, stands for itself: `status == active`
+/// - `+`, `-`, `*` — Integer when both sides are Integers, otherwise Float;
+///   `/` always yields a Float
+/// - `x is [not] empty` — string/array/object emptiness
+/// - `x is boolean` / `integer` / `float` / `string` — type test
+/// - `old(expr)` — `expr` evaluated against the state before the operation
+/// - `all x in xs: ...` / `any x in xs: ...` — over array elements or map
+///   values; `all` holds and `any` fails for an empty collection
+/// - `len(items)`, `contains(tags, "x")`, ... — pure functions from
+///   [`crate::builtins`]
+/// - Field paths resolve into state, nested objects (`profile.age`),
+///   array elements (`scores[0]`) and map values (`counters["a"]`)
 ///
-/// Supports common invariant/condition patterns from ICL contracts:
-/// - `"<field> is not empty"` — string/array length > 0
-/// - `"<field> >= <number>"` — numeric comparison
-/// - `"<field> <= <number>"` — numeric comparison
-/// - `"<field> > <number>"` — numeric comparison  
-/// - `"<field> < <number>"` — numeric comparison
-/// - `"<field> is boolean"` — type check
-/// - `"<field> is valid ..."` — always true (advisory)
-/// - Opaque strings — always true (not machine-evaluable)
+/// `and` and `or` evaluate their right side only when the left side does
+/// not decide the result, so `profile == null or profile.age >= 18` is safe.
+///
+/// A condition that reads a field, index or key that does not exist on a
+/// value it resolved does not hold. It is not machine-evaluable (opaque)
+/// when its text does not parse, it names something that is neither in
+/// state nor a quantifier variable, its arithmetic overflows or divides by
+/// zero, a function is called outside its domain, or it uses `old(...)`
+/// without a prior state. Opaque conditions are treated as true.
 pub struct ExpressionEvaluator;
 
+/// States visible to an expression: the current one, and for
+/// postconditions the one before the operation ran
+#[derive(Clone, Copy)]
+struct Scope<'a> {
+    state: &'a ExecutionState,
+    old: Option<&'a ExecutionState>,
+    /// Innermost quantifier variable, shadowing state fields
+    vars: Option<&'a Binding<'a>>,
+    /// Declared state fields and parameters, for the types of paths
+    declared: &'a [Field],
+    /// Set when a field, index or key does not exist
+    missing: &'a Cell<bool>,
+}
+
+/// A quantifier variable bound to one element, chained to the enclosing ones
+struct Binding<'a> {
+    name: &'a str,
+    value: &'a Value,
+    /// Declared type of the elements, if known
+    ty: Option<&'a Type>,
+    parent: Option<&'a Binding<'a>>,
+}
+
+impl<'a> Scope<'a> {
+    fn binding(&self, name: &str) -> Option<&'a Binding<'a>> {
+        let mut binding = self.vars;
+        while let Some(b) = binding {
+            if b.name == name {
+                return Some(b);
+            }
+            binding = b.parent;
+        }
+        None
+    }
+
+    fn lookup(&self, name: &str) -> Option<&'a Value> {
+        match self.binding(name) {
+            Some(b) => Some(b.value),
+            None => self.state.get(name),
+        }
+    }
+
+    /// Declared type of a path, `old(...)`, field access or index; `None`
+    /// for other expressions and for values of unknown type
+    fn declared_type(&self, expr: &Expr) -> Option<&'a Type> {
+        match expr {
+            Expr::Path(segments, _) => {
+                let mut ty = match self.binding(&segments[0]) {
+                    Some(b) => b.ty?,
+                    None => &self.declared.iter().find(|f| f.name == segments[0])?.ty,
+                };
+                for segment in &segments[1..] {
+                    ty = field_type(ty, segment)?;
+                }
+                Some(ty)
+            }
+            Expr::Old(operand, _) => self.declared_type(operand),
+            Expr::Field(base, name, _) => field_type(self.declared_type(base)?, name),
+            Expr::Index(base, _, _) => element_type(self.declared_type(base)?),
+            _ => None,
+        }
+    }
+}
+
+/// Type of field `name` of an `Object` type
+fn field_type<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    match ty.unwrapped() {
+        Type::Object { fields } => fields.iter().find(|f| f.name == name).map(|f| &f.ty),
+        _ => None,
+    }
+}
+
+/// Type of the elements of an `Array` or the values of a `Map` type
+fn element_type(ty: &Type) -> Option<&Type> {
+    match ty.unwrapped() {
+        Type::Array { items } => Some(items),
+        Type::Map { value, .. } => Some(value),
+        _ => None,
+    }
+}
+
 impl ExpressionEvaluator {
     /// Evaluate a condition string against the current state.
     /// Returns (result, is_evaluable) — false for `is_evaluable` means
     /// the condition is an opaque string that can't be machine-evaluated.
     pub fn evaluate(condition: &str, state: &ExecutionState) -> (bool, bool) {
-        let trimmed = condition.trim();
+        match parse_condition(condition) {
+            Ok(expr) => Self::evaluate_expr(&expr, state),
+            // Opaque condition — not machine-evaluable, treat as true
+            Err(_) => (true, false),
+        }
+    }
 
-        // Pattern: "<field> is not empty"
-        if let Some(field) = trimmed.strip_suffix(" is not empty") {
-            let field = field.trim();
-            if let Some(value) = state.get(field) {
-                return (value.is_truthy(), true);
-            }
-            // Field doesn't exist — fails
-            return (false, true);
+    /// Evaluate a parsed condition expression against the current state.
+    /// Returns (result, is_evaluable) with the same meaning as [`Self::evaluate`].
+    pub fn evaluate_expr(expr: &Expr, state: &ExecutionState) -> (bool, bool) {
+        Self::evaluate_in(expr, state, None, &[])
+    }
+
+    /// Evaluate a postcondition against the state after an operation,
+    /// resolving `old(...)` against the state before it.
+    /// Returns (result, is_evaluable) with the same meaning as [`Self::evaluate`].
+    pub fn evaluate_postcondition(
+        condition: &str,
+        before: &ExecutionState,
+        after: &ExecutionState,
+    ) -> (bool, bool) {
+        match parse_condition(condition) {
+            Ok(expr) => Self::evaluate_in(&expr, after, Some(before), &[]),
+            Err(_) => (true, false),
         }
+    }
 
-        // Pattern: "<field> >= <number>"
-        if let Some((field, num)) = Self::parse_comparison(trimmed, " >= ") {
-            return (Self::numeric_cmp(state, field, num, |a, b| a >= b), true);
+    /// Evaluate a contract condition through the expression it was parsed
+    /// to, with `old(...)` reading `before` if given. `declared` holds the
+    /// state fields and parameters in scope; only with their types can a
+    /// bare enum variant (`status == open`) be told from an unknown name.
+    pub fn evaluate_condition(
+        condition: &Condition,
+        before: Option<&ExecutionState>,
+        state: &ExecutionState,
+        declared: &[Field],
+    ) -> (bool, bool) {
+        match &condition.expr {
+            Some(expr) => Self::evaluate_in(expr, state, before, declared),
+            None => (true, false),
         }
+    }
 
-        // Pattern: "<field> <= <number>"
-        if let Some((field, num)) = Self::parse_comparison(trimmed, " <= ") {
-            return (Self::numeric_cmp(state, field, num, |a, b| a <= b), true);
+    fn evaluate_in(
+        expr: &Expr,
+        state: &ExecutionState,
+        old: Option<&ExecutionState>,
+        declared: &[Field],
+    ) -> (bool, bool) {
+        let missing = Cell::new(false);
+        let scope = Scope {
+            state,
+            old,
+            vars: None,
+            declared,
+            missing: &missing,
+        };
+        let result = Self::eval_bool(expr, scope);
+        if missing.get() {
+            return (false, true);
         }
+        match result {
+            Some(result) => (result, true),
+            None => (true, false),
+        }
+    }
 
-        // Pattern: "<field> > <number>"
-        if let Some((field, num)) = Self::parse_comparison(trimmed, " > ") {
-            // Don't match ">=" which was already handled
-            return (Self::numeric_cmp(state, field, num, |a, b| a > b), true);
+    /// Evaluate to a boolean — `None` if the result is unknown
+    fn eval_bool(expr: &Expr, scope: Scope) -> Option<bool> {
+        match expr {
+            Expr::Unary(UnaryOp::Not, operand, _) => Self::eval_bool(operand, scope).map(|b| !b),
+            Expr::Binary(BinaryOp::And, left, right, _) => match Self::eval_bool(left, scope) {
+                Some(false) => Some(false),
+                left => match (left, Self::eval_bool(right, scope)) {
+                    (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                },
+            },
+            Expr::Binary(BinaryOp::Or, left, right, _) => match Self::eval_bool(left, scope) {
+                Some(true) => Some(true),
+                left => match (left, Self::eval_bool(right, scope)) {
+                    (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                },
+            },
+            Expr::Binary(op, left, right, _) if op.is_comparison() => {
+                let (lhs, rhs) = match (
+                    Self::eval_value(left, scope),
+                    Self::eval_value(right, scope),
+                ) {
+                    (Some(lhs), Some(rhs)) => (lhs, rhs),
+                    (None, Some(rhs)) => (Self::symbol(*op, left, right, scope)?, rhs),
+                    (Some(lhs), None) if *op != BinaryOp::In => {
+                        let rhs = Self::symbol(*op, right, left, scope)?;
+                        (lhs, rhs)
+                    }
+                    (Some(_), None) => return None,
+                    (None, None) => return None,
+                };
+                Some(Self::compare(*op, &lhs, &rhs))
+            }
+            Expr::IsEmpty(operand, _) => Self::eval_value(operand, scope).map(|v| !v.is_truthy()),
+            Expr::IsType(operand, ty, _) => {
+                let value = Self::eval_value(operand, scope)?;
+                Some(matches!(
+                    (ty, &value),
+                    (PrimitiveType::Boolean, Value::Boolean(_))
+                        | (PrimitiveType::Integer, Value::Integer(_))
+                        | (PrimitiveType::Float, Value::Float(_))
+                        | (PrimitiveType::String, Value::String(_))
+                ))
+            }
+            Expr::Quantified(quantifier, var, collection, body, _) => {
+                let items = match Self::eval_value(collection, scope)? {
+                    Value::Array(items) => items,
+                    Value::Object(map) => map.into_values().collect(),
+                    _ => return None,
+                };
+                let ty = scope.declared_type(collection).and_then(element_type);
+                let target = *quantifier == Quantifier::Any;
+                let mut unknown = false;
+                for item in &items {
+                    let binding = Binding {
+                        name: var,
+                        value: item,
+                        ty,
+                        parent: scope.vars,
+                    };
+                    let inner = Scope {
+                        vars: Some(&binding),
+                        ..scope
+                    };
+                    // `any` stops at the first true element, `all` at the first false
+                    match Self::eval_bool(body, inner) {
+                        Some(result) if result == target => return Some(target),
+                        Some(_) => {}
+                        None => unknown = true,
+                    }
+                }
+                (!unknown).then_some(!target)
+            }
+            Expr::Literal(_)
+            | Expr::Path(_, _)
+            | Expr::Binary(..)
+            | Expr::Old(..)
+            | Expr::Index(..)
+            | Expr::Field(..)
+            | Expr::Call(..) => match Self::eval_value(expr, scope)? {
+                Value::Boolean(b) => Some(b),
+                _ => None,
+            },
         }
+    }
 
-        // Pattern: "<field> < <number>"
-        if let Some((field, num)) = Self::parse_comparison(trimmed, " < ") {
-            return (Self::numeric_cmp(state, field, num, |a, b| a < b), true);
+    /// Evaluate to a value — `None` if the result is unknown or a field,
+    /// index or key does not exist, which also sets `scope.missing`
+    fn eval_value(expr: &Expr, scope: Scope) -> Option<Value> {
+        match expr {
+            Expr::Literal(lit) => Some(Value::from_literal(lit)),
+            Expr::Path(segments, _) => {
+                let mut current = scope.lookup(&segments[0])?;
+                for segment in &segments[1..] {
+                    current = match current {
+                        Value::Object(map) => map.get(segment),
+                        _ => None,
+                    }
+                    .or_else(|| Self::missing(scope))?;
+                }
+                Some(current.clone())
+            }
+            Expr::Field(base, name, _) => match Self::eval_value(base, scope)? {
+                Value::Object(mut map) => map.remove(name),
+                _ => None,
+            }
+            .or_else(|| Self::missing(scope)),
+            Expr::Index(base, index, _) => {
+                match (
+                    Self::eval_value(base, scope)?,
+                    Self::eval_value(index, scope)?,
+                ) {
+                    (Value::Array(mut items), Value::Integer(i)) => {
+                        match usize::try_from(i).ok().filter(|i| *i < items.len()) {
+                            Some(i) => Some(items.swap_remove(i)),
+                            None => Self::missing(scope),
+                        }
+                    }
+                    (Value::Object(mut map), Value::String(key)) => {
+                        map.remove(&key).or_else(|| Self::missing(scope))
+                    }
+                    (Value::Null, _) => Self::missing(scope),
+                    _ => None,
+                }
+            }
+            Expr::Old(operand, _) => {
+                let before = Scope {
+                    state: scope.old?,
+                    old: None,
+                    ..scope
+                };
+                Self::eval_value(operand, before)
+            }
+            Expr::Call(name, args, _) => {
+                let args = args
+                    .iter()
+                    .map(|arg| Self::eval_value(arg, scope))
+                    .collect::<Option<Vec<_>>>()?;
+                builtins::lookup(name)?.call(&args)
+            }
+            Expr::Binary(op, left, right, _) if op.is_arithmetic() => {
+                let lhs = Self::eval_value(left, scope)?;
+                let rhs = Self::eval_value(right, scope)?;
+                Self::arithmetic(*op, &lhs, &rhs)
+            }
+            _ => Self::eval_bool(expr, scope).map(Value::Boolean),
         }
+    }
+
+    /// Record that a field, index or key does not exist
+    fn missing<T>(scope: Scope) -> Option<T> {
+        scope.missing.set(true);
+        None
+    }
 
-        // Pattern: "<field> is boolean"
-        if let Some(field) = trimmed.strip_suffix(" is boolean") {
-            let field = field.trim();
-            if let Some(Value::Boolean(_)) = state.get(field) {
-                return (true, true);
+    /// An unresolved bare name compared with `==`, `!=` or `in` against
+    /// an enum (or array of enums) declaring it as a variant stands for
+    /// that variant: `status == active`
+    fn symbol(op: BinaryOp, expr: &Expr, other: &Expr, scope: Scope) -> Option<Value> {
+        let ty = scope.declared_type(other)?;
+        let ty = match (op, ty.unwrapped()) {
+            (BinaryOp::In, Type::Array { items }) => items,
+            (BinaryOp::In, Type::Map { key, .. }) => key,
+            (BinaryOp::Eq | BinaryOp::Ne, _) => ty,
+            _ => return None,
+        };
+        match (expr, ty.unwrapped()) {
+            (Expr::Path(segments, _), Type::Enum { variants })
+                if segments.len() == 1 && variants.contains(&segments[0]) =>
+            {
+                Some(Value::String(segments[0].clone()))
             }
-            return (false, true);
+            _ => None,
         }
+    }
 
-        // Pattern: "<field> is valid ..." — advisory, always true
-        if trimmed.contains("is valid ") {
-            return (true, false);
+    /// Apply an arithmetic operator — `None` unless both sides are numbers
+    /// and the result is defined
+    fn arithmetic(op: BinaryOp, lhs: &Value, rhs: &Value) -> Option<Value> {
+        if let (Value::Integer(a), Value::Integer(b), false) = (lhs, rhs, op == BinaryOp::Div) {
+            let result = match op {
+                BinaryOp::Add => a.checked_add(*b),
+                BinaryOp::Sub => a.checked_sub(*b),
+                _ => a.checked_mul(*b),
+            };
+            return result.map(Value::Integer);
         }
+        let (a, b) = (Self::as_number(lhs)?, Self::as_number(rhs)?);
+        let result = match op {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mul => a * b,
+            BinaryOp::Div if b == 0.0 => return None,
+            _ => a / b,
+        };
+        result.is_finite().then_some(Value::Float(result))
+    }
+
+    /// Apply a comparison operator to two values
+    fn compare(op: BinaryOp, lhs: &Value, rhs: &Value) -> bool {
+        use std::cmp::Ordering::{Equal, Greater, Less};
+        let order = Self::order(lhs, rhs);
+        match op {
+            BinaryOp::Eq => Self::values_equal(lhs, rhs),
+            BinaryOp::Ne => !Self::values_equal(lhs, rhs),
+            BinaryOp::Lt => order == Some(Less),
+            BinaryOp::Le => matches!(order, Some(Less | Equal)),
+            BinaryOp::Gt => order == Some(Greater),
+            BinaryOp::Ge => matches!(order, Some(Greater | Equal)),
+            BinaryOp::In => match rhs {
+                Value::Array(items) => items.iter().any(|item| Self::values_equal(lhs, item)),
+                Value::Object(map) => matches!(lhs, Value::String(key) if map.contains_key(key)),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
 
-        // Opaque condition — not machine-evaluable, treat as true
-        (true, false)
+    /// Equality — Integer and Float compare numerically, timestamps by
+    /// instant, otherwise structural
+    pub(crate) fn values_equal(lhs: &Value, rhs: &Value) -> bool {
+        match Self::order(lhs, rhs) {
+            Some(order) => order.is_eq(),
+            None => lhs == rhs,
+        }
     }
 
-    /// Parse a comparison pattern like "field >= 0" into (field_name, number)
-    fn parse_comparison<'a>(s: &'a str, operator: &str) -> Option<(&'a str, f64)> {
-        let parts: Vec<&str> = s.splitn(2, operator).collect();
-        if parts.len() == 2 {
-            let field = parts[0].trim();
-            let num_str = parts[1].trim();
-            if let Ok(num) = num_str.parse::<f64>() {
-                return Some((field, num));
+    /// Ordering of two numbers or two ISO8601 timestamps; `None` otherwise
+    fn order(lhs: &Value, rhs: &Value) -> Option<std::cmp::Ordering> {
+        match (lhs, rhs) {
+            (Value::String(a), Value::String(b)) => {
+                Some(parse_timestamp(a)?.cmp(&parse_timestamp(b)?))
             }
+            _ => Self::as_number(lhs)?.partial_cmp(&Self::as_number(rhs)?),
         }
-        None
     }
 
-    /// Do numeric comparison on a state field
-    fn numeric_cmp(
-        state: &ExecutionState,
-        field: &str,
-        rhs: f64,
-        cmp: fn(f64, f64) -> bool,
-    ) -> bool {
-        match state.get(field) {
-            Some(Value::Integer(i)) => cmp(*i as f64, rhs),
-            Some(Value::Float(f)) => cmp(*f, rhs),
-            _ => false,
+    fn as_number(value: &Value) -> Option<f64> {
+        match value {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
         }
     }
 
-    /// Evaluate all contract invariants against state
+    /// Evaluate all contract invariants against state, whose declared
+    /// fields are `declared`
     pub fn check_invariants(
-        invariants: &[String],
+        invariants: &[Condition],
         state: &ExecutionState,
+        declared: &[Field],
     ) -> std::result::Result<(), Vec<String>> {
         let mut violations = Vec::new();
         for inv in invariants {
-            let (result, evaluable) = Self::evaluate(inv, state);
+            let (result, evaluable) = Self::evaluate_condition(inv, None, state, declared);
             if evaluable && !result {
-                violations.push(inv.clone());
+                violations.push(inv.text.clone());
             }
         }
         if violations.is_empty() {
@@ -379,6 +744,163 @@ impl ExpressionEvaluator {
     }
 }
 
+// ── Refinements ───────────────────────────────────────────
+
+/// Check a value against the refinements of its type, e.g. that an
+/// `Integer(min: 0)` is not negative, and that `UUID` and `ISO8601`
+/// strings are RFC 4122 UUIDs and RFC 3339 timestamps
+///
+/// Refinements nested in `Optional`, `Array`, `Map` and `Object` types
+/// are checked for every element and field. A refined value must have its
+/// base type before its bounds are checked, e.g. an `Integer(min: 0)` must
+/// be a JSON integer. A `pattern` matches anywhere in the string unless
+/// anchored with `^`/`$`.
+///
+/// Returns the first violation, e.g. `"scores[2] is 120, above max 100"`,
+/// where `name` is the root of the path.
+pub fn check_refinements(name: &str, value: &Value, ty: &Type) -> std::result::Result<(), String> {
+    match ty {
+        Type::Refined { base, refinements } => {
+            check_base_type(name, value, base)?;
+            check_refinements(name, value, base)?;
+            refinements
+                .iter()
+                .try_for_each(|r| check_refinement(name, value, r))
+        }
+        Type::Optional { inner } if *value != Value::Null => check_refinements(name, value, inner),
+        Type::Array { items } => match value {
+            Value::Array(values) => values.iter().enumerate().try_for_each(|(i, item)| {
+                check_refinements(&format!("{}[{}]", name, i), item, items)
+            }),
+            _ => Ok(()),
+        },
+        Type::Map { value: inner, .. } => match value {
+            Value::Object(map) => map.iter().try_for_each(|(key, item)| {
+                check_refinements(&format!("{}[{}]", name, quote(key)), item, inner)
+            }),
+            _ => Ok(()),
+        },
+        Type::Object { fields } => match value {
+            Value::Object(map) => fields
+                .iter()
+                .try_for_each(|field| match map.get(&field.name) {
+                    Some(item) => {
+                        check_refinements(&format!("{}.{}", name, field.name), item, &field.ty)
+                    }
+                    None => Ok(()),
+                }),
+            _ => Ok(()),
+        },
+        Type::Uuid => match value {
+            Value::String(s) if !is_uuid(s) => {
+                Err(format!("{} is {}, not an RFC 4122 UUID", name, quote(s)))
+            }
+            _ => Ok(()),
+        },
+        Type::Iso8601 => match value {
+            Value::String(s) if parse_timestamp(s).is_none() => Err(format!(
+                "{} is {}, not an RFC 3339 timestamp",
+                name,
+                quote(s)
+            )),
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Check that a value has the JSON shape of `ty`: an integer for `Integer`,
+/// any number for `Float`, a string for `String`, `UUID`, `ISO8601` and
+/// `Enum`, and so on. A non-null `Optional<T>` value must be a `T`.
+/// Elements of collections are not visited.
+fn check_base_type(name: &str, value: &Value, ty: &Type) -> std::result::Result<(), String> {
+    let matches = match ty {
+        Type::Integer => matches!(value, Value::Integer(_)),
+        Type::Float => matches!(value, Value::Integer(_) | Value::Float(_)),
+        Type::String | Type::Iso8601 | Type::Uuid | Type::Enum { .. } => {
+            matches!(value, Value::String(_))
+        }
+        Type::Boolean => matches!(value, Value::Boolean(_)),
+        Type::Array { .. } => matches!(value, Value::Array(_)),
+        Type::Map { .. } | Type::Object { .. } => matches!(value, Value::Object(_)),
+        Type::Refined { base, .. } => return check_base_type(name, value, base),
+        Type::Optional { inner } => {
+            return match value {
+                Value::Null => Ok(()),
+                _ => check_base_type(name, value, inner),
+            }
+        }
+        Type::Named { .. } => true,
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(format!("{} is {}, expected {}", name, value, ty))
+    }
+}
+
+fn check_refinement(
+    name: &str,
+    value: &Value,
+    refinement: &Refinement,
+) -> std::result::Result<(), String> {
+    let kind = refinement.name.as_str();
+    let limit = &refinement.value;
+    let violation = match (kind, value) {
+        ("min" | "max", Value::Integer(_) | Value::Float(_)) => {
+            out_of_bounds(kind, ExpressionEvaluator::as_number(value), limit)
+                .map(|side| format!("{} is {}, {} {} {}", name, value, side, kind, limit))
+        }
+        ("min_len" | "max_len", Value::String(s)) => {
+            let len = s.chars().count();
+            out_of_bounds(kind, Some(len as f64), limit)
+                .map(|side| format!("{} has length {}, {} {} {}", name, len, side, kind, limit))
+        }
+        ("min_items" | "max_items", Value::Array(_) | Value::Object(_)) => {
+            let len = match value {
+                Value::Array(items) => items.len(),
+                Value::Object(map) => map.len(),
+                _ => unreachable!(),
+            };
+            out_of_bounds(kind, Some(len as f64), limit)
+                .map(|side| format!("{} has {} items, {} {} {}", name, len, side, kind, limit))
+        }
+        ("pattern", Value::String(s)) => match limit.as_str() {
+            Some(pattern) => match Regex::new(pattern) {
+                Ok(re) if re.is_match(s) => None,
+                Ok(_) => Some(format!(
+                    "{} does not match pattern {}",
+                    name,
+                    quote(pattern)
+                )),
+                Err(_) => Some(format!("{} has invalid pattern {}", name, quote(pattern))),
+            },
+            None => None,
+        },
+        _ => None,
+    };
+    match violation {
+        Some(message) => Err(message),
+        None => Ok(()),
+    }
+}
+
+/// `"below"` / `"above"` if `actual` falls outside a `min*` / `max*` limit
+fn out_of_bounds(
+    kind: &str,
+    actual: Option<f64>,
+    limit: &serde_json::Value,
+) -> Option<&'static str> {
+    let (actual, limit) = (actual?, limit.as_f64()?);
+    if kind.starts_with("min") && actual < limit {
+        Some("below")
+    } else if kind.starts_with("max") && actual > limit {
+        Some("above")
+    } else {
+        None
+    }
+}
+
 // ── Sandbox ───────────────────────────────────────────────
 
 /// Isolated execution environment with resource limits
@@ -493,6 +1015,42 @@ pub struct ProvenanceEntry {
     pub postconditions_verified: bool,
     /// Whether all invariants held
     pub invariants_verified: bool,
+    /// Every condition checked: the precondition, the postcondition, then
+    /// each invariant
+    #[serde(default)]
+    pub conditions: Vec<ConditionCheck>,
+}
+
+/// One condition checked during an operation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConditionCheck {
+    pub role: ConditionRole,
+    /// Condition text as written in the contract
+    pub condition: String,
+    /// Whether the condition was machine-evaluated. Opaque conditions
+    /// are not, and count as holding.
+    pub evaluated: bool,
+    /// Whether the condition held
+    pub holds: bool,
+}
+
+/// Where a checked condition comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionRole {
+    Precondition,
+    Postcondition,
+    Invariant,
+}
+
+impl std::fmt::Display for ConditionRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionRole::Precondition => write!(f, "precondition"),
+            ConditionRole::Postcondition => write!(f, "postcondition"),
+            ConditionRole::Invariant => write!(f, "invariant"),
+        }
+    }
 }
 
 /// A single field change within a state transition
@@ -571,6 +1129,15 @@ pub struct ExecutionResult {
 
 // ── Executor ──────────────────────────────────────────────
 
+/// Execution policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecuteOptions {
+    /// Fail an operation when its precondition, its postcondition or an
+    /// invariant cannot be machine-evaluated, instead of treating it as
+    /// holding
+    pub strict_conditions: bool,
+}
+
 /// The contract executor — runs operations deterministically in a sandbox
 pub struct Executor {
     /// The contract being executed
@@ -583,11 +1150,18 @@ pub struct Executor {
     provenance: ProvenanceLog,
     /// Operation counter
     sequence: u64,
+    /// Execution policy
+    options: ExecuteOptions,
 }
 
 impl Executor {
     /// Create a new executor for a contract
     pub fn new(contract: Contract) -> Self {
+        Self::with_options(contract, ExecuteOptions::default())
+    }
+
+    /// Create a new executor for a contract with an execution policy
+    pub fn with_options(contract: Contract, options: ExecuteOptions) -> Self {
         let state = ExecutionState::from_contract(&contract);
         let sandbox = Sandbox::from_contract(&contract);
         Executor {
@@ -596,6 +1170,7 @@ impl Executor {
             sandbox,
             provenance: ProvenanceLog::new(),
             sequence: 0,
+            options,
         }
     }
 
@@ -623,16 +1198,38 @@ impl Executor {
             })?
             .clone();
 
-        // 2. Parse inputs
-        let inputs: serde_json::Value = serde_json::from_str(inputs_json)
+        // 2. Parse inputs, filling in parameter defaults
+        let mut inputs: serde_json::Value = serde_json::from_str(inputs_json)
             .map_err(|e| Error::ExecutionError(format!("Invalid JSON input: {}", e)))?;
+        Self::fill_defaults(&op, &mut inputs);
 
         // 3. Validate input parameters against operation definition
         self.validate_inputs(&op, &inputs)?;
 
-        // 4. Check precondition
+        // 4. Check precondition, with the inputs shadowing state fields
+        let declared: Vec<Field> = self
+            .contract
+            .data_semantics
+            .state
+            .iter()
+            .chain(&op.parameters)
+            .cloned()
+            .collect();
+        let mut scope = self.state.clone();
+        if let serde_json::Value::Object(input_map) = &inputs {
+            for (key, value) in input_map {
+                scope.set(key.clone(), Value::from_json(value));
+            }
+        }
         let (pre_result, pre_evaluable) =
-            ExpressionEvaluator::evaluate(&op.precondition, &self.state);
+            ExpressionEvaluator::evaluate_condition(&op.precondition, None, &scope, &declared);
+        let mut conditions = vec![ConditionCheck {
+            role: ConditionRole::Precondition,
+            condition: op.precondition.text.clone(),
+            evaluated: pre_evaluable,
+            holds: pre_result,
+        }];
+        self.require_evaluated(&conditions[0], operation_name)?;
         if pre_evaluable && !pre_result {
             return Err(Error::ExecutionError(format!(
                 "Precondition failed for operation '{}': {}",
@@ -660,45 +1257,89 @@ impl Executor {
             }
         }
 
-        // 8. Check postcondition
-        let (post_result, post_evaluable) =
-            ExpressionEvaluator::evaluate(&op.postcondition, &self.state);
+        // 8. Check refinements of every changed state field
+        if let Err(violation) = self.check_state_refinements(&state_before) {
+            self.state.fields = state_before;
+            return Err(Error::ContractViolation {
+                commitment: "type refinement".into(),
+                violation,
+            });
+        }
+
+        // 9. Check postcondition, with old(...) reading the snapshot
+        let before = ExecutionState {
+            fields: state_before.clone(),
+        };
+        let (post_result, post_evaluable) = ExpressionEvaluator::evaluate_condition(
+            &op.postcondition,
+            Some(&before),
+            &self.state,
+            &declared,
+        );
         let postconditions_verified = !post_evaluable || post_result;
+        conditions.push(ConditionCheck {
+            role: ConditionRole::Postcondition,
+            condition: op.postcondition.text.clone(),
+            evaluated: post_evaluable,
+            holds: post_result,
+        });
+        if let Err(e) = self.require_evaluated(&conditions[1], operation_name) {
+            self.state.fields = state_before;
+            return Err(e);
+        }
 
         if post_evaluable && !post_result {
             // Rollback state
             self.state.fields = state_before;
             return Err(Error::ContractViolation {
                 commitment: format!("postcondition of '{}'", operation_name),
-                violation: op.postcondition.clone(),
+                violation: op.postcondition.text.clone(),
             });
         }
 
-        // 9. Check all invariants
-        let invariants_verified = match ExpressionEvaluator::check_invariants(
-            &self.contract.data_semantics.invariants,
-            &self.state,
-        ) {
-            Ok(()) => true,
-            Err(violations) => {
-                // Rollback state
+        // 10. Check all invariants
+        let mut violations = Vec::new();
+        for invariant in &self.contract.data_semantics.invariants {
+            let (holds, evaluated) = ExpressionEvaluator::evaluate_condition(
+                invariant,
+                None,
+                &self.state,
+                &self.contract.data_semantics.state,
+            );
+            let check = ConditionCheck {
+                role: ConditionRole::Invariant,
+                condition: invariant.text.clone(),
+                evaluated,
+                holds,
+            };
+            if let Err(e) = self.require_evaluated(&check, operation_name) {
                 self.state.fields = state_before;
-                return Err(Error::ContractViolation {
-                    commitment: "invariant".into(),
-                    violation: format!("Violated invariants: {}", violations.join(", ")),
-                });
+                return Err(e);
             }
-        };
+            if !holds {
+                violations.push(invariant.text.clone());
+            }
+            conditions.push(check);
+        }
+        if !violations.is_empty() {
+            // Rollback state
+            self.state.fields = state_before;
+            return Err(Error::ContractViolation {
+                commitment: "invariant".into(),
+                violation: format!("Violated invariants: {}", violations.join(", ")),
+            });
+        }
+        let invariants_verified = true;
 
-        // 10. Check resource limits
+        // 11. Check resource limits
         self.sandbox.check_memory(&self.state).inspect_err(|_| {
             self.state.fields = state_before.clone();
         })?;
 
-        // 11. Compute changes
+        // 12. Compute changes
         let changes = Self::compute_changes(&state_before, &self.state.fields);
 
-        // 12. Record provenance
+        // 13. Record provenance
         let entry = ProvenanceEntry {
             sequence: self.sequence,
             operation: operation_name.to_string(),
@@ -708,6 +1349,7 @@ impl Executor {
             changes,
             postconditions_verified,
             invariants_verified,
+            conditions,
         };
         self.provenance.append(entry.clone());
         self.sequence += 1;
@@ -721,27 +1363,99 @@ impl Executor {
         })
     }
 
+    /// Under `strict_conditions`, a condition that could not be evaluated
+    /// fails the operation
+    fn require_evaluated(&self, check: &ConditionCheck, operation: &str) -> Result<()> {
+        if self.options.strict_conditions && !check.evaluated {
+            return Err(Error::ExecutionError(format!(
+                "Strict conditions: {} '{}' of operation '{}' could not be evaluated",
+                check.role, check.condition, operation
+            )));
+        }
+        Ok(())
+    }
+
+    /// Add the declared default of every parameter missing from `inputs`
+    pub(crate) fn fill_defaults(op: &crate::Operation, inputs: &mut serde_json::Value) {
+        if let serde_json::Value::Object(input_map) = inputs {
+            for param in &op.parameters {
+                if let Some(ref default) = param.default {
+                    input_map
+                        .entry(param.name.clone())
+                        .or_insert_with(|| default.clone());
+                }
+            }
+        }
+    }
+
     /// Validate that inputs match operation parameter types
+    ///
+    /// `Optional<T>` parameters may be omitted or `null`; all others are
+    /// required and non-null. Present values must have the base type of
+    /// the parameter (the inner type, for a non-null `Optional<T>`) and
+    /// satisfy the refinements of their type.
     fn validate_inputs(&self, op: &crate::Operation, inputs: &serde_json::Value) -> Result<()> {
-        if let serde_json::Value::Object(params_def) = &op.parameters {
-            if let serde_json::Value::Object(input_map) = inputs {
-                // Check all required parameters are provided
-                for (param_name, _param_type) in params_def {
-                    if !input_map.contains_key(param_name) {
+        if let serde_json::Value::Object(input_map) = inputs {
+            for param in &op.parameters {
+                // `null` is checked against nullability below
+                if let Some(value) = input_map.get(&param.name).filter(|v| !v.is_null()) {
+                    let value = Value::from_json(value);
+                    check_base_type(&param.name, &value, &param.ty)
+                        .and_then(|()| check_refinements(&param.name, &value, &param.ty))
+                        .map_err(|violation| {
+                            Error::ExecutionError(format!(
+                                "Parameter '{}' of operation '{}' violates its type: {}",
+                                param.name, op.name, violation
+                            ))
+                        })?;
+                }
+                if param.ty.is_optional() {
+                    continue;
+                }
+                match input_map.get(&param.name) {
+                    None => {
                         return Err(Error::ExecutionError(format!(
                             "Missing required parameter '{}' for operation '{}'",
-                            param_name, op.name
-                        )));
+                            param.name, op.name
+                        )))
+                    }
+                    Some(serde_json::Value::Null) => {
+                        return Err(Error::ExecutionError(format!(
+                            "Parameter '{}' of operation '{}' is not Optional and cannot be null",
+                            param.name, op.name
+                        )))
                     }
+                    Some(_) => {}
                 }
             }
         }
         Ok(())
     }
 
+    /// The declared state field `name`, if any
+    fn state_field(&self, name: &str) -> Option<&Field> {
+        self.contract
+            .data_semantics
+            .state
+            .iter()
+            .find(|f| f.name == name)
+    }
+
     /// Apply input values to the execution state
+    ///
+    /// Declared state fields only accept `null` if they are `Optional<T>`;
+    /// nothing is applied if any input violates this.
     fn apply_inputs(&mut self, inputs: &serde_json::Value) -> Result<()> {
         if let serde_json::Value::Object(input_map) = inputs {
+            for (key, value) in input_map {
+                let declared = self.state_field(key);
+                if value.is_null() && declared.is_some_and(|f| !f.ty.is_optional()) {
+                    return Err(Error::ExecutionError(format!(
+                        "State field '{}' is not Optional and cannot be set to null",
+                        key
+                    )));
+                }
+            }
             for (key, value) in input_map {
                 let typed_value = Value::from_json(value);
                 self.state.set(key.clone(), typed_value);
@@ -750,6 +1464,23 @@ impl Executor {
         Ok(())
     }
 
+    /// Check declared state fields that differ from `before` against the
+    /// refinements of their types
+    fn check_state_refinements(
+        &self,
+        before: &BTreeMap<String, Value>,
+    ) -> std::result::Result<(), String> {
+        for field in &self.contract.data_semantics.state {
+            match self.state.get(&field.name) {
+                Some(value) if before.get(&field.name) != Some(value) => {
+                    check_refinements(&field.name, value, &field.ty)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     /// Compute the list of field changes between two state snapshots
     fn compute_changes(
         before: &BTreeMap<String, Value>,
@@ -873,7 +1604,16 @@ impl Executor {
 /// - Verifiable: preconditions checked, postconditions verified
 /// - Logged: all state changes recorded in provenance
 pub fn execute_contract(contract: &Contract, inputs: &str) -> Result<String> {
-    let mut executor = Executor::new(contract.clone());
+    execute_contract_with_options(contract, inputs, ExecuteOptions::default())
+}
+
+/// Execute a contract with given inputs under an execution policy
+pub fn execute_contract_with_options(
+    contract: &Contract,
+    inputs: &str,
+    options: ExecuteOptions,
+) -> Result<String> {
+    let mut executor = Executor::with_options(contract.clone(), options);
 
     // Detect if inputs is a single request or array
     let inputs_trimmed = inputs.trim();
@@ -900,6 +1640,14 @@ mod tests {
     use super::*;
     use crate::*;
 
+    /// Fields from `(name, type)` pairs written in ICL syntax
+    fn fields(specs: &[(&str, &str)]) -> Vec<Field> {
+        specs
+            .iter()
+            .map(|(name, ty)| Field::new(*name, ty.parse().unwrap()))
+            .collect()
+    }
+
     /// Helper: create a minimal contract for testing
     fn test_contract() -> Contract {
         Contract {
@@ -916,19 +1664,14 @@ mod tests {
                 confidence_level: 1.0,
             },
             data_semantics: DataSemantics {
-                state: serde_json::json!({
-                    "message": "String",
-                    "count": "Integer"
-                }),
+                state: fields(&[("message", "String"), ("count", "Integer")]),
                 invariants: vec!["message is not empty".into(), "count >= 0".into()],
             },
             behavioral_semantics: BehavioralSemantics {
                 operations: vec![Operation {
                     name: "echo".into(),
                     precondition: "input_provided".into(),
-                    parameters: serde_json::json!({
-                        "message": "String"
-                    }),
+                    parameters: fields(&[("message", "String")]),
                     postcondition: "state_updated".into(),
                     side_effects: vec!["log_operation".into()],
                     idempotence: "idempotent".into(),
@@ -1123,6 +1866,62 @@ mod tests {
         assert!(result); // opaque = pass
     }
 
+    #[test]
+    fn test_eval_boolean_connectives() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        state.set("count".into(), Value::Integer(5));
+        state.set("active".into(), Value::Boolean(true));
+
+        assert!(ExpressionEvaluator::evaluate("active and count > 0", &state).0);
+        assert!(!ExpressionEvaluator::evaluate("active AND count > 10", &state).0);
+        assert!(ExpressionEvaluator::evaluate("count > 10 or active", &state).0);
+        assert!(!ExpressionEvaluator::evaluate("not (count == 5)", &state).0);
+        assert!(ExpressionEvaluator::evaluate("active is true", &state).0);
+    }
+
+    #[test]
+    fn test_eval_unknown_field_is_opaque() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        state.set("count".into(), Value::Integer(5));
+
+        assert_eq!(
+            ExpressionEvaluator::evaluate("input_provided", &state),
+            (true, false)
+        );
+        // A known false operand decides `and` even if the other side is unknown
+        assert_eq!(
+            ExpressionEvaluator::evaluate("count < 0 and missing > 1", &state),
+            (false, true)
+        );
+        assert_eq!(
+            ExpressionEvaluator::evaluate("count > 0 and missing > 1", &state),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_eval_nested_object_path() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        let mut profile = BTreeMap::new();
+        profile.insert("age".to_string(), Value::Integer(30));
+        state.set("profile".into(), Value::Object(profile));
+
+        assert_eq!(
+            ExpressionEvaluator::evaluate("profile.age >= 18", &state),
+            (true, true)
+        );
+        assert_eq!(
+            ExpressionEvaluator::evaluate("profile.age < 18", &state),
+            (false, true)
+        );
+    }
+
     #[test]
     fn test_check_invariants_all_pass() {
         let mut state = ExecutionState {
@@ -1132,7 +1931,7 @@ mod tests {
         state.set("count".into(), Value::Integer(5));
 
         let invariants = vec!["message is not empty".into(), "count >= 0".into()];
-        assert!(ExpressionEvaluator::check_invariants(&invariants, &state).is_ok());
+        assert!(ExpressionEvaluator::check_invariants(&invariants, &state, &[]).is_ok());
     }
 
     #[test]
@@ -1144,7 +1943,7 @@ mod tests {
         state.set("count".into(), Value::Integer(5));
 
         let invariants = vec!["message is not empty".into(), "count >= 0".into()];
-        let result = ExpressionEvaluator::check_invariants(&invariants, &state);
+        let result = ExpressionEvaluator::check_invariants(&invariants, &state, &[]);
         assert!(result.is_err());
         let violations = result.unwrap_err();
         assert_eq!(violations, vec!["message is not empty"]);
@@ -1231,6 +2030,7 @@ mod tests {
             changes: vec![],
             postconditions_verified: true,
             invariants_verified: true,
+            conditions: vec![],
         };
         log.append(entry);
         assert_eq!(log.len(), 1);
@@ -1320,6 +2120,293 @@ mod tests {
         assert_eq!(*executor.state(), state_after_success);
     }
 
+    /// `test_contract` plus an `Optional<String>` state field and parameter
+    fn optional_contract() -> Contract {
+        let mut contract = test_contract();
+        contract.data_semantics.state = fields(&[
+            ("message", "String"),
+            ("count", "Integer"),
+            ("nickname", "Optional<String>"),
+            ("tags", "Array<String>"),
+        ]);
+        contract.behavioral_semantics.operations[0].parameters =
+            fields(&[("message", "String"), ("nickname", "Optional<String>")]);
+        contract
+    }
+
+    #[test]
+    fn test_execution_state_optional_defaults() {
+        let state = ExecutionState::from_contract(&optional_contract());
+        assert_eq!(state.get("nickname"), Some(&Value::Null));
+        assert_eq!(state.get("tags"), Some(&Value::Array(Vec::new())));
+    }
+
+    #[test]
+    fn test_execute_null_only_for_optional() {
+        let mut executor = Executor::new(optional_contract());
+
+        // Optional parameter may be omitted or null
+        executor
+            .execute_operation("echo", r#"{"message": "hi"}"#)
+            .unwrap();
+        executor
+            .execute_operation("echo", r#"{"message": "hi", "nickname": null}"#)
+            .unwrap();
+        assert_eq!(executor.state().get("nickname"), Some(&Value::Null));
+
+        let before = executor.state().clone();
+        let err = executor
+            .execute_operation("echo", r#"{"message": null}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("is not Optional"), "{}", err);
+
+        let err = executor
+            .execute_operation("echo", r#"{"message": "hi", "count": null}"#)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("State field 'count' is not Optional and cannot be set to null"),
+            "{}",
+            err
+        );
+        assert_eq!(*executor.state(), before);
+    }
+
+    #[test]
+    fn test_execute_checks_optional_inner_type() {
+        let mut contract = test_contract();
+        contract.behavioral_semantics.operations[0].parameters =
+            fields(&[("message", "String"), ("limit", "Optional<Integer>")]);
+        let mut executor = Executor::new(contract);
+
+        let err = executor
+            .execute_operation("echo", r#"{"message": "hi", "limit": "str"}"#)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Parameter 'limit' of operation 'echo' violates its type: \
+             limit is \"str\", expected Integer"
+        );
+        executor
+            .execute_operation("echo", r#"{"message": "hi", "limit": null}"#)
+            .unwrap();
+        executor
+            .execute_operation("echo", r#"{"message": "hi", "limit": 3}"#)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_execution_state_typed_defaults() {
+        let mut contract = test_contract();
+        contract.data_semantics.state = fields(&[
+            ("scores", "Map<String, Integer>"),
+            ("status", "Enum [\"open\", \"closed\"]"),
+            (
+                "owner",
+                "Object { name: String, age: Integer = 7, nick: Optional<String> }",
+            ),
+        ]);
+        contract.data_semantics.state[0].default = Some(serde_json::json!({"a": 1}));
+        let state = ExecutionState::from_contract(&contract);
+
+        assert_eq!(
+            state.get("scores"),
+            Some(&Value::from_json(&serde_json::json!({"a": 1})))
+        );
+        assert_eq!(state.get("status"), Some(&Value::String("open".into())));
+        assert_eq!(
+            state.get("owner"),
+            Some(&Value::from_json(
+                &serde_json::json!({"name": "", "age": 7, "nick": null})
+            ))
+        );
+    }
+
+    #[test]
+    fn test_execute_parameter_defaults() {
+        let mut contract = test_contract();
+        contract.behavioral_semantics.operations[0].parameters =
+            fields(&[("message", "String"), ("count", "Integer(min: 1)")]);
+        contract.behavioral_semantics.operations[0].parameters[1].default =
+            Some(serde_json::json!(5));
+        let mut executor = Executor::new(contract);
+
+        let result = executor
+            .execute_operation("echo", r#"{"message": "hi"}"#)
+            .unwrap();
+        assert_eq!(executor.state().get("count"), Some(&Value::Integer(5)));
+        assert_eq!(
+            result.provenance.unwrap().inputs,
+            serde_json::json!({"message": "hi", "count": 5})
+        );
+
+        // An explicit input wins over the default, and is still checked
+        executor
+            .execute_operation("echo", r#"{"message": "hi", "count": 2}"#)
+            .unwrap();
+        assert_eq!(executor.state().get("count"), Some(&Value::Integer(2)));
+        assert!(executor
+            .execute_operation("echo", r#"{"message": "hi", "count": 0}"#)
+            .is_err());
+    }
+
+    fn refined_contract() -> Contract {
+        let mut contract = test_contract();
+        contract.data_semantics.state = fields(&[
+            ("message", "String(min_len: 1, max_len: 5)"),
+            ("count", "Integer(min: 1)"),
+            ("scores", "Map<String, Integer(max: 10)>"),
+        ]);
+        contract.behavioral_semantics.operations[0].parameters =
+            fields(&[("message", "String(pattern: \"^h\")")]);
+        contract
+    }
+
+    #[test]
+    fn test_check_refinements_nested() {
+        let ty: Type =
+            "Array<Object { name: String(min_len: 1), tags: Map<String, Integer(min: 0)> }>"
+                .parse()
+                .unwrap();
+        let value = Value::from_json(&serde_json::json!([
+            {"name": "a", "tags": {"x": 1}},
+            {"name": "b", "tags": {"y": -1}}
+        ]));
+        assert_eq!(
+            check_refinements("items", &value, &ty),
+            Err("items[1].tags[\"y\"] is -1, below min 0".to_string())
+        );
+
+        let ty: Type = "Array<Integer>(min_items: 1, max_items: 2)"
+            .parse()
+            .unwrap();
+        let value = Value::from_json(&serde_json::json!([1, 2, 3]));
+        assert_eq!(
+            check_refinements("ids", &value, &ty),
+            Err("ids has 3 items, above max_items 2".to_string())
+        );
+        // The base type is checked before the refinements
+        assert_eq!(
+            check_refinements("ids", &Value::Integer(3), &ty),
+            Err("ids is 3, expected Array<Integer>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_execute_rejects_non_integral_refined_integer() {
+        let mut contract = test_contract();
+        contract.behavioral_semantics.operations[0].parameters =
+            fields(&[("message", "String"), ("count", "Integer(min: 0, max: 10)")]);
+        let mut executor = Executor::new(contract);
+
+        let err = executor
+            .execute_operation("echo", r#"{"message": "hi", "count": 3.5}"#)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Parameter 'count' of operation 'echo' violates its type: \
+             count is 3.5, expected Integer"
+        );
+        executor
+            .execute_operation("echo", r#"{"message": "hi", "count": 3}"#)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_check_refinements_uuid_and_timestamp() {
+        let uuid = Type::Uuid;
+        let valid = Value::String("550e8400-e29b-41d4-a716-446655440000".into());
+        assert!(check_refinements("id", &valid, &uuid).is_ok());
+        assert_eq!(
+            check_refinements("id", &Value::String("not-a-uuid".into()), &uuid),
+            Err("id is \"not-a-uuid\", not an RFC 4122 UUID".to_string())
+        );
+
+        let ts = Type::Iso8601;
+        let valid = Value::String("2026-02-01T12:30:00+01:00".into());
+        assert!(check_refinements("at", &valid, &ts).is_ok());
+        assert_eq!(
+            check_refinements("at", &Value::String("2026-02-30T00:00:00Z".into()), &ts),
+            Err("at is \"2026-02-30T00:00:00Z\", not an RFC 3339 timestamp".to_string())
+        );
+    }
+
+    #[test]
+    fn test_execution_state_leaves_uuid_and_timestamp_unset() {
+        let mut contract = test_contract();
+        contract.data_semantics.state = fields(&[
+            ("message", "String"),
+            ("id", "UUID"),
+            ("at", "ISO8601"),
+            ("owner", "Object { id: UUID, name: String }"),
+        ]);
+        let mut executor = Executor::new(contract);
+        let state = executor.state();
+        assert_eq!(state.get("id"), None);
+        assert_eq!(state.get("at"), None);
+        assert_eq!(
+            state.get("owner"),
+            Some(&Value::from_json(&serde_json::json!({"name": ""})))
+        );
+
+        // Unset fields are only checked once an operation sets them
+        executor
+            .execute_operation("echo", r#"{"message": "hi"}"#)
+            .unwrap();
+        executor
+            .execute_operation(
+                "echo",
+                r#"{"message": "hi", "id": "550e8400-e29b-41d4-a716-446655440000"}"#,
+            )
+            .unwrap();
+        assert!(executor
+            .execute_operation("echo", r#"{"message": "hi", "at": ""}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_execution_state_refined_defaults() {
+        let state = ExecutionState::from_contract(&refined_contract());
+        assert_eq!(state.get("message"), Some(&Value::String(String::new())));
+        assert_eq!(state.get("count"), Some(&Value::Integer(0)));
+        assert_eq!(state.get("scores"), Some(&Value::Object(BTreeMap::new())));
+    }
+
+    #[test]
+    fn test_execute_enforces_refinements() {
+        let mut executor = Executor::new(refined_contract());
+        executor
+            .execute_operation("echo", r#"{"message": "hi", "count": 2}"#)
+            .unwrap();
+        let before = executor.state().clone();
+
+        // Inputs are checked against parameter refinements
+        let err = executor
+            .execute_operation("echo", r#"{"message": "yo"}"#)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Parameter 'message' of operation 'echo' violates its type: \
+             message does not match pattern \"^h\""
+        );
+
+        // The new state is checked against state refinements and rolled back
+        let err = executor
+            .execute_operation("echo", r#"{"message": "hello!"}"#)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("message has length 6, above max_len 5"),
+            "{}",
+            err
+        );
+        let err = executor
+            .execute_operation("echo", r#"{"message": "hi", "scores": {"a": 11}}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("scores[\"a\"] is 11"), "{}", err);
+        assert_eq!(*executor.state(), before);
+    }
+
     #[test]
     fn test_execute_all_success() {
         let contract = test_contract();
@@ -1525,6 +2612,444 @@ mod tests {
         assert!(err.contains("Precondition failed"));
     }
 
+    #[test]
+    fn test_precondition_reads_parameters() {
+        let mut contract = test_contract();
+        let op = &mut contract.behavioral_semantics.operations[0];
+        op.parameters = fields(&[("amount", "Integer")]);
+        op.precondition = "amount > 0".into();
+        op.postcondition = "amount == 5".into();
+        contract.data_semantics.invariants.clear();
+
+        let mut executor = Executor::new(contract.clone());
+        let err = executor
+            .execute_operation("echo", r#"{"amount": -5}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("Precondition failed"), "{}", err);
+        assert!(executor.state().get("amount").is_none());
+
+        let result = executor
+            .execute_operation("echo", r#"{"amount": 5}"#)
+            .unwrap();
+        let check = &result.provenance.unwrap().conditions[0];
+        assert!(check.evaluated && check.holds);
+
+        // Strict execution accepts what strict verification accepts
+        let strict = ExecuteOptions {
+            strict_conditions: true,
+        };
+        let mut executor = Executor::with_options(contract, strict);
+        assert!(executor
+            .execute_operation("echo", r#"{"amount": 5}"#)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        state.set("a".into(), Value::Integer(7));
+        state.set("b".into(), Value::Integer(2));
+        state.set("x".into(), Value::Float(1.5));
+        state.set("big".into(), Value::Integer(i64::MAX));
+
+        for (condition, expected) in [
+            ("a + b == 9", true),
+            ("a - b * 3 == 1", true),
+            ("a / b == 3.5", true),
+            ("x * 2 == 3", true),
+            ("a + x > 8.5", false),
+            ("(a + b) * 2 == 18", true),
+        ] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (expected, true),
+                "{}",
+                condition
+            );
+        }
+
+        // Undefined results leave the condition unevaluable
+        for condition in ["a / 0 == 1", "big + 1 > 0", "a + \"s\" == 1"] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (true, false),
+                "{}",
+                condition
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_nested_and_indexed_paths() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        let json = serde_json::json!({
+            "profile": {"age": 36},
+            "scores": [3, 5, 8],
+            "counters": {"a": 1},
+            "entries": [{"label": "x", "tags": ["t"]}]
+        });
+        for (name, value) in json.as_object().unwrap() {
+            state.set(name.clone(), Value::from_json(value));
+        }
+
+        for (condition, expected) in [
+            ("profile.age >= 0", true),
+            ("scores[2] == 8", true),
+            ("scores[1 + 1] > scores[0]", true),
+            ("counters['a'] == 1", true),
+            ("entries[0].label == 'x'", true),
+            ("entries[0].tags[0] == 'y'", false),
+        ] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (expected, true),
+                "{}",
+                condition
+            );
+        }
+
+        // A missing index, key or field fails the whole condition
+        for condition in [
+            "scores[3] == 0",
+            "scores[-1] == 0",
+            "counters['b'] == 0",
+            "profile.height > 0",
+            "not (profile.height > 0)",
+            "scores[3] == 0 or profile.age > 0",
+            "entries[0].label.first == 'x'",
+        ] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (false, true),
+                "{}",
+                condition
+            );
+        }
+        // unless the left side of `and`/`or` already decides it
+        assert_eq!(
+            ExpressionEvaluator::evaluate("profile.age > 0 or scores[3] == 0", &state),
+            (true, true)
+        );
+        // An index of the wrong type leaves it unevaluable
+        assert_eq!(
+            ExpressionEvaluator::evaluate("scores['a'] == 0", &state),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_comparisons_between_fields() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        let json = serde_json::json!({
+            "count": 3,
+            "max_count": 5,
+            "ratio": 3.0,
+            "status": "created",
+            "active": true,
+            "tags": ["a", "b"],
+            "counters": {"x": 1},
+            "started_at": "2026-02-01T10:00:00Z",
+            "ended_at": "2026-02-01T11:30:00+02:00"
+        });
+        for (name, value) in json.as_object().unwrap() {
+            state.set(name.clone(), Value::from_json(value));
+        }
+
+        for (condition, expected) in [
+            ("count <= max_count", true),
+            ("count == ratio", true),
+            ("status == 'created'", true),
+            ("active == true", true),
+            ("status in ['created', 'shipped']", true),
+            ("status not in ['shipped']", true),
+            ("'a' in tags", true),
+            ("count in [1, 2, 3.0]", true),
+            ("'x' in counters", true),
+            ("'y' in counters", false),
+            // 11:30+02:00 is 09:30Z, before the start
+            ("ended_at < started_at", true),
+            ("started_at == '2026-02-01T12:00:00+02:00'", true),
+            ("started_at > '2026-02-01T09:59:59.999Z'", true),
+            // Strings that are not timestamps are not ordered
+            ("status < 'd'", false),
+        ] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (expected, true),
+                "{}",
+                condition
+            );
+        }
+
+        // A bare name only stands for a variant of a declared enum
+        let declared = fields(&[
+            ("status", "Enum[\"created\", \"shipped\"]"),
+            ("statuses", "Array<Enum[\"created\", \"shipped\"]>"),
+            ("count", "Integer"),
+            ("owner", "String"),
+        ]);
+        state.set("owner".into(), Value::String("ada".into()));
+        state.set(
+            "statuses".into(),
+            Value::from_json(&serde_json::json!(["shipped"])),
+        );
+        for (condition, expected) in [
+            ("status equals created", (true, true)),
+            ("status != shipped", (true, true)),
+            ("created in statuses", (false, true)),
+            ("all s in statuses: s == shipped", (true, true)),
+            ("status == archived", (true, false)),
+            ("count == finite", (true, false)),
+            ("owner != previus_owner", (true, false)),
+            ("owner == ada", (true, false)),
+        ] {
+            let condition = Condition::from(condition);
+            assert_eq!(
+                ExpressionEvaluator::evaluate_condition(&condition, None, &state, &declared),
+                expected,
+                "{}",
+                condition
+            );
+        }
+        // Without declared types, no bare name is a variant
+        assert_eq!(
+            ExpressionEvaluator::evaluate("status == created", &state),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_builtin_calls() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        let json = serde_json::json!({
+            "items": ["a", "b", "c"],
+            "tags": ["urgent"],
+            "scores": [4, -1, 7],
+            "metadata": {"owner": "ada", "team": "core"},
+            "email": "ada@example.com",
+            "session_id": "123e4567-e89b-12d3-a456-426614174000"
+        });
+        for (name, value) in json.as_object().unwrap() {
+            state.set(name.clone(), Value::from_json(value));
+        }
+
+        for (condition, expected) in [
+            ("len(items) <= 100", true),
+            ("len(items) == len(metadata) + 1", true),
+            ("contains(tags, \"urgent\")", true),
+            (
+                "contains(metadata, 'owner') and not contains(tags, 'x')",
+                true,
+            ),
+            ("matches(email, \"^[^@]+@[^@]+$\")", true),
+            (
+                "sum(scores) >= 0 and min(scores) == -1 and max(scores) == 7",
+                true,
+            ),
+            ("keys(metadata) == ['owner', 'team']", true),
+            ("all v in values(metadata): len(v) > 2", true),
+            ("is_uuid(session_id) and not is_uuid(email)", true),
+            (
+                "starts_with(upper(email), 'ADA') and ends_with(lower(email), '.com')",
+                true,
+            ),
+            ("abs(min(scores)) > 1", false),
+            ("length(email) == len(email)", true),
+        ] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (expected, true),
+                "{}",
+                condition
+            );
+        }
+
+        // Unknown functions and calls outside the domain are opaque
+        for condition in ["size(items) > 0", "len(missing) > 0", "max(tags) > 0"] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (true, false),
+                "{}",
+                condition
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_quantifiers() {
+        let mut state = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        state.set(
+            "scores".into(),
+            Value::from_json(&serde_json::json!([3, -5, 8])),
+        );
+        state.set("none".into(), Value::Array(vec![]));
+        state.set(
+            "counters".into(),
+            Value::from_json(&serde_json::json!({"a": 1, "b": 2})),
+        );
+        state.set(
+            "entries".into(),
+            Value::from_json(&serde_json::json!([{"tags": ["a"]}, {"tags": []}])),
+        );
+
+        for (condition, expected) in [
+            ("all s in scores: s >= 0", false),
+            ("any s in scores: s < 0", true),
+            ("all s in scores: s > -10", true),
+            ("all s in none: s > 0", true),
+            ("any s in none: s > 0", false),
+            ("all c in counters: c >= 1", true),
+            ("any e in entries: e.tags is empty", true),
+            ("all e in entries: all t in e.tags: t == 'a'", true),
+            // The bound variable shadows state fields
+            ("all scores in counters: scores > 0", true),
+        ] {
+            assert_eq!(
+                ExpressionEvaluator::evaluate(condition, &state),
+                (expected, true),
+                "{}",
+                condition
+            );
+        }
+
+        // A missing element fails `all`; `any` stops at the first match
+        assert_eq!(
+            ExpressionEvaluator::evaluate("all e in entries: e.tags[0] == 'a'", &state),
+            (false, true)
+        );
+        assert_eq!(
+            ExpressionEvaluator::evaluate("any e in entries: e.tags[0] == 'a'", &state),
+            (true, true)
+        );
+        assert_eq!(
+            ExpressionEvaluator::evaluate("all s in missing: s > 0", &state),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_old_needs_prior_state() {
+        let mut before = ExecutionState {
+            fields: BTreeMap::new(),
+        };
+        before.set("count".into(), Value::Integer(1));
+        let mut after = before.clone();
+        after.set("count".into(), Value::Integer(2));
+
+        let condition = "count == old(count) + 1";
+        assert_eq!(
+            ExpressionEvaluator::evaluate_postcondition(condition, &before, &after),
+            (true, true)
+        );
+        assert_eq!(
+            ExpressionEvaluator::evaluate_postcondition(condition, &after, &after),
+            (false, true)
+        );
+        // Outside a postcondition there is no prior state
+        assert_eq!(
+            ExpressionEvaluator::evaluate(condition, &after),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_postcondition_old_counter() {
+        let mut contract = test_contract();
+        contract.behavioral_semantics.operations[0].parameters = fields(&[("count", "Integer")]);
+        contract.behavioral_semantics.operations[0].postcondition =
+            "count == old(count) + 1".into();
+        contract.data_semantics.invariants.clear();
+        let mut executor = Executor::new(contract);
+
+        let result = executor
+            .execute_operation("echo", r#"{"count": 1}"#)
+            .unwrap();
+        assert!(result.provenance.unwrap().postconditions_verified);
+        executor
+            .execute_operation("echo", r#"{"count": 2}"#)
+            .unwrap();
+
+        // Skipping ahead violates the postcondition and rolls back
+        let err = executor
+            .execute_operation("echo", r#"{"count": 5}"#)
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::ContractViolation { ref commitment, .. } if commitment == "postcondition of 'echo'"),
+            "{}",
+            err
+        );
+        assert_eq!(executor.state().get("count"), Some(&Value::Integer(2)));
+    }
+
+    #[test]
+    fn test_execution_records_condition_checks() {
+        let mut executor = Executor::new(test_contract());
+        let entry = executor
+            .execute_operation("echo", r#"{"message": "hello"}"#)
+            .unwrap()
+            .provenance
+            .unwrap();
+        let checks: Vec<(ConditionRole, &str, bool)> = entry
+            .conditions
+            .iter()
+            .map(|c| (c.role, c.condition.as_str(), c.evaluated))
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                (ConditionRole::Precondition, "input_provided", false),
+                (ConditionRole::Postcondition, "state_updated", false),
+                (ConditionRole::Invariant, "message is not empty", true),
+                (ConditionRole::Invariant, "count >= 0", true),
+            ]
+        );
+        assert!(entry.conditions.iter().all(|c| c.holds));
+    }
+
+    #[test]
+    fn test_strict_execution_requires_evaluated_conditions() {
+        let strict = ExecuteOptions {
+            strict_conditions: true,
+        };
+        let mut executor = Executor::with_options(test_contract(), strict);
+        let err = executor
+            .execute_operation("echo", r#"{"message": "hello"}"#)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "precondition 'input_provided' of operation 'echo' could not be evaluated"
+            ),
+            "{}",
+            err
+        );
+        assert!(executor.provenance().is_empty());
+
+        // An unevaluated postcondition rolls the operation back
+        let mut contract = test_contract();
+        contract.behavioral_semantics.operations[0].precondition = "true".into();
+        let initial = ExecutionState::from_contract(&contract);
+        let mut executor = Executor::with_options(contract, strict);
+        let err = executor
+            .execute_operation("echo", r#"{"message": "hello"}"#)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("postcondition 'state_updated'"),
+            "{}",
+            err
+        );
+        assert_eq!(executor.state(), &initial);
+    }
+
     #[test]
     fn test_postcondition_verification() {
         // Create a contract where postcondition is evaluable
@@ -0,0 +1,679 @@
+//! Diagnostic catalog — long explanations of every diagnostic code
+//!
+//! Every diagnostic ICL reports carries a stable code: `ICL-P…` for
+//! parse errors, `ICL-M…` for import errors, and the [`lint`] rule codes
+//! (`ICL-T…`, `ICL-I…`, `ICL-D…`, `ICL-C…`) for verifier findings. Codes
+//! never change meaning, so tools may match on them instead of on message
+//! text. This module explains each code, with a contract fragment that
+//! triggers it and the same fragment fixed.
+//!
+//! # Guarantees
+//!
+//! - Every code the parser, loader or verifier can report has an entry
+//! - Codes are unique and listed in a fixed order
+//!
+//! [`lint`]: crate::lint
+
+use crate::lint;
+use crate::loader;
+use crate::parser;
+
+/// Long-form documentation of one diagnostic code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Explanation {
+    /// Stable identifier, e.g. `ICL-C004`
+    pub code: &'static str,
+    /// Short name; for lint rules, the name used in configuration
+    pub name: &'static str,
+    /// One-line description
+    pub summary: &'static str,
+    /// What the diagnostic means and why it is reported
+    pub explanation: &'static str,
+    /// Contract fragment that triggers the diagnostic
+    pub broken: &'static str,
+    /// The same fragment with the problem fixed
+    pub fixed: &'static str,
+}
+
+/// Find an explanation by code (`ICL-C004`, case-insensitive) or name
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    EXPLANATIONS
+        .iter()
+        .find(|e| e.code.eq_ignore_ascii_case(code) || e.name == code)
+}
+
+/// Every diagnostic code, parse and import codes first, then lint rules
+pub static EXPLANATIONS: &[Explanation] = &[
+    // ── Parse ──────────────────────────────────────────────
+    Explanation {
+        code: parser::UNEXPECTED_CHARACTER,
+        name: "unexpected-character",
+        summary: "A character that starts no token",
+        explanation: "The tokenizer found a character that cannot begin any ICL token. \
+                      String literals use double quotes only, and comments start with `//`.",
+        broken: "owner: 'payments-team',",
+        fixed: "owner: \"payments-team\",",
+    },
+    Explanation {
+        code: parser::UNTERMINATED_STRING,
+        name: "unterminated-string",
+        summary: "A string literal or escape sequence runs to the end of the input",
+        explanation: "A string literal was opened but never closed, so the rest of the \
+                      file was read as part of it. The location points at the opening quote.",
+        broken: "narrative: \"Tracks account balances,\nintent_source: \"product spec\",",
+        fixed: "narrative: \"Tracks account balances\",\nintent_source: \"product spec\",",
+    },
+    Explanation {
+        code: parser::INVALID_ESCAPE,
+        name: "invalid-escape",
+        summary: "A backslash escape that is not `\\\\`, `\\\"`, `\\n` or `\\t`",
+        explanation: "String literals support only the escapes `\\\\`, `\\\"`, `\\n` and \
+                      `\\t`. Any other character after a backslash is rejected, so that a \
+                      literal has exactly one meaning.",
+        broken: "narrative: \"Writes to C:\\data\",",
+        fixed: "narrative: \"Writes to C:\\\\data\",",
+    },
+    Explanation {
+        code: parser::INVALID_LITERAL,
+        name: "invalid-literal",
+        summary: "A malformed number or timestamp literal",
+        explanation: "A number does not fit a 64-bit integer or float, or a timestamp is \
+                      not an RFC 3339 date-time: a real calendar date and time of day \
+                      with a `Z` or `+hh:mm` offset. A quoted `created_timestamp` is \
+                      checked the same way.",
+        broken: "created_timestamp: 2026-02-30T10:00Z,",
+        fixed: "created_timestamp: 2026-02-01T10:00:00Z,",
+    },
+    Explanation {
+        code: parser::UNEXPECTED_TOKEN,
+        name: "unexpected-token",
+        summary: "A token the grammar does not allow at this point",
+        explanation: "The parser expected a different token, such as the `:` between a \
+                      field name and its type or the `}` closing a section. The message \
+                      names what was expected and what was found.",
+        broken: "state: {\n  balance Integer\n}",
+        fixed: "state: {\n  balance: Integer\n}",
+    },
+    Explanation {
+        code: parser::UNEXPECTED_FIELD,
+        name: "unexpected-field",
+        summary: "A field name the section does not define",
+        explanation: "Each section accepts a fixed set of fields. An unknown field is \
+                      usually a misspelling; extension data belongs in the `Extensions` \
+                      section.",
+        broken: "Identity {\n  id: \"ledger\",\n  ...\n}",
+        fixed: "Identity {\n  stable_id: \"ledger\",\n  ...\n}",
+    },
+    Explanation {
+        code: parser::MISSING_FIELD,
+        name: "missing-field",
+        summary: "A required field is absent",
+        explanation: "A section omits a field the language requires. Every field of \
+                      `Identity`, `PurposeStatement`, `ExecutionConstraints` and \
+                      `HumanMachineContract` is required, as are the fields of each \
+                      operation.",
+        broken: "Identity {\n  stable_id: \"ledger\",\n  version: 1,\n  \
+                 created_timestamp: 2026-02-01T10:00:00Z,\n  semantic_hash: \"0000\"\n}",
+        fixed: "Identity {\n  stable_id: \"ledger\",\n  version: 1,\n  \
+                created_timestamp: 2026-02-01T10:00:00Z,\n  owner: \"payments-team\",\n  \
+                semantic_hash: \"0000\"\n}",
+    },
+    Explanation {
+        code: parser::VALUE_OUT_OF_RANGE,
+        name: "value-out-of-range",
+        summary: "A well-formed value outside its allowed range",
+        explanation: "A literal parses but lies outside the range its field allows, such \
+                      as a `confidence_level` outside [0.0, 1.0].",
+        broken: "confidence_level: 1.5,",
+        fixed: "confidence_level: 0.95,",
+    },
+    // ── Imports ────────────────────────────────────────────
+    Explanation {
+        code: loader::UNREADABLE_IMPORT,
+        name: "unreadable-import",
+        summary: "A module file cannot be read",
+        explanation: "An `import` names a file that does not exist or cannot be read. \
+                      Import paths are relative to the importing file.",
+        broken: "import \"types/comon.icl\"",
+        fixed: "import \"types/common.icl\"",
+    },
+    Explanation {
+        code: loader::INVALID_MODULE,
+        name: "invalid-module",
+        summary: "A module file does not parse as a module",
+        explanation: "An imported file must be a module: imports, then optional `Types` \
+                      and `Invariants` sections. A module cannot contain a contract, and \
+                      its syntax errors are reported through the import.",
+        broken: "// types/common.icl\nContract {\n  ...\n}",
+        fixed: "// types/common.icl\nTypes {\n  Money: Integer(min: 0)\n}",
+    },
+    Explanation {
+        code: loader::IMPORT_CYCLE,
+        name: "import-cycle",
+        summary: "Modules import each other in a cycle",
+        explanation: "A module imports itself, directly or through other modules. Move \
+                      the definitions both modules need into a third module that imports \
+                      neither.",
+        broken: "// a.icl\nimport \"b.icl\"\n\n// b.icl\nimport \"a.icl\"",
+        fixed: "// a.icl\nimport \"shared.icl\"\n\n// b.icl\nimport \"shared.icl\"",
+    },
+    // ── Types ──────────────────────────────────────────────
+    Explanation {
+        code: lint::NEGATIVE_VERSION.code,
+        name: lint::NEGATIVE_VERSION.name,
+        summary: lint::NEGATIVE_VERSION.summary,
+        explanation: "Contract versions count up from zero; a negative version cannot be \
+                      ordered against other versions of the same contract.",
+        broken: "version: -1,",
+        fixed: "version: 1,",
+    },
+    Explanation {
+        code: lint::INVALID_STABLE_ID.code,
+        name: lint::INVALID_STABLE_ID.name,
+        summary: lint::INVALID_STABLE_ID.summary,
+        explanation: "A stable_id is lowercase letters, digits and hyphens, starts and \
+                      ends with a letter or digit, and is 2 to 32 characters long. It \
+                      names the contract across versions, so it must be safe in paths \
+                      and URLs.",
+        broken: "stable_id: \"Ledger_Service\",",
+        fixed: "stable_id: \"ledger-service\",",
+    },
+    Explanation {
+        code: lint::INVALID_SEMANTIC_HASH.code,
+        name: lint::INVALID_SEMANTIC_HASH.name,
+        summary: lint::INVALID_SEMANTIC_HASH.summary,
+        explanation: "The semantic_hash is the hexadecimal SHA-256 of the contract's \
+                      canonical form. `icl hash` prints the value to use, and `icl fix` \
+                      writes it.",
+        broken: "semantic_hash: \"not-a-hash\",",
+        fixed:
+            "semantic_hash: \"de58d709c49ebbcda83e49d343290515afbbabee02245549e80bf49f951ee4ff\",",
+    },
+    Explanation {
+        code: lint::CONFIDENCE_OUT_OF_RANGE.code,
+        name: lint::CONFIDENCE_OUT_OF_RANGE.name,
+        summary: lint::CONFIDENCE_OUT_OF_RANGE.summary,
+        explanation: "confidence_level is a probability. The verifier checks the range \
+                      again for contracts built without the parser.",
+        broken: "confidence_level: 1.5,",
+        fixed: "confidence_level: 0.95,",
+    },
+    Explanation {
+        code: lint::LONG_NARRATIVE.code,
+        name: lint::LONG_NARRATIVE.name,
+        summary: lint::LONG_NARRATIVE.summary,
+        explanation: "The narrative summarizes the contract's purpose. Long prose belongs \
+                      in external documentation; state precise requirements as \
+                      invariants and conditions instead.",
+        broken: "narrative: \"<600 characters of design discussion>\",",
+        fixed: "narrative: \"Tracks account balances and transfers between accounts\",",
+    },
+    Explanation {
+        code: lint::DUPLICATE_TYPE_NAME.code,
+        name: lint::DUPLICATE_TYPE_NAME.name,
+        summary: lint::DUPLICATE_TYPE_NAME.summary,
+        explanation: "Each name in a `Types` section, including those from imported \
+                      modules, must denote one type. Rename one definition or remove the \
+                      copy.",
+        broken: "Types {\n  Money: Integer(min: 0),\n  Money: Float\n}",
+        fixed: "Types {\n  Money: Integer(min: 0),\n  Rate: Float\n}",
+    },
+    Explanation {
+        code: lint::RECURSIVE_TYPE.code,
+        name: lint::RECURSIVE_TYPE.name,
+        summary: lint::RECURSIVE_TYPE.summary,
+        explanation: "Type definitions are expanded in place, so a type that refers to \
+                      itself, directly or through other definitions, has no finite form.",
+        broken: "Types {\n  Node: Object { next: Optional<Node> }\n}",
+        fixed: "Types {\n  Node: Object { next_id: Optional<UUID> }\n}",
+    },
+    Explanation {
+        code: lint::NESTED_OPTIONAL.code,
+        name: lint::NESTED_OPTIONAL.name,
+        summary: lint::NESTED_OPTIONAL.summary,
+        explanation: "`Optional<Optional<T>>` has the same values as `Optional<T>`; the \
+                      extra layer adds nothing and is usually a mistake.",
+        broken: "nickname: Optional<Optional<String>>",
+        fixed: "nickname: Optional<String>",
+    },
+    Explanation {
+        code: lint::DUPLICATE_OBJECT_FIELD.code,
+        name: lint::DUPLICATE_OBJECT_FIELD.name,
+        summary: lint::DUPLICATE_OBJECT_FIELD.summary,
+        explanation: "An Object type maps each field name to one type; a repeated name \
+                      makes field access ambiguous.",
+        broken: "profile: Object { email: String, email: String }",
+        fixed: "profile: Object { email: String, backup_email: String }",
+    },
+    Explanation {
+        code: lint::EMPTY_ENUM.code,
+        name: lint::EMPTY_ENUM.name,
+        summary: lint::EMPTY_ENUM.summary,
+        explanation: "An Enum with no variants has no values, so a field of that type \
+                      can never be initialized.",
+        broken: "status: Enum[]",
+        fixed: "status: Enum[\"open\", \"closed\"]",
+    },
+    Explanation {
+        code: lint::DUPLICATE_ENUM_VARIANT.code,
+        name: lint::DUPLICATE_ENUM_VARIANT.name,
+        summary: lint::DUPLICATE_ENUM_VARIANT.summary,
+        explanation: "Each Enum variant must be listed once.",
+        broken: "status: Enum[\"open\", \"closed\", \"open\"]",
+        fixed: "status: Enum[\"open\", \"closed\"]",
+    },
+    Explanation {
+        code: lint::UNKNOWN_TYPE.code,
+        name: lint::UNKNOWN_TYPE.name,
+        summary: lint::UNKNOWN_TYPE.summary,
+        explanation: "A named type must be a built-in type or be defined in a `Types` \
+                      section of the contract or an imported module.",
+        broken: "balance: Money",
+        fixed: "Types {\n  Money: Integer(min: 0)\n}\n...\nbalance: Money",
+    },
+    Explanation {
+        code: lint::INVALID_REFINEMENT.code,
+        name: lint::INVALID_REFINEMENT.name,
+        summary: lint::INVALID_REFINEMENT.summary,
+        explanation: "Each primitive accepts its own refinements: `min` and `max` on \
+                      Integer and Float, `min_len`, `max_len` and `pattern` on String, \
+                      `min_items` and `max_items` on collections. A \
+                      refinement may appear once, and its value must have the right type.",
+        broken: "name: String(min: 1)",
+        fixed: "name: String(min_len: 1)",
+    },
+    Explanation {
+        code: lint::INVERTED_REFINEMENT_BOUNDS.code,
+        name: lint::INVERTED_REFINEMENT_BOUNDS.name,
+        summary: lint::INVERTED_REFINEMENT_BOUNDS.summary,
+        explanation: "A refinement whose lower bound exceeds its upper bound admits no \
+                      values.",
+        broken: "retries: Integer(min: 10, max: 1)",
+        fixed: "retries: Integer(min: 1, max: 10)",
+    },
+    Explanation {
+        code: lint::FLOAT_MAP_KEY.code,
+        name: lint::FLOAT_MAP_KEY.name,
+        summary: lint::FLOAT_MAP_KEY.summary,
+        explanation: "Float keys compare unreliably: NaN equals nothing and rounding \
+                      makes lookups fail. Key the map by an Integer or String instead.",
+        broken: "rates: Map<Float, String>",
+        fixed: "rates: Map<Integer, String>",
+    },
+    Explanation {
+        code: lint::INVALID_MAP_KEY.code,
+        name: lint::INVALID_MAP_KEY.name,
+        summary: lint::INVALID_MAP_KEY.summary,
+        explanation: "Map keys must have a total order for canonical serialization, so \
+                      they are limited to primitive and Enum types.",
+        broken: "owners: Map<Array<String>, String>",
+        fixed: "owners: Map<String, String>",
+    },
+    Explanation {
+        code: lint::DEFAULT_TYPE_MISMATCH.code,
+        name: lint::DEFAULT_TYPE_MISMATCH.name,
+        summary: lint::DEFAULT_TYPE_MISMATCH.summary,
+        explanation: "A field's default value must be a value of the field's type, \
+                      including its refinements.",
+        broken: "count: Integer = \"zero\"",
+        fixed: "count: Integer = 0",
+    },
+    Explanation {
+        code: lint::NON_POSITIVE_RESOURCE_LIMIT.code,
+        name: lint::NON_POSITIVE_RESOURCE_LIMIT.name,
+        summary: lint::NON_POSITIVE_RESOURCE_LIMIT.summary,
+        explanation: "Resource limits bound execution; a limit of zero or less would \
+                      reject every operation.",
+        broken: "computation_timeout_ms: 0,",
+        fixed: "computation_timeout_ms: 100,",
+    },
+    Explanation {
+        code: lint::UNKNOWN_IDENTIFIER.code,
+        name: lint::UNKNOWN_IDENTIFIER.name,
+        summary: lint::UNKNOWN_IDENTIFIER.summary,
+        explanation: "Conditions may refer only to state fields and, in an operation, \
+                      that operation's parameters. An unknown name is usually a typo.",
+        broken: "precondition: \"amount <= balanse\",",
+        fixed: "precondition: \"amount <= balance\",",
+    },
+    Explanation {
+        code: lint::UNKNOWN_FIELD.code,
+        name: lint::UNKNOWN_FIELD.name,
+        summary: lint::UNKNOWN_FIELD.summary,
+        explanation: "Field access on an Object value must name one of its declared \
+                      fields.",
+        broken:
+            "// profile: Object { email: String }\nprecondition: \"profile.emial is not empty\",",
+        fixed:
+            "// profile: Object { email: String }\nprecondition: \"profile.email is not empty\",",
+    },
+    Explanation {
+        code: lint::UNKNOWN_VARIANT.code,
+        name: lint::UNKNOWN_VARIANT.name,
+        summary: lint::UNKNOWN_VARIANT.summary,
+        explanation: "Comparing an Enum with a string that is not one of its variants is \
+                      always false (or, for `!=`, always true).",
+        broken:
+            "// status: Enum[\"open\", \"closed\"]\nprecondition: \"status == \\\"archived\\\"\",",
+        fixed: "// status: Enum[\"open\", \"closed\"]\nprecondition: \"status == \\\"closed\\\"\",",
+    },
+    Explanation {
+        code: lint::UNKNOWN_FUNCTION.code,
+        name: lint::UNKNOWN_FUNCTION.name,
+        summary: lint::UNKNOWN_FUNCTION.summary,
+        explanation: "Conditions may call only the built-in functions, such as `len`, \
+                      `contains` and `matches`.",
+        broken: "precondition: \"length(name) > 0\",",
+        fixed: "precondition: \"len(name) > 0\",",
+    },
+    Explanation {
+        code: lint::INVALID_PATTERN.code,
+        name: lint::INVALID_PATTERN.name,
+        summary: lint::INVALID_PATTERN.summary,
+        explanation: "A pattern passed to `matches` is compiled when the contract is \
+                      verified, so a malformed expression is caught before execution.",
+        broken: "precondition: \"matches(code, \\\"[A-Z\\\")\",",
+        fixed: "precondition: \"matches(code, \\\"[A-Z]+\\\")\",",
+    },
+    Explanation {
+        code: lint::CONDITION_TYPE_MISMATCH.code,
+        name: lint::CONDITION_TYPE_MISMATCH.name,
+        summary: lint::CONDITION_TYPE_MISMATCH.summary,
+        explanation: "Operators and function arguments have fixed types: arithmetic and \
+                      ordering need numbers, `and` and `or` need Booleans, and equality \
+                      needs two values of the same type.",
+        broken: "// count: Integer\nprecondition: \"count == \\\"ten\\\"\",",
+        fixed: "// count: Integer\nprecondition: \"count == 10\",",
+    },
+    Explanation {
+        code: lint::NEVER_NULL_COMPARISON.code,
+        name: lint::NEVER_NULL_COMPARISON.name,
+        summary: lint::NEVER_NULL_COMPARISON.summary,
+        explanation: "Only Optional values can be null, so comparing any other value \
+                      with null always gives the same result. Either the field should be \
+                      Optional or the check is redundant.",
+        broken: "// count: Integer\nprecondition: \"count != null\",",
+        fixed: "// count: Optional<Integer>\nprecondition: \"count != null\",",
+    },
+    // ── Invariants ─────────────────────────────────────────
+    Explanation {
+        code: lint::INVARIANT_WITHOUT_FIELDS.code,
+        name: lint::INVARIANT_WITHOUT_FIELDS.name,
+        summary: lint::INVARIANT_WITHOUT_FIELDS.summary,
+        explanation: "An invariant constrains the state. One that names no state field \
+                      cannot be checked against it.",
+        broken: "invariants: [\"the ledger is always consistent\"]",
+        fixed: "invariants: [\"balance >= 0\"]",
+    },
+    Explanation {
+        code: lint::DUPLICATE_INVARIANT.code,
+        name: lint::DUPLICATE_INVARIANT.name,
+        summary: lint::DUPLICATE_INVARIANT.summary,
+        explanation: "Listing an invariant twice adds nothing, and the copies can drift \
+                      apart when one is edited.",
+        broken: "invariants: [\"balance >= 0\", \"balance >= 0\"]",
+        fixed: "invariants: [\"balance >= 0\"]",
+    },
+    Explanation {
+        code: lint::CONTRADICTORY_INVARIANTS.code,
+        name: lint::CONTRADICTORY_INVARIANTS.name,
+        summary: lint::CONTRADICTORY_INVARIANTS.summary,
+        explanation: "No state satisfies all the invariants, so no operation can ever \
+                      complete. The verifier finds contradictions between numeric bounds, \
+                      Enum values and Boolean fields.",
+        broken: "invariants: [\"count >= 5\", \"count < 3\"]",
+        fixed: "invariants: [\"count >= 0\", \"count < 3\"]",
+    },
+    Explanation {
+        code: lint::INITIAL_STATE_VIOLATES_INVARIANT.code,
+        name: lint::INITIAL_STATE_VIOLATES_INVARIANT.name,
+        summary: lint::INITIAL_STATE_VIOLATES_INVARIANT.summary,
+        explanation: "The initial state is built from the declared defaults, and it must \
+                      satisfy every invariant.",
+        broken: "state: { count: Integer = 0 }\n...\ninvariants: [\"count >= 1\"]",
+        fixed: "state: { count: Integer = 1 }\n...\ninvariants: [\"count >= 1\"]",
+    },
+    Explanation {
+        code: lint::TYPE_DEFAULT_VIOLATES_INVARIANT.code,
+        name: lint::TYPE_DEFAULT_VIOLATES_INVARIANT.name,
+        summary: lint::TYPE_DEFAULT_VIOLATES_INVARIANT.summary,
+        explanation: "A field without a declared default starts at its type's zero \
+                      value, and that value breaks an invariant. Declare a default that \
+                      satisfies it.",
+        broken: "state: { count: Integer }\n...\ninvariants: [\"count >= 1\"]",
+        fixed: "state: { count: Integer = 1 }\n...\ninvariants: [\"count >= 1\"]",
+    },
+    Explanation {
+        code: lint::INVARIANT_NOT_PRESERVED.code,
+        name: lint::INVARIANT_NOT_PRESERVED.name,
+        summary: lint::INVARIANT_NOT_PRESERVED.summary,
+        explanation: "An operation sets each state field that has a parameter of the \
+                      same name. Assuming the invariants and the precondition hold before \
+                      it runs, its postcondition allows a state that breaks an \
+                      invariant. The message gives a counterexample; strengthen the \
+                      precondition to rule it out.",
+        broken: "// invariants: [\"count >= 0\"]\n\
+                 precondition: \"true\",\n\
+                 parameters: { count: Integer },\n\
+                 postcondition: \"count == old(count) - 1\",",
+        fixed: "// invariants: [\"count >= 0\"]\n\
+                precondition: \"count >= 1\",\n\
+                parameters: { count: Integer },\n\
+                postcondition: \"count == old(count) - 1\",",
+    },
+    Explanation {
+        code: lint::OPAQUE_INVARIANT.code,
+        name: lint::OPAQUE_INVARIANT.name,
+        summary: lint::OPAQUE_INVARIANT.summary,
+        explanation: "In strict mode every invariant must parse as an expression, so \
+                      that it can be checked. Reported only by `icl verify --strict`.",
+        broken: "invariants: [\"balance is never negative\"]",
+        fixed: "invariants: [\"balance >= 0\"]",
+    },
+    // ── Determinism ────────────────────────────────────────
+    Explanation {
+        code: lint::RANDOMNESS_IN_CONDITION.code,
+        name: lint::RANDOMNESS_IN_CONDITION.name,
+        summary: lint::RANDOMNESS_IN_CONDITION.summary,
+        explanation: "Contracts must be deterministic: the same state and inputs always \
+                      give the same result. Random values and generated IDs must be \
+                      passed in as parameters.",
+        broken: "side_effects: [\"assign a random reviewer\"],",
+        fixed: "side_effects: [\"assign the reviewer given as input\"],",
+    },
+    Explanation {
+        code: lint::SYSTEM_TIME_IN_CONDITION.code,
+        name: lint::SYSTEM_TIME_IN_CONDITION.name,
+        summary: lint::SYSTEM_TIME_IN_CONDITION.summary,
+        explanation: "Reading the clock makes results depend on when an operation runs. \
+                      Pass the time in as an ISO8601 parameter.",
+        broken: "side_effects: [\"stamp the entry with current_time\"],",
+        fixed: "parameters: { at: ISO8601 },\nside_effects: [\"stamp the entry with at\"],",
+    },
+    Explanation {
+        code: lint::EXTERNAL_IO_IN_CONDITION.code,
+        name: lint::EXTERNAL_IO_IN_CONDITION.name,
+        summary: lint::EXTERNAL_IO_IN_CONDITION.summary,
+        explanation: "Network and file access make results depend on the outside world. \
+                      Fetch the data before the operation and pass it in.",
+        broken: "side_effects: [\"http_request to the rates service\"],",
+        fixed: "parameters: { rate: Float },\nside_effects: [\"record the given rate\"],",
+    },
+    Explanation {
+        code: lint::HASH_ITERATION_IN_CONDITION.code,
+        name: lint::HASH_ITERATION_IN_CONDITION.name,
+        summary: lint::HASH_ITERATION_IN_CONDITION.summary,
+        explanation: "Hash-ordered collections iterate in an unspecified order. ICL maps \
+                      are ordered by key; describe iteration in that order.",
+        broken: "side_effects: [\"visit each entry of the HashMap\"],",
+        fixed: "side_effects: [\"visit each entry in key order\"],",
+    },
+    // ── Coherence ──────────────────────────────────────────
+    Explanation {
+        code: lint::DUPLICATE_OPERATION.code,
+        name: lint::DUPLICATE_OPERATION.name,
+        summary: lint::DUPLICATE_OPERATION.summary,
+        explanation: "Operations are invoked by name, so each name must be unique.",
+        broken: "operations: [\n  { name: \"deposit\", ... },\n  { name: \"deposit\", ... }\n]",
+        fixed: "operations: [\n  { name: \"deposit\", ... },\n  { name: \"withdraw\", ... }\n]",
+    },
+    Explanation {
+        code: lint::DUPLICATE_STATE_FIELD.code,
+        name: lint::DUPLICATE_STATE_FIELD.name,
+        summary: lint::DUPLICATE_STATE_FIELD.summary,
+        explanation: "The state maps each field name to one value, so names must be \
+                      unique.",
+        broken: "state: {\n  balance: Integer,\n  balance: Float\n}",
+        fixed: "state: {\n  balance: Integer,\n  rate: Float\n}",
+    },
+    Explanation {
+        code: lint::UNKNOWN_SANDBOX_MODE.code,
+        name: lint::UNKNOWN_SANDBOX_MODE.name,
+        summary: lint::UNKNOWN_SANDBOX_MODE.summary,
+        explanation: "The runtime knows the sandbox modes `full_isolation`, \
+                      `restricted` and `none`. Any other mode is treated as \
+                      `full_isolation`, the safest mode.",
+        broken: "sandbox_mode: \"isolated\",",
+        fixed: "sandbox_mode: \"full_isolation\",",
+    },
+    Explanation {
+        code: lint::UNKNOWN_TRIGGER_TYPE.code,
+        name: lint::UNKNOWN_TRIGGER_TYPE.name,
+        summary: lint::UNKNOWN_TRIGGER_TYPE.summary,
+        explanation: "The recognized trigger types are `manual`, `time_based` and \
+                      `event_based`. Other values are allowed but have no defined meaning.",
+        broken: "trigger_types: [\"api_call\"],",
+        fixed: "trigger_types: [\"event_based\"],",
+    },
+    Explanation {
+        code: lint::UNKNOWN_FIELD_REFERENCE.code,
+        name: lint::UNKNOWN_FIELD_REFERENCE.name,
+        summary: lint::UNKNOWN_FIELD_REFERENCE.summary,
+        explanation: "A prose condition contains a snake_case word that is neither a \
+                      state field nor a parameter. It is probably a misspelled field.",
+        broken: "// parameters: { user_name: String }\nprecondition: \"user_nmae was provided\",",
+        fixed: "// parameters: { user_name: String }\nprecondition: \"user_name was provided\",",
+    },
+    Explanation {
+        code: lint::MISPLACED_OLD.code,
+        name: lint::MISPLACED_OLD.name,
+        summary: lint::MISPLACED_OLD.summary,
+        explanation: "`old(x)` is the value of `x` before the operation ran, which exists \
+                      only in a postcondition.",
+        broken: "precondition: \"count > old(count)\",",
+        fixed: "postcondition: \"count > old(count)\",",
+    },
+    Explanation {
+        code: lint::NESTED_OLD.code,
+        name: lint::NESTED_OLD.name,
+        summary: lint::NESTED_OLD.summary,
+        explanation: "There is only one prior state, so `old(old(x))` means nothing more \
+                      than `old(x)`.",
+        broken: "postcondition: \"count == old(old(count)) + 1\",",
+        fixed: "postcondition: \"count == old(count) + 1\",",
+    },
+    Explanation {
+        code: lint::OLD_OF_PARAMETER.code,
+        name: lint::OLD_OF_PARAMETER.name,
+        summary: lint::OLD_OF_PARAMETER.summary,
+        explanation: "Parameters are inputs to the operation and do not change, so they \
+                      have no prior value. Use the parameter directly.",
+        broken: "postcondition: \"balance == old(balance) + old(amount)\",",
+        fixed: "postcondition: \"balance == old(balance) + amount\",",
+    },
+    Explanation {
+        code: lint::DUPLICATE_EXTENSION_NAMESPACE.code,
+        name: lint::DUPLICATE_EXTENSION_NAMESPACE.name,
+        summary: lint::DUPLICATE_EXTENSION_NAMESPACE.summary,
+        explanation: "Each extension system owns one namespace; two systems with the same \
+                      name would have conflicting fields.",
+        broken: "Extensions {\n  logging { level: \"debug\" }\n  logging { format: \"json\" }\n}",
+        fixed: "Extensions {\n  logging { level: \"debug\", format: \"json\" }\n}",
+    },
+    Explanation {
+        code: lint::OPAQUE_CONDITION.code,
+        name: lint::OPAQUE_CONDITION.name,
+        summary: lint::OPAQUE_CONDITION.summary,
+        explanation: "In strict mode every pre- and postcondition must parse as an \
+                      expression, so that the executor can check it. Reported only by \
+                      `icl verify --strict`.",
+        broken: "precondition: \"the user is logged in\",",
+        fixed: "precondition: \"logged_in == true\",",
+    },
+    Explanation {
+        code: lint::UNKNOWN_LINT_RULE.code,
+        name: lint::UNKNOWN_LINT_RULE.name,
+        summary: lint::UNKNOWN_LINT_RULE.summary,
+        explanation: "An `icl-allow` comment names a rule by code or name; an unknown \
+                      rule suppresses nothing. `icl explain` lists every rule.",
+        broken: "trigger_types: [\"api_call\"],  // icl-allow(unknown-trigger)",
+        fixed: "trigger_types: [\"api_call\"],  // icl-allow(unknown-trigger-type)",
+    },
+    Explanation {
+        code: lint::SEMANTIC_HASH_MISMATCH.code,
+        name: lint::SEMANTIC_HASH_MISMATCH.name,
+        summary: lint::SEMANTIC_HASH_MISMATCH.summary,
+        explanation: "Registries and caches identify a contract by its semantic_hash, so \
+                      the declared value must be the SHA-256 of the contract's canonical \
+                      form, imports included. Any change to the contract's meaning changes \
+                      the hash; formatting and comments do not. `icl fix` writes the \
+                      computed value. Set the rule to `warning` in `icl.json` while \
+                      drafting.",
+        broken: "semantic_hash: \"0000000000000000\",",
+        fixed:
+            "semantic_hash: \"de58d709c49ebbcda83e49d343290515afbbabee02245549e80bf49f951ee4ff\",",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_lint_rule_is_explained() {
+        for rule in lint::RULES {
+            let entry = explain(rule.code).unwrap_or_else(|| panic!("{} not explained", rule));
+            assert_eq!(entry.name, rule.name);
+            assert_eq!(entry.summary, rule.summary);
+        }
+    }
+
+    #[test]
+    fn test_every_parse_and_import_code_is_explained() {
+        for code in [
+            parser::UNEXPECTED_CHARACTER,
+            parser::UNTERMINATED_STRING,
+            parser::INVALID_ESCAPE,
+            parser::INVALID_LITERAL,
+            parser::UNEXPECTED_TOKEN,
+            parser::UNEXPECTED_FIELD,
+            parser::MISSING_FIELD,
+            parser::VALUE_OUT_OF_RANGE,
+            loader::UNREADABLE_IMPORT,
+            loader::INVALID_MODULE,
+            loader::IMPORT_CYCLE,
+        ] {
+            assert!(explain(code).is_some(), "{} not explained", code);
+        }
+        assert_eq!(EXPLANATIONS.len(), lint::RULES.len() + 11);
+    }
+
+    #[test]
+    fn test_codes_and_names_unique() {
+        let mut codes = HashSet::new();
+        let mut names = HashSet::new();
+        for e in EXPLANATIONS {
+            assert!(codes.insert(e.code), "duplicate code {}", e.code);
+            assert!(names.insert(e.name), "duplicate name {}", e.name);
+            assert_ne!(e.broken, e.fixed, "{} example unchanged", e.code);
+        }
+    }
+
+    #[test]
+    fn test_explain_by_code_or_name() {
+        assert_eq!(explain("icl-c004").unwrap().name, "unknown-trigger-type");
+        assert_eq!(explain("import-cycle").unwrap().code, "ICL-M003");
+        assert!(explain("ICL-X999").is_none());
+    }
+}
@@ -0,0 +1,426 @@
+//! Fix suggestions — text edits that resolve diagnostics
+//!
+//! A verifier [`Diagnostic`] may carry a [`Fix`]: a short description and
+//! the edits to the contract source that resolve it. A fix is *safe* when
+//! it keeps the contract's meaning (removing a duplicate Enum variant,
+//! recomputing `semantic_hash`); other fixes are suggestions (replacing a
+//! misspelled name with the closest declared one) that need a human to
+//! confirm them.
+//!
+//! # Architecture
+//!
+//! ```text
+//! source → Loader → Verifier → safe fixes → apply → source → ... (until none)
+//! ```
+//!
+//! [`fix_source`] applies safe fixes in passes, re-verifying in between: a
+//! fix can reveal or invalidate others, and `semantic_hash` must be
+//! recomputed after every other change.
+//!
+//! # Guarantees
+//!
+//! - Edits replace exact byte ranges, so formatting and comments outside
+//!   them are kept
+//! - Edits never touch `import` statements: content merged from a module
+//!   carries the span of the import that brought it in, and is fixed in
+//!   the module itself
+//! - Overlapping edits are never applied together; the later fix waits
+//!   for the next pass
+
+use std::cmp::Reverse;
+use std::ops::Range;
+
+use crate::lint::{self, Suppressions};
+use crate::loader::{self, Loader};
+use crate::parser::tokenizer::Span;
+use crate::verifier::{self, Diagnostic, VerificationResult, VerifyOptions};
+use crate::Result;
+
+/// Most passes [`fix_source`] makes before giving up on reaching a fixpoint
+const MAX_PASSES: usize = 8;
+
+/// Replace the source text at `span` with `replacement`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Replace the text at `span`
+    pub fn replace(span: &Span, replacement: impl Into<String>) -> Self {
+        TextEdit {
+            span: span.clone(),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Delete the text at `span`
+    pub fn delete(span: &Span) -> Self {
+        Self::replace(span, "")
+    }
+}
+
+/// Edits resolving one diagnostic
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fix {
+    /// What the fix does, e.g. "replace 'balanse' with 'balance'"
+    pub message: String,
+    pub edits: Vec<TextEdit>,
+    /// Whether the fix keeps the contract's meaning and may be applied
+    /// without review
+    pub safe: bool,
+}
+
+impl Fix {
+    /// A fix that keeps the contract's meaning
+    pub fn safe(message: impl Into<String>, edits: Vec<TextEdit>) -> Self {
+        Fix {
+            message: message.into(),
+            edits,
+            safe: true,
+        }
+    }
+
+    /// A likely fix that needs review
+    pub fn suggestion(message: impl Into<String>, edits: Vec<TextEdit>) -> Self {
+        Fix {
+            message: message.into(),
+            edits,
+            safe: false,
+        }
+    }
+}
+
+// ── Applying ───────────────────────────────────────────────
+
+/// Whether every edit of `fix` lies in `source` and clear of its
+/// `import` statements
+pub fn applicable(source: &str, fix: &Fix) -> bool {
+    fits(source, &import_spans(source), fix)
+}
+
+/// Apply fixes to `source`, returning the new text and the fixes applied.
+/// Fixes that are not [`applicable`] or overlap an earlier fix are skipped.
+pub fn apply<'a>(source: &str, fixes: &[&'a Fix]) -> (String, Vec<&'a Fix>) {
+    let imports = import_spans(source);
+    let overlaps = |a: &Range<usize>, b: &Range<usize>| {
+        (a.start < b.end && b.start < a.end) || a.start == b.start
+    };
+    let mut taken: Vec<Range<usize>> = Vec::new();
+    let mut applied = Vec::new();
+    for &fix in fixes {
+        let ranges: Vec<_> = fix.edits.iter().map(|e| e.span.range()).collect();
+        let clash = ranges.iter().enumerate().any(|(i, range)| {
+            taken.iter().any(|t| overlaps(t, range))
+                || ranges[..i].iter().any(|other| overlaps(other, range))
+        });
+        if clash || !fits(source, &imports, fix) {
+            continue;
+        }
+        taken.extend(ranges);
+        applied.push(fix);
+    }
+
+    let mut edits: Vec<&TextEdit> = applied.iter().flat_map(|f| &f.edits).collect();
+    edits.sort_by_key(|e| Reverse(e.span.offset));
+    let mut text = source.to_string();
+    for edit in edits {
+        text.replace_range(edit.span.range(), &edit.replacement);
+    }
+    (text, applied)
+}
+
+fn fits(source: &str, imports: &[Span], fix: &Fix) -> bool {
+    fix.edits.iter().all(|edit| {
+        let range = edit.span.range();
+        range.end <= source.len()
+            && source.is_char_boundary(range.start)
+            && source.is_char_boundary(range.end)
+            && !imports
+                .iter()
+                .any(|import| range.start <= import.end && import.offset <= range.end)
+    })
+}
+
+/// Result of [`fix_source`]
+#[derive(Debug, Clone)]
+pub struct Fixed {
+    /// Source with every safe fix applied
+    pub source: String,
+    /// Diagnostics whose fixes were applied, in the order applied
+    pub applied: Vec<Diagnostic>,
+    /// Verification of the fixed source; its remaining fixes are
+    /// suggestions or could not be applied
+    pub result: VerificationResult,
+}
+
+/// Apply every safe fix to contract `source` located at `path`, in passes
+/// until none is left
+///
+/// `options.suppressions` is rebuilt from the source on every pass.
+///
+/// # Errors
+/// The contract does not parse or its imports cannot be resolved.
+pub fn fix_source(
+    path: &str,
+    source: &str,
+    loader: &dyn Loader,
+    options: &VerifyOptions,
+) -> Result<Fixed> {
+    let mut source = source.to_string();
+    let mut applied = Vec::new();
+    let mut passes = 0;
+    loop {
+        let ast = loader::load_source(path, &source, loader)?;
+        let options = VerifyOptions {
+            suppressions: Suppressions::from_source(&source),
+            ..options.clone()
+        };
+        let result = verifier::verify_with_options(&ast, &options);
+
+        // semantic_hash covers the whole contract, so it is fixed once
+        // nothing else is left to fix
+        let (hash, other): (Vec<&Diagnostic>, Vec<&Diagnostic>) = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.fix.as_ref().is_some_and(|f| f.safe))
+            .partition(|d| {
+                d.rule == &lint::INVALID_SEMANTIC_HASH || d.rule == &lint::SEMANTIC_HASH_MISMATCH
+            });
+        let batch = if other.is_empty() { hash } else { other };
+        let fixes: Vec<&Fix> = batch.iter().filter_map(|d| d.fix.as_ref()).collect();
+        let (fixed, done) = apply(&source, &fixes);
+
+        if done.is_empty() || passes == MAX_PASSES {
+            return Ok(Fixed {
+                source,
+                applied,
+                result,
+            });
+        }
+        applied.extend(
+            batch
+                .into_iter()
+                .filter(|d| d.fix.as_ref().is_some_and(|f| done.contains(&f)))
+                .cloned(),
+        );
+        source = fixed;
+        passes += 1;
+    }
+}
+
+/// Spans of the `import` statements of `source`
+fn import_spans(source: &str) -> Vec<Span> {
+    crate::parser::parse_with_diagnostics(source)
+        .contract
+        .imports
+        .into_iter()
+        .map(|import| import.span)
+        .collect()
+}
+
+// ── Suggestions ────────────────────────────────────────────
+
+/// The candidate closest to a misspelled `name`, if one is near enough
+/// and no other is as near
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (name.chars().count() / 3).clamp(1, 3);
+    let mut best: Option<(usize, &str)> = None;
+    let mut tied = false;
+    for candidate in candidates {
+        if candidate == name {
+            continue;
+        }
+        let distance = edit_distance(name, candidate);
+        if distance > limit {
+            continue;
+        }
+        match best {
+            Some((d, c)) if distance == d && candidate != c => tied = true,
+            Some((d, _)) if distance >= d => {}
+            _ => {
+                best = Some((distance, candidate));
+                tied = false;
+            }
+        }
+    }
+    best.filter(|_| !tied).map(|(_, c)| c)
+}
+
+/// Levenshtein distance, counting a swap of adjacent characters as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            rows[i][j] = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::MemoryLoader;
+
+    const CONTRACT: &str = r#"Contract {
+  Identity {
+    stable_id: "ic-fix-001",
+    version: 1,
+    created_timestamp: 2026-02-01T00:00:00Z,
+    owner: "test",
+    semantic_hash: "not-a-hash"
+  }
+
+  PurposeStatement {
+    narrative: "Fix test contract",
+    intent_source: "test",
+    confidence_level: 1.0
+  }
+
+  DataSemantics {
+    state: {
+      // Current status
+      status: Enum["open", "closed", "open"],
+      count: Integer = 0
+    },
+    invariants: ["count >= 0", "count >= 0"]
+  }
+
+  BehavioralSemantics {
+    operations: [
+      {
+        name: "bump",
+        precondition: "cuont >= 0",
+        parameters: {},
+        postcondition: "count == old(old(count)) + 1",
+        side_effects: [],
+        idempotence: "not idempotent"
+      }
+    ]
+  }
+
+  ExecutionConstraints {
+    trigger_types: ["manaul"],
+    resource_limits: {
+      max_memory_bytes: 1048576,
+      computation_timeout_ms: 100,
+      max_state_size_bytes: 1048576
+    },
+    external_permissions: [],
+    sandbox_mode: "full_isolation"
+  }
+
+  HumanMachineContract {
+    system_commitments: [],
+    system_refusals: [],
+    user_obligations: []
+  }
+}
+"#;
+
+    fn fix(source: &str) -> Fixed {
+        fix_source(
+            "main.icl",
+            source,
+            &MemoryLoader::new(),
+            &VerifyOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fix_source_applies_safe_fixes() {
+        let fixed = fix(CONTRACT);
+        assert!(fixed
+            .source
+            .contains("      // Current status\n      status: Enum[\"open\", \"closed\"],\n"));
+        assert!(fixed.source.contains("invariants: [\"count >= 0\"]"));
+        assert!(fixed.source.contains("\"count == old(count) + 1\""));
+
+        // The hash is recomputed after the other fixes
+        let ast = crate::parser::parse(&fixed.source).unwrap();
+        let hash = crate::normalizer::compute_semantic_hash(&crate::normalizer::normalize_ast(
+            ast.clone(),
+        ));
+        assert_eq!(ast.identity.semantic_hash.value, hash);
+
+        let mut codes: Vec<&str> = fixed.applied.iter().map(|d| d.rule.code).collect();
+        codes.sort();
+        assert_eq!(codes, ["ICL-C007", "ICL-I002", "ICL-T003", "ICL-T011"]);
+    }
+
+    #[test]
+    fn test_fix_source_leaves_suggestions() {
+        let fixed = fix(CONTRACT);
+        assert!(fixed.source.contains("\"cuont >= 0\""));
+        assert!(fixed.source.contains("[\"manaul\"]"));
+
+        let suggestions: Vec<&Fix> = fixed
+            .result
+            .diagnostics
+            .iter()
+            .filter_map(|d| d.fix.as_ref())
+            .collect();
+        assert_eq!(suggestions.len(), 2, "{:?}", suggestions);
+        assert!(suggestions.iter().all(|f| !f.safe));
+        assert_eq!(suggestions[0].message, "replace 'cuont' with 'count'");
+        assert_eq!(suggestions[1].message, "replace 'manaul' with 'manual'");
+
+        let (text, applied) = apply(&fixed.source, &suggestions);
+        assert_eq!(applied.len(), 2);
+        assert!(text.contains("\"count >= 0\"") && text.contains("[\"manual\"]"));
+    }
+
+    #[test]
+    fn test_fix_source_is_a_fixpoint() {
+        let once = fix(CONTRACT).source;
+        let twice = fix(&once);
+        assert_eq!(twice.source, once);
+        assert!(twice.applied.is_empty());
+    }
+
+    #[test]
+    fn test_apply_skips_overlaps_and_imports() {
+        let source = "import \"a.icl\"\nContract {}";
+        let at = |offset, end| Span {
+            line: 1,
+            column: 1,
+            offset,
+            end,
+        };
+        let first = Fix::safe("first", vec![TextEdit::replace(&at(15, 23), "Contract")]);
+        let overlapping = Fix::safe("second", vec![TextEdit::delete(&at(20, 22))]);
+        let on_import = Fix::safe("import", vec![TextEdit::delete(&at(0, 6))]);
+        let (_, applied) = apply(source, &[&first, &overlapping, &on_import]);
+        assert_eq!(applied, vec![&first]);
+        assert!(!applicable(
+            source,
+            &Fix::safe("out", vec![TextEdit::delete(&at(0, 99))])
+        ));
+    }
+
+    #[test]
+    fn test_closest() {
+        let fields = ["balance", "owner", "status"];
+        assert_eq!(closest("balanse", fields), Some("balance"));
+        assert_eq!(closest("onwer", fields), Some("owner"));
+        assert_eq!(closest("total", fields), None);
+        // Ties suggest nothing
+        assert_eq!(closest("ab", ["aa", "bb"]), None);
+    }
+}
@@ -0,0 +1,167 @@
+//! Literal formats — RFC 3339 timestamps and RFC 4122 UUIDs
+//!
+//! The parser checks `created_timestamp` and timestamp literals, the
+//! verifier `ISO8601` and `UUID` defaults, the executor inputs and state
+//! of those types, and the condition language compares timestamps and
+//! provides `is_iso8601` / `is_uuid`. They all share these checks so a
+//! value accepted in one place is accepted everywhere.
+
+// ── Timestamps ────────────────────────────────────────────
+
+/// Parse an ISO8601 (RFC 3339) timestamp such as `2026-02-01T10:00:00Z`
+/// or `2026-02-01T12:00:00.5+02:00` into seconds and nanoseconds since the
+/// Unix epoch, so timestamps in different offsets compare correctly
+pub(crate) fn parse_timestamp(text: &str) -> Option<(i64, u32)> {
+    let b = text.as_bytes();
+    let digits = |range: std::ops::Range<usize>| -> Option<i64> {
+        let part = b.get(range)?;
+        part.iter()
+            .all(u8::is_ascii_digit)
+            .then(|| part.iter().fold(0i64, |n, d| n * 10 + i64::from(d - b'0')))
+    };
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    if b.len() < 20 || !matches!(b[10], b'T' | b't') || separators.iter().any(|&(i, c)| b[i] != c) {
+        return None;
+    }
+    let (year, month, day) = (digits(0..4)?, digits(5..7)?, digits(8..10)?);
+    let (hour, minute, second) = (digits(11..13)?, digits(14..16)?, digits(17..19)?);
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    // Optional fraction of a second, up to nanosecond precision
+    let mut pos = 19;
+    let mut nanos = 0u32;
+    if b[pos] == b'.' {
+        let start = pos + 1;
+        pos = start;
+        while pos < b.len() && b[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        if pos == start || pos - start > 9 {
+            return None;
+        }
+        let fraction = digits(start..pos)? * 10i64.pow((9 - (pos - start)) as u32);
+        nanos = fraction as u32;
+    }
+
+    let offset = match b.get(pos)? {
+        b'Z' | b'z' if pos + 1 == b.len() => 0,
+        sign @ (b'+' | b'-') if pos + 6 == b.len() && b[pos + 3] == b':' => {
+            let (h, m) = (digits(pos + 1..pos + 3)?, digits(pos + 4..pos + 6)?);
+            if h > 23 || m > 59 {
+                return None;
+            }
+            let minutes = h * 60 + m;
+            if *sign == b'-' {
+                -minutes
+            } else {
+                minutes
+            }
+        }
+        _ => return None,
+    };
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86_400 + hour * 3600 + minute * 60 + second - offset * 60;
+    Some((seconds, nanos))
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12; // March = 0
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// ── UUIDs ─────────────────────────────────────────────────
+
+/// Whether `text` is an RFC 4122 UUID: the hyphenated 8-4-4-4-12 hex
+/// form with the RFC 4122 variant and a version from 1 to 8 (RFC 9562
+/// added 6 to 8), or the nil UUID
+pub(crate) fn is_uuid(text: &str) -> bool {
+    let b = text.as_bytes();
+    let shaped = b.len() == 36
+        && b.iter().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => *c == b'-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    if !shaped {
+        return false;
+    }
+    let version = b[14];
+    let variant = b[19].to_ascii_lowercase();
+    (b'1'..=b'8').contains(&version) && matches!(variant, b'8' | b'9' | b'a' | b'b')
+        || text == "00000000-0000-0000-0000-000000000000"
+}
+
+// ── Tests ─────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some((0, 0)));
+        assert_eq!(
+            parse_timestamp("2026-02-01T10:00:00Z"),
+            Some((1_769_940_000, 0))
+        );
+        assert_eq!(
+            parse_timestamp("2026-02-01T12:00:00.25+02:00"),
+            Some((1_769_940_000, 250_000_000))
+        );
+        assert_eq!(parse_timestamp("1969-12-31T23:00:00-01:00"), Some((0, 0)));
+        assert_eq!(
+            parse_timestamp("2024-02-29T00:00:00z").map(|t| t.0),
+            Some(1_709_164_800)
+        );
+        for invalid in [
+            "2026-02-01",
+            "2026-02-01 10:00:00Z",
+            "2026-13-01T10:00:00Z",
+            "2025-02-29T00:00:00Z",
+            "2026-02-01T24:00:00Z",
+            "2026-02-01T10:00:00",
+            "2026-02-01T10:00:00+0200",
+            "2026-02-01T10:00:00.Z",
+            "2026-02-01T10:00:00Zjunk",
+        ] {
+            assert_eq!(parse_timestamp(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_is_uuid() {
+        assert!(is_uuid("123e4567-e89b-12d3-a456-426614174000"));
+        assert!(is_uuid("00000000-0000-0000-0000-000000000000"));
+        for invalid in [
+            "123e4567e89b12d3a456426614174000",
+            "123e4567-e89b-02d3-a456-426614174000",
+            "123e4567-e89b-12d3-c456-426614174000",
+            "123e4567-e89b-12d3-a456-42661417400g",
+        ] {
+            assert!(!is_uuid(invalid), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! ICL formatter — fixes whitespace and indentation, nothing else
+//!
+//! Unlike the normalizer, the formatter keeps the contract as the author
+//! wrote it: section and field order, comments and line breaks all survive.
+//! Only layout changes:
+//!
+//! - indentation is two spaces per `{` / `[` nesting level
+//! - spacing within a line is normalized (`a: 1, b: 2`, `Map<K, V>`, `x = 0`)
+//! - runs of blank lines collapse to one; blank lines directly inside
+//!   `{` / `[` and at the start of the file are dropped
+//! - trailing whitespace is removed and the file ends with one newline
+//!
+//! Canonical normalization (sorting, comment removal) remains a separate,
+//! explicit step in [`crate::normalizer`].
+//!
+//! # Guarantees
+//!
+//! - **Idempotent**: `format(format(x)) == format(x)`
+//! - **Token preserving**: the formatted text has exactly the same tokens
+//!   and comments as the input
+
+use crate::parser::cst::{
+    parse_cst, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree, TriviaKind,
+};
+use crate::parser::tokenizer::Token;
+use crate::Result;
+
+const INDENT: &str = "  ";
+
+// ── Public API ─────────────────────────────────────────────
+
+/// Format ICL text, keeping author order and comments
+///
+/// Accepts contracts and imported modules (see [`crate::loader`]).
+///
+/// # Errors
+/// Returns `ParseError` if the input is neither a valid contract nor a
+/// valid module — the formatter never rewrites text it cannot parse.
+pub fn format(input: &str) -> Result<String> {
+    if let Err(e) = crate::parser::parse(input) {
+        crate::parser::parse_module(input).map_err(|_| e)?;
+    }
+    let tree = parse_cst(input)?;
+    Ok(format_tree(&tree))
+}
+
+/// Format an already parsed syntax tree
+pub fn format_tree(tree: &SyntaxTree) -> String {
+    let mut tokens = Vec::new();
+    flatten(&tree.root, 0, &mut tokens);
+
+    let mut out = String::new();
+    let mut prev: Option<&SyntaxToken> = None;
+
+    for (token, depth) in tokens {
+        if let Some(p) = prev {
+            for comment in p.trailing.iter().filter(|t| t.kind == TriviaKind::Comment) {
+                out.push(' ');
+                out.push_str(&comment.text);
+            }
+        }
+
+        let closer = is_closer(&token.token);
+        // Comments before a closing delimiter belong to the block's body
+        let comment_depth = if closer { depth + 1 } else { depth };
+        let mut after_opener = prev.is_some_and(|p| is_opener(&p.token));
+        let mut newlines = 0;
+
+        for trivia in &token.leading {
+            match trivia.kind {
+                TriviaKind::Newline => newlines += 1,
+                TriviaKind::Comment => {
+                    start_line(&mut out, newlines, after_opener, comment_depth);
+                    out.push_str(&trivia.text);
+                    newlines = 0;
+                    after_opener = false;
+                }
+                TriviaKind::Whitespace => {}
+            }
+        }
+
+        if token.token == Token::Eof {
+            out.push('\n');
+            break;
+        }
+
+        if newlines > 0 {
+            start_line(&mut out, newlines, after_opener || closer, depth);
+        } else if let Some(p) = prev {
+            out.push_str(spacing(&p.token, &token.token));
+        }
+        out.push_str(&token.text);
+        prev = Some(token);
+    }
+
+    out
+}
+
+// ── Layout helpers ────────────────────────────────────────
+
+/// Tokens in source order with their indentation depth. Delimiters sit at
+/// the depth of their block; the body of `{}` / `[]` is one level deeper.
+fn flatten<'a>(node: &'a SyntaxNode, depth: usize, out: &mut Vec<(&'a SyntaxToken, usize)>) {
+    let last = node.children.len().saturating_sub(1);
+    for (i, child) in node.children.iter().enumerate() {
+        let is_delimiter = node.kind != SyntaxKind::Root && (i == 0 || i == last);
+        let child_depth = match node.kind {
+            SyntaxKind::Block | SyntaxKind::List if !is_delimiter => depth + 1,
+            _ => depth,
+        };
+        match child {
+            SyntaxElement::Node(inner) => flatten(inner, child_depth, out),
+            SyntaxElement::Token(token) => out.push((token, child_depth)),
+        }
+    }
+}
+
+/// Start a new line (unless at the very start of the file), keeping at
+/// most one blank line, none when `tight`
+fn start_line(out: &mut String, newlines: usize, tight: bool, depth: usize) {
+    if !out.is_empty() {
+        let max = if tight { 1 } else { 2 };
+        for _ in 0..newlines.clamp(1, max) {
+            out.push('\n');
+        }
+    }
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+/// Space between two tokens on the same line
+fn spacing(prev: &Token, next: &Token) -> &'static str {
+    match (prev, next) {
+        (Token::LBrace, Token::RBrace) => "",
+        (_, Token::Comma | Token::Colon) => "",
+        (Token::LAngle, _) | (_, Token::LAngle | Token::RAngle) => "",
+        (Token::LParen, _) | (_, Token::LParen | Token::RParen) => "",
+        (Token::LBracket, _) | (_, Token::RBracket) => "",
+        (Token::Colon | Token::Comma | Token::Equals | Token::Invariants, Token::LBracket) => " ",
+        (_, Token::LBracket) => "",
+        _ => " ",
+    }
+}
+
+fn is_opener(token: &Token) -> bool {
+    matches!(token, Token::LBrace | Token::LBracket)
+}
+
+fn is_closer(token: &Token) -> bool {
+    matches!(token, Token::RBrace | Token::RBracket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn read_fixture(name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/fixtures/conformance/valid")
+            .join(name);
+        fs::read_to_string(path).unwrap()
+    }
+
+    const MESSY: &str = r#"// Header comment
+Contract{
+      Identity {
+  stable_id:"ic-fmt-001" ,   // trailing comment
+version : 1,
+    created_timestamp: 2026-02-01T00:00:00Z,
+    owner: "test",
+    semantic_hash: "0000000000000000"
+  }
+
+
+
+  PurposeStatement {
+    narrative: "Formatting test",   intent_source: "test",
+    confidence_level: 1.0
+  }
+  DataSemantics {
+    state: {
+
+      tags: Array < String >,
+      scores: Map<String,Integer>,
+      status: Enum [ "a","b" ],
+      label: String ( min_len:1 , max_len: 8 ),
+      count: Integer=0
+    },
+    invariants: [ "count >= 0" ]
+  }
+  BehavioralSemantics { operations: [] }
+  ExecutionConstraints {
+    trigger_types: ["manual"],
+    resource_limits: { max_memory_bytes: 1, computation_timeout_ms: 1, max_state_size_bytes: 1 },
+    external_permissions: [],
+    sandbox_mode: "full_isolation"
+    // comment before closing brace
+  }
+  HumanMachineContract {
+    system_commitments: [],
+    system_refusals: [],
+    user_obligations: []
+  }
+}"#;
+
+    const EXPECTED: &str = r#"// Header comment
+Contract {
+  Identity {
+    stable_id: "ic-fmt-001", // trailing comment
+    version: 1,
+    created_timestamp: 2026-02-01T00:00:00Z,
+    owner: "test",
+    semantic_hash: "0000000000000000"
+  }
+
+  PurposeStatement {
+    narrative: "Formatting test", intent_source: "test",
+    confidence_level: 1.0
+  }
+  DataSemantics {
+    state: {
+      tags: Array<String>,
+      scores: Map<String, Integer>,
+      status: Enum["a", "b"],
+      label: String(min_len: 1, max_len: 8),
+      count: Integer = 0
+    },
+    invariants: ["count >= 0"]
+  }
+  BehavioralSemantics { operations: [] }
+  ExecutionConstraints {
+    trigger_types: ["manual"],
+    resource_limits: { max_memory_bytes: 1, computation_timeout_ms: 1, max_state_size_bytes: 1 },
+    external_permissions: [],
+    sandbox_mode: "full_isolation"
+    // comment before closing brace
+  }
+  HumanMachineContract {
+    system_commitments: [],
+    system_refusals: [],
+    user_obligations: []
+  }
+}
+"#;
+
+    #[test]
+    fn test_format_fixes_whitespace_only() {
+        assert_eq!(format(MESSY).unwrap(), EXPECTED);
+    }
+
+    #[test]
+    fn test_format_keeps_comments_and_order() {
+        let input = read_fixture("comments-everywhere.icl");
+        let formatted = format(&input).unwrap();
+        assert_eq!(formatted, input.trim_end().to_string() + "\n");
+    }
+
+    #[test]
+    fn test_format_idempotent_all_fixtures() {
+        let root =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/conformance/valid");
+        let mut paths: Vec<_> = fs::read_dir(&root)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        paths.sort();
+        for path in paths {
+            let input = fs::read_to_string(&path).unwrap();
+            let once = format(&input).unwrap();
+            let twice = format(&once).unwrap();
+            assert_eq!(once, twice, "{}", path.display());
+            // Formatting never changes meaning
+            assert_eq!(
+                crate::normalizer::normalize(&input).unwrap(),
+                crate::normalizer::normalize(&once).unwrap(),
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_collapses_blank_lines() {
+        let input = read_fixture("whitespace-flexible.icl");
+        let formatted = format(&input).unwrap();
+        assert!(!formatted.contains("\n\n\n"));
+        assert!(formatted.contains("Contract {\n  Identity {\n    stable_id"));
+        assert!(formatted.contains("\n  }\n\n  PurposeStatement {"));
+    }
+
+    #[test]
+    fn test_format_rejects_invalid_contract() {
+        assert!(format("Contract {").is_err());
+    }
+
+    #[test]
+    fn test_format_modules() {
+        let formatted =
+            format("import   \"a.icl\"\nTypes {\nId:UUID,\n  Pair: Map<String,Integer>\n}")
+                .unwrap();
+        assert_eq!(
+            formatted,
+            "import \"a.icl\"\nTypes {\n  Id: UUID,\n  Pair: Map<String, Integer>\n}\n"
+        );
+
+        let input = fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../../tests/fixtures/imports/common/invariants.icl"),
+        )
+        .unwrap();
+        assert_eq!(format(&input).unwrap(), input);
+        assert!(format("Types { Id: }").is_err());
+    }
+
+    #[test]
+    fn test_format_determinism_100_iterations() {
+        let first = format(MESSY).unwrap();
+        for i in 0..100 {
+            assert_eq!(
+                first,
+                format(MESSY).unwrap(),
+                "Determinism failure at iteration {}",
+                i
+            );
+        }
+    }
+}
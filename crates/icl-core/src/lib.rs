@@ -11,6 +11,12 @@
 //!                           Verifier → Type Check + Invariants + Determinism
 //!                              ↓
 //!                           Executor → Sandboxed Execution
+//!
+//! AST → Lowering → Contract (typed schema) → Executor
+//!
+//! ICL Text → CST (lossless) → Formatter → Formatted Text
+//!
+//! Contract + imported modules → Loader → self-contained AST
 //! ```
 //!
 //! # Guarantees
@@ -20,10 +26,21 @@
 //! - **Bounded**: All execution bounded in memory and time
 //! - **Canonical**: One normalized form per contract
 
+pub mod builtins;
 pub mod error;
 pub mod executor;
+pub mod explain;
+pub mod fix;
+pub mod formats;
+pub mod formatter;
+pub mod lint;
+pub mod loader;
 pub mod normalizer;
 pub mod parser;
+pub mod preservation;
+mod satisfiability;
+pub mod schema;
+pub mod snippet;
 pub mod verifier;
 
 pub use error::{Error, Result};
@@ -58,8 +75,9 @@ pub struct PurposeStatement {
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DataSemantics {
-    pub state: serde_json::Value,
-    pub invariants: Vec<String>,
+    /// State fields in declaration order
+    pub state: Vec<schema::Field>,
+    pub invariants: Vec<Condition>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -70,13 +88,65 @@ pub struct BehavioralSemantics {
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Operation {
     pub name: String,
-    pub precondition: String,
-    pub parameters: serde_json::Value,
-    pub postcondition: String,
+    pub precondition: Condition,
+    pub parameters: Vec<schema::Field>,
+    pub postcondition: Condition,
     pub side_effects: Vec<String>,
     pub idempotence: String,
 }
 
+/// A precondition, postcondition or invariant with its parsed expression
+///
+/// Serializes as its text; deserializing parses it again. Conditions the
+/// grammar does not cover keep `expr: None` and are opaque to the executor.
+/// Two conditions are equal when their texts are.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct Condition {
+    pub text: String,
+    pub expr: Option<Expr>,
+}
+
+impl From<String> for Condition {
+    fn from(text: String) -> Self {
+        let expr = parser::condition::parse_condition(&text).ok();
+        Condition { text, expr }
+    }
+}
+
+impl From<&str> for Condition {
+    fn from(text: &str) -> Self {
+        Condition::from(text.to_string())
+    }
+}
+
+impl From<&ConditionNode> for Condition {
+    fn from(node: &ConditionNode) -> Self {
+        Condition {
+            text: node.text.clone(),
+            expr: node.expr.clone(),
+        }
+    }
+}
+
+impl From<Condition> for String {
+    fn from(condition: Condition) -> Self {
+        condition.text
+    }
+}
+
+impl PartialEq for Condition {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ExecutionConstraints {
     pub trigger_types: Vec<String>,
@@ -118,14 +188,17 @@ mod tests {
                 confidence_level: 1.0,
             },
             data_semantics: DataSemantics {
-                state: serde_json::json!({"message": "String", "count": "Integer"}),
+                state: vec![
+                    schema::Field::new("message", schema::Type::String),
+                    schema::Field::new("count", schema::Type::Integer),
+                ],
                 invariants: vec!["count >= 0".into()],
             },
             behavioral_semantics: BehavioralSemantics {
                 operations: vec![Operation {
                     name: "echo".into(),
                     precondition: "input_provided".into(),
-                    parameters: serde_json::json!({"message": "String"}),
+                    parameters: vec![schema::Field::new("message", schema::Type::String)],
                     postcondition: "state_updated".into(),
                     side_effects: vec!["log".into()],
                     idempotence: "idempotent".into(),
@@ -157,6 +230,23 @@ mod tests {
         assert_eq!(contract, deserialized);
     }
 
+    #[test]
+    fn test_condition_serializes_as_text() {
+        let contract = test_contract();
+        let json = serde_json::to_value(&contract).unwrap();
+        assert_eq!(json["data_semantics"]["invariants"][0], "count >= 0");
+
+        let deserialized: Contract = serde_json::from_value(json).unwrap();
+        let invariant = &deserialized.data_semantics.invariants[0];
+        assert!(matches!(
+            invariant.expr,
+            Some(Expr::Binary(BinaryOp::Ge, ..))
+        ));
+        let precondition = &deserialized.behavioral_semantics.operations[0].precondition;
+        assert!(precondition.expr.is_some());
+        assert!(Condition::from("the user is logged in").expr.is_none());
+    }
+
     #[test]
     fn test_determinism_100_iterations() {
         let contract = test_contract();
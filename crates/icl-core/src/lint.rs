@@ -0,0 +1,585 @@
+//! Lint rules — named verifier checks with configurable severity
+//!
+//! Every diagnostic the verifier reports comes from a [`Rule`] with a
+//! stable code (`ICL-C004`) and a name (`unknown-trigger-type`). A rule
+//! has a default severity, which a project may change, and individual
+//! findings may be suppressed in the contract source.
+//!
+//! # Configuration
+//!
+//! A project sets rule levels in an `icl.json` file, found in the
+//! contract's directory or the nearest parent directory:
+//!
+//! ```json
+//! { "lint": { "unknown-trigger-type": "off", "ICL-D001": "warning" } }
+//! ```
+//!
+//! Rules are named by code or name; levels are `error`, `warning` or
+//! `off`.
+//!
+//! # Suppression
+//!
+//! An `// icl-allow(rule, ...)` comment suppresses the named rules on its
+//! own line, or, when the comment stands alone, on the next line with
+//! code:
+//!
+//! ```text
+//! // icl-allow(randomness-in-condition)
+//! precondition: "seed was chosen at random by the caller",
+//! trigger_types: ["api_call"],  // icl-allow(unknown-trigger-type)
+//! ```
+//!
+//! # Rules
+//!
+//! | Code       | Name                              | Default |
+//! |------------|-----------------------------------|---------|
+//! | `ICL-T001` | `negative-version`                | error   |
+//! | `ICL-T002` | `invalid-stable-id`               | error   |
+//! | `ICL-T003` | `invalid-semantic-hash`           | error   |
+//! | `ICL-T004` | `confidence-out-of-range`         | error   |
+//! | `ICL-T005` | `long-narrative`                  | warning |
+//! | `ICL-T006` | `duplicate-type-name`             | error   |
+//! | `ICL-T007` | `recursive-type`                  | error   |
+//! | `ICL-T008` | `nested-optional`                 | warning |
+//! | `ICL-T009` | `duplicate-object-field`          | error   |
+//! | `ICL-T010` | `empty-enum`                      | error   |
+//! | `ICL-T011` | `duplicate-enum-variant`          | error   |
+//! | `ICL-T012` | `unknown-type`                    | error   |
+//! | `ICL-T013` | `invalid-refinement`              | error   |
+//! | `ICL-T014` | `inverted-refinement-bounds`      | error   |
+//! | `ICL-T015` | `float-map-key`                   | error   |
+//! | `ICL-T016` | `invalid-map-key`                 | error   |
+//! | `ICL-T017` | `default-type-mismatch`           | error   |
+//! | `ICL-T018` | `non-positive-resource-limit`     | error   |
+//! | `ICL-T019` | `unknown-identifier`              | error   |
+//! | `ICL-T020` | `unknown-field`                   | error   |
+//! | `ICL-T021` | `unknown-variant`                 | error   |
+//! | `ICL-T022` | `unknown-function`                | error   |
+//! | `ICL-T023` | `invalid-pattern`                 | error   |
+//! | `ICL-T024` | `condition-type-mismatch`         | error   |
+//! | `ICL-T025` | `never-null-comparison`           | warning |
+//! | `ICL-I001` | `invariant-without-fields`        | warning |
+//! | `ICL-I002` | `duplicate-invariant`             | warning |
+//! | `ICL-I003` | `contradictory-invariants`        | error   |
+//! | `ICL-I004` | `initial-state-violates-invariant`| error   |
+//! | `ICL-I005` | `type-default-violates-invariant` | warning |
+//! | `ICL-I006` | `invariant-not-preserved`         | warning |
+//! | `ICL-I007` | `opaque-invariant`                | error   |
+//! | `ICL-D001` | `randomness-in-condition`         | error   |
+//! | `ICL-D002` | `system-time-in-condition`        | error   |
+//! | `ICL-D003` | `external-io-in-condition`        | error   |
+//! | `ICL-D004` | `hash-iteration-in-condition`     | error   |
+//! | `ICL-C001` | `duplicate-operation`             | error   |
+//! | `ICL-C002` | `duplicate-state-field`           | error   |
+//! | `ICL-C003` | `unknown-sandbox-mode`            | warning |
+//! | `ICL-C004` | `unknown-trigger-type`            | warning |
+//! | `ICL-C005` | `unknown-field-reference`         | warning |
+//! | `ICL-C006` | `misplaced-old`                   | error   |
+//! | `ICL-C007` | `nested-old`                      | error   |
+//! | `ICL-C008` | `old-of-parameter`                | error   |
+//! | `ICL-C009` | `duplicate-extension-namespace`   | error   |
+//! | `ICL-C010` | `opaque-condition`                | error   |
+//! | `ICL-C011` | `unknown-lint-rule`               | warning |
+//! | `ICL-C012` | `semantic-hash-mismatch`          | error   |
+//!
+//! `opaque-invariant` and `opaque-condition` only run in strict mode.
+//!
+//! # Guarantees
+//!
+//! - **Stable**: codes and names never change meaning once released
+//! - **Deterministic**: configuration is a sorted map, so the same project
+//!   always yields the same diagnostics
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::parser::tokenizer::Span;
+use crate::verifier::{DiagnosticKind, Severity};
+use crate::{Error, Result};
+
+/// Name of the project configuration file
+pub const CONFIG_FILE: &str = "icl.json";
+
+// ── Rules ─────────────────────────────────────────────────
+
+/// A named verifier check
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rule {
+    /// Stable identifier, e.g. `ICL-D001`
+    pub code: &'static str,
+    /// Name used in configuration and `icl-allow` comments
+    pub name: &'static str,
+    /// Category of the diagnostics the rule reports
+    pub kind: DiagnosticKind,
+    /// Severity unless the project configures another level
+    pub default_severity: Severity,
+    /// One-line description
+    pub summary: &'static str,
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.code, self.name)
+    }
+}
+
+macro_rules! rules {
+    ($($rule:ident = $code:literal, $name:literal, $kind:ident, $severity:ident, $summary:literal;)*) => {
+        $(
+            #[doc = $summary]
+            pub static $rule: Rule = Rule {
+                code: $code,
+                name: $name,
+                kind: DiagnosticKind::$kind,
+                default_severity: Severity::$severity,
+                summary: $summary,
+            };
+        )*
+
+        /// Every rule, ordered by code within each phase
+        pub static RULES: &[&Rule] = &[$(&$rule),*];
+    };
+}
+
+rules! {
+    NEGATIVE_VERSION = "ICL-T001", "negative-version", TypeError, Error,
+        "Contract version is negative";
+    INVALID_STABLE_ID = "ICL-T002", "invalid-stable-id", TypeError, Error,
+        "stable_id does not match the required pattern";
+    INVALID_SEMANTIC_HASH = "ICL-T003", "invalid-semantic-hash", TypeError, Error,
+        "semantic_hash is not hexadecimal";
+    CONFIDENCE_OUT_OF_RANGE = "ICL-T004", "confidence-out-of-range", TypeError, Error,
+        "confidence_level is outside [0.0, 1.0]";
+    LONG_NARRATIVE = "ICL-T005", "long-narrative", TypeError, Warning,
+        "Narrative is longer than 500 characters";
+    DUPLICATE_TYPE_NAME = "ICL-T006", "duplicate-type-name", TypeError, Error,
+        "Two type definitions share a name";
+    RECURSIVE_TYPE = "ICL-T007", "recursive-type", TypeError, Error,
+        "A type definition refers to itself";
+    NESTED_OPTIONAL = "ICL-T008", "nested-optional", TypeError, Warning,
+        "Optional wraps a type that is already optional";
+    DUPLICATE_OBJECT_FIELD = "ICL-T009", "duplicate-object-field", TypeError, Error,
+        "Two fields of an Object type share a name";
+    EMPTY_ENUM = "ICL-T010", "empty-enum", TypeError, Error,
+        "Enum type has no variants";
+    DUPLICATE_ENUM_VARIANT = "ICL-T011", "duplicate-enum-variant", TypeError, Error,
+        "Enum variant is listed twice";
+    UNKNOWN_TYPE = "ICL-T012", "unknown-type", TypeError, Error,
+        "Type name is neither built in nor defined";
+    INVALID_REFINEMENT = "ICL-T013", "invalid-refinement", TypeError, Error,
+        "Refinement is unknown, repeated, inapplicable or has a bad value";
+    INVERTED_REFINEMENT_BOUNDS = "ICL-T014", "inverted-refinement-bounds", TypeError, Error,
+        "Lower refinement bound is greater than the upper one";
+    FLOAT_MAP_KEY = "ICL-T015", "float-map-key", TypeError, Error,
+        "Map is keyed by Float";
+    INVALID_MAP_KEY = "ICL-T016", "invalid-map-key", TypeError, Error,
+        "Map key type is not a primitive or Enum";
+    DEFAULT_TYPE_MISMATCH = "ICL-T017", "default-type-mismatch", TypeError, Error,
+        "Default value does not match the declared type";
+    NON_POSITIVE_RESOURCE_LIMIT = "ICL-T018", "non-positive-resource-limit", TypeError, Error,
+        "Resource limit is zero or negative";
+    UNKNOWN_IDENTIFIER = "ICL-T019", "unknown-identifier", TypeError, Error,
+        "Condition names something that is not a state field or parameter";
+    UNKNOWN_FIELD = "ICL-T020", "unknown-field", TypeError, Error,
+        "Condition reads a field the Object type does not have";
+    UNKNOWN_VARIANT = "ICL-T021", "unknown-variant", TypeError, Error,
+        "Condition compares an Enum with a string that is not a variant";
+    UNKNOWN_FUNCTION = "ICL-T022", "unknown-function", TypeError, Error,
+        "Condition calls a function that is not built in";
+    INVALID_PATTERN = "ICL-T023", "invalid-pattern", TypeError, Error,
+        "Regular expression in a condition does not compile";
+    CONDITION_TYPE_MISMATCH = "ICL-T024", "condition-type-mismatch", TypeError, Error,
+        "Condition combines values of incompatible types";
+    NEVER_NULL_COMPARISON = "ICL-T025", "never-null-comparison", TypeError, Warning,
+        "Condition compares a non-optional value with null";
+    INVARIANT_WITHOUT_FIELDS = "ICL-I001", "invariant-without-fields", InvariantError, Warning,
+        "Invariant references no declared state field";
+    DUPLICATE_INVARIANT = "ICL-I002", "duplicate-invariant", InvariantError, Warning,
+        "Invariant is listed twice";
+    CONTRADICTORY_INVARIANTS = "ICL-I003", "contradictory-invariants", InvariantError, Error,
+        "Invariants cannot all hold at once";
+    INITIAL_STATE_VIOLATES_INVARIANT = "ICL-I004", "initial-state-violates-invariant",
+        InvariantError, Error,
+        "Declared defaults violate an invariant";
+    TYPE_DEFAULT_VIOLATES_INVARIANT = "ICL-I005", "type-default-violates-invariant",
+        InvariantError, Warning,
+        "Initial state violates an invariant through an undeclared default";
+    INVARIANT_NOT_PRESERVED = "ICL-I006", "invariant-not-preserved", InvariantError, Warning,
+        "An operation can break an invariant";
+    OPAQUE_INVARIANT = "ICL-I007", "opaque-invariant", InvariantError, Error,
+        "Invariant is prose, not a machine-checkable expression (strict mode)";
+    RANDOMNESS_IN_CONDITION = "ICL-D001", "randomness-in-condition", DeterminismViolation, Error,
+        "Text mentions randomness or runtime ID generation";
+    SYSTEM_TIME_IN_CONDITION = "ICL-D002", "system-time-in-condition", DeterminismViolation, Error,
+        "Text mentions reading the system clock";
+    EXTERNAL_IO_IN_CONDITION = "ICL-D003", "external-io-in-condition", DeterminismViolation, Error,
+        "Text mentions network or file I/O";
+    HASH_ITERATION_IN_CONDITION = "ICL-D004", "hash-iteration-in-condition",
+        DeterminismViolation, Error,
+        "Text mentions hash-ordered collections";
+    DUPLICATE_OPERATION = "ICL-C001", "duplicate-operation", CoherenceError, Error,
+        "Two operations share a name";
+    DUPLICATE_STATE_FIELD = "ICL-C002", "duplicate-state-field", CoherenceError, Error,
+        "Two state fields share a name";
+    UNKNOWN_SANDBOX_MODE = "ICL-C003", "unknown-sandbox-mode", CoherenceError, Warning,
+        "sandbox_mode is not a recognized mode";
+    UNKNOWN_TRIGGER_TYPE = "ICL-C004", "unknown-trigger-type", CoherenceError, Warning,
+        "Trigger type is not a recognized type";
+    UNKNOWN_FIELD_REFERENCE = "ICL-C005", "unknown-field-reference", CoherenceError, Warning,
+        "Prose condition seems to name an unknown field";
+    MISPLACED_OLD = "ICL-C006", "misplaced-old", CoherenceError, Error,
+        "old(...) outside a postcondition";
+    NESTED_OLD = "ICL-C007", "nested-old", CoherenceError, Error,
+        "old(...) inside another old(...)";
+    OLD_OF_PARAMETER = "ICL-C008", "old-of-parameter", CoherenceError, Error,
+        "old(...) of a parameter, which has no prior value";
+    DUPLICATE_EXTENSION_NAMESPACE = "ICL-C009", "duplicate-extension-namespace",
+        CoherenceError, Error,
+        "Two extension systems share a namespace";
+    OPAQUE_CONDITION = "ICL-C010", "opaque-condition", CoherenceError, Error,
+        "Pre- or postcondition is prose, not a machine-checkable expression (strict mode)";
+    UNKNOWN_LINT_RULE = "ICL-C011", "unknown-lint-rule", CoherenceError, Warning,
+        "icl-allow comment names no rule";
+    SEMANTIC_HASH_MISMATCH = "ICL-C012", "semantic-hash-mismatch", CoherenceError, Error,
+        "semantic_hash is not the hash of the contract";
+}
+
+/// Find a rule by code (`ICL-C004`) or name (`unknown-trigger-type`)
+pub fn lookup(rule: &str) -> Option<&'static Rule> {
+    RULES
+        .iter()
+        .copied()
+        .find(|r| r.code.eq_ignore_ascii_case(rule) || r.name == rule)
+}
+
+// ── Configuration ─────────────────────────────────────────
+
+/// Configured severity of a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+    Off,
+}
+
+impl Level {
+    /// Parse `error`, `warning` or `off`
+    pub fn parse(level: &str) -> Option<Level> {
+        match level {
+            "error" => Some(Level::Error),
+            "warning" => Some(Level::Warning),
+            "off" => Some(Level::Off),
+            _ => None,
+        }
+    }
+}
+
+impl From<Severity> for Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => Level::Error,
+            Severity::Warning => Level::Warning,
+        }
+    }
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Level::Error => write!(f, "error"),
+            Level::Warning => write!(f, "warning"),
+            Level::Off => write!(f, "off"),
+        }
+    }
+}
+
+/// Rule levels set by a project, keyed by rule code
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    levels: BTreeMap<&'static str, Level>,
+}
+
+impl LintConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the level of a rule named by code or name
+    pub fn set(&mut self, rule: &str, level: Level) -> Result<()> {
+        let found = lookup(rule)
+            .ok_or_else(|| Error::ConfigError(format!("unknown lint rule '{}'", rule)))?;
+        self.levels.insert(found.code, level);
+        Ok(())
+    }
+
+    /// The configured level of a rule, or its default severity
+    pub fn level(&self, rule: &Rule) -> Level {
+        self.levels
+            .get(rule.code)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity.into())
+    }
+
+    /// Read the `lint` object of a project configuration file
+    pub fn from_json(text: &str) -> Result<Self> {
+        let json: serde_json::Value = serde_json::from_str(text)
+            .map_err(|e| Error::ConfigError(format!("invalid JSON: {}", e)))?;
+        let mut config = Self::new();
+        let Some(lint) = json.get("lint") else {
+            return Ok(config);
+        };
+        let rules = lint
+            .as_object()
+            .ok_or_else(|| Error::ConfigError("'lint' must be an object".to_string()))?;
+        for (rule, level) in rules {
+            let level = level.as_str().and_then(Level::parse).ok_or_else(|| {
+                Error::ConfigError(format!(
+                    "level of '{}' must be \"error\", \"warning\" or \"off\", found {}",
+                    rule, level
+                ))
+            })?;
+            config.set(rule, level)?;
+        }
+        Ok(config)
+    }
+
+    /// Configuration for a contract file: the nearest `icl.json` in the
+    /// file's directory or its parents, or the defaults when there is none
+    pub fn for_file(file: &Path) -> Result<Self> {
+        let Some(dir) = file.parent() else {
+            return Ok(Self::new());
+        };
+        for dir in dir.ancestors() {
+            let path = dir.join(CONFIG_FILE);
+            if path.is_file() {
+                let text = std::fs::read_to_string(&path).map_err(|e| {
+                    Error::ConfigError(format!("cannot read '{}': {}", path.display(), e))
+                })?;
+                return Self::from_json(&text).map_err(|e| match e {
+                    Error::ConfigError(message) => {
+                        Error::ConfigError(format!("{}: {}", path.display(), message))
+                    }
+                    other => other,
+                });
+            }
+        }
+        Ok(Self::new())
+    }
+}
+
+// ── Suppression ───────────────────────────────────────────
+
+/// A rule named in an `icl-allow` comment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Allow {
+    /// Rule code or name as written
+    pub rule: String,
+    /// Line whose diagnostics it suppresses
+    pub line: usize,
+    /// Location of the name in the comment
+    pub span: Span,
+}
+
+/// The `icl-allow` comments of a source text
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Suppressions {
+    allows: Vec<Allow>,
+}
+
+impl Suppressions {
+    /// Collect the `// icl-allow(...)` comments of a contract's source
+    pub fn from_source(source: &str) -> Self {
+        let mut allows = Vec::new();
+        let mut pending: Vec<Allow> = Vec::new();
+        let mut in_string = false;
+        let mut line_start = 0;
+
+        for (index, line) in source.split('\n').enumerate() {
+            let number = index + 1;
+            let comment = comment_start(line, &mut in_string);
+            let code = &line[..comment.unwrap_or(line.len())];
+            let has_code = !code.trim().is_empty();
+
+            if has_code {
+                for mut allow in pending.drain(..) {
+                    allow.line = number;
+                    allows.push(allow);
+                }
+            }
+            if let Some(start) = comment {
+                for (rule, offset) in allowed_rules(&line[start..]) {
+                    let offset = start + offset;
+                    let allow = Allow {
+                        rule: rule.to_string(),
+                        line: number,
+                        span: Span {
+                            line: number,
+                            column: line[..offset].chars().count() + 1,
+                            offset: line_start + offset,
+                            end: line_start + offset + rule.len(),
+                        },
+                    };
+                    if has_code {
+                        allows.push(allow);
+                    } else {
+                        pending.push(allow);
+                    }
+                }
+            }
+            line_start += line.len() + 1;
+        }
+
+        Self { allows }
+    }
+
+    /// Whether a comment allows `rule` on `line`
+    pub fn allows(&self, rule: &Rule, line: usize) -> bool {
+        self.allows
+            .iter()
+            .any(|a| a.line == line && lookup(&a.rule).is_some_and(|r| r.code == rule.code))
+    }
+
+    /// Comments naming rules that do not exist
+    pub fn unknown(&self) -> impl Iterator<Item = &Allow> {
+        self.allows.iter().filter(|a| lookup(&a.rule).is_none())
+    }
+}
+
+/// Byte offset of the `//` starting a comment on this line, skipping
+/// string literals (which may continue from earlier lines)
+fn comment_start(line: &str, in_string: &mut bool) -> Option<usize> {
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if *in_string => {
+                chars.next();
+            }
+            '"' => *in_string = !*in_string,
+            '/' if !*in_string && matches!(chars.peek(), Some((_, '/'))) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Rule names in the `icl-allow(...)` groups of a comment, with their
+/// byte offsets
+fn allowed_rules(comment: &str) -> Vec<(&str, usize)> {
+    const MARKER: &str = "icl-allow(";
+    let mut rules = Vec::new();
+    let mut rest = 0;
+    while let Some(found) = comment[rest..].find(MARKER) {
+        let open = rest + found + MARKER.len();
+        let Some(close) = comment[open..].find(')') else {
+            break;
+        };
+        let mut offset = open;
+        for part in comment[open..open + close].split(',') {
+            let name = part.trim();
+            if !name.is_empty() {
+                rules.push((name, offset + (part.len() - part.trim_start().len())));
+            }
+            offset += part.len() + 1;
+        }
+        rest = open + close + 1;
+    }
+    rules
+}
+
+// ── Tests ─────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_have_unique_codes_and_names() {
+        let mut codes = std::collections::BTreeSet::new();
+        let mut names = std::collections::BTreeSet::new();
+        for rule in RULES {
+            assert!(codes.insert(rule.code), "duplicate code {}", rule.code);
+            assert!(names.insert(rule.name), "duplicate name {}", rule.name);
+            let prefix = match rule.kind {
+                DiagnosticKind::TypeError => "ICL-T",
+                DiagnosticKind::InvariantError => "ICL-I",
+                DiagnosticKind::DeterminismViolation => "ICL-D",
+                DiagnosticKind::CoherenceError => "ICL-C",
+            };
+            assert!(
+                rule.code.starts_with(prefix),
+                "{} is {}",
+                rule.code,
+                rule.kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_lookup_by_code_or_name() {
+        assert_eq!(lookup("ICL-C004"), Some(&UNKNOWN_TRIGGER_TYPE));
+        assert_eq!(lookup("icl-c004"), Some(&UNKNOWN_TRIGGER_TYPE));
+        assert_eq!(lookup("unknown-trigger-type"), Some(&UNKNOWN_TRIGGER_TYPE));
+        assert_eq!(lookup("no-such-rule"), None);
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let config = LintConfig::from_json(
+            r#"{"lint": {"unknown-trigger-type": "off", "ICL-D001": "warning"}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.level(&UNKNOWN_TRIGGER_TYPE), Level::Off);
+        assert_eq!(config.level(&RANDOMNESS_IN_CONDITION), Level::Warning);
+        assert_eq!(config.level(&DUPLICATE_OPERATION), Level::Error);
+        assert_eq!(config.level(&UNKNOWN_SANDBOX_MODE), Level::Warning);
+        assert_eq!(LintConfig::from_json("{}").unwrap(), LintConfig::new());
+
+        for (json, expected) in [
+            (
+                r#"{"lint": {"no-such-rule": "off"}}"#,
+                "unknown lint rule 'no-such-rule'",
+            ),
+            (
+                r#"{"lint": {"nested-old": "allow"}}"#,
+                "level of 'nested-old' must be",
+            ),
+            (r#"{"lint": ["nested-old"]}"#, "'lint' must be an object"),
+            ("{", "invalid JSON"),
+        ] {
+            let err = LintConfig::from_json(json).unwrap_err().to_string();
+            assert!(err.contains(expected), "{}: {}", json, err);
+        }
+    }
+
+    #[test]
+    fn test_suppressions_target_lines() {
+        let source = "a: \"x\" // icl-allow(nested-old)\n\
+                      // icl-allow(ICL-C004, unknown-sandbox-mode)\n\
+                      \n\
+                      // another comment\n\
+                      b: 1\n\
+                      c: \"// icl-allow(duplicate-operation)\"\n";
+        let suppressions = Suppressions::from_source(source);
+        assert!(suppressions.allows(&NESTED_OLD, 1));
+        assert!(!suppressions.allows(&NESTED_OLD, 2));
+        assert!(suppressions.allows(&UNKNOWN_TRIGGER_TYPE, 5));
+        assert!(suppressions.allows(&UNKNOWN_SANDBOX_MODE, 5));
+        assert!(!suppressions.allows(&UNKNOWN_TRIGGER_TYPE, 2));
+        // Inside a string literal it is not a comment
+        assert!(!suppressions.allows(&DUPLICATE_OPERATION, 6));
+        assert_eq!(suppressions.unknown().count(), 0);
+    }
+
+    #[test]
+    fn test_suppressions_unknown_rule_span() {
+        let source = "x: 1\n  // icl-allow( typo-rule )\ny: 2\n";
+        let suppressions = Suppressions::from_source(source);
+        let unknown: Vec<&Allow> = suppressions.unknown().collect();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].rule, "typo-rule");
+        assert_eq!(unknown[0].line, 3);
+        assert_eq!(unknown[0].span.line, 2);
+        assert_eq!(unknown[0].span.column, 17);
+        assert_eq!(&source[unknown[0].span.range()], "typo-rule");
+    }
+}
@@ -0,0 +1,591 @@
+//! Import resolution — multi-file contracts
+//!
+//! A contract may start with `import "path"` statements naming modules of
+//! shared types and invariants (see [`crate::parser::parse_module`]). The
+//! loader reads each module through a [`Loader`], resolves the module's own
+//! imports depth-first, and merges everything into one self-contained
+//! `ContractNode`:
+//!
+//! - imported type definitions are added to the contract's `Types`
+//! - imported invariants are added to `DataSemantics.invariants`
+//! - spans inside imported content point at the contract's `import`
+//!   statement that brought it in, so diagnostics always refer to the
+//!   contract's own source
+//!
+//! The resolved contract has no `imports` left, so its canonical form and
+//! semantic hash cover the imported content: changing a module changes the
+//! hash of every contract that imports it.
+//!
+//! # Paths
+//!
+//! Import paths are relative to the importing file and use `/` separators.
+//! They are resolved lexically (`.` and `..`) before reaching the loader,
+//! so every module has exactly one path.
+//!
+//! # Guarantees
+//!
+//! - **Deterministic**: imports are resolved in source order, and a module
+//!   imported several times is merged once
+//! - **Terminating**: import cycles are reported, never followed
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+
+use crate::parser::ast::*;
+use crate::parser::tokenizer::Span;
+use crate::{Error, Result};
+
+// ── Loaders ────────────────────────────────────────────────
+
+/// Source of module text, keyed by resolved import path
+pub trait Loader {
+    /// Read the module at `path`
+    fn read(&self, path: &str) -> io::Result<String>;
+}
+
+/// Reads modules from the filesystem
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileLoader;
+
+impl Loader for FileLoader {
+    fn read(&self, path: &str) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+}
+
+/// Serves modules from memory — for bindings without filesystem access
+#[derive(Debug, Clone, Default)]
+pub struct MemoryLoader {
+    files: BTreeMap<String, String>,
+}
+
+impl MemoryLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a module. `path` is normalized like an import path, so
+    /// `./common/types.icl` and `common/types.icl` are the same module.
+    pub fn insert(&mut self, path: &str, source: impl Into<String>) {
+        self.files.insert(resolve_path("", path), source.into());
+    }
+}
+
+impl<K: AsRef<str>, V: Into<String>> FromIterator<(K, V)> for MemoryLoader {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut loader = MemoryLoader::new();
+        for (path, source) in iter {
+            loader.insert(path.as_ref(), source);
+        }
+        loader
+    }
+}
+
+impl Loader for MemoryLoader {
+    fn read(&self, path: &str) -> io::Result<String> {
+        self.files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such module"))
+    }
+}
+
+// ── Errors ─────────────────────────────────────────────────
+
+// Stable codes of import errors, explained in [`crate::explain`]
+
+/// A module file cannot be read
+pub const UNREADABLE_IMPORT: &str = "ICL-M001";
+/// A module file does not parse as a module
+pub const INVALID_MODULE: &str = "ICL-M002";
+/// Modules import each other in a cycle
+pub const IMPORT_CYCLE: &str = "ICL-M003";
+
+/// An import that could not be resolved, located at the contract's
+/// `import` statement it was reached through
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    /// Stable code, e.g. `ICL-M003`
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+}
+
+impl ImportError {
+    /// Render with the offending `import` line and underline carets
+    pub fn render(&self, source: &str, origin: &str) -> String {
+        let header = format!("error[{}]: {}", self.code, self.message);
+        crate::snippet::render(source, origin, &header, &self.span)
+    }
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{}] {} at {}", self.code, self.message, self.span)
+    }
+}
+
+impl From<ImportError> for Error {
+    fn from(e: ImportError) -> Self {
+        Error::ImportError(e.to_string())
+    }
+}
+
+// ── Public API ─────────────────────────────────────────────
+
+/// Read the contract at `path` through `loader`, parse it and resolve
+/// its imports
+///
+/// # Errors
+/// `ImportError` if a file cannot be read or imports form a cycle,
+/// `ParseError` for syntax errors in the contract.
+pub fn load(path: &str, loader: &dyn Loader) -> Result<ContractNode> {
+    let source = loader
+        .read(path)
+        .map_err(|e| Error::ImportError(format!("cannot read '{}': {}", path, e)))?;
+    load_source(path, &source, loader)
+}
+
+/// Parse contract text located at `path` and resolve its imports
+///
+/// # Errors
+/// `ImportError` if a module cannot be read or imports form a cycle,
+/// `ParseError` for syntax errors in the contract.
+pub fn load_source(path: &str, source: &str, loader: &dyn Loader) -> Result<ContractNode> {
+    let contract = crate::parser::parse(source)?;
+    Ok(resolve_imports(contract, path, loader)?)
+}
+
+/// Merge the modules imported by an already parsed contract
+///
+/// Imported types come before the contract's own definitions, imported
+/// invariants before its own invariants. A contract without imports is
+/// returned unchanged.
+///
+/// # Errors
+/// Returns the first import that cannot be read, does not parse as a
+/// module, or closes a cycle.
+pub fn resolve_imports(
+    mut contract: ContractNode,
+    path: &str,
+    loader: &dyn Loader,
+) -> std::result::Result<ContractNode, ImportError> {
+    let imports = std::mem::take(&mut contract.imports);
+    let root = resolve_path("", path);
+    let mut resolver = Resolver {
+        loader,
+        stack: vec![root.clone()],
+        loaded: BTreeSet::new(),
+        types: Vec::new(),
+        invariants: Vec::new(),
+    };
+    for import in &imports {
+        resolver.import(&root, import, &import.span)?;
+    }
+
+    if !resolver.types.is_empty() {
+        let types = contract.types.get_or_insert_with(TypesNode::default);
+        types.definitions.splice(0..0, resolver.types);
+    }
+    contract
+        .data_semantics
+        .invariants
+        .splice(0..0, resolver.invariants);
+    Ok(contract)
+}
+
+/// Resolve `import` relative to the file at `from`
+///
+/// `resolve_path("contracts/a.icl", "../common/types.icl")` is
+/// `"common/types.icl"`. Absolute imports (`/...`) ignore `from`.
+pub fn resolve_path(from: &str, import: &str) -> String {
+    let joined = match from.rfind('/') {
+        Some(i) if !import.starts_with('/') => format!("{}/{}", &from[..i], import),
+        _ => import.to_string(),
+    };
+    let absolute = joined.starts_with('/');
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in joined.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|s| *s != "..") => {
+                segments.pop();
+            }
+            // `..` above the filesystem root stays at the root
+            ".." if absolute => {}
+            _ => segments.push(segment),
+        }
+    }
+
+    let path = segments.join("/");
+    if absolute {
+        format!("/{}", path)
+    } else {
+        path
+    }
+}
+
+// ── Resolver ───────────────────────────────────────────────
+
+struct Resolver<'a> {
+    loader: &'a dyn Loader,
+    /// Files on the current import chain, for cycle detection
+    stack: Vec<String>,
+    /// Modules already merged
+    loaded: BTreeSet<String>,
+    types: Vec<TypeDefinitionNode>,
+    invariants: Vec<ConditionNode>,
+}
+
+impl Resolver<'_> {
+    /// Load `import` (found in `from`) and everything it imports. `at` is
+    /// the root contract's import statement this chain started from.
+    fn import(
+        &mut self,
+        from: &str,
+        import: &ImportNode,
+        at: &Span,
+    ) -> std::result::Result<(), ImportError> {
+        let error = |code: &'static str, message: String| ImportError {
+            code,
+            message,
+            span: at.clone(),
+        };
+        let path = resolve_path(from, &import.path.value);
+
+        if let Some(start) = self.stack.iter().position(|p| *p == path) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(path);
+            return Err(error(
+                IMPORT_CYCLE,
+                format!("import cycle: {}", cycle.join(" -> ")),
+            ));
+        }
+        if self.loaded.contains(&path) {
+            return Ok(());
+        }
+
+        let source = self
+            .loader
+            .read(&path)
+            .map_err(|e| error(UNREADABLE_IMPORT, format!("cannot read '{}': {}", path, e)))?;
+        let module = crate::parser::parse_module(&source)
+            .map_err(|e| error(INVALID_MODULE, format!("in '{}': {}", path, e)))?;
+
+        self.stack.push(path.clone());
+        for inner in &module.imports {
+            self.import(&path, inner, at)?;
+        }
+        self.stack.pop();
+        self.loaded.insert(path);
+
+        for mut definition in module.types.map(|t| t.definitions).unwrap_or_default() {
+            definition.name.span = at.clone();
+            definition.span = at.clone();
+            relocate_type(&mut definition.type_expr, at);
+            self.types.push(definition);
+        }
+        for mut invariant in module.invariants {
+            invariant.span = at.clone();
+            if let Some(ref mut expr) = invariant.expr {
+                relocate_expr(expr, at);
+            }
+            self.invariants.push(invariant);
+        }
+        Ok(())
+    }
+}
+
+// ── Span relocation ────────────────────────────────────────
+
+fn relocate_type(ty: &mut TypeExpression, at: &Span) {
+    match ty {
+        TypeExpression::Primitive(_, span) | TypeExpression::Named(_, span) => *span = at.clone(),
+        TypeExpression::Array(inner, span) | TypeExpression::Optional(inner, span) => {
+            *span = at.clone();
+            relocate_type(inner, at);
+        }
+        TypeExpression::Map(key, value, span) => {
+            *span = at.clone();
+            relocate_type(key, at);
+            relocate_type(value, at);
+        }
+        TypeExpression::Object(fields, span) => {
+            *span = at.clone();
+            for field in fields {
+                field.name.span = at.clone();
+                field.span = at.clone();
+                relocate_type(&mut field.type_expr, at);
+                if let Some(ref mut default) = field.default_value {
+                    relocate_literal(default, at);
+                }
+            }
+        }
+        TypeExpression::Enum(variants, span) => {
+            *span = at.clone();
+            for variant in variants {
+                variant.span = at.clone();
+            }
+        }
+        TypeExpression::Refined(base, refinements, span) => {
+            *span = at.clone();
+            relocate_type(base, at);
+            for refinement in refinements {
+                refinement.name.span = at.clone();
+                refinement.span = at.clone();
+                relocate_literal(&mut refinement.value, at);
+            }
+        }
+    }
+}
+
+fn relocate_literal(lit: &mut LiteralValue, at: &Span) {
+    match lit {
+        LiteralValue::String(_, span)
+        | LiteralValue::Integer(_, span)
+        | LiteralValue::Float(_, span)
+        | LiteralValue::Boolean(_, span)
+        | LiteralValue::Null(span) => *span = at.clone(),
+        LiteralValue::Array(items, span) => {
+            *span = at.clone();
+            for item in items {
+                relocate_literal(item, at);
+            }
+        }
+    }
+}
+
+fn relocate_expr(expr: &mut Expr, at: &Span) {
+    match expr {
+        Expr::Literal(lit) => relocate_literal(lit, at),
+        Expr::Path(_, span) => *span = at.clone(),
+        Expr::Unary(_, operand, span)
+        | Expr::IsEmpty(operand, span)
+        | Expr::IsType(operand, _, span)
+        | Expr::Old(operand, span)
+        | Expr::Field(operand, _, span) => {
+            *span = at.clone();
+            relocate_expr(operand, at);
+        }
+        Expr::Binary(_, left, right, span)
+        | Expr::Index(left, right, span)
+        | Expr::Quantified(_, _, left, right, span) => {
+            *span = at.clone();
+            relocate_expr(left, at);
+            relocate_expr(right, at);
+        }
+        Expr::Call(_, args, span) => {
+            *span = at.clone();
+            for arg in args {
+                relocate_expr(arg, at);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = r#"import "common/types.icl"
+import "common/invariants.icl"
+
+Contract {
+  Identity {
+    stable_id: "ic-import-001",
+    version: 1,
+    created_timestamp: 2026-02-01T00:00:00Z,
+    owner: "test",
+    semantic_hash: "72bf63e7883845324b927e286ce1653b72107718a69135ef608f99fdb52c6563"
+  }
+  PurposeStatement {
+    narrative: "Uses shared modules",
+    intent_source: "test",
+    confidence_level: 1.0
+  }
+  DataSemantics {
+    state: { balance: Money = 0, history: Array<Entry> },
+    invariants: ["history is not empty or balance == 0"]
+  }
+  BehavioralSemantics { operations: [] }
+  ExecutionConstraints {
+    trigger_types: ["manual"],
+    resource_limits: {
+      max_memory_bytes: 1048576,
+      computation_timeout_ms: 100,
+      max_state_size_bytes: 1048576
+    },
+    external_permissions: [],
+    sandbox_mode: "full_isolation"
+  }
+  HumanMachineContract {
+    system_commitments: [],
+    system_refusals: [],
+    user_obligations: []
+  }
+}
+"#;
+
+    fn modules() -> MemoryLoader {
+        MemoryLoader::from_iter([
+            (
+                "common/types.icl",
+                "import \"money.icl\"\nTypes { Entry: Object { amount: Money, note: String } }",
+            ),
+            ("common/money.icl", "Types { Money: Integer }"),
+            (
+                "common/invariants.icl",
+                "import \"./money.icl\"\nInvariants [\"balance >= 0\"]",
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        assert_eq!(resolve_path("a.icl", "b.icl"), "b.icl");
+        assert_eq!(resolve_path("x/a.icl", "./b.icl"), "x/b.icl");
+        assert_eq!(resolve_path("x/y/a.icl", "../b.icl"), "x/b.icl");
+        assert_eq!(resolve_path("a.icl", "../b.icl"), "../b.icl");
+        assert_eq!(resolve_path("/x/a.icl", "b.icl"), "/x/b.icl");
+        assert_eq!(resolve_path("/a.icl", "../../b.icl"), "/b.icl");
+        assert_eq!(resolve_path("x/a.icl", "/abs/b.icl"), "/abs/b.icl");
+    }
+
+    #[test]
+    fn test_load_merges_modules() {
+        let ast = load_source("main.icl", CONTRACT, &modules()).unwrap();
+        assert!(ast.imports.is_empty());
+
+        // Depth-first, each module once: money.icl is imported twice
+        let names: Vec<&str> = ast
+            .type_definitions()
+            .iter()
+            .map(|d| d.name.value.as_str())
+            .collect();
+        assert_eq!(names, vec!["Money", "Entry"]);
+
+        let invariants: Vec<&str> = ast
+            .data_semantics
+            .invariants
+            .iter()
+            .map(|i| i.text.as_str())
+            .collect();
+        assert_eq!(
+            invariants,
+            vec!["balance >= 0", "history is not empty or balance == 0"]
+        );
+
+        let result = crate::verifier::verify(&ast);
+        assert!(result.is_valid(), "{:?}", result.errors());
+    }
+
+    #[test]
+    fn test_imported_spans_point_at_import() {
+        let ast = load_source("main.icl", CONTRACT, &modules()).unwrap();
+        let import = CONTRACT.find("import \"common/types.icl\"").unwrap();
+        let money = &ast.type_definitions()[0];
+        assert_eq!(money.name.span.offset, import);
+        assert_eq!(money.type_expr.span().offset, import);
+
+        let invariant = &ast.data_semantics.invariants[0];
+        let import = CONTRACT.find("import \"common/invariants.icl\"").unwrap();
+        assert_eq!(invariant.span.offset, import);
+        assert_eq!(invariant.expr.as_ref().unwrap().span().offset, import);
+    }
+
+    #[test]
+    fn test_semantic_hash_covers_imports() {
+        let hash = |loader: &MemoryLoader| {
+            let ast = load_source("main.icl", CONTRACT, loader).unwrap();
+            let normalized = crate::normalizer::normalize_ast(ast);
+            crate::normalizer::compute_semantic_hash(&normalized)
+        };
+        let mut changed = modules();
+        changed.insert("common/money.icl", "Types { Money: Float }");
+        assert_ne!(hash(&modules()), hash(&changed));
+        assert_eq!(hash(&modules()), hash(&modules()));
+    }
+
+    #[test]
+    fn test_import_cycle_detected() {
+        let loader = MemoryLoader::from_iter([
+            ("common/types.icl", "import \"money.icl\""),
+            ("common/money.icl", "import \"types.icl\""),
+            ("common/invariants.icl", ""),
+        ]);
+        let err = load_source("main.icl", CONTRACT, &loader)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "Import error: [ICL-M003] import cycle: common/types.icl -> common/money.icl -> common/types.icl at 1:1"
+        );
+
+        // A module importing the contract itself is a cycle too
+        let loader = MemoryLoader::from_iter([
+            ("common/types.icl", "import \"../main.icl\""),
+            ("common/invariants.icl", ""),
+        ]);
+        let err = load_source("main.icl", CONTRACT, &loader).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("import cycle: main.icl -> common/types.icl -> main.icl"));
+    }
+
+    #[test]
+    fn test_missing_and_invalid_modules() {
+        let loader = MemoryLoader::from_iter([("common/types.icl", "Types { }")]);
+        let err = load_source("main.icl", CONTRACT, &loader)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("cannot read 'common/invariants.icl'") && err.ends_with("at 2:1"),
+            "{}",
+            err
+        );
+
+        let loader = MemoryLoader::from_iter([
+            ("common/types.icl", "Contract { }"),
+            ("common/invariants.icl", ""),
+        ]);
+        let err = load_source("main.icl", CONTRACT, &loader)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("in 'common/types.icl'") && err.contains("Expected Types, Invariants"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_contract_without_imports_unchanged() {
+        let source = CONTRACT.split_once("\n\n").unwrap().1;
+        let ast = crate::parser::parse(source).unwrap();
+        let resolved = resolve_imports(ast.clone(), "main.icl", &MemoryLoader::new()).unwrap();
+        assert_eq!(resolved, ast);
+    }
+
+    #[test]
+    fn test_load_fixtures_from_filesystem() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/fixtures/imports");
+        let ast = load(&format!("{}/ledger.icl", dir), &FileLoader).unwrap();
+        assert_eq!(ast.type_definitions().len(), 3);
+        assert_eq!(ast.data_semantics.invariants.len(), 2);
+        assert!(crate::verifier::verify(&ast).is_valid());
+
+        let err = load(&format!("{}/cycle.icl", dir), &FileLoader).unwrap_err();
+        assert!(err.to_string().contains("import cycle: "), "{}", err);
+        assert!(err.to_string().contains("cycle/a.icl -> "), "{}", err);
+    }
+
+    #[test]
+    fn test_load_determinism_100_iterations() {
+        let first = load_source("main.icl", CONTRACT, &modules()).unwrap();
+        for i in 0..100 {
+            let ast = load_source("main.icl", CONTRACT, &modules()).unwrap();
+            assert_eq!(first, ast, "Determinism failure at iteration {}", i);
+        }
+    }
+}
@@ -16,6 +16,7 @@
 
 use sha2::{Digest, Sha256};
 
+use crate::builtins;
 use crate::parser::ast::*;
 use crate::parser::tokenizer::Span;
 use crate::Result;
@@ -44,27 +45,44 @@ pub fn normalize(icl: &str) -> Result<String> {
 /// Normalize a parsed AST to canonical form (sorted, expanded, hashed)
 ///
 /// Steps per CORE-SPECIFICATION.md §6.1:
-/// 1. Sort state fields alphabetically
-/// 2. Sort operation parameters alphabetically
-/// 3. Sort operations by name
-/// 4. Sort string lists alphabetically
-/// 5. Expand defaults (already in AST)
-/// 6. Compute SHA-256 semantic hash
+/// 1. Sort state fields and named type definitions alphabetically
+/// 2. Rewrite parsed conditions in canonical form (see [`canonical_expr`])
+/// 3. Sort operation parameters alphabetically
+/// 4. Sort operations by name
+/// 5. Sort string lists alphabetically
+/// 6. Expand defaults (already in AST)
+/// 7. Compute SHA-256 semantic hash
 pub fn normalize_ast(mut ast: ContractNode) -> ContractNode {
     // ── Step 1: Sort state fields ──────────────────────
     ast.data_semantics
         .state
         .sort_by(|a, b| a.name.value.cmp(&b.name.value));
 
+    // Sort unresolved imports by path
+    ast.imports.sort_by(|a, b| a.path.value.cmp(&b.path.value));
+
+    // Sort named type definitions, and their Object fields
+    if let Some(ref mut types) = ast.types {
+        types
+            .definitions
+            .sort_by(|a, b| a.name.value.cmp(&b.name.value));
+        for definition in &mut types.definitions {
+            normalize_type_fields(&mut definition.type_expr);
+        }
+    }
+
     // Sort Object type fields recursively
     for field in &mut ast.data_semantics.state {
         normalize_type_fields(&mut field.type_expr);
     }
 
-    // ── Step 2: Sort invariants ────────────────────────
+    // ── Step 2: Canonicalize conditions, sort invariants ─
+    for invariant in &mut ast.data_semantics.invariants {
+        normalize_condition(invariant);
+    }
     ast.data_semantics
         .invariants
-        .sort_by(|a, b| a.value.cmp(&b.value));
+        .sort_by(|a, b| a.text.cmp(&b.text));
 
     // ── Step 3: Sort operations by name ────────────────
     ast.behavioral_semantics
@@ -73,6 +91,8 @@ pub fn normalize_ast(mut ast: ContractNode) -> ContractNode {
 
     // ── Step 4: Sort operation internals ───────────────
     for op in &mut ast.behavioral_semantics.operations {
+        normalize_condition(&mut op.precondition);
+        normalize_condition(&mut op.postcondition);
         op.parameters
             .sort_by(|a, b| a.name.value.cmp(&b.name.value));
         for param in &mut op.parameters {
@@ -136,9 +156,19 @@ pub fn normalize_contract(contract: &crate::Contract) -> Result<crate::Contract>
 pub fn serialize_canonical(ast: &ContractNode) -> String {
     let mut out = String::new();
 
+    // Unresolved imports (see `crate::loader`); a resolved contract has none
+    for import in &ast.imports {
+        out.push_str(&format!("import {}\n", quote(&import.path.value)));
+    }
+    if !ast.imports.is_empty() {
+        out.push('\n');
+    }
     out.push_str("Contract {\n");
     serialize_identity(&mut out, &ast.identity);
     serialize_purpose_statement(&mut out, &ast.purpose_statement);
+    if let Some(ref types) = ast.types {
+        serialize_types(&mut out, types);
+    }
     serialize_data_semantics(&mut out, &ast.data_semantics);
     serialize_behavioral_semantics(&mut out, &ast.behavioral_semantics);
     serialize_execution_constraints(&mut out, &ast.execution_constraints);
@@ -173,11 +203,23 @@ fn serialize_purpose_statement(out: &mut String, ps: &PurposeStatementNode) {
     out.push_str("  }\n");
 }
 
+fn serialize_types(out: &mut String, types: &TypesNode) {
+    out.push_str("  Types {\n");
+    for definition in &types.definitions {
+        write_indent(out, 4);
+        out.push_str(&definition.name.value);
+        out.push_str(": ");
+        serialize_type_expression(out, &definition.type_expr);
+        out.push_str(",\n");
+    }
+    out.push_str("  }\n");
+}
+
 fn serialize_data_semantics(out: &mut String, ds: &DataSemanticsNode) {
     out.push_str("  DataSemantics {\n");
     write_indent(out, 4);
     out.push_str("invariants: ");
-    serialize_string_list(out, &ds.invariants);
+    serialize_condition_list(out, &ds.invariants);
     out.push_str(",\n");
     write_indent(out, 4);
     out.push_str("state: {\n");
@@ -217,8 +259,8 @@ fn serialize_operation(out: &mut String, op: &OperationNode, indent: usize) {
     }
     write_indent(out, indent + 2);
     out.push_str("},\n");
-    write_field_str(out, indent + 2, "postcondition", &op.postcondition.value);
-    write_field_str(out, indent + 2, "precondition", &op.precondition.value);
+    write_field_str(out, indent + 2, "postcondition", &op.postcondition.text);
+    write_field_str(out, indent + 2, "precondition", &op.precondition.text);
     write_indent(out, indent + 2);
     out.push_str("side_effects: ");
     serialize_string_list(out, &op.side_effects);
@@ -354,22 +396,36 @@ fn serialize_type_expression(out: &mut String, ty: &TypeExpression) {
                 if i > 0 {
                     out.push_str(", ");
                 }
-                out.push('"');
-                out.push_str(&v.value);
-                out.push('"');
+                out.push_str(&quote(&v.value));
             }
             out.push(']');
         }
+        TypeExpression::Optional(inner, _) => {
+            out.push_str("Optional<");
+            serialize_type_expression(out, inner);
+            out.push('>');
+        }
+        TypeExpression::Refined(base, refinements, _) => {
+            serialize_type_expression(out, base);
+            out.push('(');
+            // Refinements already sorted during normalization
+            for (i, r) in refinements.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&r.name.value);
+                out.push_str(": ");
+                serialize_literal_value(out, &r.value);
+            }
+            out.push(')');
+        }
+        TypeExpression::Named(name, _) => out.push_str(name),
     }
 }
 
 fn serialize_literal_value(out: &mut String, val: &LiteralValue) {
     match val {
-        LiteralValue::String(s, _) => {
-            out.push('"');
-            out.push_str(s);
-            out.push('"');
-        }
+        LiteralValue::String(s, _) => out.push_str(&quote(s)),
         LiteralValue::Integer(n, _) => out.push_str(&n.to_string()),
         LiteralValue::Float(f, _) => {
             // Ensure we always have a decimal point
@@ -381,6 +437,7 @@ fn serialize_literal_value(out: &mut String, val: &LiteralValue) {
             }
         }
         LiteralValue::Boolean(b, _) => out.push_str(if *b { "true" } else { "false" }),
+        LiteralValue::Null(_) => out.push_str("null"),
         LiteralValue::Array(items, _) => {
             out.push('[');
             for (i, item) in items.iter().enumerate() {
@@ -400,9 +457,18 @@ fn serialize_string_list(out: &mut String, items: &[SpannedValue<String>]) {
         if i > 0 {
             out.push_str(", ");
         }
-        out.push('"');
-        out.push_str(&item.value);
-        out.push('"');
+        out.push_str(&quote(&item.value));
+    }
+    out.push(']');
+}
+
+fn serialize_condition_list(out: &mut String, items: &[ConditionNode]) {
+    out.push('[');
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(&quote(&item.text));
     }
     out.push(']');
 }
@@ -418,9 +484,19 @@ fn write_indent(out: &mut String, n: usize) {
 fn write_field_str(out: &mut String, indent: usize, name: &str, value: &str) {
     write_indent(out, indent);
     out.push_str(name);
-    out.push_str(": \"");
-    out.push_str(value);
-    out.push_str("\",\n");
+    out.push_str(": ");
+    out.push_str(&quote(value));
+    out.push_str(",\n");
+}
+
+/// `{ name: Type = default, ... }` on one line; `normalize` re-indents it
+fn write_fields(out: &mut String, fields: &[crate::schema::Field]) {
+    out.push('{');
+    for (i, field) in fields.iter().enumerate() {
+        out.push_str(if i > 0 { ", " } else { " " });
+        out.push_str(&field.to_string());
+    }
+    out.push_str(if fields.is_empty() { "}" } else { " }" });
 }
 
 fn write_field_int(out: &mut String, indent: usize, name: &str, value: i64) {
@@ -444,6 +520,89 @@ fn write_field_float(out: &mut String, indent: usize, name: &str, value: f64) {
     out.push_str(",\n");
 }
 
+/// Replace a parsed condition's text with its canonical form; opaque
+/// conditions are kept as written
+fn normalize_condition(condition: &mut ConditionNode) {
+    if let Some(expr) = condition.expr.take() {
+        let expr = canonical_expr(expr);
+        condition.text = expr.to_string();
+        condition.expr = Some(expr);
+    }
+}
+
+/// Canonical form of a condition expression.
+///
+/// Printing the result gives one spelling per meaning: single spaces
+/// around operators, `==` for `=` / `equals`, lowercase keywords, double
+/// quoted strings and only the parentheses precedence needs. On top of
+/// that, the operands of a chain of `and` (or of `or`) are sorted by their
+/// canonical text, since both are commutative: `b and a` becomes `a and b`.
+/// Calls to a built-in alias use the built-in's name (`length` → `len`).
+pub fn canonical_expr(expr: Expr) -> Expr {
+    match expr {
+        Expr::Binary(op @ (BinaryOp::And | BinaryOp::Or), left, right, span) => {
+            let mut operands = Vec::new();
+            flatten(op, *left, &mut operands);
+            flatten(op, *right, &mut operands);
+            let mut operands: Vec<(String, Expr)> = operands
+                .into_iter()
+                .map(|e| {
+                    let e = canonical_expr(e);
+                    (e.to_string(), e)
+                })
+                .collect();
+            operands.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut operands = operands.into_iter().map(|(_, e)| e);
+            let first = operands.next().expect("a binary chain has two operands");
+            operands.fold(first, |acc, e| {
+                Expr::Binary(op, Box::new(acc), Box::new(e), span.clone())
+            })
+        }
+        Expr::Binary(op, left, right, span) => Expr::Binary(
+            op,
+            Box::new(canonical_expr(*left)),
+            Box::new(canonical_expr(*right)),
+            span,
+        ),
+        Expr::Unary(op, operand, span) => Expr::Unary(op, Box::new(canonical_expr(*operand)), span),
+        Expr::IsEmpty(operand, span) => Expr::IsEmpty(Box::new(canonical_expr(*operand)), span),
+        Expr::IsType(operand, ty, span) => {
+            Expr::IsType(Box::new(canonical_expr(*operand)), ty, span)
+        }
+        Expr::Old(operand, span) => Expr::Old(Box::new(canonical_expr(*operand)), span),
+        Expr::Field(base, name, span) => Expr::Field(Box::new(canonical_expr(*base)), name, span),
+        Expr::Index(base, index, span) => Expr::Index(
+            Box::new(canonical_expr(*base)),
+            Box::new(canonical_expr(*index)),
+            span,
+        ),
+        Expr::Quantified(quantifier, var, collection, body, span) => Expr::Quantified(
+            quantifier,
+            var,
+            Box::new(canonical_expr(*collection)),
+            Box::new(canonical_expr(*body)),
+            span,
+        ),
+        Expr::Call(name, args, span) => {
+            // Aliases are written as the built-in they stand for
+            let name = builtins::lookup(&name).map_or(name, |b| b.name.to_string());
+            Expr::Call(name, args.into_iter().map(canonical_expr).collect(), span)
+        }
+        Expr::Literal(_) | Expr::Path(_, _) => expr,
+    }
+}
+
+/// Collect the operands of a chain of `op`, left to right
+fn flatten(op: BinaryOp, expr: Expr, out: &mut Vec<Expr>) {
+    match expr {
+        Expr::Binary(inner, left, right, _) if inner == op => {
+            flatten(op, *left, out);
+            flatten(op, *right, out);
+        }
+        other => out.push(other),
+    }
+}
+
 fn normalize_type_fields(ty: &mut TypeExpression) {
     match ty {
         TypeExpression::Object(fields, _) => {
@@ -452,7 +611,9 @@ fn normalize_type_fields(ty: &mut TypeExpression) {
                 normalize_type_fields(&mut f.type_expr);
             }
         }
-        TypeExpression::Array(inner, _) => normalize_type_fields(inner),
+        TypeExpression::Array(inner, _) | TypeExpression::Optional(inner, _) => {
+            normalize_type_fields(inner)
+        }
         TypeExpression::Map(k, v, _) => {
             normalize_type_fields(k);
             normalize_type_fields(v);
@@ -461,7 +622,11 @@ fn normalize_type_fields(ty: &mut TypeExpression) {
             // Sort enum variants alphabetically for canonical form
             variants.sort_by(|a, b| a.value.cmp(&b.value));
         }
-        TypeExpression::Primitive(_, _) => {}
+        TypeExpression::Refined(base, refinements, _) => {
+            normalize_type_fields(base);
+            refinements.sort_by(|a, b| a.name.value.cmp(&b.name.value));
+        }
+        TypeExpression::Primitive(_, _) | TypeExpression::Named(_, _) => {}
     }
 }
 
@@ -470,6 +635,7 @@ fn dummy_span() -> Span {
         line: 0,
         column: 0,
         offset: 0,
+        end: 0,
     }
 }
 
@@ -543,18 +709,19 @@ fn serialize_contract_to_icl(contract: &crate::Contract) -> String {
     );
     out.push_str("  }\n");
 
-    // DataSemantics — state as empty since Contract uses serde_json::Value
+    // DataSemantics
     out.push_str("  DataSemantics {\n");
-    out.push_str("    state: {},\n");
+    write_indent(&mut out, 4);
+    out.push_str("state: ");
+    write_fields(&mut out, &contract.data_semantics.state);
+    out.push_str(",\n");
     write_indent(&mut out, 4);
     out.push_str("invariants: [");
     for (i, inv) in contract.data_semantics.invariants.iter().enumerate() {
         if i > 0 {
             out.push_str(", ");
         }
-        out.push('"');
-        out.push_str(inv);
-        out.push('"');
+        out.push_str(&quote(&inv.text));
     }
     out.push_str("]\n");
     out.push_str("  }\n");
@@ -565,18 +732,19 @@ fn serialize_contract_to_icl(contract: &crate::Contract) -> String {
     for op in &contract.behavioral_semantics.operations {
         out.push_str("      {\n");
         write_field_str(&mut out, 8, "name", &op.name);
-        write_field_str(&mut out, 8, "precondition", &op.precondition);
-        out.push_str("        parameters: {},\n");
-        write_field_str(&mut out, 8, "postcondition", &op.postcondition);
+        write_field_str(&mut out, 8, "precondition", &op.precondition.text);
+        write_indent(&mut out, 8);
+        out.push_str("parameters: ");
+        write_fields(&mut out, &op.parameters);
+        out.push_str(",\n");
+        write_field_str(&mut out, 8, "postcondition", &op.postcondition.text);
         write_indent(&mut out, 8);
         out.push_str("side_effects: [");
         for (i, se) in op.side_effects.iter().enumerate() {
             if i > 0 {
                 out.push_str(", ");
             }
-            out.push('"');
-            out.push_str(se);
-            out.push('"');
+            out.push_str(&quote(se));
         }
         out.push_str("],\n");
         write_field_str(&mut out, 8, "idempotence", &op.idempotence);
@@ -598,9 +766,7 @@ fn serialize_contract_to_icl(contract: &crate::Contract) -> String {
         if i > 0 {
             out.push_str(", ");
         }
-        out.push('"');
-        out.push_str(t);
-        out.push('"');
+        out.push_str(&quote(t));
     }
     out.push_str("],\n");
     out.push_str("    resource_limits: {\n");
@@ -643,9 +809,7 @@ fn serialize_contract_to_icl(contract: &crate::Contract) -> String {
         if i > 0 {
             out.push_str(", ");
         }
-        out.push('"');
-        out.push_str(p);
-        out.push('"');
+        out.push_str(&quote(p));
     }
     out.push_str("],\n");
     write_field_str(
@@ -690,9 +854,7 @@ fn write_string_list(out: &mut String, indent: usize, name: &str, items: &[Strin
         if i > 0 {
             out.push_str(", ");
         }
-        out.push('"');
-        out.push_str(item);
-        out.push('"');
+        out.push_str(&quote(item));
     }
     out.push_str("],\n");
 }
@@ -1027,6 +1189,152 @@ mod tests {
         );
     }
 
+    // ── Condition canonicalization ─────────────────────
+
+    /// MINIMAL_CONTRACT with a numeric state field, the given invariants
+    /// and one operation with the given pre- and postcondition
+    fn conditions_contract(invariants: &[&str], pre: &str, post: &str) -> String {
+        let quoted: Vec<String> = invariants.iter().map(|i| quote(i)).collect();
+        MINIMAL_CONTRACT
+            .replace(
+                "value: String\n    },\n    invariants: []",
+                &format!(
+                    "value: String,\n      count: Integer\n    }},\n    invariants: [{}]",
+                    quoted.join(", ")
+                ),
+            )
+            .replace(
+                "operations: []",
+                &format!(
+                    "operations: [\n      {{\n        name: \"bump\",\n        precondition: {},\n        parameters: {{}},\n        postcondition: {},\n        side_effects: [],\n        idempotence: \"none\"\n      }}\n    ]",
+                    quote(pre),
+                    quote(post)
+                ),
+            )
+    }
+
+    fn hash_of(icl: &str) -> String {
+        let n = normalize(icl).unwrap();
+        crate::parser::parse(&n)
+            .unwrap()
+            .identity
+            .semantic_hash
+            .value
+    }
+
+    #[test]
+    fn test_canonical_conditions_ignore_spelling() {
+        let a = conditions_contract(
+            &["count >= 0", "value != \"x\"", "len(value) < 10"],
+            "count >= 0",
+            "count == old(count) + 1",
+        );
+        let b = conditions_contract(
+            &["value is not 'x'", "(count)>=0", "length(value)<10"],
+            "((count >= 0))",
+            "count equals old(count)+1",
+        );
+        assert_eq!(normalize(&a).unwrap(), normalize(&b).unwrap());
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn test_canonical_conditions_order_commutative_operands() {
+        let a = conditions_contract(
+            &["count >= 0 and value is not empty or count < 10"],
+            "count < 5 and count >= 0",
+            "count > 0",
+        );
+        let b = conditions_contract(
+            &["count < 10 or (value is not empty and count >= 0)"],
+            "count >= 0 and count < 5",
+            "count > 0",
+        );
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        let normalized = normalize(&a).unwrap();
+        assert!(normalized.contains("\"count < 5 and count >= 0\""));
+        assert!(normalized.contains("\"count < 10 or count >= 0 and not value is empty\""));
+    }
+
+    #[test]
+    fn test_canonical_conditions_keep_meaningful_parentheses() {
+        let grouped = conditions_contract(
+            &["(count < 1 or count > 5) and value is empty"],
+            "true",
+            "true",
+        );
+        let normalized = normalize(&grouped).unwrap();
+        assert!(
+            normalized.contains("\"(count < 1 or count > 5) and value is empty\""),
+            "{normalized}"
+        );
+
+        // Non-commutative operators keep their operand order
+        let a = conditions_contract(&["count - 1 >= 0"], "true", "true");
+        let b = conditions_contract(&["1 - count >= 0"], "true", "true");
+        assert_ne!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn test_canonical_conditions_keep_opaque_text() {
+        let icl = conditions_contract(
+            &["count is always sensible"],
+            "true",
+            "count was incremented",
+        );
+        let normalized = normalize(&icl).unwrap();
+        assert!(normalized.contains("\"count is always sensible\""));
+        assert!(normalized.contains("\"count was incremented\""));
+    }
+
+    #[test]
+    fn test_canonical_conditions_idempotent_and_reparse() {
+        let icl = conditions_contract(
+            &["all t in [\"a\", 'b'] : len(t) >= 1 AND count>=0"],
+            "value == 'hi' or count = 0",
+            "count equals old(count)",
+        );
+        let once = normalize(&icl).unwrap();
+        let twice = normalize(&once).unwrap();
+        assert_eq!(once, twice);
+
+        let ast = crate::parser::parse(&once).unwrap();
+        for invariant in &ast.data_semantics.invariants {
+            assert!(
+                invariant.expr.is_some(),
+                "{} should re-parse",
+                invariant.text
+            );
+        }
+        let op = &ast.behavioral_semantics.operations[0];
+        assert!(op.precondition.expr.is_some(), "{}", op.precondition.text);
+        assert_eq!(op.precondition.text, "count == 0 or value == \"hi\"");
+    }
+
+    #[test]
+    fn test_canonical_conditions_roundtrip_escaped_literals() {
+        // Condition literals with quotes, backslashes and newlines survive
+        // being quoted into the contract and parsed back
+        let icl = conditions_contract(
+            &[r#"value != 'say \"hi\"'"#, r#"value != "a\\b""#],
+            r#"value != "line\none""#,
+            "true",
+        );
+        let once = normalize(&icl).unwrap();
+        let twice = normalize(&once).unwrap();
+        assert_eq!(once, twice);
+        assert_eq!(hash_of(&icl), hash_of(&once));
+
+        let ast = crate::parser::parse(&once).unwrap();
+        for invariant in &ast.data_semantics.invariants {
+            assert!(invariant.expr.is_some(), "{}", invariant.text);
+        }
+        let op = &ast.behavioral_semantics.operations[0];
+        assert!(op.precondition.expr.is_some(), "{}", op.precondition.text);
+        assert_eq!(op.precondition.text, r#"value != "line\none""#);
+    }
+
     // ── Idempotence proof ──────────────────────────────
 
     #[test]
@@ -1174,8 +1482,114 @@ mod tests {
         assert_eq!(orig_names, norm_names);
     }
 
+    #[test]
+    fn test_normalize_unresolved_imports() {
+        let input = format!("import \"./z.icl\"\nimport \"a.icl\"\n{}", MINIMAL_CONTRACT);
+        let normalized = normalize(&input).unwrap();
+        assert!(normalized.starts_with("import \"./z.icl\"\nimport \"a.icl\"\n\nContract {\n"));
+        assert_eq!(normalize(&normalized).unwrap(), normalized);
+    }
+
+    #[test]
+    fn test_normalize_refinements() {
+        let input = MINIMAL_CONTRACT.replace(
+            "value: String",
+            "value: String(pattern: \"^a\\\"b$\", max_len: 8, min_len: 1)",
+        );
+        let normalized = normalize(&input).unwrap();
+        // Sorted by name; strings stay escaped so the output re-parses
+        assert!(
+            normalized.contains("value: String(max_len: 8, min_len: 1, pattern: \"^a\\\"b$\"),")
+        );
+        assert_eq!(normalize(&normalized).unwrap(), normalized);
+
+        // Refinements are part of the hash
+        let hash = |text: &str| {
+            crate::parser::parse(&normalize(text).unwrap())
+                .unwrap()
+                .identity
+                .semantic_hash
+                .value
+        };
+        assert_ne!(
+            hash(&input),
+            hash(&input.replace("max_len: 8", "max_len: 9"))
+        );
+        assert_ne!(hash(&input), hash(MINIMAL_CONTRACT));
+    }
+
+    #[test]
+    fn test_normalize_contract_keeps_schema() {
+        let input = MINIMAL_CONTRACT.replace(
+            "value: String",
+            "value: String(max_len: 4) = \"a\", note: Optional<String> = null, \
+             pos: Object { y: Float, x: Float = 1.5 }",
+        );
+        let contract = crate::parser::parse_contract(&input).unwrap();
+        let normalized = normalize_contract(&contract).unwrap();
+
+        // Same fields, sorted by name, with types and defaults intact
+        let state = &normalized.data_semantics.state;
+        let names: Vec<&str> = state.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["note", "pos", "value"]);
+        assert_eq!(state[0].default, Some(serde_json::Value::Null));
+        assert_eq!(
+            state[1].ty.to_string(),
+            "Object { x: Float = 1.5, y: Float }"
+        );
+        assert_eq!(state[2].to_string(), "value: String(max_len: 4) = \"a\"");
+        assert_eq!(normalize_contract(&normalized).unwrap(), normalized);
+    }
+
+    #[test]
+    fn test_normalize_contract_quotes_string_lists() {
+        let input = conditions_contract(&[r#"value == "active""#], "true", "true")
+            .replace(
+                "trigger_types: [\"manual\"]",
+                r#"trigger_types: ["manual", "say \"hi\""]"#,
+            )
+            .replace(
+                "system_commitments: []",
+                r#"system_commitments: ["keep \"value\" set"]"#,
+            );
+        let contract = crate::parser::parse_contract(&input).unwrap();
+        let normalized = normalize_contract(&contract).unwrap();
+
+        assert_eq!(
+            normalized.data_semantics.invariants[0].text,
+            r#"value == "active""#
+        );
+        assert!(normalized
+            .execution_constraints
+            .trigger_types
+            .contains(&r#"say "hi""#.to_string()));
+        assert_eq!(normalize_contract(&normalized).unwrap(), normalized);
+    }
+
     // ── Conformance fixtures ───────────────────────────
 
+    #[test]
+    fn test_normalize_types_section() {
+        let input = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../../tests/fixtures/conformance/valid/types-section.icl"),
+        )
+        .unwrap();
+        let normalized = normalize(&input).unwrap();
+
+        // Definitions sorted by name, placed before DataSemantics
+        let amount = normalized.find("    Amount: Integer,").unwrap();
+        let entry = normalized.find("    Entry: Object {").unwrap();
+        let status = normalized
+            .find("    Status: Enum[\"active\", \"archived\"],")
+            .unwrap();
+        assert!(amount < entry && entry < status);
+        assert!(status < normalized.find("  DataSemantics {").unwrap());
+        // References stay by name
+        assert!(normalized.contains("entries: Array<Entry>,"));
+        assert_eq!(normalize(&normalized).unwrap(), normalized);
+    }
+
     #[test]
     fn test_normalize_conformance_valid_minimal() {
         let input = read_fixture("conformance/valid/minimal-contract.icl");
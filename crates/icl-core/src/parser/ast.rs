@@ -13,10 +13,14 @@ use super::tokenizer::Span;
 // ── Top-Level ──────────────────────────────────────────────
 
 /// Root AST node for an ICL contract definition
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ContractNode {
+    /// `import` statements before `Contract`. Empty once imports have
+    /// been resolved (see `crate::loader`).
+    pub imports: Vec<ImportNode>,
     pub identity: IdentityNode,
     pub purpose_statement: PurposeStatementNode,
+    pub types: Option<TypesNode>,
     pub data_semantics: DataSemanticsNode,
     pub behavioral_semantics: BehavioralSemanticsNode,
     pub execution_constraints: ExecutionConstraintsNode,
@@ -25,9 +29,40 @@ pub struct ContractNode {
     pub span: Span,
 }
 
+impl ContractNode {
+    /// Named type definitions from the `Types` section (empty if absent)
+    pub fn type_definitions(&self) -> &[TypeDefinitionNode] {
+        self.types.as_ref().map_or(&[], |t| &t.definitions)
+    }
+}
+
+// ── Imports ───────────────────────────────────────────────
+
+/// `import "path/to/module.icl"`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportNode {
+    pub path: SpannedValue<String>,
+    pub span: Span,
+}
+
+/// Root AST node for an imported module: shared types and invariants
+///
+/// ```text
+/// import "other.icl"
+/// Types { ... }
+/// Invariants ["...", ...]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModuleNode {
+    pub imports: Vec<ImportNode>,
+    pub types: Option<TypesNode>,
+    pub invariants: Vec<ConditionNode>,
+    pub span: Span,
+}
+
 // ── Identity (§1.2) ───────────────────────────────────────
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct IdentityNode {
     pub stable_id: SpannedValue<String>,
     pub version: SpannedValue<i64>,
@@ -39,7 +74,7 @@ pub struct IdentityNode {
 
 // ── Purpose Statement (§1.3) ──────────────────────────────
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct PurposeStatementNode {
     pub narrative: SpannedValue<String>,
     pub intent_source: SpannedValue<String>,
@@ -47,12 +82,29 @@ pub struct PurposeStatementNode {
     pub span: Span,
 }
 
-// ── Data Semantics (§1.4) ─────────────────────────────────
+// ── Types ─────────────────────────────────────────────────
+
+/// Optional `Types { Name: TypeExpression, ... }` section
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypesNode {
+    pub definitions: Vec<TypeDefinitionNode>,
+    pub span: Span,
+}
 
+/// A reusable named type: `Entry: Object { ... }`
 #[derive(Debug, Clone, PartialEq)]
+pub struct TypeDefinitionNode {
+    pub name: SpannedValue<String>,
+    pub type_expr: TypeExpression,
+    pub span: Span,
+}
+
+// ── Data Semantics (§1.4) ─────────────────────────────────
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct DataSemanticsNode {
     pub state: Vec<StateFieldNode>,
-    pub invariants: Vec<SpannedValue<String>>,
+    pub invariants: Vec<ConditionNode>,
     pub span: Span,
 }
 
@@ -80,6 +132,46 @@ pub enum TypeExpression {
     Object(Vec<StateFieldNode>, Span),
     /// Enum ["a", "b", "c"]
     Enum(Vec<SpannedValue<String>>, Span),
+    /// Optional<T> — a T, or `null` when intentionally absent
+    Optional(Box<TypeExpression>, Span),
+    /// T(name: value, ...) — a base type narrowed by refinements,
+    /// e.g. `Integer(min: 0, max: 100)`
+    Refined(Box<TypeExpression>, Vec<RefinementNode>, Span),
+    /// Reference to a type defined in the `Types` section
+    Named(String, Span),
+}
+
+impl TypeExpression {
+    /// Follow `Named` references through `types` to the type they stand
+    /// for. Stops at the last `Named` reached for unknown or cyclic names;
+    /// nested types (`Array<Entry>`) are left as written.
+    pub fn resolve<'a>(&'a self, types: &'a [TypeDefinitionNode]) -> &'a TypeExpression {
+        let mut current = self;
+        // Any chain longer than the number of definitions is a cycle
+        for _ in 0..=types.len() {
+            let TypeExpression::Named(name, _) = current else {
+                return current;
+            };
+            match types.iter().find(|d| d.name.value == *name) {
+                Some(definition) => current = &definition.type_expr,
+                None => return current,
+            }
+        }
+        current
+    }
+
+    /// True if the type (after resolving `Named` references) accepts `null`
+    pub fn is_optional(&self, types: &[TypeDefinitionNode]) -> bool {
+        matches!(self.resolve(types), TypeExpression::Optional(..))
+    }
+}
+
+/// One `name: value` constraint of a refined type, e.g. `max_len: 64`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefinementNode {
+    pub name: SpannedValue<String>,
+    pub value: LiteralValue,
+    pub span: Span,
 }
 
 /// ICL primitive types
@@ -101,22 +193,157 @@ pub enum LiteralValue {
     Float(f64, Span),
     Boolean(bool, Span),
     Array(Vec<LiteralValue>, Span),
+    /// `null` — only valid for `Optional<T>` values
+    Null(Span),
 }
 
-// ── Behavioral Semantics (§1.5) ───────────────────────────
+// ── Conditions ────────────────────────────────────────────
+
+/// A precondition, postcondition or invariant.
+///
+/// Keeps the source text as written and, when the text matches the
+/// condition grammar (see `parser::condition`), its parsed expression.
+/// Prose that the grammar does not cover stays opaque (`expr: None`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConditionNode {
+    pub text: String,
+    pub expr: Option<Expr>,
+    pub span: Span,
+}
+
+impl ConditionNode {
+    /// Returns true if the condition could not be parsed into an expression
+    pub fn is_opaque(&self) -> bool {
+        self.expr.is_none()
+    }
+
+    /// Position of the first character of the condition text, just after
+    /// the opening quote
+    pub fn text_start(&self) -> Span {
+        Span {
+            line: self.span.line,
+            column: self.span.column + 1,
+            offset: self.span.offset + 1,
+            end: self.span.offset + 1,
+        }
+    }
+
+    /// Why the condition text is outside the condition grammar — `None`
+    /// unless the condition is opaque
+    pub fn parse_error(&self) -> Option<String> {
+        if !self.is_opaque() {
+            return None;
+        }
+        match super::condition::parse_condition_at(&self.text, &self.text_start()) {
+            Err(crate::Error::ParseError(reason)) => Some(reason),
+            Err(other) => Some(other.to_string()),
+            Ok(_) => None,
+        }
+    }
+}
 
+/// Typed condition expression
 #[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Literal value: `0`, `1.5`, `"text"`, `true`
+    Literal(LiteralValue),
+    /// Field path: `count`, `profile.age`
+    Path(Vec<String>, Span),
+    /// Unary operation: `not x`
+    Unary(UnaryOp, Box<Expr>, Span),
+    /// Binary operation: `a and b`, `x >= 0`
+    Binary(BinaryOp, Box<Expr>, Box<Expr>, Span),
+    /// Emptiness test: `x is empty`
+    IsEmpty(Box<Expr>, Span),
+    /// Runtime type test: `x is boolean`
+    IsType(Box<Expr>, PrimitiveType, Span),
+    /// Value before the operation ran: `old(count)` — postconditions only
+    Old(Box<Expr>, Span),
+    /// Element of an array or map: `scores[0]`, `counters["a"]`
+    Index(Box<Expr>, Box<Expr>, Span),
+    /// Field of an indexed or computed object: `entries[0].label`
+    Field(Box<Expr>, String, Span),
+    /// Quantifier over array elements or map values: `all s in scores: s >= 0`
+    Quantified(Quantifier, String, Box<Expr>, Box<Expr>, Span),
+    /// Built-in function call: `len(items)`, `contains(tags, "x")`
+    Call(String, Vec<Expr>, Span),
+}
+
+/// Quantifiers over collections in conditions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantifier {
+    All,
+    Any,
+}
+
+/// Unary operators in conditions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Not,
+}
+
+/// Binary operators in conditions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// Membership: `status in ["open", "paused"]`
+    In,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinaryOp {
+    /// Binding strength — higher binds tighter
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Or => 1,
+            BinaryOp::And => 2,
+            BinaryOp::Eq
+            | BinaryOp::Ne
+            | BinaryOp::Lt
+            | BinaryOp::Le
+            | BinaryOp::Gt
+            | BinaryOp::Ge
+            | BinaryOp::In => 3,
+            BinaryOp::Add | BinaryOp::Sub => 4,
+            BinaryOp::Mul | BinaryOp::Div => 5,
+        }
+    }
+
+    /// Returns true for `==`, `!=`, `<`, `<=`, `>`, `>=`, `in`
+    pub fn is_comparison(&self) -> bool {
+        self.precedence() == 3
+    }
+
+    /// Returns true for `+`, `-`, `*`, `/`
+    pub fn is_arithmetic(&self) -> bool {
+        self.precedence() >= 4
+    }
+}
+
+// ── Behavioral Semantics (§1.5) ───────────────────────────
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct BehavioralSemanticsNode {
     pub operations: Vec<OperationNode>,
     pub span: Span,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct OperationNode {
     pub name: SpannedValue<String>,
-    pub precondition: SpannedValue<String>,
+    pub precondition: ConditionNode,
     pub parameters: Vec<StateFieldNode>,
-    pub postcondition: SpannedValue<String>,
+    pub postcondition: ConditionNode,
     pub side_effects: Vec<SpannedValue<String>>,
     pub idempotence: SpannedValue<String>,
     pub span: Span,
@@ -124,7 +351,7 @@ pub struct OperationNode {
 
 // ── Execution Constraints (§1.6) ──────────────────────────
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ExecutionConstraintsNode {
     pub trigger_types: Vec<SpannedValue<String>>,
     pub resource_limits: ResourceLimitsNode,
@@ -133,7 +360,7 @@ pub struct ExecutionConstraintsNode {
     pub span: Span,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ResourceLimitsNode {
     pub max_memory_bytes: SpannedValue<i64>,
     pub computation_timeout_ms: SpannedValue<i64>,
@@ -143,7 +370,7 @@ pub struct ResourceLimitsNode {
 
 // ── Human-Machine Contract (§1.7) ─────────────────────────
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct HumanMachineContractNode {
     pub system_commitments: Vec<SpannedValue<String>>,
     pub system_refusals: Vec<SpannedValue<String>>,
@@ -153,7 +380,7 @@ pub struct HumanMachineContractNode {
 
 // ── Extensions (§5) ───────────────────────────────────────
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ExtensionsNode {
     pub systems: Vec<SystemExtensionNode>,
     pub span: Span,
@@ -176,7 +403,7 @@ pub struct CustomFieldNode {
 // ── Spanned Value (generic wrapper) ───────────────────────
 
 /// A value annotated with its source span
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SpannedValue<T> {
     pub value: T,
     pub span: Span,
@@ -225,10 +452,22 @@ impl std::fmt::Display for TypeExpression {
                     if i > 0 {
                         write!(f, ", ")?;
                     }
-                    write!(f, "\"{}\"", v.value)?;
+                    write!(f, "{}", quote(&v.value))?;
                 }
                 write!(f, "]")
             }
+            TypeExpression::Optional(inner, _) => write!(f, "Optional<{}>", inner),
+            TypeExpression::Refined(base, refinements, _) => {
+                write!(f, "{}(", base)?;
+                for (i, r) in refinements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", r.name.value, r.value)?;
+                }
+                write!(f, ")")
+            }
+            TypeExpression::Named(name, _) => write!(f, "{}", name),
         }
     }
 }
@@ -236,10 +475,15 @@ impl std::fmt::Display for TypeExpression {
 impl std::fmt::Display for LiteralValue {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            LiteralValue::String(s, _) => write!(f, "\"{}\"", s),
+            LiteralValue::String(s, _) => write!(f, "{}", quote(s)),
             LiteralValue::Integer(n, _) => write!(f, "{}", n),
+            // Keep a decimal point so `1.0` does not read back as an Integer
+            LiteralValue::Float(n, _) if n.fract() == 0.0 => {
+                write!(f, "{:.1}", n)
+            }
             LiteralValue::Float(n, _) => write!(f, "{}", n),
             LiteralValue::Boolean(b, _) => write!(f, "{}", b),
+            LiteralValue::Null(_) => write!(f, "null"),
             LiteralValue::Array(items, _) => {
                 write!(f, "[")?;
                 for (i, item) in items.iter().enumerate() {
@@ -254,6 +498,153 @@ impl std::fmt::Display for LiteralValue {
     }
 }
 
+/// Render `s` as an ICL string literal, escaping what the tokenizer unescapes
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl std::fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UnaryOp::Not => write!(f, "not"),
+        }
+    }
+}
+
+impl std::fmt::Display for Quantifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Quantifier::All => write!(f, "all"),
+            Quantifier::Any => write!(f, "any"),
+        }
+    }
+}
+
+impl std::fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BinaryOp::Or => write!(f, "or"),
+            BinaryOp::And => write!(f, "and"),
+            BinaryOp::Eq => write!(f, "=="),
+            BinaryOp::Ne => write!(f, "!="),
+            BinaryOp::Lt => write!(f, "<"),
+            BinaryOp::Le => write!(f, "<="),
+            BinaryOp::Gt => write!(f, ">"),
+            BinaryOp::Ge => write!(f, ">="),
+            BinaryOp::In => write!(f, "in"),
+            BinaryOp::Add => write!(f, "+"),
+            BinaryOp::Sub => write!(f, "-"),
+            BinaryOp::Mul => write!(f, "*"),
+            BinaryOp::Div => write!(f, "/"),
+        }
+    }
+}
+
+impl Expr {
+    /// Precedence of the outermost operator (atoms bind tightest)
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary(op, _, _, _) => op.precedence(),
+            Expr::IsEmpty(_, _) | Expr::IsType(_, _, _) | Expr::Unary(_, _, _) => 3,
+            Expr::Quantified(..) => 0,
+            Expr::Literal(_)
+            | Expr::Path(_, _)
+            | Expr::Old(_, _)
+            | Expr::Index(..)
+            | Expr::Field(..)
+            | Expr::Call(..) => 6,
+        }
+    }
+
+    /// Write a sub-expression, parenthesized if it binds looser than `min`
+    fn fmt_operand(&self, f: &mut std::fmt::Formatter, min: u8) -> std::fmt::Result {
+        if self.precedence() < min {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+
+    /// Write the base of an index or field access. Literals only take
+    /// postfixes inside parentheses: `("abc")[0]`.
+    fn fmt_base(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Expr::Literal(_) => write!(f, "({})", self),
+            _ => self.fmt_operand(f, 6),
+        }
+    }
+}
+
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Expr::Literal(lit) => write!(f, "{}", lit),
+            Expr::Path(segments, _) => write!(f, "{}", segments.join(".")),
+            Expr::Old(operand, _) => write!(f, "old({})", operand),
+            Expr::Index(base, index, _) => {
+                base.fmt_base(f)?;
+                write!(f, "[{}]", index)
+            }
+            Expr::Field(base, name, _) => {
+                base.fmt_base(f)?;
+                write!(f, ".{}", name)
+            }
+            Expr::Call(name, args, _) => {
+                write!(f, "{}(", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Quantified(quantifier, var, collection, body, _) => {
+                write!(f, "{} {} in ", quantifier, var)?;
+                collection.fmt_operand(f, 4)?;
+                write!(f, ": {}", body)
+            }
+            Expr::Unary(op, operand, _) => {
+                write!(f, "{} ", op)?;
+                operand.fmt_operand(f, 3)
+            }
+            Expr::Binary(op, left, right, _) => {
+                let prec = op.precedence();
+                if op.is_comparison() {
+                    // Comparisons do not chain: both sides must bind tighter
+                    left.fmt_operand(f, prec + 1)?;
+                    write!(f, " {} ", op)?;
+                    right.fmt_operand(f, prec + 1)
+                } else {
+                    left.fmt_operand(f, prec)?;
+                    write!(f, " {} ", op)?;
+                    right.fmt_operand(f, prec + 1)
+                }
+            }
+            Expr::IsEmpty(operand, _) => {
+                operand.fmt_operand(f, 4)?;
+                write!(f, " is empty")
+            }
+            Expr::IsType(operand, ty, _) => {
+                operand.fmt_operand(f, 4)?;
+                write!(f, " is {}", ty.to_string().to_lowercase())
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for ContractNode {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
@@ -274,6 +665,41 @@ impl TypeExpression {
             TypeExpression::Map(_, _, s) => s,
             TypeExpression::Object(_, s) => s,
             TypeExpression::Enum(_, s) => s,
+            TypeExpression::Optional(_, s) => s,
+            TypeExpression::Refined(_, _, s) => s,
+            TypeExpression::Named(_, s) => s,
+        }
+    }
+}
+
+impl Expr {
+    pub fn span(&self) -> &Span {
+        match self {
+            Expr::Literal(lit) => lit.span(),
+            Expr::Path(_, s) => s,
+            Expr::Unary(_, _, s) => s,
+            Expr::Binary(_, _, _, s) => s,
+            Expr::IsEmpty(_, s) => s,
+            Expr::IsType(_, _, s) => s,
+            Expr::Old(_, s) => s,
+            Expr::Index(_, _, s) => s,
+            Expr::Field(_, _, s) => s,
+            Expr::Quantified(_, _, _, _, s) => s,
+            Expr::Call(_, _, s) => s,
+        }
+    }
+
+    /// Whether the expression is only bare names joined by `and`, `or` and
+    /// `not`, none of which `in_scope` knows — prose written as flags
+    /// (`item_added AND count_incremented`) rather than a condition
+    pub fn is_flag_prose(&self, in_scope: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            Expr::Path(segments, _) => segments.len() == 1 && !in_scope(&segments[0]),
+            Expr::Unary(UnaryOp::Not, operand, _) => operand.is_flag_prose(in_scope),
+            Expr::Binary(BinaryOp::And | BinaryOp::Or, left, right, _) => {
+                left.is_flag_prose(in_scope) && right.is_flag_prose(in_scope)
+            }
+            _ => false,
         }
     }
 }
@@ -286,6 +712,7 @@ impl LiteralValue {
             LiteralValue::Float(_, s) => s,
             LiteralValue::Boolean(_, s) => s,
             LiteralValue::Array(_, s) => s,
+            LiteralValue::Null(s) => s,
         }
     }
 }
@@ -310,6 +737,7 @@ mod tests {
             line: 1,
             column: 1,
             offset: 0,
+            end: 0,
         };
 
         let int_ty = TypeExpression::Primitive(PrimitiveType::Integer, span.clone());
@@ -344,6 +772,7 @@ mod tests {
             line: 1,
             column: 1,
             offset: 0,
+            end: 0,
         };
         let enum_ty = TypeExpression::Enum(
             vec![
@@ -361,6 +790,7 @@ mod tests {
             line: 1,
             column: 1,
             offset: 0,
+            end: 0,
         };
         assert_eq!(
             LiteralValue::String("hello".to_string(), span.clone()).to_string(),
@@ -370,6 +800,10 @@ mod tests {
         #[allow(clippy::approx_constant)]
         let float_val = LiteralValue::Float(3.14, span.clone());
         assert_eq!(float_val.to_string(), "3.14");
+        assert_eq!(
+            LiteralValue::Float(1e15, span.clone()).to_string(),
+            "1000000000000000.0"
+        );
         assert_eq!(
             LiteralValue::Boolean(true, span.clone()).to_string(),
             "true"
@@ -382,6 +816,7 @@ mod tests {
             line: 5,
             column: 10,
             offset: 50,
+            end: 56,
         };
         let sv = SpannedValue::new("test".to_string(), span.clone());
         assert_eq!(sv.value, "test");
@@ -394,6 +829,7 @@ mod tests {
             line: 3,
             column: 7,
             offset: 30,
+            end: 37,
         };
         let ty = TypeExpression::Primitive(PrimitiveType::Boolean, span.clone());
         assert_eq!(ty.span(), &span);
@@ -405,6 +841,7 @@ mod tests {
             line: 1,
             column: 1,
             offset: 0,
+            end: 0,
         };
         let obj = TypeExpression::Object(
             vec![
@@ -432,6 +869,7 @@ mod tests {
             line: 1,
             column: 1,
             offset: 0,
+            end: 0,
         };
         // Array<Map<String, Integer>>
         let inner = TypeExpression::Map(
@@ -448,4 +886,31 @@ mod tests {
         let outer = TypeExpression::Array(Box::new(inner), span.clone());
         assert_eq!(outer.to_string(), "Array<Map<String, Integer>>");
     }
+
+    #[test]
+    fn test_named_type_resolve() {
+        let span = Span::default();
+        let named = |name: &str| TypeExpression::Named(name.to_string(), span.clone());
+        let define = |name: &str, type_expr: TypeExpression| TypeDefinitionNode {
+            name: SpannedValue::new(name.to_string(), span.clone()),
+            type_expr,
+            span: span.clone(),
+        };
+        let types = vec![
+            define(
+                "Amount",
+                TypeExpression::Primitive(PrimitiveType::Integer, span.clone()),
+            ),
+            define("Balance", named("Amount")),
+            define("Loop", named("Loop")),
+        ];
+
+        assert_eq!(named("Balance").to_string(), "Balance");
+        assert_eq!(
+            named("Balance").resolve(&types),
+            &TypeExpression::Primitive(PrimitiveType::Integer, span.clone())
+        );
+        assert_eq!(named("Missing").resolve(&types), &named("Missing"));
+        assert_eq!(named("Loop").resolve(&types), &named("Loop"));
+    }
 }
@@ -0,0 +1,1379 @@
+//! Condition language — tokenizer and parser for preconditions,
+//! postconditions and invariants
+//!
+//! Conditions are written as string literals inside a contract. This module
+//! turns their text into a typed [`Expr`] tree so the verifier and executor
+//! can work on structure instead of matching text patterns.
+//!
+//! # Grammar
+//!
+//! ```text
+//! condition   := or_expr EOF
+//! or_expr     := and_expr (("or" | "||") and_expr)*
+//! and_expr    := not_expr (("and" | "&&") not_expr)*
+//! not_expr    := ("not" | "!") not_expr | quantified | shorthand | comparison
+//! quantified  := ("all" | "any") identifier "in" sum ":" or_expr
+//! shorthand   := ("all" | "any") operand cmp_op sum
+//! comparison  := sum [cmp_op sum | ["not"] "in" sum | "is" ["not"] is_target]
+//! cmp_op      := "==" | "=" | "equals" | "!=" | "<" | "<=" | ">" | ">="
+//! is_target   := "empty" | "boolean" | "integer" | "float" | "string" | literal
+//! sum         := product (("+" | "-") product)*
+//! product     := operand (("*" | "/") operand)*
+//! operand     := literal | (old | call | path | "(" or_expr ")") postfix*
+//! old         := "old" "(" or_expr ")"
+//! call        := identifier "(" [or_expr ("," or_expr)*] ")"
+//! path        := identifier ("." identifier)*
+//! postfix     := "[" or_expr "]" | "." identifier
+//! literal     := ["-"] integer | ["-"] float | string | "true" | "false" | "null"
+//!              | "[" [literal ("," literal)*] "]"
+//! ```
+//!
+//! Keywords are case-insensitive (`AND`, `IS NOT`). Strings may use single
+//! or double quotes. `old(expr)` evaluates `expr` against the state before
+//! the operation ran, e.g. `count == old(count) + 1`. Other calls such as
+//! `len(items)` name functions from [`crate::builtins`]. A quantifier body
+//! extends to the end of the condition unless parenthesized, and binds its
+//! variable to each array element or map value in turn. The shorthand
+//! `all scores >= 0` parses as `all item in scores: item >= 0`, the variable
+//! renamed when the right side already uses `item`. Text that does not match the grammar is not an error
+//! for the contract: the caller keeps it as an opaque (prose) condition.
+//!
+//! # Guarantees
+//!
+//! - **Deterministic**: same text always produces the same expression
+//! - **Pure**: no side effects, no I/O, no randomness
+
+use super::ast::{BinaryOp, Expr, LiteralValue, PrimitiveType, Quantifier, UnaryOp};
+use super::tokenizer::Span;
+use crate::{Error, Result};
+
+// ── Public API ─────────────────────────────────────────────
+
+/// Parse condition text into an expression.
+///
+/// Spans are relative to the condition text (line 1, column 1).
+pub fn parse_condition(text: &str) -> Result<Expr> {
+    let start = Span {
+        line: 1,
+        column: 1,
+        offset: 0,
+        end: 0,
+    };
+    parse_condition_at(text, &start)
+}
+
+/// Parse condition text whose first character sits at `start` in the
+/// contract source, so expression spans point into the contract.
+pub fn parse_condition_at(text: &str, start: &Span) -> Result<Expr> {
+    parse_tokens(Lexer::new(text, start).tokenize()?)
+}
+
+/// Parse the text of a contract string literal written as `raw` between
+/// its quotes, the first character at `start`. An escape sequence is two
+/// characters of `raw` for one of `text`; spans still point at the source.
+pub fn parse_condition_in_source(text: &str, raw: &str, start: &Span) -> Result<Expr> {
+    let mut lexer = Lexer::new(text, start);
+    lexer.positions = source_positions(text, raw, start);
+    parse_tokens(lexer.tokenize()?)
+}
+
+/// Source span of each character of `text` and of its end, or `None` when
+/// `raw` is not an escaped form of `text`
+fn source_positions(text: &str, raw: &str, start: &Span) -> Option<Vec<Span>> {
+    let mut positions = Vec::with_capacity(text.len() + 1);
+    let mut at = start.clone();
+    let mut raw_chars = raw.chars();
+    for _ in text.chars() {
+        positions.push(at.clone());
+        match raw_chars.next()? {
+            '\\' => {
+                let escaped = raw_chars.next()?;
+                at.offset += 1 + escaped.len_utf8();
+                at.column += 2;
+            }
+            '\n' => {
+                at.offset += 1;
+                at.line += 1;
+                at.column = 1;
+            }
+            c => {
+                at.offset += c.len_utf8();
+                at.column += 1;
+            }
+        }
+        at.end = at.offset;
+    }
+    if raw_chars.next().is_some() {
+        return None;
+    }
+    positions.push(at);
+    Some(positions)
+}
+
+fn parse_tokens(tokens: Vec<CondSpannedToken>) -> Result<Expr> {
+    let mut parser = ConditionParser {
+        tokens,
+        position: 0,
+    };
+    let expr = parser.parse_or()?;
+    let trailing = parser.peek();
+    if trailing.token != CondToken::Eof {
+        return Err(Error::ParseError(format!(
+            "Unexpected {} in condition at {}",
+            trailing.token, trailing.span
+        )));
+    }
+    Ok(expr)
+}
+
+// ── Tokens ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum CondToken {
+    Identifier(String),
+    Integer(i64),
+    Float(f64),
+    Str(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Colon,
+    Comma,
+    Dot,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Bang,
+    EqEq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    AndAnd,
+    OrOr,
+    Eof,
+}
+
+impl std::fmt::Display for CondToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CondToken::Identifier(s) => write!(f, "'{}'", s),
+            CondToken::Integer(n) => write!(f, "'{}'", n),
+            CondToken::Float(n) => write!(f, "'{}'", n),
+            CondToken::Str(s) => write!(f, "string \"{}\"", s),
+            CondToken::LParen => write!(f, "'('"),
+            CondToken::RParen => write!(f, "')'"),
+            CondToken::LBracket => write!(f, "'['"),
+            CondToken::RBracket => write!(f, "']'"),
+            CondToken::Colon => write!(f, "':'"),
+            CondToken::Comma => write!(f, "','"),
+            CondToken::Dot => write!(f, "'.'"),
+            CondToken::Plus => write!(f, "'+'"),
+            CondToken::Minus => write!(f, "'-'"),
+            CondToken::Star => write!(f, "'*'"),
+            CondToken::Slash => write!(f, "'/'"),
+            CondToken::Bang => write!(f, "'!'"),
+            CondToken::EqEq => write!(f, "'=='"),
+            CondToken::NotEq => write!(f, "'!='"),
+            CondToken::Lt => write!(f, "'<'"),
+            CondToken::Le => write!(f, "'<='"),
+            CondToken::Gt => write!(f, "'>'"),
+            CondToken::Ge => write!(f, "'>='"),
+            CondToken::AndAnd => write!(f, "'&&'"),
+            CondToken::OrOr => write!(f, "'||'"),
+            CondToken::Eof => write!(f, "end of condition"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CondSpannedToken {
+    token: CondToken,
+    span: Span,
+}
+
+// ── Lexer ──────────────────────────────────────────────────
+
+struct Lexer {
+    input: Vec<char>,
+    position: usize,
+    start: Span,
+    /// Source span of each input character, when escapes make the source
+    /// differ from the input
+    positions: Option<Vec<Span>>,
+}
+
+impl Lexer {
+    fn new(text: &str, start: &Span) -> Self {
+        Lexer {
+            input: text.chars().collect(),
+            position: 0,
+            start: start.clone(),
+            positions: None,
+        }
+    }
+
+    /// Span of the current character in contract coordinates
+    fn current_span(&self) -> Span {
+        if let Some(positions) = &self.positions {
+            return positions[self.position].clone();
+        }
+        let offset = self.start.offset + self.byte_offset();
+        Span {
+            line: self.start.line,
+            column: self.start.column + self.position,
+            offset,
+            end: offset,
+        }
+    }
+
+    fn byte_offset(&self) -> usize {
+        self.input[..self.position]
+            .iter()
+            .map(|c| c.len_utf8())
+            .sum()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.position).copied()
+    }
+
+    fn peek_ahead(&self, offset: usize) -> Option<char> {
+        self.input.get(self.position + offset).copied()
+    }
+
+    fn tokenize(&mut self) -> Result<Vec<CondSpannedToken>> {
+        let mut tokens = Vec::new();
+        loop {
+            while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+                self.position += 1;
+            }
+            let span = self.current_span();
+            let Some(c) = self.peek() else {
+                tokens.push(CondSpannedToken {
+                    token: CondToken::Eof,
+                    span,
+                });
+                return Ok(tokens);
+            };
+
+            let token = match c {
+                '(' => self.symbol(CondToken::LParen, 1),
+                ')' => self.symbol(CondToken::RParen, 1),
+                '[' => self.symbol(CondToken::LBracket, 1),
+                ']' => self.symbol(CondToken::RBracket, 1),
+                ':' => self.symbol(CondToken::Colon, 1),
+                ',' => self.symbol(CondToken::Comma, 1),
+                '.' => self.symbol(CondToken::Dot, 1),
+                '+' => self.symbol(CondToken::Plus, 1),
+                '-' => self.symbol(CondToken::Minus, 1),
+                '*' => self.symbol(CondToken::Star, 1),
+                '/' => self.symbol(CondToken::Slash, 1),
+                '=' if self.peek_ahead(1) == Some('=') => self.symbol(CondToken::EqEq, 2),
+                '=' => self.symbol(CondToken::EqEq, 1),
+                '!' if self.peek_ahead(1) == Some('=') => self.symbol(CondToken::NotEq, 2),
+                '!' => self.symbol(CondToken::Bang, 1),
+                '<' if self.peek_ahead(1) == Some('=') => self.symbol(CondToken::Le, 2),
+                '<' => self.symbol(CondToken::Lt, 1),
+                '>' if self.peek_ahead(1) == Some('=') => self.symbol(CondToken::Ge, 2),
+                '>' => self.symbol(CondToken::Gt, 1),
+                '&' if self.peek_ahead(1) == Some('&') => self.symbol(CondToken::AndAnd, 2),
+                '|' if self.peek_ahead(1) == Some('|') => self.symbol(CondToken::OrOr, 2),
+                '"' | '\'' => self.read_string(c, &span)?,
+                c if c.is_ascii_digit() => self.read_number(&span)?,
+                c if c.is_ascii_alphabetic() || c == '_' => self.read_identifier(),
+                other => {
+                    return Err(Error::ParseError(format!(
+                        "Unexpected character '{}' in condition at {}",
+                        other, span
+                    )));
+                }
+            };
+            let span = span.to(&self.current_span());
+            tokens.push(CondSpannedToken { token, span });
+        }
+    }
+
+    fn symbol(&mut self, token: CondToken, width: usize) -> CondToken {
+        self.position += width;
+        token
+    }
+
+    fn read_string(&mut self, quote: char, span: &Span) -> Result<CondToken> {
+        self.position += 1; // opening quote
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(Error::ParseError(format!(
+                        "Unterminated string in condition at {}",
+                        span
+                    )));
+                }
+                Some(c) if c == quote => {
+                    self.position += 1;
+                    return Ok(CondToken::Str(value));
+                }
+                // The escapes `ast::quote` writes, plus `\'` for single quotes
+                Some('\\') => {
+                    let escaped = match self.peek_ahead(1) {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some(c @ ('\\' | '"' | '\'')) => c,
+                        Some(c) => {
+                            return Err(Error::ParseError(format!(
+                                "Invalid escape sequence '\\{}' in condition at {}",
+                                c, span
+                            )));
+                        }
+                        None => {
+                            return Err(Error::ParseError(format!(
+                                "Unterminated string in condition at {}",
+                                span
+                            )));
+                        }
+                    };
+                    value.push(escaped);
+                    self.position += 2;
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.position += 1;
+                }
+            }
+        }
+    }
+
+    fn read_number(&mut self, span: &Span) -> Result<CondToken> {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let is_float =
+            self.peek() == Some('.') && matches!(self.peek_ahead(1), Some(c) if c.is_ascii_digit());
+        if is_float {
+            self.position += 1;
+            while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+                self.position += 1;
+            }
+        }
+        let text: String = self.input[start..self.position].iter().collect();
+        if is_float {
+            text.parse::<f64>().map(CondToken::Float).map_err(|_| {
+                Error::ParseError(format!(
+                    "Invalid number '{}' in condition at {}",
+                    text, span
+                ))
+            })
+        } else {
+            text.parse::<i64>().map(CondToken::Integer).map_err(|_| {
+                Error::ParseError(format!(
+                    "Invalid number '{}' in condition at {}",
+                    text, span
+                ))
+            })
+        }
+    }
+
+    fn read_identifier(&mut self) -> CondToken {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_') {
+            self.position += 1;
+        }
+        CondToken::Identifier(self.input[start..self.position].iter().collect())
+    }
+}
+
+// ── Parser ─────────────────────────────────────────────────
+
+struct ConditionParser {
+    tokens: Vec<CondSpannedToken>,
+    position: usize,
+}
+
+impl ConditionParser {
+    // ── Token helpers ──────────────────────────────────
+
+    fn peek(&self) -> &CondSpannedToken {
+        &self.tokens[self.position]
+    }
+
+    fn advance(&mut self) -> CondSpannedToken {
+        let token = self.tokens[self.position].clone();
+        if self.position < self.tokens.len() - 1 {
+            self.position += 1;
+        }
+        token
+    }
+
+    /// Check whether the current token is the given keyword (case-insensitive)
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.peek().token, CondToken::Identifier(s) if s.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Check whether the token `offset` places ahead is the given keyword
+    fn at_keyword_ahead(&self, offset: usize, keyword: &str) -> bool {
+        matches!(
+            self.tokens.get(self.position + offset).map(|t| &t.token),
+            Some(CondToken::Identifier(s)) if s.eq_ignore_ascii_case(keyword)
+        )
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T> {
+        let current = self.peek();
+        Err(Error::ParseError(format!(
+            "Expected {}, found {} in condition at {}",
+            expected, current.token, current.span
+        )))
+    }
+
+    // ── Boolean structure ──────────────────────────────
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.at_keyword("or") || self.peek().token == CondToken::OrOr {
+            let span = self.advance().span;
+            let right = self.parse_and()?;
+            left = Expr::Binary(BinaryOp::Or, Box::new(left), Box::new(right), span);
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_not()?;
+        while self.at_keyword("and") || self.peek().token == CondToken::AndAnd {
+            let span = self.advance().span;
+            let right = self.parse_not()?;
+            left = Expr::Binary(BinaryOp::And, Box::new(left), Box::new(right), span);
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr> {
+        if self.at_keyword("not") || self.peek().token == CondToken::Bang {
+            let span = self.advance().span;
+            let operand = self.parse_not()?;
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(operand), span));
+        }
+        if let Some(quantifier) = self.peek_quantifier() {
+            return self.parse_quantified(quantifier);
+        }
+        if let Some(quantifier) = self.peek_shorthand() {
+            return self.parse_shorthand(quantifier);
+        }
+        self.parse_comparison()
+    }
+
+    /// `all`/`any` only start a quantifier when followed by `<name> in`,
+    /// so they stay usable as field names
+    fn peek_quantifier(&self) -> Option<Quantifier> {
+        let quantifier = if self.at_keyword("all") {
+            Quantifier::All
+        } else if self.at_keyword("any") {
+            Quantifier::Any
+        } else {
+            return None;
+        };
+        let var = self.tokens.get(self.position + 1)?;
+        let keyword = self.tokens.get(self.position + 2)?;
+        match (&var.token, &keyword.token) {
+            (CondToken::Identifier(name), CondToken::Identifier(word))
+                if !is_reserved(name) && word.eq_ignore_ascii_case("in") =>
+            {
+                Some(quantifier)
+            }
+            _ => None,
+        }
+    }
+
+    /// `all`/`any` followed by a name that is not a quantifier variable
+    /// start the shorthand `all scores >= 0`
+    fn peek_shorthand(&self) -> Option<Quantifier> {
+        let quantifier = if self.at_keyword("all") {
+            Quantifier::All
+        } else if self.at_keyword("any") {
+            Quantifier::Any
+        } else {
+            return None;
+        };
+        match &self.tokens.get(self.position + 1)?.token {
+            CondToken::Identifier(name)
+                if !is_reserved(name)
+                    && !name.eq_ignore_ascii_case("equals")
+                    && !name.eq_ignore_ascii_case("in") =>
+            {
+                Some(quantifier)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse: `("all" | "any") operand cmp_op sum`, desugared to a
+    /// quantifier whose body compares each element
+    fn parse_shorthand(&mut self, quantifier: Quantifier) -> Result<Expr> {
+        let start = self.advance().span; // consume `all` / `any`
+        let collection = self.parse_operand()?;
+        let op = match self.peek_comparison_op() {
+            Some(op) if op != BinaryOp::In => op,
+            _ => return self.unexpected("comparison after quantified collection"),
+        };
+        let op_span = self.advance().span;
+        let right = self.parse_sum()?;
+
+        let mut var = "item".to_string();
+        let mut n = 1;
+        while mentions(&right, &var) {
+            var = format!("item{}", n);
+            n += 1;
+        }
+        let element = Expr::Path(vec![var.clone()], collection.span().clone());
+        let span = start.to(right.span());
+        let body = Expr::Binary(op, Box::new(element), Box::new(right), op_span);
+        Ok(Expr::Quantified(
+            quantifier,
+            var,
+            Box::new(collection),
+            Box::new(body),
+            span,
+        ))
+    }
+
+    /// Parse: `("all" | "any") identifier "in" sum ":" or_expr`
+    fn parse_quantified(&mut self, quantifier: Quantifier) -> Result<Expr> {
+        let start = self.advance().span; // consume `all` / `any`
+        let CondToken::Identifier(var) = self.advance().token else {
+            unreachable!("peek_quantifier checked the variable");
+        };
+        self.advance(); // consume `in`
+        let collection = self.parse_sum()?;
+        if self.peek().token != CondToken::Colon {
+            return self.unexpected("':' after quantifier collection");
+        }
+        self.advance();
+        let body = self.parse_or()?;
+        let span = start.to(body.span());
+        Ok(Expr::Quantified(
+            quantifier,
+            var,
+            Box::new(collection),
+            Box::new(body),
+            span,
+        ))
+    }
+
+    // ── Comparisons ────────────────────────────────────
+
+    fn parse_comparison(&mut self) -> Result<Expr> {
+        let left = self.parse_sum()?;
+
+        if let Some(op) = self.peek_comparison_op() {
+            let span = self.advance().span;
+            let right = self.parse_sum()?;
+            return Ok(Expr::Binary(op, Box::new(left), Box::new(right), span));
+        }
+
+        if self.at_keyword("not") && self.at_keyword_ahead(1, "in") {
+            let span = self.advance().span;
+            self.advance(); // consume `in`
+            let right = self.parse_sum()?;
+            let test = Expr::Binary(BinaryOp::In, Box::new(left), Box::new(right), span.clone());
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(test), span));
+        }
+
+        if self.at_keyword("is") {
+            return self.parse_is(left);
+        }
+
+        Ok(left)
+    }
+
+    fn peek_comparison_op(&self) -> Option<BinaryOp> {
+        match self.peek().token {
+            CondToken::EqEq => Some(BinaryOp::Eq),
+            CondToken::NotEq => Some(BinaryOp::Ne),
+            CondToken::Lt => Some(BinaryOp::Lt),
+            CondToken::Le => Some(BinaryOp::Le),
+            CondToken::Gt => Some(BinaryOp::Gt),
+            CondToken::Ge => Some(BinaryOp::Ge),
+            _ if self.at_keyword("equals") => Some(BinaryOp::Eq),
+            _ if self.at_keyword("in") => Some(BinaryOp::In),
+            _ => None,
+        }
+    }
+
+    /// Parse the tail of `x is [not] (empty | <type> | literal)`
+    fn parse_is(&mut self, left: Expr) -> Result<Expr> {
+        let span = self.advance().span; // consume `is`
+        let negated = if self.at_keyword("not") {
+            self.advance();
+            true
+        } else {
+            false
+        };
+
+        let test = if self.at_keyword("empty") {
+            self.advance();
+            Expr::IsEmpty(Box::new(left), span.clone())
+        } else if let Some(ty) = self.peek_type_name() {
+            self.advance();
+            Expr::IsType(Box::new(left), ty, span.clone())
+        } else {
+            // Only a literal: `balance is finite` is prose, not a comparison
+            // with a field named `finite`
+            let right = self.parse_operand()?;
+            if !matches!(right, Expr::Literal(_)) {
+                return Err(Error::ParseError(format!(
+                    "Expected 'empty', a type name or a literal after 'is', found '{}' in condition at {}",
+                    right,
+                    right.span()
+                )));
+            }
+            let op = if negated { BinaryOp::Ne } else { BinaryOp::Eq };
+            return Ok(Expr::Binary(op, Box::new(left), Box::new(right), span));
+        };
+
+        if negated {
+            Ok(Expr::Unary(UnaryOp::Not, Box::new(test), span))
+        } else {
+            Ok(test)
+        }
+    }
+
+    /// Type names accepted after `is` for runtime type tests
+    fn peek_type_name(&self) -> Option<PrimitiveType> {
+        match &self.peek().token {
+            CondToken::Identifier(s) => match s.to_ascii_lowercase().as_str() {
+                "boolean" => Some(PrimitiveType::Boolean),
+                "integer" => Some(PrimitiveType::Integer),
+                "float" => Some(PrimitiveType::Float),
+                "string" => Some(PrimitiveType::String),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // ── Arithmetic ─────────────────────────────────────
+
+    fn parse_sum(&mut self) -> Result<Expr> {
+        let mut left = self.parse_product()?;
+        loop {
+            let op = match self.peek().token {
+                CondToken::Plus => BinaryOp::Add,
+                CondToken::Minus => BinaryOp::Sub,
+                _ => return Ok(left),
+            };
+            let span = self.advance().span;
+            let right = self.parse_product()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right), span);
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Expr> {
+        let mut left = self.parse_operand()?;
+        loop {
+            let op = match self.peek().token {
+                CondToken::Star => BinaryOp::Mul,
+                CondToken::Slash => BinaryOp::Div,
+                _ => return Ok(left),
+            };
+            let span = self.advance().span;
+            let right = self.parse_operand()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right), span);
+        }
+    }
+
+    // ── Operands ───────────────────────────────────────
+
+    fn parse_operand(&mut self) -> Result<Expr> {
+        let current = self.peek().clone();
+        match current.token {
+            CondToken::LParen => {
+                self.advance();
+                let inner = self.parse_or()?;
+                if self.peek().token != CondToken::RParen {
+                    return self.unexpected("')'");
+                }
+                self.advance();
+                self.parse_postfix(inner)
+            }
+            CondToken::LBracket => self.parse_list(),
+            CondToken::Integer(n) => {
+                self.advance();
+                Ok(Expr::Literal(LiteralValue::Integer(n, current.span)))
+            }
+            CondToken::Float(n) => {
+                self.advance();
+                Ok(Expr::Literal(LiteralValue::Float(n, current.span)))
+            }
+            CondToken::Str(s) => {
+                self.advance();
+                Ok(Expr::Literal(LiteralValue::String(s, current.span)))
+            }
+            CondToken::Minus => {
+                self.advance();
+                match self.advance().token {
+                    CondToken::Integer(n) => {
+                        Ok(Expr::Literal(LiteralValue::Integer(-n, current.span)))
+                    }
+                    CondToken::Float(n) => Ok(Expr::Literal(LiteralValue::Float(-n, current.span))),
+                    other => Err(Error::ParseError(format!(
+                        "Expected number after '-', found {} in condition at {}",
+                        other, current.span
+                    ))),
+                }
+            }
+            CondToken::Identifier(ref name) if is_reserved(name) => {
+                if name.eq_ignore_ascii_case("true") || name.eq_ignore_ascii_case("false") {
+                    self.advance();
+                    let value = name.eq_ignore_ascii_case("true");
+                    Ok(Expr::Literal(LiteralValue::Boolean(value, current.span)))
+                } else if name.eq_ignore_ascii_case("null") {
+                    self.advance();
+                    Ok(Expr::Literal(LiteralValue::Null(current.span)))
+                } else {
+                    self.unexpected("operand")
+                }
+            }
+            CondToken::Identifier(ref name)
+                if name.eq_ignore_ascii_case("old")
+                    && self.tokens[self.position + 1].token == CondToken::LParen =>
+            {
+                let old = self.parse_old()?;
+                self.parse_postfix(old)
+            }
+            CondToken::Identifier(_)
+                if self.tokens[self.position + 1].token == CondToken::LParen =>
+            {
+                let call = self.parse_call()?;
+                self.parse_postfix(call)
+            }
+            CondToken::Identifier(_) => {
+                let path = self.parse_path()?;
+                self.parse_postfix(path)
+            }
+            _ => self.unexpected("operand"),
+        }
+    }
+
+    /// Parse: `"[" [literal ("," literal)*] "]"`
+    fn parse_list(&mut self) -> Result<Expr> {
+        let start = self.advance().span; // consume `[`
+        let mut items = Vec::new();
+        while self.peek().token != CondToken::RBracket {
+            if !items.is_empty() {
+                if self.peek().token != CondToken::Comma {
+                    return self.unexpected("',' or ']' in list");
+                }
+                self.advance();
+            }
+            match self.parse_operand()? {
+                Expr::Literal(lit) => items.push(lit),
+                other => {
+                    return Err(Error::ParseError(format!(
+                        "Expected literal in list, found '{}' in condition at {}",
+                        other,
+                        other.span()
+                    )));
+                }
+            }
+        }
+        let span = start.to(&self.advance().span);
+        Ok(Expr::Literal(LiteralValue::Array(items, span)))
+    }
+
+    /// Parse: `old "(" or_expr ")"` — a field named `old` is still a path
+    fn parse_old(&mut self) -> Result<Expr> {
+        let start = self.advance().span; // consume `old`
+        self.advance(); // consume `(`
+        let inner = self.parse_or()?;
+        if self.peek().token != CondToken::RParen {
+            return self.unexpected("')' after old(...)");
+        }
+        let end = self.advance().span;
+        Ok(Expr::Old(Box::new(inner), start.to(&end)))
+    }
+
+    /// Parse: `identifier "(" [or_expr ("," or_expr)*] ")"`
+    ///
+    /// Any name is accepted here; the verifier checks it against the
+    /// built-in library.
+    fn parse_call(&mut self) -> Result<Expr> {
+        let first = self.advance();
+        let name = match first.token {
+            CondToken::Identifier(name) => name,
+            _ => unreachable!("parse_call starts at an identifier"),
+        };
+        self.advance(); // consume `(`
+        let mut args = Vec::new();
+        while self.peek().token != CondToken::RParen {
+            if !args.is_empty() {
+                if self.peek().token != CondToken::Comma {
+                    return self.unexpected("',' or ')' in call");
+                }
+                self.advance();
+            }
+            args.push(self.parse_or()?);
+        }
+        let span = first.span.to(&self.advance().span);
+        Ok(Expr::Call(name, args, span))
+    }
+
+    /// Parse: `identifier ("." identifier)*`
+    fn parse_path(&mut self) -> Result<Expr> {
+        let first = self.advance();
+        let mut span = first.span.clone();
+        let mut segments = Vec::new();
+        if let CondToken::Identifier(name) = first.token {
+            segments.push(name);
+        }
+        while self.peek().token == CondToken::Dot {
+            self.advance();
+            match self.peek().token.clone() {
+                CondToken::Identifier(name) if !is_reserved(&name) => {
+                    span = span.to(&self.advance().span);
+                    segments.push(name);
+                }
+                _ => return self.unexpected("field name after '.'"),
+            }
+        }
+        Ok(Expr::Path(segments, span))
+    }
+
+    /// Parse index and field accesses after an operand:
+    /// `entries[0].label`, `counters["a"]`
+    fn parse_postfix(&mut self, mut expr: Expr) -> Result<Expr> {
+        loop {
+            match self.peek().token {
+                CondToken::LBracket => {
+                    self.advance();
+                    let index = self.parse_or()?;
+                    if self.peek().token != CondToken::RBracket {
+                        return self.unexpected("']'");
+                    }
+                    let span = expr.span().to(&self.advance().span);
+                    expr = Expr::Index(Box::new(expr), Box::new(index), span);
+                }
+                CondToken::Dot => {
+                    self.advance();
+                    match self.peek().token.clone() {
+                        CondToken::Identifier(name) if !is_reserved(&name) => {
+                            let span = expr.span().to(&self.advance().span);
+                            expr = Expr::Field(Box::new(expr), name, span);
+                        }
+                        _ => return self.unexpected("field name after '.'"),
+                    }
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+}
+
+/// Words with fixed meaning in the condition grammar (case-insensitive)
+fn is_reserved(word: &str) -> bool {
+    ["and", "or", "not", "is", "empty", "true", "false", "null"]
+        .iter()
+        .any(|k| word.eq_ignore_ascii_case(k))
+}
+
+/// Whether `expr` reads a path rooted at `name`
+fn mentions(expr: &Expr, name: &str) -> bool {
+    match expr {
+        Expr::Literal(_) => false,
+        Expr::Path(segments, _) => segments[0] == name,
+        Expr::Unary(_, operand, _)
+        | Expr::IsEmpty(operand, _)
+        | Expr::IsType(operand, _, _)
+        | Expr::Old(operand, _)
+        | Expr::Field(operand, _, _) => mentions(operand, name),
+        Expr::Binary(_, left, right, _) | Expr::Index(left, right, _) => {
+            mentions(left, name) || mentions(right, name)
+        }
+        Expr::Quantified(_, var, collection, body, _) => {
+            mentions(collection, name) || (var != name && mentions(body, name))
+        }
+        Expr::Call(_, args, _) => args.iter().any(|arg| mentions(arg, name)),
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ok(text: &str) -> Expr {
+        parse_condition(text).unwrap_or_else(|e| panic!("Expected '{}' to parse, got: {}", text, e))
+    }
+
+    #[test]
+    fn test_parse_numeric_comparison() {
+        let expr = parse_ok("count >= 0");
+        match expr {
+            Expr::Binary(BinaryOp::Ge, left, right, _) => {
+                assert!(matches!(*left, Expr::Path(ref p, _) if p == &["count"]));
+                assert!(matches!(*right, Expr::Literal(LiteralValue::Integer(0, _))));
+            }
+            other => panic!("Expected comparison, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_negative_and_float_literals() {
+        assert_eq!(parse_ok("x > -5").to_string(), "x > -5");
+        assert_eq!(parse_ok("ratio <= 1.0").to_string(), "ratio <= 1.0");
+        assert_eq!(parse_ok("ratio > -0.5").to_string(), "ratio > -0.5");
+    }
+
+    #[test]
+    fn test_parse_boolean_structure_precedence() {
+        // and binds tighter than or
+        let expr = parse_ok("a or b and c");
+        match expr {
+            Expr::Binary(BinaryOp::Or, _, right, _) => {
+                assert!(matches!(*right, Expr::Binary(BinaryOp::And, _, _, _)));
+            }
+            other => panic!("Expected or at the root, got {:?}", other),
+        }
+        assert_eq!(parse_ok("(a or b) and c").to_string(), "(a or b) and c");
+    }
+
+    #[test]
+    fn test_parse_keywords_case_insensitive() {
+        assert_eq!(
+            parse_ok("active IS true AND count > 0").to_string(),
+            "active == true and count > 0"
+        );
+        assert_eq!(parse_ok("NOT done").to_string(), "not done");
+    }
+
+    #[test]
+    fn test_parse_symbolic_operators() {
+        assert_eq!(parse_ok("!a && b || c").to_string(), "not a and b or c");
+        assert_eq!(parse_ok("x = 1").to_string(), "x == 1");
+        assert_eq!(parse_ok("x != 1").to_string(), "x != 1");
+    }
+
+    #[test]
+    fn test_parse_is_empty_and_type_tests() {
+        assert!(matches!(
+            parse_ok("message is not empty"),
+            Expr::Unary(UnaryOp::Not, ref inner, _) if matches!(**inner, Expr::IsEmpty(_, _))
+        ));
+        assert!(matches!(
+            parse_ok("flag is boolean"),
+            Expr::IsType(_, PrimitiveType::Boolean, _)
+        ));
+        assert_eq!(
+            parse_ok("name is not empty").to_string(),
+            "not name is empty"
+        );
+    }
+
+    #[test]
+    fn test_parse_null_literal() {
+        assert!(matches!(
+            parse_ok("nickname is null"),
+            Expr::Binary(BinaryOp::Eq, _, ref right, _)
+                if matches!(**right, Expr::Literal(LiteralValue::Null(_)))
+        ));
+        assert_eq!(
+            parse_ok("nickname is not NULL").to_string(),
+            "nickname != null"
+        );
+        assert!(parse_condition("null.x == 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_arithmetic_precedence() {
+        assert_eq!(
+            parse_ok("a + b * c == d - e / 2").to_string(),
+            "a + b * c == d - e / 2"
+        );
+        match parse_ok("a - b - c") {
+            Expr::Binary(BinaryOp::Sub, left, _, _) => {
+                assert!(matches!(*left, Expr::Binary(BinaryOp::Sub, _, _, _)));
+            }
+            other => panic!("Expected subtraction, got {:?}", other),
+        }
+        assert_eq!(parse_ok("(a + b) * c").to_string(), "(a + b) * c");
+        assert_eq!(parse_ok("a - -1").to_string(), "a - -1");
+        assert!(matches!(
+            parse_ok("not a + 1 > b"),
+            Expr::Unary(UnaryOp::Not, _, _)
+        ));
+    }
+
+    #[test]
+    fn test_parse_old() {
+        let expr = parse_ok("counter == old(counter) + 1");
+        let Expr::Binary(BinaryOp::Eq, _, right, _) = expr else {
+            panic!("Expected comparison");
+        };
+        let Expr::Binary(BinaryOp::Add, old, _, _) = *right else {
+            panic!("Expected addition");
+        };
+        match *old {
+            Expr::Old(inner, span) => {
+                assert_eq!(
+                    *inner,
+                    Expr::Path(vec!["counter".into()], inner.span().clone())
+                );
+                assert_eq!((span.offset, span.end), (11, 23));
+            }
+            other => panic!("Expected old(...), got {:?}", other),
+        }
+        assert!(matches!(parse_ok("OLD(a.b)"), Expr::Old(_, _)));
+        // Without parentheses `old` is an ordinary field name
+        assert_eq!(parse_ok("old == 1").to_string(), "old == 1");
+        assert!(parse_condition("old(counter").is_err());
+    }
+
+    #[test]
+    fn test_parse_field_paths() {
+        match parse_ok("profile.age >= 18") {
+            Expr::Binary(_, left, _, _) => {
+                assert!(matches!(*left, Expr::Path(ref p, _) if p == &["profile", "age"]));
+            }
+            other => panic!("Expected comparison, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_index_and_field_access() {
+        match parse_ok("entries[0].label") {
+            Expr::Field(base, name, span) => {
+                assert_eq!(name, "label");
+                assert_eq!((span.offset, span.end), (0, 16));
+                match *base {
+                    Expr::Index(path, index, _) => {
+                        assert!(matches!(*path, Expr::Path(ref p, _) if p == &["entries"]));
+                        assert!(matches!(*index, Expr::Literal(LiteralValue::Integer(0, _))));
+                    }
+                    other => panic!("Expected index, got {:?}", other),
+                }
+            }
+            other => panic!("Expected field access, got {:?}", other),
+        }
+        assert_eq!(
+            parse_ok("counters['a'] >= old(items)[i + 1]").to_string(),
+            "counters[\"a\"] >= old(items)[i + 1]"
+        );
+        assert!(parse_condition("scores[0").is_err());
+        assert!(parse_condition("scores[0].").is_err());
+    }
+
+    #[test]
+    fn test_parse_quantifiers() {
+        match parse_ok("all s in scores: s >= 0 and s < 10") {
+            Expr::Quantified(Quantifier::All, var, collection, body, _) => {
+                assert_eq!(var, "s");
+                assert!(matches!(*collection, Expr::Path(ref p, _) if p == &["scores"]));
+                // The body extends to the end of the condition
+                assert!(matches!(*body, Expr::Binary(BinaryOp::And, _, _, _)));
+            }
+            other => panic!("Expected quantifier, got {:?}", other),
+        }
+        assert!(matches!(
+            parse_ok("ANY e IN entries: e.active"),
+            Expr::Quantified(Quantifier::Any, _, _, _, _)
+        ));
+        assert_eq!(
+            parse_ok("ready and (all x in xs: x > 0)").to_string(),
+            "ready and (all x in xs: x > 0)"
+        );
+        // Without a name after them, `all` and `any` are field names
+        assert_eq!(parse_ok("all == any").to_string(), "all == any");
+        assert_eq!(parse_ok("all equals 3").to_string(), "all == 3");
+        assert!(parse_condition("all s in scores s >= 0").is_err());
+    }
+
+    #[test]
+    fn test_parse_quantifier_shorthand() {
+        for (shorthand, desugared) in [
+            ("all scores >= 0", "all item in scores: item >= 0"),
+            ("ANY tags == 'x'", "any item in tags: item == \"x\""),
+            (
+                "all old(scores) <= max",
+                "all item in old(scores): item <= max",
+            ),
+            (
+                "all profile.scores < item + item1",
+                "all item2 in profile.scores: item2 < item + item1",
+            ),
+            (
+                "ready and all xs > 0",
+                "ready and (all item in xs: item > 0)",
+            ),
+        ] {
+            assert_eq!(parse_ok(shorthand).to_string(), desugared, "{}", shorthand);
+        }
+        match parse_ok("all scores >= 0") {
+            Expr::Quantified(_, _, collection, body, span) => {
+                assert_eq!((span.offset, span.end), (0, 15));
+                // The element stands at the collection in the source
+                match *body {
+                    Expr::Binary(BinaryOp::Ge, element, _, _) => {
+                        assert_eq!(element.span(), collection.span());
+                    }
+                    other => panic!("Expected comparison, got {:?}", other),
+                }
+            }
+            other => panic!("Expected quantifier, got {:?}", other),
+        }
+        for prose in [
+            "all scores",
+            "all priorities between 1 and 10",
+            "any tags in allowed",
+        ] {
+            assert!(parse_condition(prose).is_err(), "{}", prose);
+        }
+    }
+
+    #[test]
+    fn test_parse_function_calls() {
+        match parse_ok("contains(tags, \"x\")") {
+            Expr::Call(name, args, span) => {
+                assert_eq!(name, "contains");
+                assert_eq!(args.len(), 2);
+                assert!(
+                    matches!(args[1], Expr::Literal(LiteralValue::String(ref s, _)) if s == "x")
+                );
+                assert_eq!((span.offset, span.end), (0, 19));
+            }
+            other => panic!("Expected call, got {:?}", other),
+        }
+        assert!(matches!(
+            parse_ok("len(items) <= 100"),
+            Expr::Binary(BinaryOp::Le, ref left, _, _) if matches!(**left, Expr::Call(..))
+        ));
+        assert_eq!(
+            parse_ok("sum(values(m)) + len(keys(m))[0] > abs(-1)").to_string(),
+            "sum(values(m)) + len(keys(m))[0] > abs(-1)"
+        );
+        assert_eq!(parse_ok("is_empty()").to_string(), "is_empty()");
+        // `not` is an operator, not a function
+        assert!(matches!(
+            parse_ok("not(a)"),
+            Expr::Unary(UnaryOp::Not, _, _)
+        ));
+        assert!(parse_condition("len(items").is_err());
+        assert!(parse_condition("len(a b)").is_err());
+    }
+
+    #[test]
+    fn test_parse_equals_and_membership() {
+        assert!(matches!(
+            parse_ok("status equals created"),
+            Expr::Binary(BinaryOp::Eq, _, _, _)
+        ));
+        match parse_ok("status in ['open', 'paused']") {
+            Expr::Binary(BinaryOp::In, _, list, _) => match *list {
+                Expr::Literal(LiteralValue::Array(items, span)) => {
+                    assert_eq!(items.len(), 2);
+                    assert_eq!((span.offset, span.end), (10, 28));
+                }
+                other => panic!("Expected list literal, got {:?}", other),
+            },
+            other => panic!("Expected membership, got {:?}", other),
+        }
+        match parse_ok("code NOT IN [1, -2, 3.5]") {
+            Expr::Unary(UnaryOp::Not, inner, _) => {
+                assert!(matches!(*inner, Expr::Binary(BinaryOp::In, _, _, _)));
+            }
+            other => panic!("Expected negated membership, got {:?}", other),
+        }
+        assert_eq!(parse_ok("tags == []").to_string(), "tags == []");
+        assert!(parse_condition("x in [1, y]").is_err());
+        assert!(parse_condition("x in [1 2]").is_err());
+    }
+
+    #[test]
+    fn test_parse_string_literals_both_quotes() {
+        assert_eq!(
+            parse_ok("status == 'active'").to_string(),
+            "status == \"active\""
+        );
+        assert_eq!(
+            parse_ok("status == \"active\"").to_string(),
+            "status == \"active\""
+        );
+    }
+
+    #[test]
+    fn test_parse_string_escapes() {
+        let expr = parse_ok(r#"message != 'say \"hi\"' and note == "a\\b\n\t\'""#);
+        assert_eq!(
+            expr.to_string(),
+            r#"message != "say \"hi\"" and note == "a\\b\n\t'""#
+        );
+        let printed = expr.to_string();
+        assert_eq!(parse_ok(&printed).to_string(), printed);
+        assert!(parse_condition(r#"x == "\d""#).is_err());
+        assert!(parse_condition(r#"x == "ab\"#).is_err());
+    }
+
+    #[test]
+    fn test_prose_does_not_parse() {
+        for prose in [
+            "counter incremented by 1",
+            "status is valid enum value",
+            "all priorities between 1 and 10",
+            "IF active THEN count > 0",
+            "count equals number of items",
+            "a >= b >= c",
+            "balance is finite",
+            "active is not toggled",
+            "count is old(count)",
+        ] {
+            assert!(
+                parse_condition(prose).is_err(),
+                "Expected '{}' to be opaque",
+                prose
+            );
+        }
+    }
+
+    #[test]
+    fn test_spans_are_offset_by_start() {
+        let start = Span {
+            line: 4,
+            column: 10,
+            offset: 40,
+            end: 40,
+        };
+        let expr = parse_condition_at("count >= 0", &start).unwrap();
+        // Span of the comparison is the operator
+        assert_eq!(expr.span().column, 16);
+        assert_eq!(expr.span().offset, 46);
+        assert_eq!(expr.span().end, 48);
+        let err = parse_condition_at("count >= $", &start)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("4:19"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for text in [
+            "count >= 0",
+            "not (a or b)",
+            "a and (b or c)",
+            "x is empty or y is integer",
+            "ratio > 0.0",
+            "total == old(total) - amount * 2",
+            "a / (b - c) >= 1.5",
+            "not (all s in scores: s >= 0) or entries[n].tags[0] == \"x\"",
+            "any e in old(entries): (all t in e.tags: t is not empty)",
+            "status in [\"open\", \"paused\"] and not code in [1.0, 2.5]",
+            "count <= max_count and started_at < \"2026-01-01T00:00:00Z\"",
+            "len(items) <= 100 and contains(tags, \"x\") and matches(email, \"^a+$\")",
+            "(\"abc\")[0] == \"a\" and ([1, 2])[n] < 1000000000000000.0",
+        ] {
+            let printed = parse_ok(text).to_string();
+            assert_eq!(parse_ok(&printed).to_string(), printed);
+        }
+    }
+
+    /// Deterministic pseudo-random source for generated expressions
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 33) % n
+        }
+    }
+
+    /// A random expression of the shapes the parser produces
+    fn random_expr(rng: &mut Lcg, depth: u32) -> Expr {
+        let span = Span::default();
+        let leaf = depth == 0 || rng.below(4) == 0;
+        if leaf {
+            let lit = match rng.below(9) {
+                0 => LiteralValue::Integer(rng.below(100) as i64 - 50, span),
+                1 => {
+                    let floats = [0.5, -2.0, 1e15, 1e21, -123456789012345680.0, 0.000001];
+                    LiteralValue::Float(floats[rng.below(6) as usize], span)
+                }
+                2 => LiteralValue::String("say \"hi\"".into(), span),
+                3 => LiteralValue::Boolean(rng.below(2) == 0, span),
+                4 => LiteralValue::Null(span),
+                5 => LiteralValue::Array(
+                    vec![
+                        LiteralValue::Integer(1, span.clone()),
+                        LiteralValue::Float(2.0, span.clone()),
+                    ],
+                    span,
+                ),
+                6 => return Expr::Path(vec!["count".into()], span),
+                _ => return Expr::Path(vec!["profile".into(), "age".into()], span),
+            };
+            return Expr::Literal(lit);
+        }
+        let ops = [
+            BinaryOp::Or,
+            BinaryOp::And,
+            BinaryOp::Eq,
+            BinaryOp::Lt,
+            BinaryOp::In,
+            BinaryOp::Add,
+            BinaryOp::Sub,
+            BinaryOp::Mul,
+            BinaryOp::Div,
+        ];
+        let op = ops[rng.below(ops.len() as u64) as usize];
+        let kind = rng.below(10);
+        let mut sub = || Box::new(random_expr(rng, depth - 1));
+        match kind {
+            0 => Expr::Unary(UnaryOp::Not, sub(), span),
+            1 => Expr::IsEmpty(sub(), span),
+            2 => Expr::IsType(sub(), PrimitiveType::Float, span),
+            3 => Expr::Old(sub(), span),
+            4 => Expr::Index(sub(), sub(), span),
+            5 => {
+                // A field of a path would read back as a longer path
+                let base = match *sub() {
+                    path @ Expr::Path(..) => Expr::Index(Box::new(path), sub(), span.clone()),
+                    base => base,
+                };
+                Expr::Field(Box::new(base), "label".into(), span)
+            }
+            6 => Expr::Quantified(Quantifier::Any, "e".into(), sub(), sub(), span),
+            7 => Expr::Call("len".into(), vec![*sub(), *sub()], span),
+            _ => Expr::Binary(op, sub(), sub(), span),
+        }
+    }
+
+    #[test]
+    fn test_display_roundtrip_generated() {
+        // Compare the Debug forms without their spans
+        let spans = regex::Regex::new(r"Span \{[^}]*\}").unwrap();
+        let shape = |expr: &Expr| spans.replace_all(&format!("{:?}", expr), "_").into_owned();
+
+        let mut rng = Lcg(7);
+        for _ in 0..2000 {
+            let expr = random_expr(&mut rng, 4);
+            let printed = expr.to_string();
+            let parsed = parse_condition(&printed)
+                .unwrap_or_else(|e| panic!("'{}' does not parse: {}", printed, e));
+            assert_eq!(shape(&parsed), shape(&expr), "{}", printed);
+        }
+    }
+
+    #[test]
+    fn test_condition_parse_determinism_100_iterations() {
+        let text = "active == true and (count >= 0 or profile.age < 120)";
+        let first = parse_ok(text);
+        for i in 0..100 {
+            assert_eq!(parse_ok(text), first, "Non-determinism at iteration {}", i);
+        }
+    }
+}
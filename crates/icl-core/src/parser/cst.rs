@@ -0,0 +1,379 @@
+//! Lossless concrete syntax tree — tokens plus trivia (whitespace, comments)
+//!
+//! The AST drops comments and layout. The CST keeps every byte of the
+//! source: each token carries the trivia around it, and brace, bracket
+//! and angle groups become nested nodes. Printing the tree reproduces the
+//! input exactly, which is what the formatter builds on.
+//!
+//! # Architecture
+//!
+//! Tokens come from the regular [`Tokenizer`]. Because token spans are byte
+//! ranges, the gap between two tokens is exactly their trivia:
+//!
+//! - text up to the first newline belongs to the preceding token (`trailing`)
+//! - the rest belongs to the following token (`leading`)
+//!
+//! AST nodes and CST tokens share spans, so the comments written above an
+//! AST node are `tree.leading_comments(&node.span)`.
+//!
+//! # Guarantees
+//!
+//! - **Lossless**: `parse_cst(x)?.to_string() == x`
+//! - **Deterministic**: same input always produces the same tree
+
+use super::tokenizer::{Span, Token, Tokenizer};
+use crate::{Error, Result};
+
+// ── Types ─────────────────────────────────────────────────
+
+/// A parsed source file: a `Root` node ending with the `Eof` token
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxTree {
+    pub root: SyntaxNode,
+}
+
+/// A group of tokens, e.g. a `{ ... }` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxKind {
+    /// The whole file
+    Root,
+    /// `{ ... }` — contract, section, object or operation body
+    Block,
+    /// `[ ... ]` — list literal or enum variants
+    List,
+    /// `< ... >` — type arguments of Array / Map
+    TypeArgs,
+    /// `( ... )` — refinements of a type, e.g. `Integer(min: 0)`
+    Refinements,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+/// A token with its exact source text and surrounding trivia
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxToken {
+    pub token: Token,
+    pub text: String,
+    pub span: Span,
+    /// Trivia on the lines before this token
+    pub leading: Vec<Trivia>,
+    /// Trivia after this token up to the end of its line
+    pub trailing: Vec<Trivia>,
+}
+
+/// Text between tokens that carries no meaning
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Spaces, tabs, carriage returns
+    Whitespace,
+    /// A single `\n`
+    Newline,
+    /// `// ...` up to (not including) the end of line
+    Comment,
+}
+
+// ── Public API ─────────────────────────────────────────────
+
+/// Parse ICL text into a lossless syntax tree
+///
+/// Only the token structure is checked (balanced `{}`, `[]`, `<>`);
+/// use [`super::parse`] to validate the contract itself.
+///
+/// # Errors
+/// Returns `ParseError` for invalid tokens or unbalanced delimiters.
+pub fn parse_cst(input: &str) -> Result<SyntaxTree> {
+    let tokens = Tokenizer::new(input).tokenize()?;
+
+    let mut syntax_tokens: Vec<SyntaxToken> = Vec::with_capacity(tokens.len());
+    let mut gap_start = 0;
+    for st in tokens {
+        let range = st.span.range();
+        let gap = &input[gap_start..range.start];
+        let (trailing, leading) = split_trivia(gap, !syntax_tokens.is_empty());
+        if let Some(prev) = syntax_tokens.last_mut() {
+            prev.trailing = trailing;
+        }
+        gap_start = range.end;
+        syntax_tokens.push(SyntaxToken {
+            text: input[range].to_string(),
+            token: st.token,
+            span: st.span,
+            leading,
+            trailing: Vec::new(),
+        });
+    }
+
+    let mut tokens = syntax_tokens.into_iter();
+    let root = build_node(SyntaxKind::Root, None, &mut tokens)?;
+    Ok(SyntaxTree { root })
+}
+
+impl SyntaxTree {
+    /// All tokens in source order, including the final `Eof`
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut out = Vec::new();
+        self.root.collect_tokens(&mut out);
+        out
+    }
+
+    /// The token starting at `offset`, if any
+    pub fn token_at(&self, offset: usize) -> Option<&SyntaxToken> {
+        self.tokens().into_iter().find(|t| t.span.offset == offset)
+    }
+
+    /// Comments on the lines directly above the node starting at `span`
+    pub fn leading_comments(&self, span: &Span) -> Vec<&str> {
+        self.token_at(span.offset)
+            .map(|t| {
+                t.leading
+                    .iter()
+                    .filter(|tr| tr.kind == TriviaKind::Comment)
+                    .map(|tr| tr.text.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl SyntaxNode {
+    fn collect_tokens<'a>(&'a self, out: &mut Vec<&'a SyntaxToken>) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(out),
+                SyntaxElement::Token(token) => out.push(token),
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SyntaxTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for token in self.tokens() {
+            for trivia in &token.leading {
+                f.write_str(&trivia.text)?;
+            }
+            f.write_str(&token.text)?;
+            for trivia in &token.trailing {
+                f.write_str(&trivia.text)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ── Tree building ─────────────────────────────────────────
+
+type TokenStream = std::vec::IntoIter<SyntaxToken>;
+
+/// Collect children until `close` (or `Eof` for the root). The opening
+/// token, if any, has already been pushed by the caller.
+fn build_node(
+    kind: SyntaxKind,
+    open: Option<SyntaxToken>,
+    tokens: &mut TokenStream,
+) -> Result<SyntaxNode> {
+    let close = match kind {
+        SyntaxKind::Root => Token::Eof,
+        SyntaxKind::Block => Token::RBrace,
+        SyntaxKind::List => Token::RBracket,
+        SyntaxKind::TypeArgs => Token::RAngle,
+        SyntaxKind::Refinements => Token::RParen,
+    };
+    let open_span = open.as_ref().map(|t| t.span.clone());
+    let mut children: Vec<SyntaxElement> = open.into_iter().map(SyntaxElement::Token).collect();
+
+    while let Some(token) = tokens.next() {
+        if token.token == close {
+            children.push(SyntaxElement::Token(token));
+            return Ok(SyntaxNode { kind, children });
+        }
+        let child_kind = match token.token {
+            Token::LBrace => Some(SyntaxKind::Block),
+            Token::LBracket => Some(SyntaxKind::List),
+            Token::LAngle => Some(SyntaxKind::TypeArgs),
+            Token::LParen => Some(SyntaxKind::Refinements),
+            _ => None,
+        };
+        match (child_kind, &token.token) {
+            (Some(child_kind), _) => {
+                let node = build_node(child_kind, Some(token), tokens)?;
+                children.push(SyntaxElement::Node(node));
+            }
+            (
+                None,
+                Token::RBrace | Token::RBracket | Token::RAngle | Token::RParen | Token::Eof,
+            ) => {
+                let found = match token.token {
+                    Token::Eof => "end of input".to_string(),
+                    _ => format!("'{}'", token.text),
+                };
+                let message = match open_span {
+                    Some(ref span) => format!(
+                        "Unexpected {}, delimiter opened at {} is not closed",
+                        found, span
+                    ),
+                    None => format!("Unexpected {}", found),
+                };
+                return Err(Error::ParseError(format!("{} at {}", message, token.span)));
+            }
+            (None, _) => children.push(SyntaxElement::Token(token)),
+        }
+    }
+
+    // The tokenizer always ends with Eof, which closes or rejects above
+    unreachable!("token stream ended without Eof")
+}
+
+/// Split the text between two tokens into (trailing, leading) trivia.
+/// Without a preceding token everything is leading.
+fn split_trivia(gap: &str, has_prev: bool) -> (Vec<Trivia>, Vec<Trivia>) {
+    let mut trailing = Vec::new();
+    let mut leading = Vec::new();
+    let mut seen_newline = !has_prev;
+    let mut rest = gap;
+
+    while let Some(c) = rest.chars().next() {
+        let (kind, len) = if c == '\n' {
+            (TriviaKind::Newline, 1)
+        } else if rest.starts_with("//") {
+            (TriviaKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else {
+            let len = rest
+                .find(|c: char| c == '\n' || !c.is_ascii_whitespace())
+                .unwrap_or(rest.len());
+            // Tokenizer gaps hold only whitespace and comments
+            (TriviaKind::Whitespace, len.max(c.len_utf8()))
+        };
+        if kind == TriviaKind::Newline {
+            seen_newline = true;
+        }
+        let trivia = Trivia {
+            kind,
+            text: rest[..len].to_string(),
+        };
+        if seen_newline {
+            leading.push(trivia);
+        } else {
+            trailing.push(trivia);
+        }
+        rest = &rest[len..];
+    }
+
+    (trailing, leading)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn fixture_paths(dir: &str) -> Vec<std::path::PathBuf> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/fixtures/conformance")
+            .join(dir);
+        let mut paths: Vec<_> = fs::read_dir(&root)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "icl"))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_cst_roundtrip_is_lossless() {
+        let input = "// head\nContract {  // open\n\n  a: [1,2 ],\t\n  b: Map<String, Integer>\r\n}\n// tail";
+        let tree = parse_cst(input).unwrap();
+        assert_eq!(tree.to_string(), input);
+    }
+
+    #[test]
+    fn test_cst_roundtrip_all_valid_fixtures() {
+        for path in fixture_paths("valid") {
+            let input = fs::read_to_string(&path).unwrap();
+            let tree = parse_cst(&input).unwrap();
+            assert_eq!(tree.to_string(), input, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_cst_trivia_attachment() {
+        let input = "// about a\na: 1, // one\n// about b\nb: 2";
+        let tree = parse_cst(input).unwrap();
+        let tokens = tree.tokens();
+        assert_eq!(tokens[0].text, "a");
+        assert_eq!(tokens[0].leading[0].text, "// about a");
+        // `,` owns the comment on its line
+        assert_eq!(tokens[3].text, ",");
+        assert_eq!(tokens[3].trailing[1].text, "// one");
+        assert_eq!(tree.leading_comments(&tokens[4].span), vec!["// about b"]);
+    }
+
+    #[test]
+    fn test_cst_nesting() {
+        let tree = parse_cst("x { y: [Array<String>(max_items: 2)] }").unwrap();
+        let SyntaxElement::Node(block) = &tree.root.children[1] else {
+            panic!("expected block");
+        };
+        assert_eq!(block.kind, SyntaxKind::Block);
+        let SyntaxElement::Node(list) = &block.children[3] else {
+            panic!("expected list");
+        };
+        assert_eq!(list.kind, SyntaxKind::List);
+        let SyntaxElement::Node(args) = &list.children[2] else {
+            panic!("expected type args");
+        };
+        assert_eq!(args.kind, SyntaxKind::TypeArgs);
+        let SyntaxElement::Node(refinements) = &list.children[3] else {
+            panic!("expected refinements");
+        };
+        assert_eq!(refinements.kind, SyntaxKind::Refinements);
+    }
+
+    #[test]
+    fn test_cst_comments_match_ast_nodes() {
+        let input = fs::read_to_string(
+            fixture_paths("valid")
+                .into_iter()
+                .find(|p| p.ends_with("comments-everywhere.icl"))
+                .unwrap(),
+        )
+        .unwrap();
+        let tree = parse_cst(&input).unwrap();
+        let ast = crate::parser::parse(&input).unwrap();
+        assert_eq!(
+            tree.leading_comments(&ast.identity.span),
+            vec!["// Comment before Identity section"]
+        );
+        assert_eq!(
+            tree.leading_comments(&ast.data_semantics.state[1].span),
+            vec!["// An integer with default"]
+        );
+    }
+
+    #[test]
+    fn test_cst_unbalanced_delimiters() {
+        let err = parse_cst("a { b: [1 }").unwrap_err().to_string();
+        assert!(err.contains("Unexpected '}'"), "{}", err);
+        let err = parse_cst("a { b").unwrap_err().to_string();
+        assert!(err.contains("Unexpected end of input"), "{}", err);
+        assert!(parse_cst("a }").is_err());
+    }
+}
@@ -14,8 +14,12 @@
 //! - **Complete errors**: line:column for every error
 
 pub mod ast;
+pub mod condition;
+pub mod cst;
 pub mod tokenizer;
 
+use crate::formats::parse_timestamp;
+use crate::schema::Field;
 use crate::{Error, Result};
 use ast::*;
 use tokenizer::{Span, SpannedToken, Token, Tokenizer};
@@ -36,8 +40,66 @@ use tokenizer::{Span, SpannedToken, Token, Tokenizer};
 pub fn parse(input: &str) -> Result<ContractNode> {
     let mut tokenizer = Tokenizer::new(input);
     let tokens = tokenizer.tokenize()?;
-    let mut parser = Parser::new(tokens);
-    parser.parse_contract_definition()
+    let mut parser = Parser::new(input, tokens, false);
+    Ok(parser.parse_contract_definition()?)
+}
+
+/// Parse ICL text, recovering from syntax errors
+///
+/// Unlike [`parse`], this does not stop at the first error. The parser
+/// resynchronizes at field and section boundaries, so one run reports every
+/// syntax error in the contract. Sections or fields that could not be parsed
+/// are filled with empty placeholders in the returned (partial) `ContractNode`.
+///
+/// # Guarantees
+/// - Deterministic: same input always produces same AST and diagnostics
+/// - `diagnostics` is empty exactly when [`parse`] succeeds
+pub fn parse_with_diagnostics(input: &str) -> ParseResult {
+    let mut tokenizer = Tokenizer::new(input);
+    let (tokens, mut diagnostics) = tokenizer.tokenize_with_diagnostics();
+    let mut parser = Parser::new(input, tokens, true);
+    let contract = match parser.parse_contract_definition() {
+        Ok(contract) => contract,
+        Err(d) => {
+            parser.record(d);
+            ContractNode::default()
+        }
+    };
+    diagnostics.extend(parser.diagnostics);
+    ParseResult {
+        contract,
+        diagnostics,
+    }
+}
+
+/// Parse an imported module: `import`s, an optional `Types` section and
+/// an optional `Invariants [...]` list
+///
+/// Modules are not contracts on their own; [`crate::loader`] merges them
+/// into the contract that imports them.
+///
+/// # Errors
+/// Returns `ParseError` with line:column for syntax violations.
+pub fn parse_module(input: &str) -> Result<ModuleNode> {
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize()?;
+    let mut parser = Parser::new(input, tokens, false);
+    Ok(parser.parse_module_definition()?)
+}
+
+/// Parse a standalone type expression, e.g. `Integer(min: 0)`
+///
+/// Used by the executor to read the type strings of a lowered `Contract`.
+///
+/// # Errors
+/// Returns `ParseError` if the text is not exactly one type expression.
+pub fn parse_type(input: &str) -> Result<TypeExpression> {
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.tokenize()?;
+    let mut parser = Parser::new(input, tokens, false);
+    let type_expr = parser.parse_type_expression()?;
+    parser.expect(Token::Eof)?;
+    Ok(type_expr)
 }
 
 /// Parse ICL text into a semantic Contract (parse + lower)
@@ -52,18 +114,129 @@ pub fn parse_contract(input: &str) -> Result<crate::Contract> {
     lower_contract(&node)
 }
 
+// ── Diagnostics ────────────────────────────────────────────
+
+// Stable codes of parse diagnostics, explained in [`crate::explain`]
+
+/// A character that starts no token
+pub const UNEXPECTED_CHARACTER: &str = "ICL-P001";
+/// A string literal or escape sequence runs to the end of the input
+pub const UNTERMINATED_STRING: &str = "ICL-P002";
+/// A backslash escape that is not `\\`, `\"`, `\n` or `\t`
+pub const INVALID_ESCAPE: &str = "ICL-P003";
+/// A malformed number or timestamp literal
+pub const INVALID_LITERAL: &str = "ICL-P004";
+/// A token the grammar does not allow at this point
+pub const UNEXPECTED_TOKEN: &str = "ICL-P005";
+/// A field name the section does not define
+pub const UNEXPECTED_FIELD: &str = "ICL-P006";
+/// A required field is absent
+pub const MISSING_FIELD: &str = "ICL-P007";
+/// A well-formed value outside its allowed range
+pub const VALUE_OUT_OF_RANGE: &str = "ICL-P008";
+
+/// A problem found while parsing, with its source location
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDiagnostic {
+    pub kind: ParseDiagnosticKind,
+    /// Stable code, e.g. `ICL-P005`
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+}
+
+/// Category of parse diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDiagnosticKind {
+    /// Malformed input (unexpected token, missing field, bad literal)
+    Syntax,
+    /// Well-formed input with an out-of-range value (e.g. confidence_level)
+    Validation,
+}
+
+impl ParseDiagnostic {
+    pub fn syntax(code: &'static str, message: impl Into<String>, span: &Span) -> Self {
+        ParseDiagnostic {
+            kind: ParseDiagnosticKind::Syntax,
+            code,
+            message: message.into(),
+            span: span.clone(),
+        }
+    }
+
+    pub fn validation(code: &'static str, message: impl Into<String>, span: &Span) -> Self {
+        ParseDiagnostic {
+            kind: ParseDiagnosticKind::Validation,
+            code,
+            message: message.into(),
+            span: span.clone(),
+        }
+    }
+
+    /// Render with the offending source line and underline carets
+    pub fn render(&self, source: &str, origin: &str) -> String {
+        let header = format!("error[{}]: {}", self.code, self.message);
+        crate::snippet::render(source, origin, &header, &self.span)
+    }
+}
+
+impl std::fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{}] {} at {}", self.code, self.message, self.span)
+    }
+}
+
+impl From<ParseDiagnostic> for Error {
+    fn from(d: ParseDiagnostic) -> Self {
+        match d.kind {
+            ParseDiagnosticKind::Syntax => Error::ParseError(d.to_string()),
+            ParseDiagnosticKind::Validation => Error::ValidationError(d.to_string()),
+        }
+    }
+}
+
+/// Result of [`parse_with_diagnostics`] — partial AST plus all diagnostics
+#[derive(Debug, Clone)]
+pub struct ParseResult {
+    pub contract: ContractNode,
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl ParseResult {
+    /// Returns true if the contract parsed without any diagnostics
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+/// Parser-internal result: errors keep their span until the public boundary
+type SyntaxResult<T> = std::result::Result<T, ParseDiagnostic>;
+
 // ── Parser ─────────────────────────────────────────────────
 
-struct Parser {
+struct Parser<'a> {
+    /// Source text, for the escapes in condition literals
+    source: &'a str,
     tokens: Vec<SpannedToken>,
     position: usize,
+    /// Recover at field/section boundaries instead of stopping at the first error
+    recover: bool,
+    /// Errors recorded while recovering
+    diagnostics: Vec<ParseDiagnostic>,
+    /// Fields that were present but failed to parse (name, offset), so
+    /// they are not reported a second time as missing
+    failed_fields: Vec<(String, usize)>,
 }
 
-impl Parser {
-    fn new(tokens: Vec<SpannedToken>) -> Self {
+impl<'a> Parser<'a> {
+    fn new(source: &'a str, tokens: Vec<SpannedToken>, recover: bool) -> Self {
         Parser {
+            source,
             tokens,
             position: 0,
+            recover,
+            diagnostics: Vec::new(),
+            failed_fields: Vec::new(),
         }
     }
 
@@ -77,6 +250,7 @@ impl Parser {
                 line: 0,
                 column: 0,
                 offset: 0,
+                end: 0,
             }
         }
     }
@@ -85,6 +259,14 @@ impl Parser {
         &self.tokens[self.position].token
     }
 
+    /// Span from `start` to the end of the last consumed token
+    fn span_from(&self, start: &Span) -> Span {
+        match self.position.checked_sub(1) {
+            Some(prev) => start.to(&self.tokens[prev].span),
+            None => start.clone(),
+        }
+    }
+
     fn advance(&mut self) -> SpannedToken {
         let token = self.tokens[self.position].clone();
         if self.position < self.tokens.len() - 1 {
@@ -94,65 +276,70 @@ impl Parser {
     }
 
     /// Expect a specific token (exact match for keywords/symbols)
-    fn expect(&mut self, expected: Token) -> Result<SpannedToken> {
+    fn expect(&mut self, expected: Token) -> SyntaxResult<SpannedToken> {
         let current = self.tokens[self.position].clone();
         if current.token == expected {
             self.advance();
             Ok(current)
         } else {
-            Err(Error::ParseError(format!(
-                "Expected {:?}, found {:?} at {}",
-                expected, current.token, current.span
-            )))
+            Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected {:?}, found {:?}", expected, current.token),
+                &current.span,
+            ))
         }
     }
 
     /// Expect a string literal and return its value with span
-    fn expect_string_literal(&mut self) -> Result<SpannedValue<String>> {
+    fn expect_string_literal(&mut self) -> SyntaxResult<SpannedValue<String>> {
         let st = self.advance();
         match st.token {
             Token::StringLiteral(s) => Ok(SpannedValue::new(s, st.span)),
-            _ => Err(Error::ParseError(format!(
-                "Expected string literal, found {:?} at {}",
-                st.token, st.span
-            ))),
+            _ => Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected string literal, found {:?}", st.token),
+                &st.span,
+            )),
         }
     }
 
     /// Expect an integer literal and return its value with span
-    fn expect_integer_literal(&mut self) -> Result<SpannedValue<i64>> {
+    fn expect_integer_literal(&mut self) -> SyntaxResult<SpannedValue<i64>> {
         let st = self.advance();
         match st.token {
             Token::IntegerLiteral(n) => Ok(SpannedValue::new(n, st.span)),
-            _ => Err(Error::ParseError(format!(
-                "Expected integer literal, found {:?} at {}",
-                st.token, st.span
-            ))),
+            _ => Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected integer literal, found {:?}", st.token),
+                &st.span,
+            )),
         }
     }
 
     /// Expect a float literal and return its value with span
-    fn expect_float_literal(&mut self) -> Result<SpannedValue<f64>> {
+    fn expect_float_literal(&mut self) -> SyntaxResult<SpannedValue<f64>> {
         let st = self.advance();
         match st.token {
             Token::FloatLiteral(f) => Ok(SpannedValue::new(f, st.span)),
-            _ => Err(Error::ParseError(format!(
-                "Expected float literal, found {:?} at {}",
-                st.token, st.span
-            ))),
+            _ => Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected float literal, found {:?}", st.token),
+                &st.span,
+            )),
         }
     }
 
     /// Expect a named field: `identifier ":"`
-    fn expect_field(&mut self, name: &str) -> Result<Span> {
+    fn expect_field(&mut self, name: &str) -> SyntaxResult<Span> {
         let st = self.advance();
         match &st.token {
             Token::Identifier(id) if id == name => {}
             _ => {
-                return Err(Error::ParseError(format!(
-                    "Expected field '{}', found {:?} at {}",
-                    name, st.token, st.span
-                )));
+                return Err(ParseDiagnostic::syntax(
+                    UNEXPECTED_TOKEN,
+                    format!("Expected field '{}', found {:?}", name, st.token),
+                    &st.span,
+                ));
             }
         }
         self.expect(Token::Colon)?;
@@ -167,55 +354,340 @@ impl Parser {
     }
 
     /// Peek at the current token and return identifier name without advancing
-    fn peek_identifier_name(&self) -> Result<String> {
+    fn peek_identifier_name(&self) -> SyntaxResult<String> {
         match &self.tokens[self.position].token {
             Token::Identifier(name) => Ok(name.clone()),
-            other => Err(Error::ParseError(format!(
-                "Expected field name identifier, found {:?} at {}",
-                other, self.tokens[self.position].span
-            ))),
+            other => Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected field name identifier, found {:?}", other),
+                &self.tokens[self.position].span,
+            )),
         }
     }
 
-    // ── Top-level parsing ──────────────────────────────
+    // ── Error recovery ─────────────────────────────────
 
-    /// Parse: `Contract { ... } [Extensions { ... }]`
-    fn parse_contract_definition(&mut self) -> Result<ContractNode> {
-        let span = self.current_span();
-        self.expect(Token::Contract)?;
-        self.expect(Token::LBrace)?;
+    /// Record a diagnostic when recovering, otherwise fail with it
+    fn report(&mut self, diagnostic: ParseDiagnostic) -> SyntaxResult<()> {
+        if self.recover {
+            self.diagnostics.push(diagnostic);
+            Ok(())
+        } else {
+            Err(diagnostic)
+        }
+    }
+
+    /// Record a syntax error, keeping at most one per source location so a
+    /// single bad token does not cascade into repeated errors
+    fn record(&mut self, diagnostic: ParseDiagnostic) {
+        if self.diagnostics.last().map(|d| &d.span) != Some(&diagnostic.span) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Unwrap a required field. When recovering, a missing field is
+    /// reported and replaced by an empty placeholder.
+    fn required<T: Default>(
+        &mut self,
+        value: Option<T>,
+        field: &str,
+        section: &str,
+        span: &Span,
+    ) -> SyntaxResult<T> {
+        match value {
+            Some(v) => Ok(v),
+            None if self.field_failed(field, span) => Ok(T::default()),
+            None => {
+                self.report(ParseDiagnostic::syntax(
+                    MISSING_FIELD,
+                    format!("Missing required field '{}' in {}", field, section),
+                    span,
+                ))?;
+                Ok(T::default())
+            }
+        }
+    }
+
+    /// True if `field` was present after `block_span` but failed to parse
+    fn field_failed(&self, field: &str, block_span: &Span) -> bool {
+        self.failed_fields
+            .iter()
+            .any(|(name, offset)| name == field && *offset >= block_span.offset)
+    }
+
+    /// Expect a token; when recovering, report it missing and carry on
+    /// without consuming anything
+    fn expect_or_report(&mut self, expected: Token) -> SyntaxResult<()> {
+        match self.expect(expected) {
+            Ok(_) => Ok(()),
+            Err(d) if self.recover => {
+                self.record(d);
+                Ok(())
+            }
+            Err(d) => Err(d),
+        }
+    }
+
+    /// True at the end of a `{ ... }` block. When recovering, a section
+    /// keyword or end of input also ends the block (missing `}`).
+    fn at_block_end(&self) -> bool {
+        matches!(self.peek(), Token::RBrace) || (self.recover && self.at_sync_point())
+    }
+
+    /// Section keywords and end of input — safe places to resume parsing
+    fn at_sync_point(&self) -> bool {
+        matches!(
+            self.peek(),
+            Token::Eof
+                | Token::Contract
+                | Token::Identity
+                | Token::PurposeStatement
+                | Token::DataSemantics
+                | Token::BehavioralSemantics
+                | Token::ExecutionConstraints
+                | Token::HumanMachineContract
+                | Token::Extensions
+                | Token::Types
+        )
+    }
+
+    /// Parse one `name: value` entry of a block. When recovering, a failing
+    /// entry is recorded and skipped up to the next field boundary.
+    fn parse_block_field(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> SyntaxResult<()>,
+    ) -> SyntaxResult<()> {
+        let field_start = self.position;
+        match parse(self) {
+            Ok(()) => Ok(()),
+            Err(d) if self.recover => {
+                self.record(d);
+                let start = &self.tokens[field_start];
+                if let Token::Identifier(name) = &start.token {
+                    self.failed_fields.push((name.clone(), start.span.offset));
+                }
+                self.skip_to_field_boundary(field_start);
+                Ok(())
+            }
+            Err(d) => Err(d),
+        }
+    }
+
+    /// Skip tokens until the next `identifier :` at the nesting level where
+    /// the failed field started, the end of its block, or a sync point.
+    /// A `}` or `]` that closes nothing opened since the field start ends
+    /// the enclosing block and is left for it, even if the failed parse
+    /// already consumed it.
+    fn skip_to_field_boundary(&mut self, field_start: usize) {
+        // Brackets opened since the field start that are still open
+        let mut open = Vec::new();
+        for index in field_start..self.position {
+            if !self.enter_bracket(&mut open, index, field_start) {
+                self.position = index;
+                return;
+            }
+        }
+
+        loop {
+            if self.at_sync_point() {
+                return;
+            }
+            let at_field_name = matches!(self.peek(), Token::Identifier(_))
+                && matches!(
+                    self.tokens.get(self.position + 1).map(|t| &t.token),
+                    Some(Token::Colon)
+                );
+            if open.is_empty() && at_field_name && self.position > field_start {
+                return;
+            }
+            if !self.enter_bracket(&mut open, self.position, field_start) {
+                return;
+            }
+            self.advance();
+        }
+    }
+
+    /// Track the bracket at `index` in the stack of `open` brackets. False
+    /// if it closes a bracket opened before `field_start`; a closer that
+    /// matches an outer open bracket also closes any unclosed inner ones.
+    fn enter_bracket(&self, open: &mut Vec<Token>, index: usize, field_start: usize) -> bool {
+        let opener = match self.tokens[index].token {
+            Token::LBrace | Token::LBracket => {
+                open.push(self.tokens[index].token.clone());
+                return true;
+            }
+            Token::RBrace => Token::LBrace,
+            Token::RBracket => Token::LBracket,
+            _ => return true,
+        };
+        match open.iter().rposition(|t| *t == opener) {
+            Some(depth) => {
+                open.truncate(depth);
+                true
+            }
+            // A stray `]` that starts the field is skipped
+            None => index == field_start && opener == Token::LBracket,
+        }
+    }
+
+    /// Parse a section introduced by `keyword`. When recovering, stray
+    /// input before the keyword is reported and skipped, a failing section
+    /// is recorded and skipped to the next section keyword, and an empty
+    /// placeholder takes its place.
+    fn parse_section<T: Default>(
+        &mut self,
+        keyword: Token,
+        parse: impl FnOnce(&mut Self) -> SyntaxResult<T>,
+    ) -> SyntaxResult<T> {
+        if self.recover && *self.peek() != keyword && !self.at_sync_point() {
+            let d = ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected {:?}, found {:?}", keyword, self.peek()),
+                &self.current_span(),
+            );
+            self.record(d);
+            // A `}` here most likely closes the contract early
+            if matches!(self.peek(), Token::RBrace) {
+                return Ok(T::default());
+            }
+            self.skip_to_sync_point();
+        }
+        match parse(self) {
+            Ok(node) => Ok(node),
+            Err(d) if self.recover => {
+                self.record(d);
+                self.skip_to_sync_point();
+                Ok(T::default())
+            }
+            Err(d) => Err(d),
+        }
+    }
+
+    fn skip_to_sync_point(&mut self) {
+        while !self.at_sync_point() {
+            self.advance();
+        }
+    }
 
-        let identity = self.parse_identity()?;
-        let purpose_statement = self.parse_purpose_statement()?;
-        let data_semantics = self.parse_data_semantics()?;
-        let behavioral_semantics = self.parse_behavioral_semantics()?;
-        let execution_constraints = self.parse_execution_constraints()?;
-        let human_machine_contract = self.parse_human_machine_contract()?;
+    // ── Top-level parsing ──────────────────────────────
 
-        self.expect(Token::RBrace)?;
+    /// Parse: `import* Contract { Identity PurposeStatement [Types] DataSemantics ... } [Extensions { ... }]`
+    fn parse_contract_definition(&mut self) -> SyntaxResult<ContractNode> {
+        let span = self.current_span();
+        let imports = self.parse_imports()?;
+        self.expect_or_report(Token::Contract)?;
+        self.expect_or_report(Token::LBrace)?;
+
+        let identity = self.parse_section(Token::Identity, Self::parse_identity)?;
+        let purpose_statement =
+            self.parse_section(Token::PurposeStatement, Self::parse_purpose_statement)?;
+        let types = if matches!(self.peek(), Token::Types) {
+            self.parse_section(Token::Types, |p| p.parse_types().map(Some))?
+        } else {
+            None
+        };
+        let data_semantics =
+            self.parse_section(Token::DataSemantics, Self::parse_data_semantics)?;
+        let behavioral_semantics =
+            self.parse_section(Token::BehavioralSemantics, Self::parse_behavioral_semantics)?;
+        let execution_constraints = self.parse_section(
+            Token::ExecutionConstraints,
+            Self::parse_execution_constraints,
+        )?;
+        let human_machine_contract = self.parse_section(
+            Token::HumanMachineContract,
+            Self::parse_human_machine_contract,
+        )?;
+
+        self.expect_or_report(Token::RBrace)?;
 
         // Optional Extensions block (outside Contract per BNF §5)
         let extensions = if matches!(self.peek(), Token::Extensions) {
-            Some(self.parse_extensions()?)
+            self.parse_section(Token::Extensions, |p| p.parse_extensions().map(Some))?
         } else {
             None
         };
 
         Ok(ContractNode {
+            imports,
             identity,
             purpose_statement,
+            types,
             data_semantics,
             behavioral_semantics,
             execution_constraints,
             human_machine_contract,
             extensions,
-            span,
+            span: self.span_from(&span),
+        })
+    }
+
+    /// Parse: `import* [Types { ... }] [Invariants [...]]`
+    fn parse_module_definition(&mut self) -> SyntaxResult<ModuleNode> {
+        let span = self.current_span();
+        let imports = self.parse_imports()?;
+
+        let types = if matches!(self.peek(), Token::Types) {
+            Some(self.parse_types()?)
+        } else {
+            None
+        };
+
+        let invariants = if matches!(self.peek(), Token::Invariants) {
+            self.advance(); // consume Invariants
+            self.parse_condition_list()?
+        } else {
+            Vec::new()
+        };
+
+        if !matches!(self.peek(), Token::Eof) {
+            return Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!(
+                    "Expected Types, Invariants or end of module, found {:?}",
+                    self.peek()
+                ),
+                &self.current_span(),
+            ));
+        }
+
+        Ok(ModuleNode {
+            imports,
+            types,
+            invariants,
+            span: self.span_from(&span),
         })
     }
 
+    // ── Imports ───────────────────────────────────────
+
+    /// Parse: `import "path"*`
+    fn parse_imports(&mut self) -> SyntaxResult<Vec<ImportNode>> {
+        let mut imports = Vec::new();
+        while matches!(self.peek(), Token::Import) {
+            let span = self.current_span();
+            self.advance(); // consume import
+            if !matches!(self.peek(), Token::StringLiteral(_)) {
+                // Leave the token for the caller: it is most likely `Contract`
+                self.report(ParseDiagnostic::syntax(
+                    UNEXPECTED_TOKEN,
+                    format!("Expected import path string, found {:?}", self.peek()),
+                    &self.current_span(),
+                ))?;
+                continue;
+            }
+            let path = self.expect_string_literal()?;
+            imports.push(ImportNode {
+                path,
+                span: self.span_from(&span),
+            });
+        }
+        Ok(imports)
+    }
+
     // ── Identity (§1.2) ───────────────────────────────
 
-    fn parse_identity(&mut self) -> Result<IdentityNode> {
+    fn parse_identity(&mut self) -> SyntaxResult<IdentityNode> {
         let span = self.current_span();
         self.expect(Token::Identity)?;
         self.expect(Token::LBrace)?;
@@ -226,80 +698,75 @@ impl Parser {
         let mut owner: Option<SpannedValue<String>> = None;
         let mut semantic_hash: Option<SpannedValue<String>> = None;
 
-        while !matches!(self.peek(), Token::RBrace) {
-            let field_name = self.peek_identifier_name()?;
-            match field_name.as_str() {
-                "stable_id" => {
-                    self.expect_field("stable_id")?;
-                    stable_id = Some(self.expect_string_literal()?);
-                }
-                "version" => {
-                    self.expect_field("version")?;
-                    version = Some(self.expect_integer_literal()?);
-                }
-                "created_timestamp" => {
-                    self.expect_field("created_timestamp")?;
-                    created_timestamp = Some(self.expect_string_literal()?);
-                }
-                "owner" => {
-                    self.expect_field("owner")?;
-                    owner = Some(self.expect_string_literal()?);
-                }
-                "semantic_hash" => {
-                    self.expect_field("semantic_hash")?;
-                    semantic_hash = Some(self.expect_string_literal()?);
-                }
-                other => {
-                    return Err(Error::ParseError(format!(
-                        "Unknown field '{}' in Identity at {}",
-                        other,
-                        self.current_span()
-                    )));
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let field_name = p.peek_identifier_name()?;
+                match field_name.as_str() {
+                    "stable_id" => {
+                        p.expect_field("stable_id")?;
+                        stable_id = Some(p.expect_string_literal()?);
+                    }
+                    "version" => {
+                        p.expect_field("version")?;
+                        version = Some(p.expect_integer_literal()?);
+                    }
+                    "created_timestamp" => {
+                        p.expect_field("created_timestamp")?;
+                        let ts = p.expect_string_literal()?;
+                        // Bare timestamps are checked by the tokenizer; quoted ones here
+                        if parse_timestamp(&ts.value).is_none() {
+                            p.report(ParseDiagnostic::validation(
+                                INVALID_LITERAL,
+                                format!(
+                                    "created_timestamp '{}' is not an RFC 3339 date-time",
+                                    ts.value
+                                ),
+                                &ts.span,
+                            ))?;
+                        }
+                        created_timestamp = Some(ts);
+                    }
+                    "owner" => {
+                        p.expect_field("owner")?;
+                        owner = Some(p.expect_string_literal()?);
+                    }
+                    "semantic_hash" => {
+                        p.expect_field("semantic_hash")?;
+                        semantic_hash = Some(p.expect_string_literal()?);
+                    }
+                    other => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_FIELD,
+                            format!("Unknown field '{}' in Identity", other),
+                            &p.current_span(),
+                        ));
+                    }
                 }
-            }
+                Ok(())
+            })?;
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
         Ok(IdentityNode {
-            stable_id: stable_id.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'stable_id' in Identity at {}",
-                    span
-                ))
-            })?,
-            version: version.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'version' in Identity at {}",
-                    span
-                ))
-            })?,
-            created_timestamp: created_timestamp.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'created_timestamp' in Identity at {}",
-                    span
-                ))
-            })?,
-            owner: owner.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'owner' in Identity at {}",
-                    span
-                ))
-            })?,
-            semantic_hash: semantic_hash.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'semantic_hash' in Identity at {}",
-                    span
-                ))
-            })?,
-            span,
+            stable_id: self.required(stable_id, "stable_id", "Identity", &span)?,
+            version: self.required(version, "version", "Identity", &span)?,
+            created_timestamp: self.required(
+                created_timestamp,
+                "created_timestamp",
+                "Identity",
+                &span,
+            )?,
+            owner: self.required(owner, "owner", "Identity", &span)?,
+            semantic_hash: self.required(semantic_hash, "semantic_hash", "Identity", &span)?,
+            span: self.span_from(&span),
         })
     }
 
     // ── PurposeStatement (§1.3) ───────────────────────
 
-    fn parse_purpose_statement(&mut self) -> Result<PurposeStatementNode> {
+    fn parse_purpose_statement(&mut self) -> SyntaxResult<PurposeStatementNode> {
         let span = self.current_span();
         self.expect(Token::PurposeStatement)?;
         self.expect(Token::LBrace)?;
@@ -308,139 +775,181 @@ impl Parser {
         let mut intent_source: Option<SpannedValue<String>> = None;
         let mut confidence_level: Option<SpannedValue<f64>> = None;
 
-        while !matches!(self.peek(), Token::RBrace) {
-            let field_name = self.peek_identifier_name()?;
-            match field_name.as_str() {
-                "narrative" => {
-                    self.expect_field("narrative")?;
-                    narrative = Some(self.expect_string_literal()?);
-                }
-                "intent_source" => {
-                    self.expect_field("intent_source")?;
-                    intent_source = Some(self.expect_string_literal()?);
-                }
-                "confidence_level" => {
-                    self.expect_field("confidence_level")?;
-                    let cl = self.expect_float_literal()?;
-                    if cl.value < 0.0 || cl.value > 1.0 {
-                        return Err(Error::ValidationError(format!(
-                            "confidence_level must be in [0.0, 1.0], found {} at {}",
-                            cl.value, cl.span
-                        )));
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let field_name = p.peek_identifier_name()?;
+                match field_name.as_str() {
+                    "narrative" => {
+                        p.expect_field("narrative")?;
+                        narrative = Some(p.expect_string_literal()?);
+                    }
+                    "intent_source" => {
+                        p.expect_field("intent_source")?;
+                        intent_source = Some(p.expect_string_literal()?);
+                    }
+                    "confidence_level" => {
+                        p.expect_field("confidence_level")?;
+                        let cl = p.expect_float_literal()?;
+                        if cl.value < 0.0 || cl.value > 1.0 {
+                            p.report(ParseDiagnostic::validation(
+                                VALUE_OUT_OF_RANGE,
+                                format!(
+                                    "confidence_level must be in [0.0, 1.0], found {}",
+                                    cl.value
+                                ),
+                                &cl.span,
+                            ))?;
+                        }
+                        confidence_level = Some(cl);
+                    }
+                    other => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_FIELD,
+                            format!("Unknown field '{}' in PurposeStatement", other),
+                            &p.current_span(),
+                        ));
                     }
-                    confidence_level = Some(cl);
-                }
-                other => {
-                    return Err(Error::ParseError(format!(
-                        "Unknown field '{}' in PurposeStatement at {}",
-                        other,
-                        self.current_span()
-                    )));
                 }
-            }
+                Ok(())
+            })?;
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
         Ok(PurposeStatementNode {
-            narrative: narrative.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'narrative' in PurposeStatement at {}",
-                    span
-                ))
-            })?,
-            intent_source: intent_source.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'intent_source' in PurposeStatement at {}",
-                    span
-                ))
-            })?,
-            confidence_level: confidence_level.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'confidence_level' in PurposeStatement at {}",
-                    span
-                ))
-            })?,
-            span,
+            narrative: self.required(narrative, "narrative", "PurposeStatement", &span)?,
+            intent_source: self.required(
+                intent_source,
+                "intent_source",
+                "PurposeStatement",
+                &span,
+            )?,
+            confidence_level: self.required(
+                confidence_level,
+                "confidence_level",
+                "PurposeStatement",
+                &span,
+            )?,
+            span: self.span_from(&span),
+        })
+    }
+
+    // ── Types ─────────────────────────────────────────
+
+    /// Parse: `Types { Name: TypeExpression, ... }`
+    fn parse_types(&mut self) -> SyntaxResult<TypesNode> {
+        let span = self.current_span();
+        self.expect(Token::Types)?;
+        self.expect(Token::LBrace)?;
+
+        let mut definitions = Vec::new();
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let def_span = p.current_span();
+                let name_st = p.advance();
+                let name = match name_st.token {
+                    Token::Identifier(s) => SpannedValue::new(s, name_st.span),
+                    _ => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_TOKEN,
+                            format!("Expected type name, found {:?}", name_st.token),
+                            &name_st.span,
+                        ));
+                    }
+                };
+                p.expect(Token::Colon)?;
+                let type_expr = p.parse_type_expression()?;
+                definitions.push(TypeDefinitionNode {
+                    name,
+                    type_expr,
+                    span: p.span_from(&def_span),
+                });
+                Ok(())
+            })?;
+            self.optional_comma();
+        }
+
+        self.expect_or_report(Token::RBrace)?;
+
+        Ok(TypesNode {
+            definitions,
+            span: self.span_from(&span),
         })
     }
 
     // ── DataSemantics (§1.4) ──────────────────────────
 
-    fn parse_data_semantics(&mut self) -> Result<DataSemanticsNode> {
+    fn parse_data_semantics(&mut self) -> SyntaxResult<DataSemanticsNode> {
         let span = self.current_span();
         self.expect(Token::DataSemantics)?;
         self.expect(Token::LBrace)?;
 
         let mut state: Option<Vec<StateFieldNode>> = None;
-        let mut invariants: Option<Vec<SpannedValue<String>>> = None;
-
-        while !matches!(self.peek(), Token::RBrace) {
-            let field_name = self.peek_identifier_name()?;
-            match field_name.as_str() {
-                "state" => {
-                    self.expect_field("state")?;
-                    self.expect(Token::LBrace)?;
-                    state = Some(self.parse_state_fields()?);
-                    self.expect(Token::RBrace)?;
-                }
-                "invariants" => {
-                    self.expect_field("invariants")?;
-                    invariants = Some(self.parse_string_list()?);
-                }
-                other => {
-                    return Err(Error::ParseError(format!(
-                        "Unknown field '{}' in DataSemantics at {}",
-                        other,
-                        self.current_span()
-                    )));
+        let mut invariants: Option<Vec<ConditionNode>> = None;
+
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let field_name = p.peek_identifier_name()?;
+                match field_name.as_str() {
+                    "state" => {
+                        p.expect_field("state")?;
+                        p.expect(Token::LBrace)?;
+                        state = Some(p.parse_state_fields()?);
+                        p.expect(Token::RBrace)?;
+                    }
+                    "invariants" => {
+                        p.expect_field("invariants")?;
+                        invariants = Some(p.parse_condition_list()?);
+                    }
+                    other => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_FIELD,
+                            format!("Unknown field '{}' in DataSemantics", other),
+                            &p.current_span(),
+                        ));
+                    }
                 }
-            }
+                Ok(())
+            })?;
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
         Ok(DataSemanticsNode {
-            state: state.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'state' in DataSemantics at {}",
-                    span
-                ))
-            })?,
-            invariants: invariants.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'invariants' in DataSemantics at {}",
-                    span
-                ))
-            })?,
-            span,
+            state: self.required(state, "state", "DataSemantics", &span)?,
+            invariants: self.required(invariants, "invariants", "DataSemantics", &span)?,
+            span: self.span_from(&span),
         })
     }
 
     /// Parse state field list: `field1: Type, field2: Type = default, ...`
-    fn parse_state_fields(&mut self) -> Result<Vec<StateFieldNode>> {
+    fn parse_state_fields(&mut self) -> SyntaxResult<Vec<StateFieldNode>> {
         let mut fields = Vec::new();
-        while !matches!(self.peek(), Token::RBrace) {
-            fields.push(self.parse_state_field()?);
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                fields.push(p.parse_state_field()?);
+                Ok(())
+            })?;
             self.optional_comma();
         }
         Ok(fields)
     }
 
     /// Parse a single state field: `name: TypeExpression [= default]`
-    fn parse_state_field(&mut self) -> Result<StateFieldNode> {
+    fn parse_state_field(&mut self) -> SyntaxResult<StateFieldNode> {
         let span = self.current_span();
 
         let name_st = self.advance();
         let name = match name_st.token {
             Token::Identifier(s) => SpannedValue::new(s, name_st.span),
             _ => {
-                return Err(Error::ParseError(format!(
-                    "Expected field name, found {:?} at {}",
-                    name_st.token, name_st.span
-                )));
+                return Err(ParseDiagnostic::syntax(
+                    UNEXPECTED_TOKEN,
+                    format!("Expected field name, found {:?}", name_st.token),
+                    &name_st.span,
+                ));
             }
         };
 
@@ -458,13 +967,27 @@ impl Parser {
             name,
             type_expr,
             default_value,
-            span,
+            span: self.span_from(&span),
         })
     }
 
     // ── Type Expressions ──────────────────────────────
 
-    fn parse_type_expression(&mut self) -> Result<TypeExpression> {
+    fn parse_type_expression(&mut self) -> SyntaxResult<TypeExpression> {
+        let span = self.current_span();
+        let base = self.parse_base_type()?;
+        if !matches!(self.peek(), Token::LParen) {
+            return Ok(base);
+        }
+        let refinements = self.parse_refinements()?;
+        Ok(TypeExpression::Refined(
+            Box::new(base),
+            refinements,
+            self.span_from(&span),
+        ))
+    }
+
+    fn parse_base_type(&mut self) -> SyntaxResult<TypeExpression> {
         let span = self.current_span();
         match self.peek().clone() {
             Token::IntegerType => {
@@ -495,41 +1018,96 @@ impl Parser {
             Token::MapType => self.parse_map_type(span),
             Token::ObjectType => self.parse_object_type(span),
             Token::EnumType => self.parse_enum_type(span),
-            _ => Err(Error::ParseError(format!(
-                "Expected type expression, found {:?} at {}",
-                self.peek(),
-                span
-            ))),
+            Token::OptionalType => self.parse_optional_type(span),
+            Token::Identifier(name) => {
+                self.advance();
+                Ok(TypeExpression::Named(name, span))
+            }
+            _ => Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected type expression, found {:?}", self.peek()),
+                &span,
+            )),
         }
     }
 
-    fn parse_array_type(&mut self, span: Span) -> Result<TypeExpression> {
+    fn parse_array_type(&mut self, span: Span) -> SyntaxResult<TypeExpression> {
         self.advance(); // consume Array
         self.expect(Token::LAngle)?;
         let inner = self.parse_type_expression()?;
         self.expect(Token::RAngle)?;
-        Ok(TypeExpression::Array(Box::new(inner), span))
+        Ok(TypeExpression::Array(
+            Box::new(inner),
+            self.span_from(&span),
+        ))
     }
 
-    fn parse_map_type(&mut self, span: Span) -> Result<TypeExpression> {
+    fn parse_map_type(&mut self, span: Span) -> SyntaxResult<TypeExpression> {
         self.advance(); // consume Map
         self.expect(Token::LAngle)?;
         let key = self.parse_type_expression()?;
         self.expect(Token::Comma)?;
         let value = self.parse_type_expression()?;
         self.expect(Token::RAngle)?;
-        Ok(TypeExpression::Map(Box::new(key), Box::new(value), span))
+        Ok(TypeExpression::Map(
+            Box::new(key),
+            Box::new(value),
+            self.span_from(&span),
+        ))
     }
 
-    fn parse_object_type(&mut self, span: Span) -> Result<TypeExpression> {
+    fn parse_optional_type(&mut self, span: Span) -> SyntaxResult<TypeExpression> {
+        self.advance(); // consume Optional
+        self.expect(Token::LAngle)?;
+        let inner = self.parse_type_expression()?;
+        self.expect(Token::RAngle)?;
+        Ok(TypeExpression::Optional(
+            Box::new(inner),
+            self.span_from(&span),
+        ))
+    }
+
+    /// `( name: literal, ... )` after a base type
+    fn parse_refinements(&mut self) -> SyntaxResult<Vec<RefinementNode>> {
+        self.expect(Token::LParen)?;
+        let mut refinements = Vec::new();
+        while !matches!(self.peek(), Token::RParen) {
+            let span = self.current_span();
+            let name = match self.peek().clone() {
+                Token::Identifier(name) => SpannedValue::new(name, self.advance().span),
+                other => {
+                    return Err(ParseDiagnostic::syntax(
+                        UNEXPECTED_TOKEN,
+                        format!("Expected refinement name, found {:?}", other),
+                        &span,
+                    ));
+                }
+            };
+            self.expect(Token::Colon)?;
+            let value = self.parse_literal_value()?;
+            refinements.push(RefinementNode {
+                name,
+                value,
+                span: self.span_from(&span),
+            });
+            if !matches!(self.peek(), Token::Comma) {
+                break;
+            }
+            self.advance(); // consume comma
+        }
+        self.expect(Token::RParen)?;
+        Ok(refinements)
+    }
+
+    fn parse_object_type(&mut self, span: Span) -> SyntaxResult<TypeExpression> {
         self.advance(); // consume Object
         self.expect(Token::LBrace)?;
         let fields = self.parse_state_fields()?;
-        self.expect(Token::RBrace)?;
-        Ok(TypeExpression::Object(fields, span))
+        self.expect_or_report(Token::RBrace)?;
+        Ok(TypeExpression::Object(fields, self.span_from(&span)))
     }
 
-    fn parse_enum_type(&mut self, span: Span) -> Result<TypeExpression> {
+    fn parse_enum_type(&mut self, span: Span) -> SyntaxResult<TypeExpression> {
         self.advance(); // consume Enum
         self.expect(Token::LBracket)?;
 
@@ -546,12 +1124,12 @@ impl Parser {
         }
 
         self.expect(Token::RBracket)?;
-        Ok(TypeExpression::Enum(variants, span))
+        Ok(TypeExpression::Enum(variants, self.span_from(&span)))
     }
 
     // ── Literal Values ────────────────────────────────
 
-    fn parse_literal_value(&mut self) -> Result<LiteralValue> {
+    fn parse_literal_value(&mut self) -> SyntaxResult<LiteralValue> {
         let span = self.current_span();
         match self.peek().clone() {
             Token::StringLiteral(_) => {
@@ -586,6 +1164,10 @@ impl Parser {
                     unreachable!()
                 }
             }
+            Token::NullLiteral => {
+                self.advance();
+                Ok(LiteralValue::Null(span))
+            }
             Token::LBracket => {
                 self.advance(); // consume [
                 let mut items = Vec::new();
@@ -600,19 +1182,19 @@ impl Parser {
                     }
                 }
                 self.expect(Token::RBracket)?;
-                Ok(LiteralValue::Array(items, span))
+                Ok(LiteralValue::Array(items, self.span_from(&span)))
             }
-            _ => Err(Error::ParseError(format!(
-                "Expected literal value, found {:?} at {}",
-                self.peek(),
-                span
-            ))),
+            _ => Err(ParseDiagnostic::syntax(
+                UNEXPECTED_TOKEN,
+                format!("Expected literal value, found {:?}", self.peek()),
+                &span,
+            )),
         }
     }
 
     // ── BehavioralSemantics (§1.5) ────────────────────
 
-    fn parse_behavioral_semantics(&mut self) -> Result<BehavioralSemanticsNode> {
+    fn parse_behavioral_semantics(&mut self) -> SyntaxResult<BehavioralSemanticsNode> {
         let span = self.current_span();
         self.expect(Token::BehavioralSemantics)?;
         self.expect(Token::LBrace)?;
@@ -629,108 +1211,84 @@ impl Parser {
         self.expect(Token::RBracket)?;
         self.optional_comma();
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
-        Ok(BehavioralSemanticsNode { operations, span })
+        Ok(BehavioralSemanticsNode {
+            operations,
+            span: self.span_from(&span),
+        })
     }
 
-    fn parse_operation(&mut self) -> Result<OperationNode> {
+    fn parse_operation(&mut self) -> SyntaxResult<OperationNode> {
         let span = self.current_span();
         self.expect(Token::LBrace)?;
 
         let mut name: Option<SpannedValue<String>> = None;
-        let mut precondition: Option<SpannedValue<String>> = None;
+        let mut precondition: Option<ConditionNode> = None;
         let mut parameters: Option<Vec<StateFieldNode>> = None;
-        let mut postcondition: Option<SpannedValue<String>> = None;
+        let mut postcondition: Option<ConditionNode> = None;
         let mut side_effects: Option<Vec<SpannedValue<String>>> = None;
         let mut idempotence: Option<SpannedValue<String>> = None;
 
-        while !matches!(self.peek(), Token::RBrace) {
-            let field_name = self.peek_identifier_name()?;
-            match field_name.as_str() {
-                "name" => {
-                    self.expect_field("name")?;
-                    name = Some(self.expect_string_literal()?);
-                }
-                "precondition" => {
-                    self.expect_field("precondition")?;
-                    precondition = Some(self.expect_string_literal()?);
-                }
-                "parameters" => {
-                    self.expect_field("parameters")?;
-                    self.expect(Token::LBrace)?;
-                    parameters = Some(self.parse_state_fields()?);
-                    self.expect(Token::RBrace)?;
-                }
-                "postcondition" => {
-                    self.expect_field("postcondition")?;
-                    postcondition = Some(self.expect_string_literal()?);
-                }
-                "side_effects" => {
-                    self.expect_field("side_effects")?;
-                    side_effects = Some(self.parse_string_list()?);
-                }
-                "idempotence" => {
-                    self.expect_field("idempotence")?;
-                    idempotence = Some(self.expect_string_literal()?);
-                }
-                other => {
-                    return Err(Error::ParseError(format!(
-                        "Unknown field '{}' in operation at {}",
-                        other,
-                        self.current_span()
-                    )));
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let field_name = p.peek_identifier_name()?;
+                match field_name.as_str() {
+                    "name" => {
+                        p.expect_field("name")?;
+                        name = Some(p.expect_string_literal()?);
+                    }
+                    "precondition" => {
+                        p.expect_field("precondition")?;
+                        precondition = Some(p.expect_condition()?);
+                    }
+                    "parameters" => {
+                        p.expect_field("parameters")?;
+                        p.expect(Token::LBrace)?;
+                        parameters = Some(p.parse_state_fields()?);
+                        p.expect(Token::RBrace)?;
+                    }
+                    "postcondition" => {
+                        p.expect_field("postcondition")?;
+                        postcondition = Some(p.expect_condition()?);
+                    }
+                    "side_effects" => {
+                        p.expect_field("side_effects")?;
+                        side_effects = Some(p.parse_string_list()?);
+                    }
+                    "idempotence" => {
+                        p.expect_field("idempotence")?;
+                        idempotence = Some(p.expect_string_literal()?);
+                    }
+                    other => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_FIELD,
+                            format!("Unknown field '{}' in operation", other),
+                            &p.current_span(),
+                        ));
+                    }
                 }
-            }
+                Ok(())
+            })?;
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
         Ok(OperationNode {
-            name: name.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'name' in operation at {}",
-                    span
-                ))
-            })?,
-            precondition: precondition.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'precondition' in operation at {}",
-                    span
-                ))
-            })?,
-            parameters: parameters.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'parameters' in operation at {}",
-                    span
-                ))
-            })?,
-            postcondition: postcondition.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'postcondition' in operation at {}",
-                    span
-                ))
-            })?,
-            side_effects: side_effects.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'side_effects' in operation at {}",
-                    span
-                ))
-            })?,
-            idempotence: idempotence.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'idempotence' in operation at {}",
-                    span
-                ))
-            })?,
-            span,
+            name: self.required(name, "name", "operation", &span)?,
+            precondition: self.required(precondition, "precondition", "operation", &span)?,
+            parameters: self.required(parameters, "parameters", "operation", &span)?,
+            postcondition: self.required(postcondition, "postcondition", "operation", &span)?,
+            side_effects: self.required(side_effects, "side_effects", "operation", &span)?,
+            idempotence: self.required(idempotence, "idempotence", "operation", &span)?,
+            span: self.span_from(&span),
         })
     }
 
     // ── ExecutionConstraints (§1.6) ───────────────────
 
-    fn parse_execution_constraints(&mut self) -> Result<ExecutionConstraintsNode> {
+    fn parse_execution_constraints(&mut self) -> SyntaxResult<ExecutionConstraintsNode> {
         let span = self.current_span();
         self.expect(Token::ExecutionConstraints)?;
         self.expect(Token::LBrace)?;
@@ -740,68 +1298,71 @@ impl Parser {
         let mut external_permissions: Option<Vec<SpannedValue<String>>> = None;
         let mut sandbox_mode: Option<SpannedValue<String>> = None;
 
-        while !matches!(self.peek(), Token::RBrace) {
-            let field_name = self.peek_identifier_name()?;
-            match field_name.as_str() {
-                "trigger_types" => {
-                    self.expect_field("trigger_types")?;
-                    trigger_types = Some(self.parse_string_list()?);
-                }
-                "resource_limits" => {
-                    self.expect_field("resource_limits")?;
-                    resource_limits = Some(self.parse_resource_limits()?);
-                }
-                "external_permissions" => {
-                    self.expect_field("external_permissions")?;
-                    external_permissions = Some(self.parse_string_list()?);
-                }
-                "sandbox_mode" => {
-                    self.expect_field("sandbox_mode")?;
-                    sandbox_mode = Some(self.expect_string_literal()?);
-                }
-                other => {
-                    return Err(Error::ParseError(format!(
-                        "Unknown field '{}' in ExecutionConstraints at {}",
-                        other,
-                        self.current_span()
-                    )));
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let field_name = p.peek_identifier_name()?;
+                match field_name.as_str() {
+                    "trigger_types" => {
+                        p.expect_field("trigger_types")?;
+                        trigger_types = Some(p.parse_string_list()?);
+                    }
+                    "resource_limits" => {
+                        p.expect_field("resource_limits")?;
+                        resource_limits = Some(p.parse_resource_limits()?);
+                    }
+                    "external_permissions" => {
+                        p.expect_field("external_permissions")?;
+                        external_permissions = Some(p.parse_string_list()?);
+                    }
+                    "sandbox_mode" => {
+                        p.expect_field("sandbox_mode")?;
+                        sandbox_mode = Some(p.expect_string_literal()?);
+                    }
+                    other => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_FIELD,
+                            format!("Unknown field '{}' in ExecutionConstraints", other),
+                            &p.current_span(),
+                        ));
+                    }
                 }
-            }
+                Ok(())
+            })?;
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
         Ok(ExecutionConstraintsNode {
-            trigger_types: trigger_types.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'trigger_types' in ExecutionConstraints at {}",
-                    span
-                ))
-            })?,
-            resource_limits: resource_limits.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'resource_limits' in ExecutionConstraints at {}",
-                    span
-                ))
-            })?,
-            external_permissions: external_permissions.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'external_permissions' in ExecutionConstraints at {}",
-                    span
-                ))
-            })?,
-            sandbox_mode: sandbox_mode.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'sandbox_mode' in ExecutionConstraints at {}",
-                    span
-                ))
-            })?,
-            span,
+            trigger_types: self.required(
+                trigger_types,
+                "trigger_types",
+                "ExecutionConstraints",
+                &span,
+            )?,
+            resource_limits: self.required(
+                resource_limits,
+                "resource_limits",
+                "ExecutionConstraints",
+                &span,
+            )?,
+            external_permissions: self.required(
+                external_permissions,
+                "external_permissions",
+                "ExecutionConstraints",
+                &span,
+            )?,
+            sandbox_mode: self.required(
+                sandbox_mode,
+                "sandbox_mode",
+                "ExecutionConstraints",
+                &span,
+            )?,
+            span: self.span_from(&span),
         })
     }
 
-    fn parse_resource_limits(&mut self) -> Result<ResourceLimitsNode> {
+    fn parse_resource_limits(&mut self) -> SyntaxResult<ResourceLimitsNode> {
         let span = self.current_span();
         self.expect(Token::LBrace)?;
 
@@ -809,60 +1370,63 @@ impl Parser {
         let mut computation_timeout_ms: Option<SpannedValue<i64>> = None;
         let mut max_state_size_bytes: Option<SpannedValue<i64>> = None;
 
-        while !matches!(self.peek(), Token::RBrace) {
-            let field_name = self.peek_identifier_name()?;
-            match field_name.as_str() {
-                "max_memory_bytes" => {
-                    self.expect_field("max_memory_bytes")?;
-                    max_memory_bytes = Some(self.expect_integer_literal()?);
-                }
-                "computation_timeout_ms" => {
-                    self.expect_field("computation_timeout_ms")?;
-                    computation_timeout_ms = Some(self.expect_integer_literal()?);
-                }
-                "max_state_size_bytes" => {
-                    self.expect_field("max_state_size_bytes")?;
-                    max_state_size_bytes = Some(self.expect_integer_literal()?);
-                }
-                other => {
-                    return Err(Error::ParseError(format!(
-                        "Unknown field '{}' in resource_limits at {}",
-                        other,
-                        self.current_span()
-                    )));
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let field_name = p.peek_identifier_name()?;
+                match field_name.as_str() {
+                    "max_memory_bytes" => {
+                        p.expect_field("max_memory_bytes")?;
+                        max_memory_bytes = Some(p.expect_integer_literal()?);
+                    }
+                    "computation_timeout_ms" => {
+                        p.expect_field("computation_timeout_ms")?;
+                        computation_timeout_ms = Some(p.expect_integer_literal()?);
+                    }
+                    "max_state_size_bytes" => {
+                        p.expect_field("max_state_size_bytes")?;
+                        max_state_size_bytes = Some(p.expect_integer_literal()?);
+                    }
+                    other => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_FIELD,
+                            format!("Unknown field '{}' in resource_limits", other),
+                            &p.current_span(),
+                        ));
+                    }
                 }
-            }
+                Ok(())
+            })?;
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
         Ok(ResourceLimitsNode {
-            max_memory_bytes: max_memory_bytes.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'max_memory_bytes' in resource_limits at {}",
-                    span
-                ))
-            })?,
-            computation_timeout_ms: computation_timeout_ms.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'computation_timeout_ms' in resource_limits at {}",
-                    span
-                ))
-            })?,
-            max_state_size_bytes: max_state_size_bytes.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'max_state_size_bytes' in resource_limits at {}",
-                    span
-                ))
-            })?,
-            span,
+            max_memory_bytes: self.required(
+                max_memory_bytes,
+                "max_memory_bytes",
+                "resource_limits",
+                &span,
+            )?,
+            computation_timeout_ms: self.required(
+                computation_timeout_ms,
+                "computation_timeout_ms",
+                "resource_limits",
+                &span,
+            )?,
+            max_state_size_bytes: self.required(
+                max_state_size_bytes,
+                "max_state_size_bytes",
+                "resource_limits",
+                &span,
+            )?,
+            span: self.span_from(&span),
         })
     }
 
     // ── HumanMachineContract (§1.7) ───────────────────
 
-    fn parse_human_machine_contract(&mut self) -> Result<HumanMachineContractNode> {
+    fn parse_human_machine_contract(&mut self) -> SyntaxResult<HumanMachineContractNode> {
         let span = self.current_span();
         self.expect(Token::HumanMachineContract)?;
         self.expect(Token::LBrace)?;
@@ -871,60 +1435,63 @@ impl Parser {
         let mut system_refusals: Option<Vec<SpannedValue<String>>> = None;
         let mut user_obligations: Option<Vec<SpannedValue<String>>> = None;
 
-        while !matches!(self.peek(), Token::RBrace) {
-            let field_name = self.peek_identifier_name()?;
-            match field_name.as_str() {
-                "system_commitments" => {
-                    self.expect_field("system_commitments")?;
-                    system_commitments = Some(self.parse_string_list()?);
-                }
-                "system_refusals" => {
-                    self.expect_field("system_refusals")?;
-                    system_refusals = Some(self.parse_string_list()?);
-                }
-                "user_obligations" => {
-                    self.expect_field("user_obligations")?;
-                    user_obligations = Some(self.parse_string_list()?);
-                }
-                other => {
-                    return Err(Error::ParseError(format!(
-                        "Unknown field '{}' in HumanMachineContract at {}",
-                        other,
-                        self.current_span()
-                    )));
+        while !self.at_block_end() {
+            self.parse_block_field(|p| {
+                let field_name = p.peek_identifier_name()?;
+                match field_name.as_str() {
+                    "system_commitments" => {
+                        p.expect_field("system_commitments")?;
+                        system_commitments = Some(p.parse_string_list()?);
+                    }
+                    "system_refusals" => {
+                        p.expect_field("system_refusals")?;
+                        system_refusals = Some(p.parse_string_list()?);
+                    }
+                    "user_obligations" => {
+                        p.expect_field("user_obligations")?;
+                        user_obligations = Some(p.parse_string_list()?);
+                    }
+                    other => {
+                        return Err(ParseDiagnostic::syntax(
+                            UNEXPECTED_FIELD,
+                            format!("Unknown field '{}' in HumanMachineContract", other),
+                            &p.current_span(),
+                        ));
+                    }
                 }
-            }
+                Ok(())
+            })?;
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
         Ok(HumanMachineContractNode {
-            system_commitments: system_commitments.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'system_commitments' in HumanMachineContract at {}",
-                    span
-                ))
-            })?,
-            system_refusals: system_refusals.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'system_refusals' in HumanMachineContract at {}",
-                    span
-                ))
-            })?,
-            user_obligations: user_obligations.ok_or_else(|| {
-                Error::ParseError(format!(
-                    "Missing required field 'user_obligations' in HumanMachineContract at {}",
-                    span
-                ))
-            })?,
-            span,
+            system_commitments: self.required(
+                system_commitments,
+                "system_commitments",
+                "HumanMachineContract",
+                &span,
+            )?,
+            system_refusals: self.required(
+                system_refusals,
+                "system_refusals",
+                "HumanMachineContract",
+                &span,
+            )?,
+            user_obligations: self.required(
+                user_obligations,
+                "user_obligations",
+                "HumanMachineContract",
+                &span,
+            )?,
+            span: self.span_from(&span),
         })
     }
 
     // ── Extensions (§5) ───────────────────────────────
 
-    fn parse_extensions(&mut self) -> Result<ExtensionsNode> {
+    fn parse_extensions(&mut self) -> SyntaxResult<ExtensionsNode> {
         let span = self.current_span();
         self.expect(Token::Extensions)?;
         self.expect(Token::LBrace)?;
@@ -934,22 +1501,26 @@ impl Parser {
             systems.push(self.parse_system_extension()?);
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
-        Ok(ExtensionsNode { systems, span })
+        Ok(ExtensionsNode {
+            systems,
+            span: self.span_from(&span),
+        })
     }
 
-    fn parse_system_extension(&mut self) -> Result<SystemExtensionNode> {
+    fn parse_system_extension(&mut self) -> SyntaxResult<SystemExtensionNode> {
         let span = self.current_span();
 
         let name_st = self.advance();
         let name = match name_st.token {
             Token::Identifier(s) => SpannedValue::new(s, name_st.span),
             _ => {
-                return Err(Error::ParseError(format!(
-                    "Expected system extension name, found {:?} at {}",
-                    name_st.token, name_st.span
-                )));
+                return Err(ParseDiagnostic::syntax(
+                    UNEXPECTED_TOKEN,
+                    format!("Expected system extension name, found {:?}", name_st.token),
+                    &name_st.span,
+                ));
             }
         };
 
@@ -961,35 +1532,44 @@ impl Parser {
             self.optional_comma();
         }
 
-        self.expect(Token::RBrace)?;
+        self.expect_or_report(Token::RBrace)?;
 
-        Ok(SystemExtensionNode { name, fields, span })
+        Ok(SystemExtensionNode {
+            name,
+            fields,
+            span: self.span_from(&span),
+        })
     }
 
-    fn parse_custom_field(&mut self) -> Result<CustomFieldNode> {
+    fn parse_custom_field(&mut self) -> SyntaxResult<CustomFieldNode> {
         let span = self.current_span();
 
         let name_st = self.advance();
         let name = match name_st.token {
             Token::Identifier(s) => SpannedValue::new(s, name_st.span),
             _ => {
-                return Err(Error::ParseError(format!(
-                    "Expected field name, found {:?} at {}",
-                    name_st.token, name_st.span
-                )));
+                return Err(ParseDiagnostic::syntax(
+                    UNEXPECTED_TOKEN,
+                    format!("Expected field name, found {:?}", name_st.token),
+                    &name_st.span,
+                ));
             }
         };
 
         self.expect(Token::Colon)?;
         let value = self.parse_literal_value()?;
 
-        Ok(CustomFieldNode { name, value, span })
+        Ok(CustomFieldNode {
+            name,
+            value,
+            span: self.span_from(&span),
+        })
     }
 
     // ── Helpers ────────────────────────────────────────
 
     /// Parse: `[ "str1", "str2", ... ]`
-    fn parse_string_list(&mut self) -> Result<Vec<SpannedValue<String>>> {
+    fn parse_string_list(&mut self) -> SyntaxResult<Vec<SpannedValue<String>>> {
         self.expect(Token::LBracket)?;
 
         let mut items = Vec::new();
@@ -1007,6 +1587,40 @@ impl Parser {
         self.expect(Token::RBracket)?;
         Ok(items)
     }
+
+    /// Parse: `[ "cond1", "cond2", ... ]`
+    fn parse_condition_list(&mut self) -> SyntaxResult<Vec<ConditionNode>> {
+        Ok(self
+            .parse_string_list()?
+            .into_iter()
+            .map(|literal| self.condition_node(literal))
+            .collect())
+    }
+
+    /// Expect a string literal holding a condition
+    fn expect_condition(&mut self) -> SyntaxResult<ConditionNode> {
+        let literal = self.expect_string_literal()?;
+        Ok(self.condition_node(literal))
+    }
+
+    /// Parse condition text into a `ConditionNode`, with expression spans
+    /// in the source even where the literal has escape sequences.
+    ///
+    /// Text outside the condition grammar is not a syntax error — it is kept
+    /// as an opaque condition with `expr: None`.
+    fn condition_node(&self, literal: SpannedValue<String>) -> ConditionNode {
+        let mut node = ConditionNode {
+            text: literal.value,
+            expr: None,
+            span: literal.span,
+        };
+        let raw = self
+            .source
+            .get(node.span.offset + 1..node.span.end.saturating_sub(1))
+            .unwrap_or(&node.text);
+        node.expr = condition::parse_condition_in_source(&node.text, raw, &node.text_start()).ok();
+        node
+    }
 }
 
 // ── Lowering: AST → semantic Contract ──────────────────────
@@ -1027,8 +1641,11 @@ pub fn lower_contract(node: &ContractNode) -> Result<crate::Contract> {
             intent_source: node.purpose_statement.intent_source.value.clone(),
             confidence_level: node.purpose_statement.confidence_level.value,
         },
-        data_semantics: lower_data_semantics(&node.data_semantics),
-        behavioral_semantics: lower_behavioral_semantics(&node.behavioral_semantics),
+        data_semantics: lower_data_semantics(&node.data_semantics, node.type_definitions()),
+        behavioral_semantics: lower_behavioral_semantics(
+            &node.behavioral_semantics,
+            node.type_definitions(),
+        ),
         execution_constraints: crate::ExecutionConstraints {
             trigger_types: node
                 .execution_constraints
@@ -1084,61 +1701,54 @@ pub fn lower_contract(node: &ContractNode) -> Result<crate::Contract> {
     })
 }
 
-fn lower_data_semantics(node: &DataSemanticsNode) -> crate::DataSemantics {
-    let mut state = serde_json::Map::new();
-    for field in &node.state {
-        let type_str = field.type_expr.to_string();
-        let value = if let Some(ref default) = field.default_value {
-            // Store as {"type": "...", "default": value} to preserve defaults
-            let default_json = lower_literal(default);
-            serde_json::json!({
-                "type": type_str,
-                "default": default_json
-            })
-        } else {
-            serde_json::Value::String(type_str)
-        };
-        state.insert(field.name.value.clone(), value);
-    }
+/// Named types are lowered to the type they stand for, so the executor
+/// sees `Integer` rather than an alias such as `Amount`
+fn lower_data_semantics(
+    node: &DataSemanticsNode,
+    types: &[TypeDefinitionNode],
+) -> crate::DataSemantics {
     crate::DataSemantics {
-        state: serde_json::Value::Object(state),
-        invariants: node.invariants.iter().map(|s| s.value.clone()).collect(),
+        state: node
+            .state
+            .iter()
+            .map(|field| Field::from_node(field, types))
+            .collect(),
+        invariants: node.invariants.iter().map(crate::Condition::from).collect(),
     }
 }
 
-fn lower_literal(lit: &ast::LiteralValue) -> serde_json::Value {
+/// Convert a literal to JSON, as stored for defaults and refinements
+pub fn lower_literal(lit: &ast::LiteralValue) -> serde_json::Value {
     match lit {
         ast::LiteralValue::String(s, _) => serde_json::Value::String(s.clone()),
         ast::LiteralValue::Integer(i, _) => serde_json::json!(*i),
         ast::LiteralValue::Float(f, _) => serde_json::json!(*f),
         ast::LiteralValue::Boolean(b, _) => serde_json::Value::Bool(*b),
+        ast::LiteralValue::Null(_) => serde_json::Value::Null,
         ast::LiteralValue::Array(arr, _) => {
             serde_json::Value::Array(arr.iter().map(lower_literal).collect())
         }
     }
 }
 
-fn lower_behavioral_semantics(node: &BehavioralSemanticsNode) -> crate::BehavioralSemantics {
+fn lower_behavioral_semantics(
+    node: &BehavioralSemanticsNode,
+    types: &[TypeDefinitionNode],
+) -> crate::BehavioralSemantics {
     let operations = node
         .operations
         .iter()
-        .map(|op| {
-            let mut params = serde_json::Map::new();
-            for p in &op.parameters {
-                params.insert(
-                    p.name.value.clone(),
-                    serde_json::Value::String(p.type_expr.to_string()),
-                );
-            }
-
-            crate::Operation {
-                name: op.name.value.clone(),
-                precondition: op.precondition.value.clone(),
-                parameters: serde_json::Value::Object(params),
-                postcondition: op.postcondition.value.clone(),
-                side_effects: op.side_effects.iter().map(|s| s.value.clone()).collect(),
-                idempotence: op.idempotence.value.clone(),
-            }
+        .map(|op| crate::Operation {
+            name: op.name.value.clone(),
+            precondition: crate::Condition::from(&op.precondition),
+            parameters: op
+                .parameters
+                .iter()
+                .map(|p| Field::from_node(p, types))
+                .collect(),
+            postcondition: crate::Condition::from(&op.postcondition),
+            side_effects: op.side_effects.iter().map(|s| s.value.clone()).collect(),
+            idempotence: op.idempotence.value.clone(),
         })
         .collect();
 
@@ -1148,11 +1758,19 @@ fn lower_behavioral_semantics(node: &BehavioralSemanticsNode) -> crate::Behavior
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::schema::Type;
     use std::fs;
     use std::path::Path;
 
     // ── Helper ─────────────────────────────────────────
 
+    fn field<'a>(fields: &'a [Field], name: &str) -> &'a Field {
+        fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("no field '{}'", name))
+    }
+
     fn parse_valid(input: &str) -> ContractNode {
         parse(input).unwrap_or_else(|e| panic!("Expected successful parse, got: {}", e))
     }
@@ -1383,6 +2001,50 @@ mod tests {
         assert!(matches!(&state[3].type_expr, TypeExpression::Map(_, _, _)));
     }
 
+    #[test]
+    fn test_parse_spans_cover_source_ranges() {
+        let input = MINIMAL_CONTRACT.replace(
+            "value: String",
+            "value: String,\n      tags: Map<String, Array<Integer>> = []",
+        );
+        let ast = parse_valid(&input);
+        let text = |span: &Span| &input[span.range()];
+
+        assert_eq!(text(&ast.identity.stable_id.span), "\"ic-test-001\"");
+        assert_eq!(text(&ast.identity.version.span), "1");
+        let tags = &ast.data_semantics.state[1];
+        assert_eq!(text(&tags.name.span), "tags");
+        assert_eq!(text(tags.type_expr.span()), "Map<String, Array<Integer>>");
+        assert_eq!(text(tags.default_value.as_ref().unwrap().span()), "[]");
+        assert_eq!(text(&tags.span), "tags: Map<String, Array<Integer>> = []");
+        assert!(text(&ast.identity.span).starts_with("Identity {"));
+        assert!(text(&ast.identity.span).ends_with('}'));
+        assert_eq!(text(&ast.span), input.as_str());
+    }
+
+    #[test]
+    fn test_parse_condition_spans_after_escapes() {
+        let input = MINIMAL_CONTRACT.replace(
+            "invariants: []",
+            r#"invariants: ["value != \"a\\\"b\" and len(value) > 0"]"#,
+        );
+        let ast = parse_valid(&input);
+        let invariant = &ast.data_semantics.invariants[0];
+        assert_eq!(invariant.text, r#"value != "a\"b" and len(value) > 0"#);
+        let Some(Expr::Binary(BinaryOp::And, left, right, _)) = &invariant.expr else {
+            panic!("expected a conjunction: {:?}", invariant.expr);
+        };
+        let text = |expr: &Expr| &input[expr.span().range()];
+        let Expr::Binary(_, _, literal, _) = left.as_ref() else {
+            panic!("expected a comparison: {:?}", left);
+        };
+        assert_eq!(text(literal), r#"\"a\\\"b\""#);
+        let Expr::Binary(_, call, _, _) = right.as_ref() else {
+            panic!("expected a comparison: {:?}", right);
+        };
+        assert_eq!(text(call), "len(value)");
+    }
+
     // ── Operations ─────────────────────────────────────
 
     #[test]
@@ -1548,6 +2210,29 @@ mod tests {
         assert!(err.contains("confidence_level"), "Error: {}", err);
     }
 
+    #[test]
+    fn test_parse_invalid_created_timestamp() {
+        let input = r#"Contract {
+  Identity {
+    stable_id: "ic-test-001",
+    version: 1,
+    created_timestamp: "2026-02-30T00:00:00Z",
+    owner: "test",
+    semantic_hash: "0000000000000000"
+  }
+  PurposeStatement {
+    narrative: "Invalid timestamp",
+    intent_source: "test",
+    confidence_level: 1.0
+  }
+}"#;
+        let err = parse_err(input);
+        assert!(err.contains("RFC 3339"), "Error: {}", err);
+        let result = parse_with_diagnostics(input);
+        assert_eq!(result.diagnostics[0].code, INVALID_LITERAL);
+        assert_eq!(result.diagnostics[0].kind, ParseDiagnosticKind::Validation);
+    }
+
     #[test]
     fn test_parse_unknown_section() {
         let input = r#"Contract {
@@ -1616,6 +2301,191 @@ mod tests {
         assert!(ast.extensions.is_some());
     }
 
+    #[test]
+    fn test_conformance_valid_types_section() {
+        let input = read_fixture("conformance/valid/types-section.icl");
+        let ast = parse_valid(&input);
+        let names: Vec<&str> = ast
+            .type_definitions()
+            .iter()
+            .map(|d| d.name.value.as_str())
+            .collect();
+        assert_eq!(names, vec!["Amount", "Status", "Entry"]);
+
+        let entries = &ast.data_semantics.state[0];
+        assert_eq!(entries.type_expr.to_string(), "Array<Entry>");
+        if let TypeExpression::Array(inner, _) = &entries.type_expr {
+            assert!(matches!(inner.as_ref(), TypeExpression::Named(n, _) if n == "Entry"));
+            assert_eq!(&input[inner.span().range()], "Entry");
+        } else {
+            panic!("Expected Array type");
+        }
+
+        // Lowering replaces aliases with the type they stand for
+        let contract = lower_contract(&ast).unwrap();
+        assert_eq!(
+            field(&contract.data_semantics.state, "total").ty,
+            Type::Integer
+        );
+        assert_eq!(
+            field(
+                &contract.behavioral_semantics.operations[0].parameters,
+                "amount"
+            )
+            .ty,
+            Type::Integer
+        );
+        let entries = field(&contract.data_semantics.state, "entries");
+        assert!(
+            matches!(&entries.ty, Type::Array { items } if matches!(**items, Type::Object { .. })),
+            "{:?}",
+            entries.ty
+        );
+    }
+
+    #[test]
+    fn test_conformance_valid_type_optional() {
+        let input = read_fixture("conformance/valid/type-optional.icl");
+        let ast = parse_valid(&input);
+        let nickname = &ast.data_semantics.state[0];
+        assert_eq!(nickname.type_expr.to_string(), "Optional<String>");
+        assert!(matches!(
+            nickname.default_value,
+            Some(LiteralValue::Null(_))
+        ));
+        assert_eq!(
+            &input[nickname.type_expr.span().range()],
+            "Optional<String>"
+        );
+
+        let contract = lower_contract(&ast).unwrap();
+        let state = &contract.data_semantics.state;
+        assert_eq!(
+            field(state, "nickname").default,
+            Some(serde_json::Value::Null)
+        );
+        assert_eq!(
+            field(state, "expires_at").ty.to_string(),
+            "Optional<ISO8601>"
+        );
+        assert_eq!(field(state, "expires_at").default, None);
+        assert_eq!(
+            field(state, "aliases").default,
+            Some(serde_json::json!(["primary", null]))
+        );
+
+        let err = parse_err(&input.replace("Optional<ISO8601>", "Optional ISO8601"));
+        assert!(err.contains("Expected LAngle"), "{}", err);
+    }
+
+    #[test]
+    fn test_conformance_valid_type_refinements() {
+        let input = read_fixture("conformance/valid/type-refinements.icl");
+        let ast = parse_valid(&input);
+        let code = &ast.data_semantics.state[2];
+        let TypeExpression::Refined(base, refinements, span) = &code.type_expr else {
+            panic!("expected refined type, got {:?}", code.type_expr);
+        };
+        assert_eq!(base.to_string(), "String");
+        assert_eq!(refinements.len(), 3);
+        assert_eq!(refinements[2].name.value, "pattern");
+        assert_eq!(
+            &input[span.range()],
+            "String(min_len: 3, max_len: 8, pattern: \"^[A-Z]+$\")"
+        );
+        assert_eq!(
+            ast.data_semantics.state[1].type_expr.to_string(),
+            "Float(min: -100.5)"
+        );
+
+        // Aliases are resolved and their refinements merged
+        let contract = lower_contract(&ast).unwrap();
+        let state = &contract.data_semantics.state;
+        assert_eq!(
+            field(state, "progress").ty.to_string(),
+            "Integer(min: 0, max: 100)"
+        );
+        assert_eq!(
+            field(
+                &contract.behavioral_semantics.operations[0].parameters,
+                "progress"
+            )
+            .ty
+            .to_string(),
+            "Integer(min: 0, max: 100, max: 100)"
+        );
+        assert_eq!(
+            field(state, "tags").ty.to_string(),
+            "Array<String(max_len: 16)>(max_items: 4)"
+        );
+
+        let err = parse_err(&input.replace("Float(min: -100.5)", "Float(min -100.5)"));
+        assert!(err.contains("Expected Colon"), "{}", err);
+        let err = parse_err(&input.replace("Float(min: -100.5)", "Float(\"min\": 1)"));
+        assert!(err.contains("Expected refinement name"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_type() {
+        let ty = parse_type("Map<String, Integer(min: 1)>(max_items: 2)").unwrap();
+        assert_eq!(ty.to_string(), "Map<String, Integer(min: 1)>(max_items: 2)");
+        assert_eq!(
+            parse_type("Enum [\"a\"]").unwrap().to_string(),
+            "Enum [\"a\"]"
+        );
+        assert!(parse_type("Integer Integer").is_err());
+        assert!(parse_type("").is_err());
+    }
+
+    #[test]
+    fn test_parse_types_section_errors_recover() {
+        let input = read_fixture("conformance/valid/types-section.icl")
+            .replace("Amount: Integer,", "Amount: 42,");
+        let result = parse_with_diagnostics(&input);
+        assert_eq!(result.diagnostics.len(), 1, "{:?}", result.diagnostics);
+        assert!(result.diagnostics[0]
+            .message
+            .contains("Expected type expression"));
+        assert_eq!(result.contract.type_definitions().len(), 2);
+        assert_eq!(result.contract.data_semantics.state.len(), 4);
+    }
+
+    // ── Imports and modules ────────────────────────────
+
+    #[test]
+    fn test_parse_imports() {
+        let input = read_fixture("imports/ledger.icl");
+        let ast = parse_valid(&input);
+        let paths: Vec<&str> = ast.imports.iter().map(|i| i.path.value.as_str()).collect();
+        assert_eq!(paths, vec!["common/types.icl", "common/invariants.icl"]);
+        assert_eq!(
+            &input[ast.imports[0].span.range()],
+            "import \"common/types.icl\""
+        );
+
+        let err = parse_err(&format!("import\n{}", MINIMAL_CONTRACT));
+        assert!(err.contains("Expected import path string"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_module() {
+        let module = parse_module(&read_fixture("imports/common/invariants.icl")).unwrap();
+        assert_eq!(module.imports[0].path.value, "types.icl");
+        assert!(module.types.is_none());
+        assert_eq!(module.invariants[0].text, "total >= 0");
+
+        let module = parse_module(&read_fixture("imports/common/types.icl")).unwrap();
+        assert_eq!(module.types.unwrap().definitions.len(), 3);
+        assert!(module.invariants.is_empty());
+
+        let err = parse_module(MINIMAL_CONTRACT).unwrap_err().to_string();
+        assert!(
+            err.contains("Expected Types, Invariants or end of module"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_conformance_invalid_missing_identity() {
         let input = read_fixture("conformance/invalid/missing-identity.icl");
@@ -1646,6 +2516,169 @@ mod tests {
         assert!(parse(&input).is_err());
     }
 
+    // ── Error recovery ─────────────────────────────────
+
+    #[test]
+    fn test_parse_with_diagnostics_valid_contract() {
+        let result = parse_with_diagnostics(MINIMAL_CONTRACT);
+        assert!(result.is_valid(), "{:?}", result.diagnostics);
+        assert_eq!(result.contract, parse_valid(MINIMAL_CONTRACT));
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_reports_multiple_errors() {
+        let input = MINIMAL_CONTRACT
+            .replace("version: 1,", "version: \"one\",")
+            .replace("confidence_level: 1.0", "confidence_level: 2.5")
+            .replace("max_memory_bytes: 1048576,", "max_memory_bytes: ,");
+        let result = parse_with_diagnostics(&input);
+        assert_eq!(result.diagnostics.len(), 3, "{:?}", result.diagnostics);
+        assert!(result.diagnostics[0]
+            .message
+            .contains("Expected integer literal"));
+        assert_eq!(result.diagnostics[0].code, UNEXPECTED_TOKEN);
+        assert_eq!(result.diagnostics[1].kind, ParseDiagnosticKind::Validation);
+        assert_eq!(result.diagnostics[1].code, VALUE_OUT_OF_RANGE);
+        assert_eq!(result.diagnostics[2].span.line, 30);
+
+        // Only the first error is reported by the fail-fast parser
+        let err = parse_err(&input);
+        let first = Error::from(result.diagnostics[0].clone());
+        assert_eq!(err, first.to_string());
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_keeps_good_sections() {
+        let input = MINIMAL_CONTRACT.replace("owner: \"test\",", "owner: ,");
+        let result = parse_with_diagnostics(&input);
+        assert_eq!(result.diagnostics.len(), 1, "{:?}", result.diagnostics);
+        assert_eq!(result.contract.identity.stable_id.value, "ic-test-001");
+        assert_eq!(
+            result.contract.purpose_statement.narrative.value,
+            "Minimal test contract"
+        );
+        assert_eq!(result.contract.data_semantics.state.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_missing_value_keeps_closing_brace() {
+        // The value parser consumes the `}` it finds instead of a value
+        let input = MINIMAL_CONTRACT
+            .replace("confidence_level: 1.0", "confidence_level:")
+            .replace("max_state_size_bytes: 1048576", "max_state_size_bytes:");
+        let result = parse_with_diagnostics(&input);
+        let messages: Vec<&str> = result
+            .diagnostics
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Expected float literal, found RBrace",
+                "Expected integer literal, found RBrace",
+            ]
+        );
+        assert_eq!(
+            result.contract.purpose_statement.intent_source.value,
+            "test"
+        );
+        assert_eq!(result.contract.data_semantics.state.len(), 1);
+
+        // An unclosed `[` does not swallow the `}` of its block
+        let input = MINIMAL_CONTRACT.replace("invariants: []", "invariants: [ ,");
+        let result = parse_with_diagnostics(&input);
+        assert_eq!(result.diagnostics.len(), 1, "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_reports_each_missing_field() {
+        let input = "Contract {\n  Identity {\n    stable_id: \"ic-1\"\n  }\n}";
+        let result = parse_with_diagnostics(input);
+        let messages: Vec<&str> = result
+            .diagnostics
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Missing required field 'version' in Identity",
+                "Missing required field 'created_timestamp' in Identity",
+                "Missing required field 'owner' in Identity",
+                "Missing required field 'semantic_hash' in Identity",
+                "Expected PurposeStatement, found RBrace",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_unknown_section_recovers() {
+        let input = MINIMAL_CONTRACT.replace(
+            "  PurposeStatement {",
+            "  FakeSection {\n    something: \"invalid\"\n  }\n\n  PurposeStatement {",
+        );
+        let result = parse_with_diagnostics(&input);
+        assert!(!result.is_valid());
+        assert_eq!(
+            result.contract.purpose_statement.intent_source.value,
+            "test"
+        );
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_tokenizer_errors() {
+        let input = MINIMAL_CONTRACT
+            .replace("owner: \"test\",", "owner: \"test\" @,")
+            .replace("intent_source: \"test\",", "intent_source: \"test\" #,");
+        let result = parse_with_diagnostics(&input);
+        assert_eq!(result.diagnostics.len(), 2, "{:?}", result.diagnostics);
+        assert!(result.diagnostics[0].span.line < result.diagnostics[1].span.line);
+        assert!(result
+            .diagnostics
+            .iter()
+            .all(|d| d.code == UNEXPECTED_CHARACTER));
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_agrees_with_parse_on_fixtures() {
+        for dir in ["valid", "invalid"] {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../../tests/fixtures/conformance")
+                .join(dir);
+            let mut paths: Vec<_> = fs::read_dir(&root)
+                .unwrap()
+                .map(|e| e.unwrap().path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "icl"))
+                .collect();
+            paths.sort();
+            for path in paths {
+                let input = fs::read_to_string(&path).unwrap();
+                let strict = parse(&input);
+                let recovered = parse_with_diagnostics(&input);
+                assert_eq!(
+                    strict.is_ok(),
+                    recovered.is_valid(),
+                    "{}: {:?}",
+                    path.display(),
+                    recovered.diagnostics
+                );
+                if let Err(e) = strict {
+                    let first = Error::from(recovered.diagnostics[0].clone());
+                    assert_eq!(e.to_string(), first.to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_empty_input() {
+        let result = parse_with_diagnostics("");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(result.diagnostics[0].message.contains("Expected Contract"));
+        assert!(result.contract.identity.stable_id.value.is_empty());
+    }
+
     // ── Determinism proof ──────────────────────────────
 
     #[test]
@@ -1669,6 +2702,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_with_diagnostics_determinism_100_iterations() {
+        let input = MINIMAL_CONTRACT
+            .replace("version: 1,", "version: \"one\",")
+            .replace("owner: \"test\",", "owner: ,");
+        let first = parse_with_diagnostics(&input);
+
+        for i in 0..100 {
+            let result = parse_with_diagnostics(&input);
+            assert_eq!(
+                first.contract, result.contract,
+                "Determinism failure at iteration {}",
+                i
+            );
+            assert_eq!(
+                first.diagnostics, result.diagnostics,
+                "Determinism failure at iteration {}",
+                i
+            );
+        }
+    }
+
     // ── Empty input / edge cases ───────────────────────
 
     #[test]
@@ -1,13 +1,19 @@
 //! ICL Tokenizer — converts ICL text into token stream
 //!
 //! Handles: keywords, identifiers, string literals, integer/float literals,
-//! ISO8601 timestamps, UUIDs, symbols (braces, colons, commas, brackets).
+//! ISO8601 timestamps, UUIDs, symbols (braces, colons, commas, brackets,
+//! parentheses).
 //! Comments (//) are discarded.
 //!
 //! Guarantees:
 //! - Deterministic: same input always produces same token stream
 //! - Complete error reporting: line:column for every error
 
+use super::{
+    ParseDiagnostic, INVALID_ESCAPE, INVALID_LITERAL, UNEXPECTED_CHARACTER, UNTERMINATED_STRING,
+};
+use crate::formats::parse_timestamp;
+
 /// Token types for ICL syntax
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
@@ -20,6 +26,9 @@ pub enum Token {
     ExecutionConstraints,
     HumanMachineContract,
     Extensions,
+    Types,
+    Invariants,
+    Import,
 
     // Type keywords
     IntegerType,
@@ -32,12 +41,14 @@ pub enum Token {
     MapType,
     ObjectType,
     EnumType,
+    OptionalType,
 
     // Literals
     StringLiteral(String),
     IntegerLiteral(i64),
     FloatLiteral(f64),
     BooleanLiteral(bool),
+    NullLiteral,
 
     // Symbols
     LBrace,   // {
@@ -46,6 +57,8 @@ pub enum Token {
     RBracket, // ]
     LAngle,   // <
     RAngle,   // >
+    LParen,   // (
+    RParen,   // )
     Colon,    // :
     Comma,    // ,
     Equals,   // =
@@ -55,12 +68,31 @@ pub enum Token {
     Eof,
 }
 
-/// Position in source text for error reporting
-#[derive(Debug, Clone, PartialEq)]
+/// Source range for error reporting
+///
+/// `line` and `column` (1-based, in characters) locate the start;
+/// `offset..end` is the byte range covered in the source text.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Span {
     pub line: usize,
     pub column: usize,
     pub offset: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Byte range of the spanned text
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.offset..self.end.max(self.offset)
+    }
+
+    /// Span from the start of `self` to the end of `other`
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            end: other.end.max(self.offset),
+            ..self.clone()
+        }
+    }
 }
 
 impl std::fmt::Display for Span {
@@ -80,6 +112,7 @@ pub struct SpannedToken {
 pub struct Tokenizer {
     input: Vec<char>,
     position: usize,
+    byte_offset: usize,
     line: usize,
     column: usize,
 }
@@ -90,6 +123,7 @@ impl Tokenizer {
         Tokenizer {
             input: text.chars().collect(),
             position: 0,
+            byte_offset: 0,
             line: 1,
             column: 1,
         }
@@ -117,6 +151,39 @@ impl Tokenizer {
         Ok(tokens)
     }
 
+    /// Tokenize the entire input, skipping over invalid characters and
+    /// literals instead of stopping at the first one. Returns every token
+    /// that could be read plus a diagnostic for each error.
+    pub fn tokenize_with_diagnostics(&mut self) -> (Vec<SpannedToken>, Vec<ParseDiagnostic>) {
+        let mut tokens = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        loop {
+            self.skip_whitespace_and_comments();
+
+            if self.is_at_end() {
+                tokens.push(SpannedToken {
+                    token: Token::Eof,
+                    span: self.current_span(),
+                });
+                break;
+            }
+
+            let start = self.position;
+            match self.next_token() {
+                Ok(token) => tokens.push(token),
+                Err(d) => {
+                    diagnostics.push(d);
+                    if self.position == start {
+                        self.advance();
+                    }
+                }
+            }
+        }
+
+        (tokens, diagnostics)
+    }
+
     // ── Character helpers ──────────────────────────────────
 
     fn is_at_end(&self) -> bool {
@@ -135,6 +202,7 @@ impl Tokenizer {
         let ch = self.input.get(self.position).copied();
         if let Some(c) = ch {
             self.position += 1;
+            self.byte_offset += c.len_utf8();
             if c == '\n' {
                 self.line += 1;
                 self.column = 1;
@@ -149,7 +217,8 @@ impl Tokenizer {
         Span {
             line: self.line,
             column: self.column,
-            offset: self.position,
+            offset: self.byte_offset,
+            end: self.byte_offset,
         }
     }
 
@@ -183,7 +252,22 @@ impl Tokenizer {
 
     // ── Main dispatch ──────────────────────────────────────
 
-    fn next_token(&mut self) -> crate::Result<SpannedToken> {
+    /// Read one token; its span ends where the token (or the error) ends
+    fn next_token(&mut self) -> Result<SpannedToken, ParseDiagnostic> {
+        let end = |t: &Self, span: &mut Span| span.end = span.end.max(t.byte_offset);
+        match self.read_token() {
+            Ok(mut token) => {
+                end(self, &mut token.span);
+                Ok(token)
+            }
+            Err(mut d) => {
+                end(self, &mut d.span);
+                Err(d)
+            }
+        }
+    }
+
+    fn read_token(&mut self) -> Result<SpannedToken, ParseDiagnostic> {
         let span = self.current_span();
         let ch = self.peek().unwrap();
 
@@ -230,6 +314,20 @@ impl Tokenizer {
                     span,
                 })
             }
+            '(' => {
+                self.advance();
+                Ok(SpannedToken {
+                    token: Token::LParen,
+                    span,
+                })
+            }
+            ')' => {
+                self.advance();
+                Ok(SpannedToken {
+                    token: Token::RParen,
+                    span,
+                })
+            }
             ':' => {
                 self.advance();
                 Ok(SpannedToken {
@@ -253,27 +351,33 @@ impl Tokenizer {
             }
             '"' => self.read_string(span),
             c if c.is_ascii_digit() => self.read_number(span),
+            '-' if self.peek_ahead(1).is_some_and(|c| c.is_ascii_digit()) => self.read_number(span),
             c if c.is_ascii_alphabetic() || c == '_' => self.read_identifier_or_keyword(span),
-            _ => Err(crate::Error::ParseError(format!(
-                "Unexpected character '{}' at {}",
-                ch, span
-            ))),
+            _ => Err(ParseDiagnostic::syntax(
+                UNEXPECTED_CHARACTER,
+                format!("Unexpected character '{}'", ch),
+                &Span {
+                    end: span.offset + ch.len_utf8(),
+                    ..span
+                },
+            )),
         }
     }
 
     // ── String literals ────────────────────────────────────
 
-    fn read_string(&mut self, span: Span) -> crate::Result<SpannedToken> {
+    fn read_string(&mut self, span: Span) -> Result<SpannedToken, ParseDiagnostic> {
         self.advance(); // consume opening "
         let mut value = String::new();
 
         loop {
             match self.advance() {
                 None => {
-                    return Err(crate::Error::ParseError(format!(
-                        "Unterminated string starting at {}",
-                        span
-                    )));
+                    return Err(ParseDiagnostic::syntax(
+                        UNTERMINATED_STRING,
+                        "Unterminated string starting",
+                        &span,
+                    ));
                 }
                 Some('"') => break,
                 Some('\\') => match self.advance() {
@@ -282,17 +386,18 @@ impl Tokenizer {
                     Some('\\') => value.push('\\'),
                     Some('"') => value.push('"'),
                     Some(c) => {
-                        return Err(crate::Error::ParseError(format!(
-                            "Invalid escape sequence '\\{}' at {}",
-                            c,
-                            self.current_span()
-                        )));
+                        return Err(ParseDiagnostic::syntax(
+                            INVALID_ESCAPE,
+                            format!("Invalid escape sequence '\\{}'", c),
+                            &self.current_span(),
+                        ));
                     }
                     None => {
-                        return Err(crate::Error::ParseError(format!(
-                            "Unterminated escape sequence at {}",
-                            self.current_span()
-                        )));
+                        return Err(ParseDiagnostic::syntax(
+                            UNTERMINATED_STRING,
+                            "Unterminated escape sequence",
+                            &self.current_span(),
+                        ));
                     }
                 },
                 Some(c) => value.push(c),
@@ -307,8 +412,12 @@ impl Tokenizer {
 
     // ── Numbers & ISO8601 timestamps ───────────────────────
 
-    fn read_number(&mut self, span: Span) -> crate::Result<SpannedToken> {
+    fn read_number(&mut self, span: Span) -> Result<SpannedToken, ParseDiagnostic> {
         let start = self.position;
+        let negative = self.peek() == Some('-');
+        if negative {
+            self.advance();
+        }
         let mut has_dot = false;
 
         // Collect all digits
@@ -325,7 +434,7 @@ impl Tokenizer {
 
         // Check for ISO8601: digits followed by '-' (like 2026-02-01T...)
         // Pattern: NNNN-NN-NNTNN:NN:NNZ
-        if self.peek() == Some('-') && !has_dot {
+        if self.peek() == Some('-') && !has_dot && !negative {
             // Could be ISO8601 timestamp — collect the rest
             while let Some(ch) = self.peek() {
                 if ch.is_ascii_alphanumeric()
@@ -342,17 +451,21 @@ impl Tokenizer {
                 }
             }
             let text: String = self.input[start..self.position].iter().collect();
-            // Validate basic ISO8601 shape
-            if is_iso8601_like(&text) {
+            // RFC 3339: a real calendar date and time with an offset
+            if parse_timestamp(&text).is_some() {
                 return Ok(SpannedToken {
                     token: Token::StringLiteral(text),
                     span,
                 });
             } else {
-                return Err(crate::Error::ParseError(format!(
-                    "Invalid timestamp '{}' at {}",
-                    text, span
-                )));
+                return Err(ParseDiagnostic::syntax(
+                    INVALID_LITERAL,
+                    format!(
+                        "Invalid timestamp '{}', expected an RFC 3339 date-time such as 2026-02-01T00:00:00Z",
+                        text
+                    ),
+                    &span,
+                ));
             }
         }
 
@@ -360,7 +473,7 @@ impl Tokenizer {
 
         if has_dot {
             let val: f64 = text.parse().map_err(|_| {
-                crate::Error::ParseError(format!("Invalid float '{}' at {}", text, span))
+                ParseDiagnostic::syntax(INVALID_LITERAL, format!("Invalid float '{}'", text), &span)
             })?;
             Ok(SpannedToken {
                 token: Token::FloatLiteral(val),
@@ -368,7 +481,11 @@ impl Tokenizer {
             })
         } else {
             let val: i64 = text.parse().map_err(|_| {
-                crate::Error::ParseError(format!("Invalid integer '{}' at {}", text, span))
+                ParseDiagnostic::syntax(
+                    INVALID_LITERAL,
+                    format!("Invalid integer '{}'", text),
+                    &span,
+                )
             })?;
             Ok(SpannedToken {
                 token: Token::IntegerLiteral(val),
@@ -379,7 +496,7 @@ impl Tokenizer {
 
     // ── Identifiers & Keywords ─────────────────────────────
 
-    fn read_identifier_or_keyword(&mut self, span: Span) -> crate::Result<SpannedToken> {
+    fn read_identifier_or_keyword(&mut self, span: Span) -> Result<SpannedToken, ParseDiagnostic> {
         let start = self.position;
 
         while let Some(ch) = self.peek() {
@@ -402,6 +519,9 @@ impl Tokenizer {
             "ExecutionConstraints" => Token::ExecutionConstraints,
             "HumanMachineContract" => Token::HumanMachineContract,
             "Extensions" => Token::Extensions,
+            "Types" => Token::Types,
+            "Invariants" => Token::Invariants,
+            "import" => Token::Import,
 
             // Type keywords
             "Integer" => Token::IntegerType,
@@ -414,10 +534,12 @@ impl Tokenizer {
             "Map" => Token::MapType,
             "Object" => Token::ObjectType,
             "Enum" => Token::EnumType,
+            "Optional" => Token::OptionalType,
 
-            // Boolean literals
+            // Boolean and null literals
             "true" => Token::BooleanLiteral(true),
             "false" => Token::BooleanLiteral(false),
+            "null" => Token::NullLiteral,
 
             // Everything else is an identifier
             _ => Token::Identifier(text),
@@ -427,16 +549,6 @@ impl Tokenizer {
     }
 }
 
-/// Basic check for ISO8601-like timestamps (YYYY-MM-DDTHH:MM:SSZ)
-fn is_iso8601_like(s: &str) -> bool {
-    // Must contain T and end with Z or timezone offset
-    // Minimal pattern: NNNN-NN-NNTNN:NN:NNZ
-    if s.len() < 20 {
-        return false;
-    }
-    s.contains('T') && (s.ends_with('Z') || s.contains('+'))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -472,7 +584,7 @@ mod tests {
 
     #[test]
     fn test_tokenize_all_section_keywords() {
-        let input = "Contract Identity PurposeStatement DataSemantics BehavioralSemantics ExecutionConstraints HumanMachineContract Extensions";
+        let input = "Contract Identity PurposeStatement DataSemantics BehavioralSemantics ExecutionConstraints HumanMachineContract Extensions Types Invariants import";
         let tokens = tokenize(input);
         assert_eq!(
             tokens,
@@ -485,6 +597,9 @@ mod tests {
                 Token::ExecutionConstraints,
                 Token::HumanMachineContract,
                 Token::Extensions,
+                Token::Types,
+                Token::Invariants,
+                Token::Import,
                 Token::Eof,
             ]
         );
@@ -492,7 +607,8 @@ mod tests {
 
     #[test]
     fn test_tokenize_type_keywords() {
-        let tokens = tokenize("Integer Float String Boolean ISO8601 UUID Array Map Object Enum");
+        let tokens =
+            tokenize("Integer Float String Boolean ISO8601 UUID Array Map Object Enum Optional");
         assert_eq!(
             tokens,
             vec![
@@ -506,6 +622,7 @@ mod tests {
                 Token::MapType,
                 Token::ObjectType,
                 Token::EnumType,
+                Token::OptionalType,
                 Token::Eof,
             ]
         );
@@ -565,6 +682,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_negative_numbers() {
+        let tokens = tokenize("-5 -0.5");
+        assert_eq!(tokens[0], Token::IntegerLiteral(-5));
+        assert_eq!(tokens[1], Token::FloatLiteral(-0.5));
+        assert!(tokenize_err("- 3").contains("Unexpected character '-'"));
+    }
+
     #[test]
     #[allow(clippy::approx_constant)]
     fn test_tokenize_float() {
@@ -594,16 +719,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_invalid_timestamp() {
+        let err = tokenize_err("2026-13-45T99:00:00Z");
+        assert!(err.contains("Invalid timestamp"), "Error: {}", err);
+        let err = tokenize_err("2026-02-30T00:00:00Z");
+        assert!(err.contains("RFC 3339"), "Error: {}", err);
+        assert!(tokenize_err("2026-02-01T00:00").contains("Invalid timestamp"));
+    }
+
     // ── Booleans ───────────────────────────────────────
 
     #[test]
     fn test_tokenize_booleans() {
-        let tokens = tokenize("true false");
+        let tokens = tokenize("true false null");
         assert_eq!(
             tokens,
             vec![
                 Token::BooleanLiteral(true),
                 Token::BooleanLiteral(false),
+                Token::NullLiteral,
                 Token::Eof,
             ]
         );
@@ -613,7 +748,7 @@ mod tests {
 
     #[test]
     fn test_tokenize_symbols() {
-        let tokens = tokenize("{ } [ ] < > : , =");
+        let tokens = tokenize("{ } [ ] < > ( ) : , =");
         assert_eq!(
             tokens,
             vec![
@@ -623,6 +758,8 @@ mod tests {
                 Token::RBracket,
                 Token::LAngle,
                 Token::RAngle,
+                Token::LParen,
+                Token::RParen,
                 Token::Colon,
                 Token::Comma,
                 Token::Equals,
@@ -692,7 +829,8 @@ mod tests {
             Span {
                 line: 1,
                 column: 1,
-                offset: 0
+                offset: 0,
+                end: 8
             }
         );
         assert_eq!(tokens[0].token, Token::Contract);
@@ -701,7 +839,8 @@ mod tests {
             Span {
                 line: 1,
                 column: 10,
-                offset: 9
+                offset: 9,
+                end: 10
             }
         );
         assert_eq!(tokens[1].token, Token::LBrace);
@@ -710,7 +849,8 @@ mod tests {
             Span {
                 line: 2,
                 column: 3,
-                offset: 13
+                offset: 13,
+                end: 21
             }
         );
         assert_eq!(tokens[2].token, Token::Identity);
@@ -719,12 +859,26 @@ mod tests {
             Span {
                 line: 3,
                 column: 1,
-                offset: 22
+                offset: 22,
+                end: 23
             }
         );
         assert_eq!(tokens[3].token, Token::RBrace);
     }
 
+    #[test]
+    fn test_span_byte_range() {
+        let source = "\"héllo\" 3.14 owner";
+        let tokens = Tokenizer::new(source).tokenize().unwrap();
+        assert_eq!(&source[tokens[0].span.range()], "\"héllo\"");
+        assert_eq!(&source[tokens[1].span.range()], "3.14");
+        assert_eq!(&source[tokens[2].span.range()], "owner");
+        // Columns count characters, offsets count bytes
+        assert_eq!(tokens[1].span.column, 9);
+        assert_eq!(tokens[1].span.offset, 9);
+        assert!(tokens[3].span.range().is_empty());
+    }
+
     // ── Edge cases ─────────────────────────────────────
 
     #[test]
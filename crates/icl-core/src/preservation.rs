@@ -0,0 +1,578 @@
+//! Invariant preservation — whether each operation keeps the invariants
+//!
+//! Part of the verifier's invariant phase: for every operation, either a
+//! proof over value intervals that the machine-checkable invariants still
+//! hold after it, a counterexample input that the executor rejects, or an
+//! unknown result naming the invariants left undecided. The results are
+//! recorded in `VerificationResult::preservation`.
+
+use crate::executor::{check_refinements, ExecutionState, Executor, ExpressionEvaluator, Value};
+use crate::lint;
+use crate::parser::ast::*;
+use crate::satisfiability::{
+    atomic_constraint, conjuncts_of, mirrored, Constraint, FieldConstraints,
+};
+use crate::schema::{Field, Type};
+use crate::verifier::{collect_path_roots, literal_number, VerificationResult};
+
+/// Whether an operation keeps every invariant true
+#[derive(Debug, Clone, PartialEq)]
+pub enum Preservation {
+    /// Every machine-checkable invariant holds after any successful run
+    Proved,
+    /// Running the operation from the initial state with `input` passes
+    /// its precondition and postcondition but leaves `invariant` false
+    Refuted {
+        invariant: String,
+        input: serde_json::Value,
+    },
+    /// Neither proved nor refuted; lists the invariants left undecided
+    Unknown { invariants: Vec<String> },
+}
+
+impl std::fmt::Display for Preservation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Preservation::Proved => write!(f, "proved"),
+            Preservation::Refuted { invariant, input } => {
+                write!(f, "refuted: input {} breaks '{}'", input, invariant)
+            }
+            Preservation::Unknown { invariants } => {
+                let quoted: Vec<String> = invariants.iter().map(|i| format!("'{}'", i)).collect();
+                write!(f, "unknown: {}", quoted.join(", "))
+            }
+        }
+    }
+}
+
+/// Preservation result for one operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationPreservation {
+    pub operation: String,
+    pub preservation: Preservation,
+}
+
+/// Most inputs tried per operation when looking for a counterexample
+const MAX_COUNTEREXAMPLE_RUNS: usize = 256;
+
+/// Decide for each operation whether it keeps the machine-checkable
+/// invariants true.
+///
+/// An operation sets the state field named by each parameter to the
+/// parameter's value and leaves every other field alone; inputs are
+/// taken to be the declared parameters with values of their types.
+///
+/// **Proof.** Each `and`-ed part of an invariant that reads no field the
+/// operation writes held before and still holds. A part that compares a
+/// written field with a constant, or two numeric fields, is decided over
+/// intervals: a written field ranges over its parameter's type, narrowed
+/// by refinements and by the postcondition; an unchanged field over its
+/// type, the invariants and the precondition.
+///
+/// **Refutation.** Inputs built from the constants in the conditions are
+/// run from the initial state; a run that the executor rejects for an
+/// invariant this operation left undecided is a counterexample.
+///
+/// Refuted operations get an `InvariantError` warning at their name.
+pub(crate) fn verify_preservation(ast: &ContractNode, result: &mut VerificationResult) {
+    let types = ast.type_definitions();
+    let state: Vec<(String, Type)> = ast
+        .data_semantics
+        .state
+        .iter()
+        .map(|f| (f.name.value.clone(), Type::from_expr(&f.type_expr, types)))
+        .collect();
+    let invariants: Vec<(&ConditionNode, &Expr)> = ast
+        .data_semantics
+        .invariants
+        .iter()
+        .filter_map(|i| Some((i, i.expr.as_ref()?)))
+        .collect();
+    let contract = crate::parser::lower_contract(ast).ok();
+
+    for op in &ast.behavioral_semantics.operations {
+        let params: Vec<Field> = op
+            .parameters
+            .iter()
+            .map(|p| Field::from_node(p, types))
+            .collect();
+        let facts = OperationFacts {
+            op,
+            params: &params,
+            state: &state,
+            invariants: &invariants,
+        };
+        let undecided: Vec<(&ConditionNode, &Expr)> = invariants
+            .iter()
+            .filter(|(_, expr)| !preserved(expr, &facts))
+            .copied()
+            .collect();
+
+        let preservation = if undecided.is_empty() {
+            Preservation::Proved
+        } else {
+            match contract
+                .as_ref()
+                .and_then(|c| counterexample(c, op, &params, &undecided, &invariants))
+            {
+                Some((invariant, input)) => Preservation::Refuted { invariant, input },
+                None => Preservation::Unknown {
+                    invariants: undecided.iter().map(|(i, _)| i.text.clone()).collect(),
+                },
+            }
+        };
+
+        if let Preservation::Refuted { invariant, input } = &preservation {
+            result.report(
+                &lint::INVARIANT_NOT_PRESERVED,
+                format!(
+                    "operation '{}' does not preserve invariant '{}': input {} from the initial state breaks it",
+                    op.name.value, invariant, input
+                ),
+                Some(op.name.span.clone()),
+            );
+        }
+        result.preservation.push(OperationPreservation {
+            operation: op.name.value.clone(),
+            preservation,
+        });
+    }
+}
+
+/// What an operation's declaration says about the state before and after it
+struct OperationFacts<'a> {
+    op: &'a OperationNode,
+    params: &'a [Field],
+    state: &'a [(String, Type)],
+    invariants: &'a [(&'a ConditionNode, &'a Expr)],
+}
+
+impl OperationFacts<'_> {
+    /// Whether the operation may set state field `name`
+    fn written(&self, name: &str) -> bool {
+        self.state.iter().any(|(field, _)| field == name)
+            && self.params.iter().any(|p| p.name == name)
+    }
+
+    /// Whether every run sets `name`; an Optional parameter without a
+    /// default may be left out, keeping the old value
+    fn always_written(&self, name: &str) -> bool {
+        self.written(name)
+            && self
+                .params
+                .iter()
+                .any(|p| p.name == name && (p.default.is_some() || !p.ty.is_optional()))
+    }
+
+    /// Values of state field `name` before a run: its type, the invariants
+    /// and, unless a parameter of the same name shadows it, the precondition
+    fn before(&self, name: &str) -> FieldConstraints<()> {
+        let ty = self
+            .state
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, ty)| ty);
+        let mut domain = FieldConstraints::new(ty.cloned());
+        if let Some(ty) = ty {
+            refine(&mut domain, ty);
+        }
+        let mut facts = Vec::new();
+        for (_, expr) in self.invariants {
+            conjuncts_of(expr, &mut facts);
+        }
+        if let Some(pre) = &self.op.precondition.expr {
+            if !self.params.iter().any(|p| p.name == name) {
+                conjuncts_of(pre, &mut facts);
+            }
+        }
+        self.narrow(&mut domain, name, &facts);
+        domain
+    }
+
+    /// Values of state field `name` after a run that sets it: its
+    /// parameter's type, the precondition, which reads the parameter, and
+    /// the postcondition, which may relate it to `old(...)` values and
+    /// unchanged fields
+    fn after(&self, name: &str) -> FieldConstraints<()> {
+        let Some(param) = self.params.iter().find(|p| p.name == name) else {
+            return self.before(name);
+        };
+        let mut domain = FieldConstraints::new(Some(param.ty.clone()));
+        refine(&mut domain, &param.ty);
+        let mut facts = Vec::new();
+        if let Some(pre) = &self.op.precondition.expr {
+            conjuncts_of(pre, &mut facts);
+        }
+        if let Some(post) = &self.op.postcondition.expr {
+            conjuncts_of(post, &mut facts);
+        }
+        self.narrow(&mut domain, name, &facts);
+        for fact in facts {
+            let Expr::Binary(op, left, right, _) = fact else {
+                continue;
+            };
+            let (op, other) = match (left.as_ref(), right.as_ref()) {
+                (Expr::Path(segments, _), other) if segments == &[name] => (*op, other),
+                (other, Expr::Path(segments, _)) if segments == &[name] => {
+                    let Some(op) = mirrored(*op) else { continue };
+                    (op, other)
+                }
+                _ => continue,
+            };
+            let Some((low, high)) = self.range(other) else {
+                continue;
+            };
+            if matches!(op, BinaryOp::Eq | BinaryOp::Ge | BinaryOp::Gt) && low.is_finite() {
+                let op = if op == BinaryOp::Eq { BinaryOp::Ge } else { op };
+                domain.bound(op, low, ());
+            }
+            if matches!(op, BinaryOp::Eq | BinaryOp::Le | BinaryOp::Lt) && high.is_finite() {
+                let op = if op == BinaryOp::Eq { BinaryOp::Le } else { op };
+                domain.bound(op, high, ());
+            }
+        }
+        domain
+    }
+
+    /// Apply every fact that compares `name` itself with a constant
+    fn narrow(&self, domain: &mut FieldConstraints<()>, name: &str, facts: &[&Expr]) {
+        for fact in facts {
+            if let Some((segments, constraint)) = atomic_constraint(fact, self.state) {
+                if segments.len() == 1 && segments[0] == name {
+                    domain.apply(&constraint, ());
+                }
+            }
+        }
+    }
+
+    /// Interval of a numeric expression in a postcondition, over constants,
+    /// `old(field)` and fields the operation leaves alone
+    fn range(&self, expr: &Expr) -> Option<(f64, f64)> {
+        let bounds = |domain: FieldConstraints<()>| {
+            (
+                domain.lower.map_or(f64::NEG_INFINITY, |b| b.value),
+                domain.upper.map_or(f64::INFINITY, |b| b.value),
+            )
+        };
+        match expr {
+            Expr::Literal(lit) => literal_number(lit).map(|n| (n, n)),
+            Expr::Old(inner, _) => match inner.as_ref() {
+                Expr::Path(segments, _) if segments.len() == 1 => {
+                    Some(bounds(self.before(&segments[0])))
+                }
+                _ => None,
+            },
+            Expr::Path(segments, _) if segments.len() == 1 && !self.written(&segments[0]) => self
+                .state
+                .iter()
+                .any(|(field, _)| *field == segments[0])
+                .then(|| bounds(self.before(&segments[0]))),
+            Expr::Binary(op, left, right, _) => {
+                let (a, b) = (self.range(left)?, self.range(right)?);
+                match op {
+                    BinaryOp::Add => Some((a.0 + b.0, a.1 + b.1)),
+                    BinaryOp::Sub => Some((a.0 - b.1, a.1 - b.0)),
+                    BinaryOp::Mul | BinaryOp::Div => {
+                        // Only scaling by a constant
+                        let (range, k) = match (a, b) {
+                            (range, (k, k2)) if k == k2 => (range, k),
+                            ((k, k2), range) if k == k2 && *op == BinaryOp::Mul => (range, k),
+                            _ => return None,
+                        };
+                        let k = match op {
+                            BinaryOp::Div if k == 0.0 => return None,
+                            BinaryOp::Div => 1.0 / k,
+                            _ => k,
+                        };
+                        if k == 0.0 {
+                            Some((0.0, 0.0))
+                        } else if k > 0.0 {
+                            Some((range.0 * k, range.1 * k))
+                        } else {
+                            Some((range.1 * k, range.0 * k))
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether `invariant` provably holds after every successful run of the
+/// operation
+fn preserved(invariant: &Expr, facts: &OperationFacts) -> bool {
+    let mut conjuncts = Vec::new();
+    conjuncts_of(invariant, &mut conjuncts);
+    conjuncts.into_iter().all(|conjunct| {
+        let mut roots = Vec::new();
+        collect_path_roots(conjunct, &mut roots);
+        if !roots.iter().any(|root| facts.written(root)) {
+            // Held before, reads nothing the operation changes
+            return true;
+        }
+        if let Some((segments, constraint)) = atomic_constraint(conjunct, facts.state) {
+            // The old value already satisfied it if the field is left alone
+            return segments.len() == 1 && facts.after(&segments[0]).entails(&constraint);
+        }
+        match conjunct {
+            Expr::Binary(op, left, right, _) => match (left.as_ref(), right.as_ref()) {
+                (Expr::Path(a, _), Expr::Path(b, _)) if a.len() == 1 && b.len() == 1 => {
+                    let settled = |name: &str| !facts.written(name) || facts.always_written(name);
+                    settled(&a[0])
+                        && settled(&b[0])
+                        && facts.after(&a[0]).entails_ordered(*op, &facts.after(&b[0]))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    })
+}
+
+/// Narrow a numeric domain with the `min` / `max` refinements of its type
+fn refine(constraints: &mut FieldConstraints<()>, ty: &Type) {
+    match ty {
+        Type::Refined { base, refinements } => {
+            for refinement in refinements {
+                let op = match refinement.name.as_str() {
+                    "min" => BinaryOp::Ge,
+                    "max" => BinaryOp::Le,
+                    _ => continue,
+                };
+                if let Some(limit) = refinement.value.as_f64() {
+                    constraints.bound(op, limit, ());
+                }
+            }
+            refine(constraints, base);
+        }
+        Type::Optional { inner } => refine(constraints, inner),
+        _ => {}
+    }
+}
+
+impl<S: Copy> FieldConstraints<S> {
+    /// Whether every value in this domain satisfies `constraint`; an
+    /// empty domain satisfies everything
+    fn entails(&self, constraint: &Constraint) -> bool {
+        if self.contradiction().is_some() {
+            return true;
+        }
+        let (lower, upper) = (self.lower.as_ref(), self.upper.as_ref());
+        let above = |c: f64, strict: bool| {
+            lower.is_some_and(|l| l.value > c || (l.value == c && !(strict && l.inclusive)))
+        };
+        let below = |c: f64, strict: bool| {
+            upper.is_some_and(|u| u.value < c || (u.value == c && !(strict && u.inclusive)))
+        };
+        match *constraint {
+            Constraint::Number(BinaryOp::Ge, c) => above(c, false),
+            Constraint::Number(BinaryOp::Gt, c) => above(c, true),
+            Constraint::Number(BinaryOp::Le, c) => below(c, false),
+            Constraint::Number(BinaryOp::Lt, c) => below(c, true),
+            Constraint::Number(BinaryOp::Eq, c) => above(c, false) && below(c, false),
+            Constraint::Number(BinaryOp::Ne, c) => {
+                above(c, true) || below(c, true) || self.excluded.iter().any(|(v, _)| *v == c)
+            }
+            Constraint::Number(..) => false,
+            Constraint::Variants(ref allowed, keep) => self
+                .variants
+                .as_ref()
+                .is_some_and(|(variants, _)| variants.iter().all(|v| allowed.contains(v) == keep)),
+        }
+    }
+
+    /// Whether `self op other` holds for every pair of values, for the
+    /// ordering operators
+    fn entails_ordered(&self, op: BinaryOp, other: &Self) -> bool {
+        let (low, high, strict) = match op {
+            BinaryOp::Lt => (self, other, true),
+            BinaryOp::Le => (self, other, false),
+            BinaryOp::Gt => (other, self, true),
+            BinaryOp::Ge => (other, self, false),
+            _ => return false,
+        };
+        match (&low.upper, &high.lower) {
+            (Some(u), Some(l)) => {
+                u.value < l.value || (u.value == l.value && !(strict && u.inclusive && l.inclusive))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Run `op` from the initial state on inputs built from the constants in
+/// the conditions, looking for one that breaks an `undecided` invariant.
+/// Invariants already false in the initial state cannot be blamed on `op`.
+fn counterexample(
+    contract: &crate::Contract,
+    op: &OperationNode,
+    params: &[Field],
+    undecided: &[(&ConditionNode, &Expr)],
+    invariants: &[(&ConditionNode, &Expr)],
+) -> Option<(String, serde_json::Value)> {
+    let operation = contract
+        .behavioral_semantics
+        .operations
+        .iter()
+        .find(|o| o.name == op.name.value)?;
+    let initial = ExecutionState::from_contract(contract);
+    let declared = &contract.data_semantics.state;
+    let pending: Vec<&(&ConditionNode, &Expr)> = undecided
+        .iter()
+        .filter(|(node, _)| {
+            ExpressionEvaluator::evaluate_condition(&(*node).into(), None, &initial, declared).0
+        })
+        .collect();
+    if pending.is_empty() {
+        return None;
+    }
+
+    let mut literals = Vec::new();
+    let conditions = [&op.precondition.expr, &op.postcondition.expr];
+    for expr in invariants
+        .iter()
+        .map(|(_, e)| *e)
+        .chain(conditions.into_iter().flatten())
+    {
+        collect_literals(expr, &mut literals);
+    }
+    // `None` leaves an Optional parameter out
+    let candidates: Vec<Vec<Option<serde_json::Value>>> = params
+        .iter()
+        .map(|param| {
+            let mut values: Vec<Option<serde_json::Value>> = candidate_values(&param.ty, &literals)
+                .into_iter()
+                .filter(|v| check_refinements(&param.name, &Value::from_json(v), &param.ty).is_ok())
+                .map(Some)
+                .collect();
+            if param.ty.is_optional() {
+                values.insert(0, None);
+            }
+            values
+        })
+        .collect();
+    if candidates.iter().any(Vec::is_empty) {
+        return None;
+    }
+
+    // Odometer over the candidates, first parameter fastest
+    let mut choice = vec![0; params.len()];
+    for _ in 0..MAX_COUNTEREXAMPLE_RUNS {
+        let mut input = serde_json::Map::new();
+        for ((param, values), &i) in params.iter().zip(&candidates).zip(&choice) {
+            if let Some(value) = &values[i] {
+                input.insert(param.name.clone(), value.clone());
+            }
+        }
+        let input = serde_json::Value::Object(input);
+
+        let mut executor = Executor::new(contract.clone());
+        if let Err(crate::Error::ContractViolation { commitment, .. }) =
+            executor.execute_operation(&op.name.value, &input.to_string())
+        {
+            if commitment == "invariant" {
+                let mut after = initial.clone();
+                let mut filled = input.clone();
+                Executor::fill_defaults(operation, &mut filled);
+                if let serde_json::Value::Object(map) = filled {
+                    for (key, value) in map {
+                        after.set(key, Value::from_json(&value));
+                    }
+                }
+                for (invariant, _) in &pending {
+                    let condition = crate::Condition::from(*invariant);
+                    if ExpressionEvaluator::evaluate_condition(&condition, None, &after, declared)
+                        == (false, true)
+                    {
+                        return Some((invariant.text.clone(), input));
+                    }
+                }
+            }
+        }
+
+        let position = (0..choice.len()).find(|&p| choice[p] + 1 < candidates[p].len())?;
+        choice[position] += 1;
+        choice[..position].iter_mut().for_each(|c| *c = 0);
+    }
+    None
+}
+
+/// Values worth trying for a parameter of type `ty`: the constants of the
+/// conditions and their neighbours, smallest magnitude first
+fn candidate_values(ty: &Type, literals: &[&LiteralValue]) -> Vec<serde_json::Value> {
+    let numbers = || {
+        let mut numbers: Vec<f64> = vec![0.0, 1.0, -1.0];
+        numbers.extend(literals.iter().filter_map(|lit| literal_number(lit)));
+        if let Type::Refined { refinements, .. } = ty {
+            numbers.extend(refinements.iter().filter_map(|r| r.value.as_f64()));
+        }
+        numbers
+    };
+    match ty.unwrapped() {
+        Type::Integer => {
+            let mut values: Vec<i64> = numbers()
+                .into_iter()
+                .flat_map(|n| [n.floor() as i64 - 1, n.floor() as i64, n.ceil() as i64 + 1])
+                .collect();
+            values.sort_by_key(|v| (v.unsigned_abs(), *v));
+            values.dedup();
+            values.into_iter().map(serde_json::Value::from).collect()
+        }
+        Type::Float => {
+            let mut values: Vec<f64> = numbers()
+                .into_iter()
+                .flat_map(|n| [n - 1.0, n - 0.5, n, n + 0.5, n + 1.0])
+                .collect();
+            values.sort_by(|a, b| a.abs().total_cmp(&b.abs()).then(a.total_cmp(b)));
+            values.dedup();
+            values.into_iter().map(serde_json::Value::from).collect()
+        }
+        Type::Boolean => vec![false.into(), true.into()],
+        Type::Enum { variants } => variants.iter().map(|v| v.as_str().into()).collect(),
+        Type::String | Type::Iso8601 | Type::Uuid => {
+            let mut values = vec![String::new()];
+            for lit in literals {
+                if let LiteralValue::String(text, _) = lit {
+                    if !values.contains(text) {
+                        values.push(text.clone());
+                    }
+                }
+            }
+            values.into_iter().map(serde_json::Value::from).collect()
+        }
+        other => ExecutionState::default_for_type(other)
+            .map(|value| value.to_json())
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Collect every literal in an expression, including inside arrays
+fn collect_literals<'a>(expr: &'a Expr, out: &mut Vec<&'a LiteralValue>) {
+    fn literal<'a>(lit: &'a LiteralValue, out: &mut Vec<&'a LiteralValue>) {
+        match lit {
+            LiteralValue::Array(items, _) => items.iter().for_each(|item| literal(item, out)),
+            other => out.push(other),
+        }
+    }
+    match expr {
+        Expr::Literal(lit) => literal(lit, out),
+        Expr::Path(_, _) => {}
+        Expr::Unary(_, operand, _)
+        | Expr::IsEmpty(operand, _)
+        | Expr::IsType(operand, _, _)
+        | Expr::Old(operand, _)
+        | Expr::Field(operand, _, _) => collect_literals(operand, out),
+        Expr::Binary(_, left, right, _) | Expr::Index(left, right, _) => {
+            collect_literals(left, out);
+            collect_literals(right, out);
+        }
+        Expr::Quantified(_, _, collection, body, _) => {
+            collect_literals(collection, out);
+            collect_literals(body, out);
+        }
+        Expr::Call(_, args, _) => args.iter().for_each(|arg| collect_literals(arg, out)),
+    }
+}
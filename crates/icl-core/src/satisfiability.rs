@@ -0,0 +1,344 @@
+//! Invariant satisfiability — contradictory invariants
+//!
+//! Part of the verifier's invariant phase: checks that the invariants of a
+//! contract can all hold at once, e.g. that `count >= 5` and `count < 3`
+//! are not both declared, and reports each invariant involved at its own
+//! span.
+//!
+//! The per-field facts gathered here (`FieldConstraints`) are also what
+//! [`crate::preservation`] reasons over.
+
+use std::collections::BTreeSet;
+
+use crate::lint;
+use crate::parser::ast::*;
+use crate::schema::Type;
+use crate::verifier::{literal_number, VerificationResult};
+
+/// An atomic invariant constraint: the invariant's index and the comparison
+type Source<'a> = (usize, &'a Expr);
+
+/// One end of a field's allowed numeric range and the constraint behind it
+pub(crate) struct Bound<S> {
+    pub(crate) value: f64,
+    pub(crate) inclusive: bool,
+    source: S,
+}
+
+/// Everything known about the values of one field, each fact tagged with
+/// the constraint `S` it came from
+pub(crate) struct FieldConstraints<S> {
+    ty: Option<Type>,
+    pub(crate) lower: Option<Bound<S>>,
+    pub(crate) upper: Option<Bound<S>>,
+    /// Values ruled out by `!=`
+    pub(crate) excluded: Vec<(f64, S)>,
+    /// Enum variants still allowed, and the constraints that narrowed them
+    pub(crate) variants: Option<(BTreeSet<String>, Vec<S>)>,
+}
+
+impl<S: Copy> FieldConstraints<S> {
+    pub(crate) fn new(ty: Option<Type>) -> Self {
+        let variants = match ty.as_ref().map(Type::unwrapped) {
+            Some(Type::Enum { variants }) => Some((variants.iter().cloned().collect(), Vec::new())),
+            _ => None,
+        };
+        Self {
+            ty,
+            lower: None,
+            upper: None,
+            excluded: Vec::new(),
+            variants,
+        }
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(self.ty.as_ref().map(Type::unwrapped), Some(Type::Integer))
+    }
+
+    /// Narrow the range with `field op value`
+    pub(crate) fn bound(&mut self, op: BinaryOp, value: f64, source: S) {
+        // Integers have no values strictly between neighbours
+        let (op, value) = match op {
+            _ if !self.is_integer() => (op, value),
+            BinaryOp::Gt => (BinaryOp::Ge, value.floor() + 1.0),
+            BinaryOp::Ge => (BinaryOp::Ge, value.ceil()),
+            BinaryOp::Lt => (BinaryOp::Le, value.ceil() - 1.0),
+            BinaryOp::Le => (BinaryOp::Le, value.floor()),
+            _ => (op, value),
+        };
+        let bound = |inclusive| Bound {
+            value,
+            inclusive,
+            source,
+        };
+        match op {
+            BinaryOp::Gt | BinaryOp::Ge => {
+                tighten(&mut self.lower, bound(op == BinaryOp::Ge), |new, old| {
+                    new > old
+                })
+            }
+            BinaryOp::Lt | BinaryOp::Le => {
+                tighten(&mut self.upper, bound(op == BinaryOp::Le), |new, old| {
+                    new < old
+                })
+            }
+            BinaryOp::Eq => {
+                tighten(&mut self.lower, bound(true), |new, old| new > old);
+                tighten(&mut self.upper, bound(true), |new, old| new < old);
+            }
+            BinaryOp::Ne => self.excluded.push((value, source)),
+            _ => {}
+        }
+    }
+
+    /// Keep only the `allowed` enum variants (or drop them, when `keep` is false)
+    fn variants(&mut self, allowed: &[String], keep: bool, source: S) {
+        let Some((variants, sources)) = &mut self.variants else {
+            return;
+        };
+        let before = variants.len();
+        variants.retain(|v| allowed.contains(v) == keep);
+        if variants.len() != before {
+            sources.push(source);
+        }
+    }
+
+    /// Narrow with an atomic constraint on this field
+    pub(crate) fn apply(&mut self, constraint: &Constraint, source: S) {
+        match constraint {
+            Constraint::Number(op, value) => self.bound(*op, *value, source),
+            Constraint::Variants(allowed, keep) => self.variants(allowed, *keep, source),
+        }
+    }
+
+    /// The constraints that together leave no value, if they do
+    pub(crate) fn contradiction(&self) -> Option<Vec<S>> {
+        if let Some((variants, sources)) = &self.variants {
+            if variants.is_empty() {
+                return Some(sources.clone());
+            }
+        }
+        let (lower, upper) = (self.lower.as_ref()?, self.upper.as_ref()?);
+        if lower.value > upper.value
+            || (lower.value == upper.value && !(lower.inclusive && upper.inclusive))
+        {
+            return Some(vec![lower.source, upper.source]);
+        }
+        if lower.value == upper.value {
+            let (_, excluded) = self.excluded.iter().find(|(v, _)| *v == lower.value)?;
+            return Some(vec![lower.source, upper.source, *excluded]);
+        }
+        None
+    }
+}
+
+/// Replace `slot` with `new` when it is strictly tighter; at equal values
+/// an exclusive bound is tighter than an inclusive one
+fn tighten<S>(slot: &mut Option<Bound<S>>, new: Bound<S>, tighter: fn(f64, f64) -> bool) {
+    let replace = match slot {
+        None => true,
+        Some(old) => {
+            tighter(new.value, old.value)
+                || (new.value == old.value && old.inclusive && !new.inclusive)
+        }
+    };
+    if replace {
+        *slot = Some(new);
+    }
+}
+
+/// Report invariants that cannot all hold at once.
+///
+/// Every invariant is split into its `and`-ed parts. Comparisons of a state
+/// field with a number narrow that field's range (Integer fields to whole
+/// numbers), and `==`, `!=` and `in` against variant names narrow the
+/// variants an enum field may take. A field left with no value makes the
+/// invariants behind its constraints contradictory; each is reported at its
+/// own span.
+pub(crate) fn verify_invariants_satisfiable(ast: &ContractNode, result: &mut VerificationResult) {
+    let types = ast.type_definitions();
+    let state: Vec<(String, Type)> = ast
+        .data_semantics
+        .state
+        .iter()
+        .map(|f| (f.name.value.clone(), Type::from_expr(&f.type_expr, types)))
+        .collect();
+    let invariants = &ast.data_semantics.invariants;
+
+    let mut fields: Vec<(String, FieldConstraints<Source>)> = Vec::new();
+    for (index, invariant) in invariants.iter().enumerate() {
+        let Some(expr) = &invariant.expr else {
+            continue;
+        };
+        let mut conjuncts = Vec::new();
+        conjuncts_of(expr, &mut conjuncts);
+        for conjunct in conjuncts {
+            let Some((segments, constraint)) = atomic_constraint(conjunct, &state) else {
+                continue;
+            };
+            let name = segments.join(".");
+            let position = match fields.iter().position(|(n, _)| *n == name) {
+                Some(position) => position,
+                None => {
+                    let ty = path_declared_type(segments, &state);
+                    fields.push((name, FieldConstraints::new(ty)));
+                    fields.len() - 1
+                }
+            };
+            fields[position].1.apply(&constraint, (index, conjunct));
+        }
+    }
+
+    for (name, constraints) in &fields {
+        let Some(sources) = constraints.contradiction() else {
+            continue;
+        };
+        let mut involved: Vec<usize> = sources.iter().map(|(index, _)| *index).collect();
+        involved.sort_unstable();
+        involved.dedup();
+        let parts: Vec<String> = sources.iter().map(|(_, e)| format!("'{}'", e)).collect();
+        let reason = format!("no value of '{}' satisfies {}", name, parts.join(" and "));
+
+        for &index in &involved {
+            let invariant = &invariants[index];
+            let others: Vec<String> = involved
+                .iter()
+                .filter(|&&other| other != index)
+                .map(|&other| format!("'{}'", invariants[other].text))
+                .collect();
+            let message = if others.is_empty() {
+                format!("invariant '{}' can never hold: {}", invariant.text, reason)
+            } else {
+                format!(
+                    "invariant '{}' contradicts {}: {}",
+                    invariant.text,
+                    others.join(" and "),
+                    reason
+                )
+            };
+            result.report(
+                &lint::CONTRADICTORY_INVARIANTS,
+                message,
+                Some(invariant.span.clone()),
+            );
+        }
+    }
+}
+
+/// What an atomic constraint says about its field
+pub(crate) enum Constraint {
+    /// `field op number`, with the field on the left
+    Number(BinaryOp, f64),
+    /// The field is (`true`) or is not (`false`) one of these enum variants
+    Variants(Vec<String>, bool),
+}
+
+/// Split a condition into the parts joined by top-level `and`
+pub(crate) fn conjuncts_of<'a>(expr: &'a Expr, out: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::Binary(BinaryOp::And, left, right, _) => {
+            conjuncts_of(left, out);
+            conjuncts_of(right, out);
+        }
+        other => out.push(other),
+    }
+}
+
+/// A comparison between a state field path and a constant, if `expr` is one
+pub(crate) fn atomic_constraint<'e>(
+    expr: &'e Expr,
+    state: &[(String, Type)],
+) -> Option<(&'e [String], Constraint)> {
+    let is_field = |e: &Expr| {
+        matches!(e, Expr::Path(segments, _)
+            if state.iter().any(|(name, _)| *name == segments[0]))
+    };
+    // A name that is not a state field stands for an enum variant
+    let variant = |e: &Expr| match e {
+        Expr::Literal(LiteralValue::String(text, _)) => Some(text.clone()),
+        Expr::Path(segments, _) if segments.len() == 1 && !is_field(e) => Some(segments[0].clone()),
+        _ => None,
+    };
+
+    let (negated, expr) = match expr {
+        Expr::Unary(UnaryOp::Not, inner, _) => (true, inner.as_ref()),
+        other => (false, other),
+    };
+    let Expr::Binary(op, left, right, _) = expr else {
+        return None;
+    };
+    let (op, field, other) = if is_field(left) {
+        (*op, left.as_ref(), right.as_ref())
+    } else if is_field(right) && *op != BinaryOp::In {
+        (mirrored(*op)?, right.as_ref(), left.as_ref())
+    } else {
+        return None;
+    };
+    let Expr::Path(segments, _) = field else {
+        return None;
+    };
+    let op = if negated { negated_op(op)? } else { op };
+
+    let constraint = match (op, other) {
+        (BinaryOp::In, Expr::Literal(LiteralValue::Array(items, _))) => {
+            let variants = items
+                .iter()
+                .map(|item| match item {
+                    LiteralValue::String(text, _) => Some(text.clone()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Constraint::Variants(variants, !negated)
+        }
+        (BinaryOp::In, _) => return None,
+        (_, Expr::Literal(lit)) if literal_number(lit).is_some() => {
+            Constraint::Number(op, literal_number(lit)?)
+        }
+        (BinaryOp::Eq | BinaryOp::Ne, other) => {
+            Constraint::Variants(vec![variant(other)?], op == BinaryOp::Eq)
+        }
+        _ => return None,
+    };
+    Some((segments, constraint))
+}
+
+/// The operator with its operands swapped: `5 < x` is `x > 5`
+pub(crate) fn mirrored(op: BinaryOp) -> Option<BinaryOp> {
+    match op {
+        BinaryOp::Lt => Some(BinaryOp::Gt),
+        BinaryOp::Le => Some(BinaryOp::Ge),
+        BinaryOp::Gt => Some(BinaryOp::Lt),
+        BinaryOp::Ge => Some(BinaryOp::Le),
+        BinaryOp::Eq | BinaryOp::Ne => Some(op),
+        _ => None,
+    }
+}
+
+/// The operator for `not (x op y)`; `in` keeps its operator and is negated
+/// through [`Constraint::Variants`]
+fn negated_op(op: BinaryOp) -> Option<BinaryOp> {
+    match op {
+        BinaryOp::Lt => Some(BinaryOp::Ge),
+        BinaryOp::Le => Some(BinaryOp::Gt),
+        BinaryOp::Gt => Some(BinaryOp::Le),
+        BinaryOp::Ge => Some(BinaryOp::Lt),
+        BinaryOp::Eq => Some(BinaryOp::Ne),
+        BinaryOp::Ne => Some(BinaryOp::Eq),
+        BinaryOp::In => Some(BinaryOp::In),
+        _ => None,
+    }
+}
+
+/// Declared type of a state field path, following Object fields
+fn path_declared_type(segments: &[String], state: &[(String, Type)]) -> Option<Type> {
+    let (_, root) = state.iter().find(|(name, _)| *name == segments[0])?;
+    let mut ty = root.clone();
+    for segment in &segments[1..] {
+        let Type::Object { fields } = ty.unwrapped() else {
+            return None;
+        };
+        ty = fields.iter().find(|f| f.name == *segment)?.ty.clone();
+    }
+    Some(ty)
+}
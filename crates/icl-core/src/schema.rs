@@ -0,0 +1,461 @@
+//! Typed schema — the types of state fields and operation parameters
+//!
+//! `parser::lower_contract` turns each `StateFieldNode` into a [`Field`]
+//! whose [`Type`] mirrors the `TypeExpression` it was written as, with
+//! spans dropped and names from the `Types` section resolved to the type
+//! they stand for. The executor and the language bindings work with this
+//! model instead of re-parsing type names.
+//!
+//! ```text
+//! tags: Array<String(max_len: 16)> = ["a"]
+//!   → Field { name: "tags", ty: Array { items: Refined { base: String, .. } },
+//!             default: Some(["a"]) }
+//! ```
+//!
+//! # Guarantees
+//!
+//! - **Lossless**: `Display` prints a type in ICL syntax that parses back
+//!   to the same `Type`
+//! - **Serializable**: every type round-trips through JSON; a `null`
+//!   default stays distinct from no default
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::parser::ast::{
+    quote, PrimitiveType, RefinementNode, StateFieldNode, TypeDefinitionNode, TypeExpression,
+};
+
+// ── Types ─────────────────────────────────────────────────
+
+/// A state field, operation parameter or `Object` field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Type,
+    /// Declared default value, as JSON
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default: Option<serde_json::Value>,
+}
+
+/// A type with every resolvable name replaced by its definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Type {
+    Integer,
+    Float,
+    String,
+    Boolean,
+    #[serde(rename = "iso8601")]
+    Iso8601,
+    Uuid,
+    Array {
+        items: Box<Type>,
+    },
+    Map {
+        key: Box<Type>,
+        value: Box<Type>,
+    },
+    Object {
+        fields: Vec<Field>,
+    },
+    Enum {
+        variants: Vec<String>,
+    },
+    Optional {
+        inner: Box<Type>,
+    },
+    Refined {
+        base: Box<Type>,
+        refinements: Vec<Refinement>,
+    },
+    /// A name that could not be resolved (unknown or recursive type)
+    Named {
+        name: String,
+    },
+}
+
+/// One `name: value` constraint of a refined type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Refinement {
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
+/// Keep an explicit `null` as `Some(Null)` instead of serde's `None`
+fn deserialize_present<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<serde_json::Value>, D::Error> {
+    serde_json::Value::deserialize(deserializer).map(Some)
+}
+
+// ── Lowering from the AST ─────────────────────────────────
+
+impl Field {
+    /// A field without a default
+    pub fn new(name: impl Into<String>, ty: Type) -> Self {
+        Field {
+            name: name.into(),
+            ty,
+            default: None,
+        }
+    }
+
+    /// Lower a field declaration, resolving names through `types`
+    pub fn from_node(node: &StateFieldNode, types: &[TypeDefinitionNode]) -> Self {
+        Field {
+            name: node.name.value.clone(),
+            ty: Type::from_expr(&node.type_expr, types),
+            default: node
+                .default_value
+                .as_ref()
+                .map(crate::parser::lower_literal),
+        }
+    }
+}
+
+impl Type {
+    /// Lower a type expression, resolving names through `types`.
+    /// Refinements of a refined alias are merged into one list, so
+    /// `Percent(max: 50)` becomes `Integer(min: 0, max: 100, max: 50)`.
+    pub fn from_expr(expr: &TypeExpression, types: &[TypeDefinitionNode]) -> Self {
+        lower(expr, types, &mut Vec::new())
+    }
+
+    /// True if the type accepts `null`
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Type::Optional { .. })
+    }
+
+    /// The type without its refinements
+    pub fn base(&self) -> &Type {
+        match self {
+            Type::Refined { base, .. } => base.base(),
+            other => other,
+        }
+    }
+
+    /// The type a value is checked against once it is known to be present:
+    /// refinements and `Optional` wrappers removed
+    pub fn unwrapped(&self) -> &Type {
+        match self {
+            Type::Refined { base, .. } => base.unwrapped(),
+            Type::Optional { inner } => inner.unwrapped(),
+            other => other,
+        }
+    }
+}
+
+/// `expanding` holds the names being resolved, to stop at recursive types
+fn lower<'a>(
+    expr: &'a TypeExpression,
+    types: &'a [TypeDefinitionNode],
+    expanding: &mut Vec<&'a str>,
+) -> Type {
+    match expr {
+        TypeExpression::Primitive(p, _) => match p {
+            PrimitiveType::Integer => Type::Integer,
+            PrimitiveType::Float => Type::Float,
+            PrimitiveType::String => Type::String,
+            PrimitiveType::Boolean => Type::Boolean,
+            PrimitiveType::Iso8601 => Type::Iso8601,
+            PrimitiveType::Uuid => Type::Uuid,
+        },
+        TypeExpression::Array(inner, _) => Type::Array {
+            items: Box::new(lower(inner, types, expanding)),
+        },
+        TypeExpression::Map(key, value, _) => Type::Map {
+            key: Box::new(lower(key, types, expanding)),
+            value: Box::new(lower(value, types, expanding)),
+        },
+        TypeExpression::Object(fields, _) => Type::Object {
+            fields: fields
+                .iter()
+                .map(|f| Field {
+                    name: f.name.value.clone(),
+                    ty: lower(&f.type_expr, types, expanding),
+                    default: f.default_value.as_ref().map(crate::parser::lower_literal),
+                })
+                .collect(),
+        },
+        TypeExpression::Enum(variants, _) => Type::Enum {
+            variants: variants.iter().map(|v| v.value.clone()).collect(),
+        },
+        TypeExpression::Optional(inner, _) => Type::Optional {
+            inner: Box::new(lower(inner, types, expanding)),
+        },
+        TypeExpression::Refined(base, refinements, _) => {
+            let refinements = refinements.iter().map(Refinement::from_node);
+            match lower(base, types, expanding) {
+                Type::Refined {
+                    base,
+                    refinements: mut inherited,
+                } => {
+                    inherited.extend(refinements);
+                    Type::Refined {
+                        base,
+                        refinements: inherited,
+                    }
+                }
+                base => Type::Refined {
+                    base: Box::new(base),
+                    refinements: refinements.collect(),
+                },
+            }
+        }
+        TypeExpression::Named(name, _) => {
+            let definition = types.iter().find(|d| d.name.value == *name);
+            match definition {
+                Some(definition) if !expanding.contains(&name.as_str()) => {
+                    expanding.push(name);
+                    let ty = lower(&definition.type_expr, types, expanding);
+                    expanding.pop();
+                    ty
+                }
+                _ => Type::Named { name: name.clone() },
+            }
+        }
+    }
+}
+
+impl Refinement {
+    fn from_node(node: &RefinementNode) -> Self {
+        Refinement {
+            name: node.name.value.clone(),
+            value: crate::parser::lower_literal(&node.value),
+        }
+    }
+}
+
+/// Parse a type written in ICL syntax, e.g. `"Array<Integer(min: 0)>"`.
+/// There is no `Types` section to resolve names through, so they stay `Named`.
+impl std::str::FromStr for Type {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        Ok(Type::from_expr(&crate::parser::parse_type(s)?, &[]))
+    }
+}
+
+// ── Display (ICL syntax) ──────────────────────────────────
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Type::Integer => write!(f, "Integer"),
+            Type::Float => write!(f, "Float"),
+            Type::String => write!(f, "String"),
+            Type::Boolean => write!(f, "Boolean"),
+            Type::Iso8601 => write!(f, "ISO8601"),
+            Type::Uuid => write!(f, "UUID"),
+            Type::Array { items } => write!(f, "Array<{}>", items),
+            Type::Map { key, value } => write!(f, "Map<{}, {}>", key, value),
+            Type::Object { fields } => {
+                write!(f, "Object {{ ")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", field)?;
+                }
+                write!(f, " }}")
+            }
+            Type::Enum { variants } => {
+                write!(f, "Enum [")?;
+                for (i, v) in variants.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", quote(v))?;
+                }
+                write!(f, "]")
+            }
+            Type::Optional { inner } => write!(f, "Optional<{}>", inner),
+            Type::Refined { base, refinements } => {
+                write!(f, "{}(", base)?;
+                for (i, r) in refinements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", r.name, json_literal(&r.value))?;
+                }
+                write!(f, ")")
+            }
+            Type::Named { name } => write!(f, "{}", name),
+        }
+    }
+}
+
+/// `name: Type = default`
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.ty)?;
+        if let Some(ref default) = self.default {
+            write!(f, " = {}", json_literal(default))?;
+        }
+        Ok(())
+    }
+}
+
+/// A JSON value as an ICL literal. Floats keep a decimal point so they
+/// parse back as `Float`; objects have no literal form and print as JSON.
+pub fn json_literal(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => quote(s),
+        serde_json::Value::Number(n) if n.is_f64() => {
+            let s = n.to_string();
+            if s.contains(['.', 'e', 'E']) {
+                s
+            } else {
+                format!("{}.0", s)
+            }
+        }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(json_literal).collect();
+            format!("[{}]", items.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn lower_field(state: &str, types: &str) -> Field {
+        let input = format!(
+            r#"Contract {{
+  Identity {{
+    stable_id: "ic-schema-001",
+    version: 1,
+    created_timestamp: 2026-02-01T00:00:00Z,
+    owner: "test",
+    semantic_hash: "0000000000000000"
+  }}
+  PurposeStatement {{
+    narrative: "Schema test",
+    intent_source: "test",
+    confidence_level: 1.0
+  }}
+  Types {{ {} }}
+  DataSemantics {{
+    state: {{ {} }},
+    invariants: []
+  }}
+  BehavioralSemantics {{ operations: [] }}
+  ExecutionConstraints {{
+    trigger_types: ["manual"],
+    resource_limits: {{ max_memory_bytes: 1, computation_timeout_ms: 1, max_state_size_bytes: 1 }},
+    external_permissions: [],
+    sandbox_mode: "full_isolation"
+  }}
+  HumanMachineContract {{
+    system_commitments: [],
+    system_refusals: [],
+    user_obligations: []
+  }}
+}}"#,
+            types, state
+        );
+        let ast = parse(&input).unwrap();
+        Field::from_node(&ast.data_semantics.state[0], ast.type_definitions())
+    }
+
+    #[test]
+    fn test_lower_resolves_nested_names() {
+        let field = lower_field(
+            "entries: Array<Entry> = []",
+            "Id: UUID, Entry: Object { id: Id, tags: Map<String, Integer> }",
+        );
+        assert_eq!(field.name, "entries");
+        assert_eq!(field.default, Some(serde_json::json!([])));
+        let Type::Array { items } = &field.ty else {
+            panic!("expected Array, got {:?}", field.ty);
+        };
+        let Type::Object { fields } = items.as_ref() else {
+            panic!("expected Object, got {:?}", items);
+        };
+        assert_eq!(fields[0].ty, Type::Uuid);
+        assert_eq!(
+            field.ty.to_string(),
+            "Array<Object { id: UUID, tags: Map<String, Integer> }>"
+        );
+    }
+
+    #[test]
+    fn test_lower_keeps_unresolvable_names() {
+        let field = lower_field("node: Node", "Node: Object { next: Optional<Node> }");
+        let Type::Object { fields } = &field.ty else {
+            panic!("expected Object, got {:?}", field.ty);
+        };
+        assert_eq!(
+            fields[0].ty,
+            Type::Optional {
+                inner: Box::new(Type::Named {
+                    name: "Node".into()
+                })
+            }
+        );
+        assert_eq!(
+            lower_field("x: Missing", "").ty,
+            Type::Named {
+                name: "Missing".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_lower_merges_alias_refinements() {
+        let field = lower_field(
+            "pct: Percent(max: 50) = 5",
+            "Percent: Integer(min: 0, max: 100)",
+        );
+        assert_eq!(field.ty.to_string(), "Integer(min: 0, max: 100, max: 50)");
+        assert_eq!(field.ty.base(), &Type::Integer);
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        for text in [
+            "Map<String, Optional<Float(min: -1.5)>>",
+            "Enum [\"a\", \"b \\\"c\\\"\"]",
+            "String(pattern: \"^\\\\d+$\", max_len: 3)",
+            "Array<Object { id: UUID, at: ISO8601, ok: Boolean }>(max_items: 2)",
+        ] {
+            let ty: Type = text.parse().unwrap();
+            let reparsed: Type = ty.to_string().parse().unwrap();
+            assert_eq!(ty, reparsed, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_field_json_roundtrip() {
+        let fields = [
+            lower_field("note: Optional<String> = null", ""),
+            lower_field("note: Optional<String>", ""),
+            lower_field("level: Enum [\"low\", \"high\"] = \"low\"", ""),
+        ];
+        for field in fields {
+            let json = serde_json::to_string(&field).unwrap();
+            let back: Field = serde_json::from_str(&json).unwrap();
+            assert_eq!(field, back, "{}", json);
+        }
+        let json = serde_json::to_value(lower_field("ratio: Float(max: 1.0)", "")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "name": "ratio",
+                "type": {
+                    "kind": "refined",
+                    "base": { "kind": "float" },
+                    "refinements": [{ "name": "max", "value": 1.0 }]
+                }
+            })
+        );
+    }
+}
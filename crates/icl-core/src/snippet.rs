@@ -0,0 +1,169 @@
+//! Source snippets — renders a span of ICL text with underline carets
+//!
+//! Used to show parse errors and verifier diagnostics in context:
+//!
+//! ```text
+//! error: Expected integer literal, found StringLiteral("one")
+//!  --> contract.icl:4:14
+//!   |
+//! 4 |     version: "one",
+//!   |              ^^^^^
+//! ```
+//!
+//! # Guarantees
+//!
+//! - **Deterministic**: same source and span always render identically
+//! - **Total**: out-of-range or mid-character spans are clamped, never panic
+
+use crate::parser::tokenizer::Span;
+
+/// Render a diagnostic header, location line and source snippet.
+///
+/// `origin` names the source (usually a file path) in the `-->` line.
+pub fn render(source: &str, origin: &str, header: &str, span: &Span) -> String {
+    let (line, column, _, _) = locate(source, span);
+    format!(
+        "{}\n{}--> {}:{}:{}\n{}",
+        header,
+        " ".repeat(gutter_width(line)),
+        origin,
+        line,
+        column,
+        render_snippet(source, span)
+    )
+}
+
+/// Render the source line containing `span` with carets under the spanned
+/// text. Spans running past the end of their first line are underlined up
+/// to the end of that line; empty spans get a single caret.
+pub fn render_snippet(source: &str, span: &Span) -> String {
+    let (line, _, start, end) = locate(source, span);
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |i| start + i);
+    let text = source[line_start..line_end].trim_end_matches('\r');
+
+    // Keep tabs so the carets line up with the source line
+    let indent: String = source[line_start..start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let width = source[start..end.min(line_end)].chars().count().max(1);
+
+    let pad = " ".repeat(gutter_width(line));
+    format!(
+        "{} |\n{} | {}\n{} | {}{}",
+        pad,
+        line,
+        text,
+        pad,
+        indent,
+        "^".repeat(width)
+    )
+}
+
+/// Render a note aligned with the gutter of the snippet for `span`, as in
+/// ` = help: remove the duplicate`
+pub fn render_note(source: &str, span: &Span, note: &str) -> String {
+    let (line, _, _, _) = locate(source, span);
+    format!("{} = {}", " ".repeat(gutter_width(line)), note)
+}
+
+/// Clamp a span to `source` and return (line, column, start, end), with
+/// start/end as byte offsets on character boundaries.
+fn locate(source: &str, span: &Span) -> (usize, usize, usize, usize) {
+    let start = floor_char_boundary(source, span.offset);
+    let end = floor_char_boundary(source, span.end).max(start);
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line = source[..start].matches('\n').count() + 1;
+    let column = source[line_start..start].chars().count() + 1;
+    (line, column, start, end)
+}
+
+fn floor_char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+fn gutter_width(line: usize) -> usize {
+    line.to_string().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(offset: usize, end: usize) -> Span {
+        Span {
+            line: 0,
+            column: 0,
+            offset,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_render_snippet_underlines_span() {
+        let source = "Contract {\n  version: \"one\",\n}";
+        let snippet = render_snippet(source, &span(22, 27));
+        assert_eq!(
+            snippet,
+            "  |\n2 |   version: \"one\",\n  |            ^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_render_includes_header_and_location() {
+        let source = "a\nb\nc";
+        let out = render(source, "x.icl", "error: bad", &span(4, 5));
+        assert_eq!(out, "error: bad\n --> x.icl:3:1\n  |\n3 | c\n  | ^");
+    }
+
+    #[test]
+    fn test_render_note_aligns_with_gutter() {
+        let source = "a\n".repeat(9) + "b";
+        assert_eq!(render_note(&source, &span(0, 1), "help: x"), "  = help: x");
+        assert_eq!(
+            render_note(&source, &span(18, 19), "help: x"),
+            "   = help: x"
+        );
+    }
+
+    #[test]
+    fn test_render_snippet_empty_span_gets_one_caret() {
+        let snippet = render_snippet("abc", &span(3, 3));
+        assert!(snippet.ends_with("|    ^"), "{}", snippet);
+    }
+
+    #[test]
+    fn test_render_snippet_multiline_span_stops_at_line_end() {
+        let snippet = render_snippet("ab{\n  x\n}", &span(2, 9));
+        assert!(snippet.ends_with("|   ^"), "{}", snippet);
+    }
+
+    #[test]
+    fn test_render_snippet_clamps_out_of_range() {
+        let snippet = render_snippet("é", &span(1, 100));
+        assert!(snippet.contains("1 | é"), "{}", snippet);
+    }
+
+    #[test]
+    fn test_render_snippet_keeps_tabs_aligned() {
+        let snippet = render_snippet("\tx: 1", &span(4, 5));
+        assert!(snippet.ends_with("| \t   ^"), "{}", snippet);
+    }
+
+    #[test]
+    fn test_render_from_parse_error_span() {
+        let source = "Contract {\n  Identity {\n    stable_id: 42\n  }\n}";
+        let result = crate::parser::parse_with_diagnostics(source);
+        let d = &result.diagnostics[0];
+        let snippet = render_snippet(source, &d.span);
+        assert!(snippet.contains("3 |     stable_id: 42"), "{}", snippet);
+        assert!(snippet.ends_with("|                ^^"), "{}", snippet);
+    }
+}
@@ -12,14 +12,35 @@
 //! # Verification Phases (per spec §4.1)
 //!
 //! 1. **Type Correctness** — All types well-formed, defaults match declared types
-//! 2. **Invariant Consistency** — Invariants reference valid state fields
+//! 2. **Invariant Consistency** — Invariants reference valid state fields and
+//!    can hold together (no contradictory numeric ranges or enum variants),
+//!    starting with the initial state built from the declared defaults; each
+//!    operation is proved to preserve them, refuted with a counterexample
+//!    input, or left unknown. The satisfiability and preservation analyses
+//!    live in their own modules ([`crate::preservation`])
 //! 3. **Determinism** — No non-deterministic patterns detected
 //! 4. **Coherence** — Structural validity (unique names, valid ranges, feasible limits)
+//! 5. **Integrity** — The declared semantic_hash is the hash of the contract
+//!
+//! Every check reports through a named [`lint::Rule`]; project levels and
+//! `icl-allow` comments are applied once the phases have run, so a rule
+//! downgraded to a warning does not block the preservation analysis.
 
 use std::collections::BTreeSet;
 
+use regex::Regex;
+
+use crate::builtins;
+use crate::executor::{check_refinements, ExecutionState, ExpressionEvaluator, Value};
+use crate::fix::{self, Fix, TextEdit};
+use crate::formats::parse_timestamp;
+use crate::lint::{self, LintConfig, Rule, Suppressions};
 use crate::parser::ast::*;
 use crate::parser::tokenizer::Span;
+use crate::preservation::verify_preservation;
+pub use crate::preservation::{OperationPreservation, Preservation};
+use crate::satisfiability::verify_invariants_satisfiable;
+use crate::schema::{Field, Type};
 
 // ── Verification Result Types ─────────────────────────────
 
@@ -27,12 +48,16 @@ use crate::parser::tokenizer::Span;
 #[derive(Debug, Clone)]
 pub struct VerificationResult {
     pub diagnostics: Vec<Diagnostic>,
+    /// Invariant preservation per operation, in declaration order; empty
+    /// when verification found errors
+    pub preservation: Vec<OperationPreservation>,
 }
 
 impl VerificationResult {
     pub fn new() -> Self {
         Self {
             diagnostics: Vec::new(),
+            preservation: Vec::new(),
         }
     }
 
@@ -60,21 +85,27 @@ impl VerificationResult {
             .collect()
     }
 
-    fn add_error(&mut self, kind: DiagnosticKind, message: String, span: Option<Span>) {
-        self.diagnostics.push(Diagnostic {
-            severity: Severity::Error,
-            kind,
-            message,
-            span,
-        });
+    /// Record a finding of `rule` at its default severity; project levels
+    /// and suppressions are applied once all phases have run
+    pub(crate) fn report(&mut self, rule: &'static Rule, message: String, span: Option<Span>) {
+        self.report_fix(rule, message, span, None);
     }
 
-    fn add_warning(&mut self, kind: DiagnosticKind, message: String, span: Option<Span>) {
+    /// Report a diagnostic that `fix` resolves
+    fn report_fix(
+        &mut self,
+        rule: &'static Rule,
+        message: String,
+        span: Option<Span>,
+        fix: Option<Fix>,
+    ) {
         self.diagnostics.push(Diagnostic {
-            severity: Severity::Warning,
-            kind,
+            severity: rule.default_severity,
+            kind: rule.kind,
+            rule,
             message,
             span,
+            fix,
         });
     }
 }
@@ -90,8 +121,12 @@ impl Default for VerificationResult {
 pub struct Diagnostic {
     pub severity: Severity,
     pub kind: DiagnosticKind,
+    /// The check that reported it
+    pub rule: &'static Rule,
     pub message: String,
     pub span: Option<Span>,
+    /// Edits to the source that resolve it
+    pub fix: Option<Fix>,
 }
 
 impl std::fmt::Display for Diagnostic {
@@ -103,15 +138,44 @@ impl std::fmt::Display for Diagnostic {
         if let Some(ref span) = self.span {
             write!(
                 f,
-                "{} [{}] at {}: {}",
-                prefix, self.kind, span, self.message
+                "{}[{}] [{}] at {}: {}",
+                prefix, self.rule.code, self.kind, span, self.message
             )
         } else {
-            write!(f, "{} [{}]: {}", prefix, self.kind, self.message)
+            write!(
+                f,
+                "{}[{}] [{}]: {}",
+                prefix, self.rule.code, self.kind, self.message
+            )
         }
     }
 }
 
+impl Diagnostic {
+    /// Render with the source line and underline carets when the
+    /// diagnostic has a span
+    pub fn render(&self, source: &str, origin: &str) -> String {
+        let prefix = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let header = format!(
+            "{}[{}]: [{}] {}",
+            prefix, self.rule.code, self.kind, self.message
+        );
+        let Some(ref span) = self.span else {
+            return header;
+        };
+        let mut out = crate::snippet::render(source, origin, &header, span);
+        if let Some(ref fix) = self.fix {
+            let note = format!("help: {}", fix.message);
+            out.push('\n');
+            out.push_str(&crate::snippet::render_note(source, span, &note));
+        }
+        out
+    }
+}
+
 /// Severity level for diagnostics
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Severity {
@@ -141,12 +205,31 @@ impl std::fmt::Display for DiagnosticKind {
 
 // ── Public API ────────────────────────────────────────────
 
+/// Verification policy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerifyOptions {
+    /// Reject every precondition, postcondition and invariant that is not
+    /// machine-checkable (opaque prose) instead of accepting it unchecked
+    pub strict_conditions: bool,
+    /// Project rule levels; rules set to `off` report nothing
+    pub lint: LintConfig,
+    /// `icl-allow` comments of the contract source
+    pub suppressions: Suppressions,
+}
+
 /// Verify a parsed contract AST for correctness.
 ///
 /// Runs all verification phases and returns accumulated diagnostics.
 /// Does not stop at first error — reports everything found.
 pub fn verify(ast: &ContractNode) -> VerificationResult {
+    verify_with_options(ast, &VerifyOptions::default())
+}
+
+/// Verify a parsed contract AST under the given policy.
+pub fn verify_with_options(ast: &ContractNode, options: &VerifyOptions) -> VerificationResult {
     let mut result = VerificationResult::new();
+    let hashed = ast;
+    let ast = &without_flag_prose(ast);
 
     // Phase 3.1 — Type Checker
     verify_types(ast, &mut result);
@@ -160,37 +243,136 @@ pub fn verify(ast: &ContractNode) -> VerificationResult {
     // Phase 3.4 — Coherence Verifier
     verify_coherence(ast, &mut result);
 
+    if options.strict_conditions {
+        verify_conditions_checkable(ast, &mut result);
+    }
+
+    verify_suppressions(&options.suppressions, &mut result);
+    apply_lint(&mut result, options);
+
+    // Preservation runs operations, so only on an otherwise valid contract
+    if result.is_valid() {
+        verify_preservation(ast, &mut result);
+        apply_lint(&mut result, options);
+    }
+
+    // Phase 3.5 — Integrity, after preservation: a stale hash says
+    // nothing about how the operations behave
+    verify_integrity(hashed, &mut result);
+    apply_lint(&mut result, options);
+
     result
 }
 
+/// The contract with conditions that are flag prose (see
+/// [`Expr::is_flag_prose`]) made opaque: `input_provided` names no state
+/// field or parameter, so it is prose rather than an unknown identifier
+fn without_flag_prose(ast: &ContractNode) -> ContractNode {
+    let mut ast = ast.clone();
+    let state: Vec<String> = ast
+        .data_semantics
+        .state
+        .iter()
+        .map(|f| f.name.value.clone())
+        .collect();
+    let strip = |condition: &mut ConditionNode, params: &[StateFieldNode]| {
+        let in_scope = |name: &str| {
+            state.iter().any(|s| s == name) || params.iter().any(|p| p.name.value == name)
+        };
+        if condition
+            .expr
+            .as_ref()
+            .is_some_and(|expr| expr.is_flag_prose(&in_scope))
+        {
+            condition.expr = None;
+        }
+    };
+    for invariant in &mut ast.data_semantics.invariants {
+        strip(invariant, &[]);
+    }
+    for op in &mut ast.behavioral_semantics.operations {
+        strip(&mut op.precondition, &op.parameters);
+        strip(&mut op.postcondition, &op.parameters);
+    }
+    ast
+}
+
+/// Report `icl-allow` comments naming rules that do not exist
+fn verify_suppressions(suppressions: &Suppressions, result: &mut VerificationResult) {
+    for allow in suppressions.unknown() {
+        result.report(
+            &lint::UNKNOWN_LINT_RULE,
+            format!("icl-allow names unknown lint rule '{}'", allow.rule),
+            Some(allow.span.clone()),
+        );
+    }
+}
+
+/// Set each diagnostic to its rule's configured level, dropping those
+/// that are turned off or allowed by a comment on their line
+fn apply_lint(result: &mut VerificationResult, options: &VerifyOptions) {
+    result.diagnostics.retain_mut(|d| {
+        let suppressed = d
+            .span
+            .as_ref()
+            .is_some_and(|span| options.suppressions.allows(d.rule, span.line));
+        d.severity = match options.lint.level(d.rule) {
+            lint::Level::Error => Severity::Error,
+            lint::Level::Warning => Severity::Warning,
+            lint::Level::Off => return false,
+        };
+        !suppressed
+    });
+}
+
 // ── Phase 3.1: Type Checker ──────────────────────────────
 
 /// Validate all types in the contract are well-formed and defaults match declared types.
 fn verify_types(ast: &ContractNode, result: &mut VerificationResult) {
     // Check Identity constraints
     verify_identity_types(&ast.identity, result);
+    verify_semantic_hash(ast, result);
 
     // Check PurposeStatement constraints
     verify_purpose_types(&ast.purpose_statement, result);
 
+    // Check named type definitions
+    let types = ast.type_definitions();
+    verify_type_definitions(types, result);
+
     // Check state field types
     for field in &ast.data_semantics.state {
-        verify_type_expression(&field.type_expr, result);
+        verify_type_expression(&field.type_expr, types, result);
         if let Some(ref default) = field.default_value {
-            verify_default_matches_type(&field.name.value, &field.type_expr, default, result);
+            verify_default_matches_type(
+                &field.name.value,
+                &field.type_expr,
+                default,
+                types,
+                result,
+            );
         }
     }
 
     // Check operation parameter types
     for op in &ast.behavioral_semantics.operations {
         for param in &op.parameters {
-            verify_type_expression(&param.type_expr, result);
+            verify_type_expression(&param.type_expr, types, result);
             if let Some(ref default) = param.default_value {
-                verify_default_matches_type(&param.name.value, &param.type_expr, default, result);
+                verify_default_matches_type(
+                    &param.name.value,
+                    &param.type_expr,
+                    default,
+                    types,
+                    result,
+                );
             }
         }
     }
 
+    // Check condition paths and comparisons against the declared types
+    verify_condition_types(ast, result);
+
     // Check resource limits are valid
     verify_resource_limit_types(&ast.execution_constraints.resource_limits, result);
 }
@@ -199,8 +381,8 @@ fn verify_types(ast: &ContractNode, result: &mut VerificationResult) {
 fn verify_identity_types(identity: &IdentityNode, result: &mut VerificationResult) {
     // Version must be non-negative
     if identity.version.value < 0 {
-        result.add_error(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::NEGATIVE_VERSION,
             format!(
                 "version must be non-negative, found {}",
                 identity.version.value
@@ -212,8 +394,8 @@ fn verify_identity_types(identity: &IdentityNode, result: &mut VerificationResul
     // stable_id must match pattern: [a-z0-9][a-z0-9\-]{0,30}[a-z0-9]
     let sid = &identity.stable_id.value;
     if !is_valid_stable_id(sid) {
-        result.add_error(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::INVALID_STABLE_ID,
             format!(
                 "stable_id '{}' does not match required pattern [a-z0-9][a-z0-9-]{{0,30}}[a-z0-9]",
                 sid
@@ -221,18 +403,38 @@ fn verify_identity_types(identity: &IdentityNode, result: &mut VerificationResul
             Some(identity.stable_id.span.clone()),
         );
     }
+}
 
-    // semantic_hash must be valid hex
-    let hash = &identity.semantic_hash.value;
-    if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
-        result.add_error(
-            DiagnosticKind::TypeError,
-            format!("semantic_hash '{}' is not valid hexadecimal", hash),
-            Some(identity.semantic_hash.span.clone()),
+/// Verify semantic_hash is valid hex; the fix sets it to the hash of the
+/// contract
+fn verify_semantic_hash(ast: &ContractNode, result: &mut VerificationResult) {
+    let declared = &ast.identity.semantic_hash;
+    if !declared.value.chars().all(|c| c.is_ascii_hexdigit()) {
+        result.report_fix(
+            &lint::INVALID_SEMANTIC_HASH,
+            format!(
+                "semantic_hash '{}' is not valid hexadecimal",
+                declared.value
+            ),
+            Some(declared.span.clone()),
+            Some(set_semantic_hash(declared, &computed_hash(ast))),
         );
     }
 }
 
+/// Canonical semantic hash of the contract
+fn computed_hash(ast: &ContractNode) -> String {
+    crate::normalizer::compute_semantic_hash(&crate::normalizer::normalize_ast(ast.clone()))
+}
+
+/// Replace the declared semantic_hash with `hash`
+fn set_semantic_hash(declared: &SpannedValue<String>, hash: &str) -> Fix {
+    Fix::safe(
+        "set semantic_hash to the computed hash",
+        vec![TextEdit::replace(&declared.span, format!("\"{}\"", hash))],
+    )
+}
+
 /// Check if a stable_id matches the spec pattern
 fn is_valid_stable_id(id: &str) -> bool {
     if id.len() < 2 || id.len() > 32 {
@@ -253,8 +455,8 @@ fn verify_purpose_types(purpose: &PurposeStatementNode, result: &mut Verificatio
     // confidence_level must be in [0.0, 1.0]
     let cl = purpose.confidence_level.value;
     if !(0.0..=1.0).contains(&cl) {
-        result.add_error(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::CONFIDENCE_OUT_OF_RANGE,
             format!("confidence_level must be in range [0.0, 1.0], found {}", cl),
             Some(purpose.confidence_level.span.clone()),
         );
@@ -262,8 +464,8 @@ fn verify_purpose_types(purpose: &PurposeStatementNode, result: &mut Verificatio
 
     // narrative should be < 500 chars (warning, not error)
     if purpose.narrative.value.len() > 500 {
-        result.add_warning(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::LONG_NARRATIVE,
             format!(
                 "narrative exceeds recommended 500 character limit ({} chars)",
                 purpose.narrative.value.len()
@@ -273,38 +475,132 @@ fn verify_purpose_types(purpose: &PurposeStatementNode, result: &mut Verificatio
     }
 }
 
+/// Verify the `Types` section: unique names, well-formed bodies, and no
+/// definition that refers back to itself
+fn verify_type_definitions(types: &[TypeDefinitionNode], result: &mut VerificationResult) {
+    let mut seen = BTreeSet::new();
+    for definition in types {
+        if !seen.insert(&definition.name.value) {
+            result.report(
+                &lint::DUPLICATE_TYPE_NAME,
+                format!("duplicate type name '{}'", definition.name.value),
+                Some(definition.name.span.clone()),
+            );
+        }
+        verify_type_expression(&definition.type_expr, types, result);
+    }
+
+    for definition in types {
+        let mut path = vec![definition.name.value.as_str()];
+        if find_type_cycle(&definition.type_expr, types, &mut path) {
+            result.report(
+                &lint::RECURSIVE_TYPE,
+                format!(
+                    "recursive type '{}' ({})",
+                    definition.name.value,
+                    path.join(" -> ")
+                ),
+                Some(definition.name.span.clone()),
+            );
+        }
+    }
+}
+
+/// Depth-first search for a reference back to `path[0]`. On success `path`
+/// holds the cycle, e.g. `["A", "B", "A"]`.
+fn find_type_cycle<'a>(
+    type_expr: &'a TypeExpression,
+    types: &'a [TypeDefinitionNode],
+    path: &mut Vec<&'a str>,
+) -> bool {
+    match type_expr {
+        TypeExpression::Primitive(_, _) | TypeExpression::Enum(_, _) => false,
+        TypeExpression::Array(inner, _)
+        | TypeExpression::Optional(inner, _)
+        | TypeExpression::Refined(inner, _, _) => find_type_cycle(inner, types, path),
+        TypeExpression::Map(key, value, _) => {
+            find_type_cycle(key, types, path) || find_type_cycle(value, types, path)
+        }
+        TypeExpression::Object(fields, _) => fields
+            .iter()
+            .any(|f| find_type_cycle(&f.type_expr, types, path)),
+        TypeExpression::Named(name, _) => {
+            if name == path[0] {
+                path.push(name);
+                return true;
+            }
+            // Cycles not through path[0] are reported for their own members
+            if path.contains(&name.as_str()) {
+                return false;
+            }
+            let Some(definition) = types.iter().find(|d| d.name.value == *name) else {
+                return false;
+            };
+            path.push(name);
+            if find_type_cycle(&definition.type_expr, types, path) {
+                return true;
+            }
+            path.pop();
+            false
+        }
+    }
+}
+
 /// Verify a type expression is well-formed
-fn verify_type_expression(type_expr: &TypeExpression, result: &mut VerificationResult) {
+fn verify_type_expression(
+    type_expr: &TypeExpression,
+    types: &[TypeDefinitionNode],
+    result: &mut VerificationResult,
+) {
     match type_expr {
         TypeExpression::Primitive(_, _) => {
             // All primitive types are valid by construction
         }
         TypeExpression::Array(inner, _) => {
-            verify_type_expression(inner, result);
+            verify_type_expression(inner, types, result);
+        }
+        TypeExpression::Optional(inner, span) => {
+            verify_type_expression(inner, types, result);
+            if inner.is_optional(types) {
+                result.report_fix(
+                    &lint::NESTED_OPTIONAL,
+                    format!(
+                        "{} is the same as {}",
+                        type_expr_name(type_expr),
+                        type_expr_name(inner)
+                    ),
+                    Some(span.clone()),
+                    Some(Fix::safe(
+                        "remove the outer Optional",
+                        unwrap_edits(span, inner.span()),
+                    )),
+                );
+            }
         }
         TypeExpression::Map(key, value, span) => {
             // Map keys must be a hashable/comparable type
-            verify_type_expression(key, result);
-            verify_type_expression(value, result);
-            verify_map_key_type(key, span, result);
+            verify_type_expression(key, types, result);
+            verify_type_expression(value, types, result);
+            verify_map_key_type(key, span, types, result);
         }
         TypeExpression::Object(fields, _) => {
             // Check for duplicate field names
             let mut seen = BTreeSet::new();
             for field in fields {
                 if !seen.insert(&field.name.value) {
-                    result.add_error(
-                        DiagnosticKind::TypeError,
+                    result.report(
+                        &lint::DUPLICATE_OBJECT_FIELD,
                         format!("duplicate field name '{}' in Object type", field.name.value),
                         Some(field.name.span.clone()),
                     );
                 }
-                verify_type_expression(&field.type_expr, result);
+                verify_type_expression(&field.type_expr, types, result);
                 if let Some(ref default) = field.default_value {
                     verify_default_matches_type(
                         &field.name.value,
                         &field.type_expr,
                         default,
+                        types,
                         result,
                     );
                 }
@@ -313,24 +609,200 @@ fn verify_type_expression(type_expr: &TypeExpression, result: &mut VerificationR
         TypeExpression::Enum(variants, span) => {
             // Enum must have at least one variant
             if variants.is_empty() {
-                result.add_error(
-                    DiagnosticKind::TypeError,
+                result.report(
+                    &lint::EMPTY_ENUM,
                     "Enum type must have at least one variant".to_string(),
                     Some(span.clone()),
                 );
             }
             // Enum variants must be unique
             let mut seen = BTreeSet::new();
-            for variant in variants {
+            for (i, variant) in variants.iter().enumerate() {
                 if !seen.insert(&variant.value) {
-                    result.add_error(
-                        DiagnosticKind::TypeError,
+                    result.report_fix(
+                        &lint::DUPLICATE_ENUM_VARIANT,
                         format!("duplicate Enum variant '{}'", variant.value),
                         Some(variant.span.clone()),
+                        Some(Fix::safe(
+                            format!("remove the duplicate '{}'", variant.value),
+                            vec![remove_from_list(&variants[i - 1].span, &variant.span)],
+                        )),
                     );
                 }
             }
         }
+        TypeExpression::Refined(base, refinements, _) => {
+            verify_type_expression(base, types, result);
+            verify_refinements(base, refinements, types, result);
+        }
+        TypeExpression::Named(name, span) => {
+            if !types.iter().any(|d| d.name.value == *name) {
+                result.report(
+                    &lint::UNKNOWN_TYPE,
+                    format!("unknown type '{}'", name),
+                    Some(span.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// Verify the refinements of `base(...)`: each is known, applies to the
+/// base type, appears once and has a value of the right kind, and lower
+/// bounds do not exceed upper bounds
+fn verify_refinements(
+    base: &TypeExpression,
+    refinements: &[RefinementNode],
+    types: &[TypeDefinitionNode],
+    result: &mut VerificationResult,
+) {
+    // Unknown or recursive base types are already reported
+    let Some(target) = refinement_target(base, types) else {
+        return;
+    };
+    let mut seen = BTreeSet::new();
+    let mut bounds = Vec::new();
+    for refinement in refinements {
+        let name = refinement.name.value.as_str();
+        let error = |message: String| (message, refinement.span.clone());
+        let problem = if !seen.insert(name) {
+            Some(error(format!("duplicate refinement '{}'", name)))
+        } else {
+            match refinement_applies(name, target) {
+                None => Some(error(format!(
+                    "unknown refinement '{}' (expected min, max, min_len, max_len, pattern, min_items or max_items)",
+                    name
+                ))),
+                Some(false) if matches!(target, TypeExpression::Optional(..)) => {
+                    Some(error(format!(
+                        "refinement '{}' does not apply to {} (refine the inner type instead)",
+                        name,
+                        type_expr_name(target)
+                    )))
+                }
+                Some(false) => Some(error(format!(
+                    "refinement '{}' does not apply to {}",
+                    name,
+                    type_expr_name(target)
+                ))),
+                Some(true) => refinement_value_error(name, &refinement.value, target)
+                    .map(|message| (message, refinement.value.span().clone())),
+            }
+        };
+        match problem {
+            Some((message, span)) => result.report(&lint::INVALID_REFINEMENT, message, Some(span)),
+            None => bounds.push(refinement),
+        }
+    }
+
+    for (lower, upper) in [
+        ("min", "max"),
+        ("min_len", "max_len"),
+        ("min_items", "max_items"),
+    ] {
+        let bound = |name: &str| {
+            bounds
+                .iter()
+                .find(|r| r.name.value == name)
+                .and_then(|r| literal_number(&r.value).map(|n| (n, *r)))
+        };
+        if let (Some((lo, lower_node)), Some((hi, upper_node))) = (bound(lower), bound(upper)) {
+            if lo > hi {
+                result.report(
+                    &lint::INVERTED_REFINEMENT_BOUNDS,
+                    format!(
+                        "refinement '{}' ({}) is greater than '{}' ({})",
+                        lower, lower_node.value, upper, upper_node.value
+                    ),
+                    Some(lower_node.span.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// The type a refinement constrains: `Named` aliases and nested
+/// refinements are looked through. `None` for unknown or recursive names.
+fn refinement_target<'a>(
+    base: &'a TypeExpression,
+    types: &'a [TypeDefinitionNode],
+) -> Option<&'a TypeExpression> {
+    match base.resolve(types) {
+        TypeExpression::Named(_, _) => None,
+        TypeExpression::Refined(inner, _, _) => refinement_target(inner, types),
+        resolved => Some(resolved),
+    }
+}
+
+/// Whether refinement `name` applies to `target` — `None` if unknown
+fn refinement_applies(name: &str, target: &TypeExpression) -> Option<bool> {
+    let applies = match name {
+        "min" | "max" => matches!(
+            target,
+            TypeExpression::Primitive(PrimitiveType::Integer | PrimitiveType::Float, _)
+        ),
+        "min_len" | "max_len" | "pattern" => {
+            matches!(target, TypeExpression::Primitive(PrimitiveType::String, _))
+        }
+        "min_items" | "max_items" => {
+            matches!(target, TypeExpression::Array(..) | TypeExpression::Map(..))
+        }
+        _ => return None,
+    };
+    Some(applies)
+}
+
+/// Check the value of a refinement that applies to `target`
+fn refinement_value_error(
+    name: &str,
+    value: &LiteralValue,
+    target: &TypeExpression,
+) -> Option<String> {
+    match (name, value) {
+        ("min" | "max", LiteralValue::Integer(_, _)) => None,
+        ("min" | "max", LiteralValue::Float(_, _))
+            if matches!(target, TypeExpression::Primitive(PrimitiveType::Float, _)) =>
+        {
+            None
+        }
+        ("min" | "max", _) => Some(format!(
+            "refinement '{}' of {} must be {}, found {}",
+            name,
+            type_expr_name(target),
+            type_expr_name(target),
+            literal_type_name(value)
+        )),
+        ("pattern", LiteralValue::String(pattern, _)) => pattern_error(pattern),
+        ("pattern", _) => Some(format!(
+            "refinement 'pattern' must be a String, found {}",
+            literal_type_name(value)
+        )),
+        (_, LiteralValue::Integer(n, _)) if *n >= 0 => None,
+        _ => Some(format!(
+            "refinement '{}' must be a non-negative Integer, found {}",
+            name, value
+        )),
+    }
+}
+
+/// Why `pattern` is not a valid regular expression, if it is not
+fn pattern_error(pattern: &str) -> Option<String> {
+    Regex::new(pattern).err().map(|e| {
+        let reason = e.to_string();
+        let reason = reason.lines().last().unwrap_or_default();
+        format!(
+            "invalid pattern {}: {}",
+            quote(pattern),
+            reason.trim_start_matches("error: ")
+        )
+    })
+}
+
+pub(crate) fn literal_number(lit: &LiteralValue) -> Option<f64> {
+    match lit {
+        LiteralValue::Integer(n, _) => Some(*n as f64),
+        LiteralValue::Float(f, _) => Some(*f),
+        _ => None,
     }
 }
 
@@ -338,9 +810,10 @@ fn verify_type_expression(type_expr: &TypeExpression, result: &mut VerificationR
 fn verify_map_key_type(
     key_type: &TypeExpression,
     map_span: &Span,
+    types: &[TypeDefinitionNode],
     result: &mut VerificationResult,
 ) {
-    match key_type {
+    match key_type.resolve(types) {
         TypeExpression::Primitive(pt, _) => match pt {
             PrimitiveType::String
             | PrimitiveType::Integer
@@ -350,19 +823,31 @@ fn verify_map_key_type(
                 // Valid key types
             }
             PrimitiveType::Float => {
-                result.add_error(
-                    DiagnosticKind::TypeError,
+                // Only a literal Float key can be replaced; a named type
+                // may be used elsewhere
+                let fix = matches!(key_type, TypeExpression::Primitive(..)).then(|| {
+                    Fix::suggestion(
+                        "key the Map by String",
+                        vec![TextEdit::replace(key_type.span(), "String")],
+                    )
+                });
+                result.report_fix(
+                    &lint::FLOAT_MAP_KEY,
                     "Float cannot be used as Map key type (non-deterministic equality)".to_string(),
                     Some(map_span.clone()),
+                    fix,
                 );
             }
         },
         TypeExpression::Enum(_, _) => {
             // Enum is a valid key type (string-based)
         }
+        TypeExpression::Named(_, _) => {
+            // Unknown or recursive — already reported
+        }
         _ => {
-            result.add_error(
-                DiagnosticKind::TypeError,
+            result.report(
+                &lint::INVALID_MAP_KEY,
                 format!(
                     "Map key type must be a primitive or Enum, found {}",
                     type_expr_name(key_type)
@@ -378,12 +863,29 @@ fn verify_default_matches_type(
     field_name: &str,
     type_expr: &TypeExpression,
     default: &LiteralValue,
+    types: &[TypeDefinitionNode],
     result: &mut VerificationResult,
 ) {
-    let matches = default_matches_type(type_expr, default);
+    // Defaults for unknown or recursive types are not checked
+    if refinement_target(type_expr, types).is_none() {
+        return;
+    }
+    if matches!(default, LiteralValue::Null(_)) && !type_expr.is_optional(types) {
+        result.report(
+            &lint::DEFAULT_TYPE_MISMATCH,
+            format!(
+                "null default for non-optional field '{}' (declare it as Optional<{}>)",
+                field_name,
+                type_expr_name(type_expr),
+            ),
+            Some(literal_span(default)),
+        );
+        return;
+    }
+    let matches = default_matches_type(type_expr, default, types);
     if !matches {
-        result.add_error(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::DEFAULT_TYPE_MISMATCH,
             format!(
                 "default value for '{}' has type {}, expected {}",
                 field_name,
@@ -392,12 +894,29 @@ fn verify_default_matches_type(
             ),
             Some(literal_span(default)),
         );
+    } else if let Err(violation) = check_refinements(
+        field_name,
+        &Value::from_literal(default),
+        &Type::from_expr(type_expr, types),
+    ) {
+        result.report(
+            &lint::DEFAULT_TYPE_MISMATCH,
+            format!(
+                "default value violates the type of '{}': {}",
+                field_name, violation
+            ),
+            Some(literal_span(default)),
+        );
     }
 }
 
 /// Check if a literal value is compatible with a type expression
-fn default_matches_type(type_expr: &TypeExpression, default: &LiteralValue) -> bool {
-    match (type_expr, default) {
+fn default_matches_type(
+    type_expr: &TypeExpression,
+    default: &LiteralValue,
+    types: &[TypeDefinitionNode],
+) -> bool {
+    match (type_expr.resolve(types), default) {
         (TypeExpression::Primitive(PrimitiveType::Integer, _), LiteralValue::Integer(_, _)) => true,
         (TypeExpression::Primitive(PrimitiveType::Float, _), LiteralValue::Float(_, _)) => true,
         // Allow integer literals as float defaults (e.g., 0 for Float)
@@ -411,10 +930,14 @@ fn default_matches_type(type_expr: &TypeExpression, default: &LiteralValue) -> b
         (TypeExpression::Enum(variants, _), LiteralValue::String(s, _)) => {
             variants.iter().any(|v| v.value == *s)
         }
+        // Optional accepts null or a value of the inner type
+        (TypeExpression::Optional(_, _), LiteralValue::Null(_)) => true,
+        (TypeExpression::Optional(inner, _), value) => default_matches_type(inner, value, types),
+        (TypeExpression::Refined(base, _, _), value) => default_matches_type(base, value, types),
         // Array default must be array of matching elements
-        (TypeExpression::Array(elem_type, _), LiteralValue::Array(elems, _)) => {
-            elems.iter().all(|e| default_matches_type(elem_type, e))
-        }
+        (TypeExpression::Array(elem_type, _), LiteralValue::Array(elems, _)) => elems
+            .iter()
+            .all(|e| default_matches_type(elem_type, e, types)),
         _ => false,
     }
 }
@@ -422,8 +945,8 @@ fn default_matches_type(type_expr: &TypeExpression, default: &LiteralValue) -> b
 /// Verify resource limits are valid positive values
 fn verify_resource_limit_types(limits: &ResourceLimitsNode, result: &mut VerificationResult) {
     if limits.max_memory_bytes.value <= 0 {
-        result.add_error(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::NON_POSITIVE_RESOURCE_LIMIT,
             format!(
                 "max_memory_bytes must be positive, found {}",
                 limits.max_memory_bytes.value
@@ -432,8 +955,8 @@ fn verify_resource_limit_types(limits: &ResourceLimitsNode, result: &mut Verific
         );
     }
     if limits.computation_timeout_ms.value <= 0 {
-        result.add_error(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::NON_POSITIVE_RESOURCE_LIMIT,
             format!(
                 "computation_timeout_ms must be positive, found {}",
                 limits.computation_timeout_ms.value
@@ -442,8 +965,8 @@ fn verify_resource_limit_types(limits: &ResourceLimitsNode, result: &mut Verific
         );
     }
     if limits.max_state_size_bytes.value <= 0 {
-        result.add_error(
-            DiagnosticKind::TypeError,
+        result.report(
+            &lint::NON_POSITIVE_RESOURCE_LIMIT,
             format!(
                 "max_state_size_bytes must be positive, found {}",
                 limits.max_state_size_bytes.value
@@ -453,6 +976,604 @@ fn verify_resource_limit_types(limits: &ResourceLimitsNode, result: &mut Verific
     }
 }
 
+// ── Condition types ───────────────────────────────────────
+
+/// Names visible in a condition with their declared types, innermost last;
+/// `None` when the type is not known
+type PathEnv = Vec<(String, Option<Type>)>;
+
+/// Verify field, index and quantifier paths and comparisons in conditions
+/// against the declared types of state fields and operation parameters
+fn verify_condition_types(ast: &ContractNode, result: &mut VerificationResult) {
+    let types = ast.type_definitions();
+    let declared = |fields: &[StateFieldNode]| -> PathEnv {
+        fields
+            .iter()
+            .map(|f| {
+                let ty = Type::from_expr(&f.type_expr, types);
+                (f.name.value.clone(), Some(ty))
+            })
+            .collect()
+    };
+    let state = declared(&ast.data_semantics.state);
+
+    for invariant in &ast.data_semantics.invariants {
+        check_condition_node(invariant, &mut state.clone(), result);
+    }
+    for op in &ast.behavioral_semantics.operations {
+        // Parameters shadow state fields
+        let mut env = state.clone();
+        env.extend(declared(&op.parameters));
+        for condition in [&op.precondition, &op.postcondition] {
+            check_condition_node(condition, &mut env, result);
+        }
+    }
+}
+
+/// Check a parsed condition, dropping fixes when its spans are not exact
+fn check_condition_node(
+    condition: &ConditionNode,
+    env: &mut PathEnv,
+    result: &mut VerificationResult,
+) {
+    let Some(expr) = &condition.expr else {
+        return;
+    };
+    let first = result.diagnostics.len();
+    check_condition(expr, env, result);
+    if !verbatim(condition) {
+        for d in &mut result.diagnostics[first..] {
+            d.fix = None;
+        }
+    }
+}
+
+/// Check a condition: every sub-expression is typed, and the condition
+/// itself and the operands of `and`, `or`, `not` and quantifier bodies
+/// must be Boolean
+fn check_condition(expr: &Expr, env: &mut PathEnv, result: &mut VerificationResult) {
+    match expr {
+        Expr::Literal(_)
+        | Expr::Path(..)
+        | Expr::Field(..)
+        | Expr::Index(..)
+        | Expr::Old(..)
+        | Expr::Call(..) => {
+            let ty = operand_type(expr, env, result);
+            require_boolean(expr, ty.as_ref(), result);
+        }
+        Expr::Binary(op, _, _, _) if op.is_arithmetic() => {
+            let ty = operand_type(expr, env, result);
+            require_boolean(expr, ty.as_ref(), result);
+        }
+        Expr::Binary(op, left, right, _) if op.is_comparison() => {
+            // A bare unknown name may be an enum variant (see `symbol`)
+            let left_unknown = is_unknown_name(left, env);
+            let right_unknown = is_unknown_name(right, env);
+            let mut left_ty = (!left_unknown)
+                .then(|| operand_type(left, env, result))
+                .flatten();
+            let mut right_ty = (!right_unknown)
+                .then(|| operand_type(right, env, result))
+                .flatten();
+            let (mut left, mut right) = (left.as_ref().clone(), right.as_ref().clone());
+            if matches!(op, BinaryOp::Eq | BinaryOp::Ne | BinaryOp::In) {
+                if let Some(lit) = symbol(&left, &right_ty, env) {
+                    (left, left_ty) = (lit, Some(Type::String));
+                } else if let Some(lit) =
+                    symbol(&right, &left_ty, env).filter(|_| *op != BinaryOp::In)
+                {
+                    (right, right_ty) = (lit, Some(Type::String));
+                }
+            }
+            for side in [&left, &right] {
+                if is_unknown_name(side, env) {
+                    path_type(side, env, result);
+                }
+            }
+            check_comparison(expr, *op, (&left, left_ty), (&right, right_ty), result);
+        }
+        Expr::IsEmpty(operand, _) => {
+            let ty = operand_type(operand, env, result);
+            if let Some(ty) = ty.as_ref().map(Type::unwrapped) {
+                let emptiable = matches!(
+                    ty,
+                    Type::String
+                        | Type::Iso8601
+                        | Type::Uuid
+                        | Type::Array { .. }
+                        | Type::Map { .. }
+                        | Type::Named { .. }
+                );
+                if !emptiable {
+                    result.report(
+                        &lint::CONDITION_TYPE_MISMATCH,
+                        format!(
+                            "'is empty' needs a String, Array or Map, found {} in '{}'",
+                            ty, expr
+                        ),
+                        Some(expr.span().clone()),
+                    );
+                }
+            }
+        }
+        Expr::IsType(operand, _, _) => {
+            operand_type(operand, env, result);
+        }
+        Expr::Unary(_, operand, _) => check_condition(operand, env, result),
+        Expr::Binary(_, left, right, _) => {
+            check_condition(left, env, result);
+            check_condition(right, env, result);
+        }
+        Expr::Quantified(_, var, collection, body, _) => {
+            let element = path_type(collection, env, result).and_then(|ty| match ty.unwrapped() {
+                Type::Array { items } => Some(*items.clone()),
+                Type::Map { value, .. } => Some(*value.clone()),
+                Type::Named { .. } => None,
+                other => {
+                    result.report(
+                        &lint::CONDITION_TYPE_MISMATCH,
+                        format!(
+                            "cannot quantify over '{}': {} is not an Array or Map",
+                            collection, other
+                        ),
+                        Some(collection.span().clone()),
+                    );
+                    None
+                }
+            });
+            env.push((var.clone(), element));
+            check_condition(body, env, result);
+            env.pop();
+        }
+    }
+}
+
+/// Report a value used as a condition whose type is not Boolean
+fn require_boolean(expr: &Expr, ty: Option<&Type>, result: &mut VerificationResult) {
+    let Some(ty) = ty else {
+        return;
+    };
+    if !matches!(ty.unwrapped(), Type::Boolean | Type::Named { .. }) {
+        result.report(
+            &lint::CONDITION_TYPE_MISMATCH,
+            format!("'{}' is {}, not a Boolean condition", expr, ty),
+            Some(expr.span().clone()),
+        );
+    }
+}
+
+/// A single name that is not a state field, parameter or bound variable
+fn is_unknown_name(expr: &Expr, env: &PathEnv) -> bool {
+    matches!(expr, Expr::Path(segments, _)
+        if segments.len() == 1 && !env.iter().any(|(name, _)| *name == segments[0]))
+}
+
+/// Declared type of a path expression, reporting unknown names and
+/// accesses the type does not have. `None` if the root is unknown or the
+/// type cannot be followed.
+fn path_type(expr: &Expr, env: &mut PathEnv, result: &mut VerificationResult) -> Option<Type> {
+    match expr {
+        Expr::Path(segments, span) => {
+            let Some((_, root)) = env.iter().rev().find(|(name, _)| *name == segments[0]) else {
+                let names = env.iter().map(|(name, _)| name.as_str());
+                result.report_fix(
+                    &lint::UNKNOWN_IDENTIFIER,
+                    format!("unknown identifier '{}'", segments[0]),
+                    Some(span.clone()),
+                    rename(&segments[0], names, segment_span(segments, 0, span)),
+                );
+                return None;
+            };
+            let mut ty = root.clone()?;
+            for (i, segment) in segments.iter().enumerate().skip(1) {
+                let owner = segments[..i].join(".");
+                let at = segment_span(segments, i, span);
+                ty = field_type(&ty, segment, &owner, span, at, result)?;
+            }
+            Some(ty)
+        }
+        Expr::Field(base, name, span) => {
+            let ty = path_type(base, env, result)?;
+            // The field name ends the access
+            let at = Span {
+                offset: span.end.saturating_sub(name.len()),
+                ..span.clone()
+            };
+            field_type(&ty, name, &base.to_string(), span, Some(at), result)
+        }
+        Expr::Index(base, index, span) => {
+            let index_ty = operand_type(index, env, result);
+            let ty = path_type(base, env, result)?;
+            let owner = base.to_string();
+            let key_kind = match (index.as_ref(), index_ty.as_ref().map(Type::unwrapped)) {
+                (Expr::Literal(lit), _) => Some(literal_type_name(lit)),
+                (_, Some(Type::Integer)) => Some("Integer".to_string()),
+                (_, Some(Type::String | Type::Uuid | Type::Iso8601 | Type::Enum { .. })) => {
+                    Some("String".to_string())
+                }
+                (_, Some(Type::Named { .. }) | None) => None,
+                (_, Some(other)) => Some(other.to_string()),
+            };
+            let (element, expected) = match ty.unwrapped() {
+                Type::Array { items } => (*items.clone(), "Integer"),
+                Type::Map { value, .. } => (*value.clone(), "String"),
+                Type::Named { .. } => return None,
+                other => {
+                    result.report(
+                        &lint::CONDITION_TYPE_MISMATCH,
+                        format!("cannot index '{}': {} is not an Array or Map", owner, other),
+                        Some(span.clone()),
+                    );
+                    return None;
+                }
+            };
+            if let Some(kind) = key_kind.filter(|kind| *kind != expected) {
+                result.report(
+                    &lint::CONDITION_TYPE_MISMATCH,
+                    format!(
+                        "index into '{}' must be {} {}, found {}",
+                        owner,
+                        if expected == "Integer" { "an" } else { "a" },
+                        expected,
+                        kind
+                    ),
+                    Some(index.span().clone()),
+                );
+            }
+            Some(element)
+        }
+        Expr::Old(operand, _) => operand_type(operand, env, result),
+        Expr::Call(name, args, span) => {
+            let arg_types: Vec<Option<Type>> = args
+                .iter()
+                .map(|arg| operand_type(arg, env, result))
+                .collect();
+            let Some(builtin) = builtins::lookup(name) else {
+                result.report(
+                    &lint::UNKNOWN_FUNCTION,
+                    format!("unknown function '{}' in '{}'", name, expr),
+                    Some(span.clone()),
+                );
+                return None;
+            };
+            if let (true, Some(Expr::Literal(LiteralValue::String(pattern, lit_span)))) =
+                (builtin.name == "matches", args.get(1))
+            {
+                if let Some(message) = pattern_error(pattern) {
+                    result.report(&lint::INVALID_PATTERN, message, Some(lit_span.clone()));
+                }
+            }
+            builtin.check(&arg_types).unwrap_or_else(|message| {
+                result.report(
+                    &lint::CONDITION_TYPE_MISMATCH,
+                    format!("{} in '{}'", message, expr),
+                    Some(span.clone()),
+                );
+                None
+            })
+        }
+        _ => {
+            check_condition(expr, env, result);
+            None
+        }
+    }
+}
+
+/// Declared type of a comparison operand, reporting arithmetic on
+/// non-numbers. `None` if unknown or `null`.
+fn operand_type(expr: &Expr, env: &mut PathEnv, result: &mut VerificationResult) -> Option<Type> {
+    match expr {
+        Expr::Literal(lit) => literal_type(lit),
+        Expr::Path(..) | Expr::Field(..) | Expr::Index(..) | Expr::Old(..) | Expr::Call(..) => {
+            path_type(expr, env, result)
+        }
+        Expr::Binary(op, left, right, span) if op.is_arithmetic() => {
+            let left_ty = operand_type(left, env, result);
+            let right_ty = operand_type(right, env, result);
+            for ty in [&left_ty, &right_ty].into_iter().flatten() {
+                if !is_number(ty) && !matches!(ty.unwrapped(), Type::Named { .. }) {
+                    result.report(
+                        &lint::CONDITION_TYPE_MISMATCH,
+                        format!("'{}' needs numbers, found {} in '{}'", op, ty, expr),
+                        Some(span.clone()),
+                    );
+                    return None;
+                }
+            }
+            match (
+                left_ty.as_ref()?.unwrapped(),
+                right_ty.as_ref()?.unwrapped(),
+            ) {
+                (Type::Integer, Type::Integer) if *op != BinaryOp::Div => Some(Type::Integer),
+                (a, b) if is_number(a) && is_number(b) => Some(Type::Float),
+                _ => None,
+            }
+        }
+        _ => {
+            check_condition(expr, env, result);
+            Some(Type::Boolean)
+        }
+    }
+}
+
+/// Check that the two sides of a comparison can be compared
+fn check_comparison(
+    expr: &Expr,
+    op: BinaryOp,
+    (left, left_ty): (&Expr, Option<Type>),
+    (right, right_ty): (&Expr, Option<Type>),
+    result: &mut VerificationResult,
+) {
+    let span = Some(expr.span().clone());
+    let is_null = |e: &Expr| matches!(e, Expr::Literal(LiteralValue::Null(_)));
+
+    if op == BinaryOp::In {
+        let element = match (right, unwrapped_opt(&right_ty)) {
+            (Expr::Literal(LiteralValue::Array(items, _)), _) => {
+                for item in items {
+                    let item_expr = Expr::Literal(item.clone());
+                    check_equality(
+                        expr,
+                        (left, &left_ty),
+                        (&item_expr, &literal_type(item)),
+                        result,
+                    );
+                }
+                return;
+            }
+            (_, Some(Type::Array { items })) => Some(*items.clone()),
+            (_, Some(Type::Map { key, .. })) => Some(*key.clone()),
+            (_, Some(Type::Named { .. }) | None) => None,
+            (_, Some(other)) => {
+                result.report(
+                    &lint::CONDITION_TYPE_MISMATCH,
+                    format!(
+                        "'in' needs an Array or Map on the right, found {} in '{}'",
+                        other, expr
+                    ),
+                    span,
+                );
+                return;
+            }
+        };
+        check_equality(expr, (left, &left_ty), (right, &element), result);
+        return;
+    }
+
+    if matches!(op, BinaryOp::Eq | BinaryOp::Ne) {
+        // `x == null` on a non-Optional x is constant
+        for (side, other, other_ty) in [(left, right, &right_ty), (right, left, &left_ty)] {
+            if let (true, Some(ty)) = (is_null(side), other_ty) {
+                if !strip_refinements(ty).is_optional() && !matches!(ty, Type::Named { .. }) {
+                    result.report(
+                        &lint::NEVER_NULL_COMPARISON,
+                        format!(
+                            "'{}' is {}, not Optional, so it is never null in '{}'",
+                            other, ty, expr
+                        ),
+                        span.clone(),
+                    );
+                }
+                return;
+            }
+        }
+        check_equality(expr, (left, &left_ty), (right, &right_ty), result);
+        return;
+    }
+
+    // Ordering: numbers, or ISO8601 timestamps
+    let (Some(a), Some(b)) = (unwrapped_opt(&left_ty), unwrapped_opt(&right_ty)) else {
+        return;
+    };
+    for (side, ty, other) in [(left, a, b), (right, b, a)] {
+        if let (Type::Iso8601, Expr::Literal(LiteralValue::String(text, lit_span))) = (other, side)
+        {
+            if ty == &Type::String && parse_timestamp(text).is_none() {
+                result.report(
+                    &lint::CONDITION_TYPE_MISMATCH,
+                    format!("{} is not an ISO8601 timestamp in '{}'", quote(text), expr),
+                    Some(lit_span.clone()),
+                );
+                return;
+            }
+        }
+    }
+    let orderable = match (a, b) {
+        (Type::Named { .. }, _) | (_, Type::Named { .. }) => true,
+        (Type::Iso8601, Type::Iso8601 | Type::String) | (Type::String, Type::Iso8601) => true,
+        (a, b) => is_number(a) && is_number(b),
+    };
+    if !orderable {
+        result.report(
+            &lint::CONDITION_TYPE_MISMATCH,
+            format!(
+                "cannot order {} and {} in '{}' (only numbers and ISO8601 timestamps are ordered)",
+                left_ty.as_ref().map_or(a, |t| t),
+                right_ty.as_ref().map_or(b, |t| t),
+                expr
+            ),
+            span,
+        );
+    }
+}
+
+/// Check that two operands can be equal: numbers with numbers, strings
+/// with String-like values, enum values with their variants, and
+/// otherwise values of the same type
+fn check_equality(
+    expr: &Expr,
+    (left, left_ty): (&Expr, &Option<Type>),
+    (right, right_ty): (&Expr, &Option<Type>),
+    result: &mut VerificationResult,
+) {
+    let (Some(a), Some(b)) = (unwrapped_opt(left_ty), unwrapped_opt(right_ty)) else {
+        return;
+    };
+
+    // A string literal compared to an enum must be one of its variants
+    for (side, ty, other) in [(left, a, right), (right, b, left)] {
+        if let (Type::Enum { variants }, Expr::Literal(LiteralValue::String(text, lit_span))) =
+            (ty, other)
+        {
+            if !variants.contains(text) {
+                let expected: Vec<String> = variants.iter().map(|v| quote(v)).collect();
+                result.report(
+                    &lint::UNKNOWN_VARIANT,
+                    format!(
+                        "{} is not a variant of '{}' (expected one of: {})",
+                        quote(text),
+                        side,
+                        expected.join(", ")
+                    ),
+                    Some(lit_span.clone()),
+                );
+                return;
+            }
+        }
+    }
+
+    if !equatable(a, b) {
+        result.report(
+            &lint::CONDITION_TYPE_MISMATCH,
+            format!(
+                "cannot compare {} with {} in '{}'",
+                left_ty.as_ref().map_or(a, |t| t),
+                right_ty.as_ref().map_or(b, |t| t),
+                expr
+            ),
+            Some(expr.span().clone()),
+        );
+    }
+}
+
+/// Whether values of two types can be equal: numbers with numbers, strings
+/// with String-like values, and otherwise values of the same type
+pub(crate) fn equatable(a: &Type, b: &Type) -> bool {
+    match (a, b) {
+        (Type::Named { .. }, _) | (_, Type::Named { .. }) => true,
+        (a, b) if is_number(a) && is_number(b) => true,
+        (Type::String, Type::String | Type::Uuid | Type::Iso8601 | Type::Enum { .. })
+        | (Type::Uuid | Type::Iso8601 | Type::Enum { .. }, Type::String) => true,
+        (a, b) => strip_refinements(a) == strip_refinements(b),
+    }
+}
+
+/// A bare name that is not in scope, compared with `==`, `!=` or `in`
+/// against an enum that declares it as a variant, stands for that variant
+/// (`status == active`), so it is checked as the equivalent string literal.
+/// Any other unknown name is an unknown identifier; String comparisons
+/// need quoted literals.
+fn symbol(expr: &Expr, other: &Option<Type>, env: &PathEnv) -> Option<Expr> {
+    let Type::Enum { variants } = (match unwrapped_opt(other)? {
+        Type::Array { items } => items.unwrapped(),
+        ty => ty,
+    }) else {
+        return None;
+    };
+    match expr {
+        Expr::Path(segments, span)
+            if segments.len() == 1
+                && variants.contains(&segments[0])
+                && !env.iter().any(|(name, _)| *name == segments[0]) =>
+        {
+            Some(Expr::Literal(LiteralValue::String(
+                segments[0].clone(),
+                span.clone(),
+            )))
+        }
+        _ => None,
+    }
+}
+
+/// Type of a literal in a condition; `None` for `null` and empty arrays
+fn literal_type(lit: &LiteralValue) -> Option<Type> {
+    match lit {
+        LiteralValue::String(_, _) => Some(Type::String),
+        LiteralValue::Integer(_, _) => Some(Type::Integer),
+        LiteralValue::Float(_, _) => Some(Type::Float),
+        LiteralValue::Boolean(_, _) => Some(Type::Boolean),
+        LiteralValue::Array(items, _) => Some(Type::Array {
+            items: Box::new(literal_type(items.first()?)?),
+        }),
+        LiteralValue::Null(_) => None,
+    }
+}
+
+fn is_number(ty: &Type) -> bool {
+    matches!(ty.unwrapped(), Type::Integer | Type::Float)
+}
+
+fn unwrapped_opt(ty: &Option<Type>) -> Option<&Type> {
+    ty.as_ref().map(Type::unwrapped)
+}
+
+/// The type with refinements removed at every level, for structural comparison
+fn strip_refinements(ty: &Type) -> Type {
+    match ty {
+        Type::Refined { base, .. } => strip_refinements(base),
+        Type::Optional { inner } => Type::Optional {
+            inner: Box::new(strip_refinements(inner)),
+        },
+        Type::Array { items } => Type::Array {
+            items: Box::new(strip_refinements(items)),
+        },
+        Type::Map { key, value } => Type::Map {
+            key: Box::new(strip_refinements(key)),
+            value: Box::new(strip_refinements(value)),
+        },
+        Type::Object { fields } => Type::Object {
+            fields: fields
+                .iter()
+                .map(|f| Field {
+                    ty: strip_refinements(&f.ty),
+                    ..f.clone()
+                })
+                .collect(),
+        },
+        other => other.clone(),
+    }
+}
+
+/// Type of field `name` in `ty`, reporting a missing field or non-object.
+/// `name_span`, when known, locates the name for a spelling fix.
+fn field_type(
+    ty: &Type,
+    name: &str,
+    owner: &str,
+    span: &Span,
+    name_span: Option<Span>,
+    result: &mut VerificationResult,
+) -> Option<Type> {
+    match ty.unwrapped() {
+        Type::Object { fields } => {
+            let field = fields.iter().find(|f| f.name == name);
+            if field.is_none() {
+                let names = fields.iter().map(|f| f.name.as_str());
+                result.report_fix(
+                    &lint::UNKNOWN_FIELD,
+                    format!("'{}' has no field '{}'", owner, name),
+                    Some(span.clone()),
+                    rename(name, names, name_span),
+                );
+            }
+            field.map(|f| f.ty.clone())
+        }
+        Type::Named { .. } => None,
+        other => {
+            result.report(
+                &lint::CONDITION_TYPE_MISMATCH,
+                format!(
+                    "cannot access field '{}' of '{}': {} is not an Object",
+                    name, owner, other
+                ),
+                Some(span.clone()),
+            );
+            None
+        }
+    }
+}
+
 // ── Phase 3.2: Invariant Verifier ─────────────────────────
 
 /// Verify invariants reference valid state fields and are logically consistent.
@@ -465,23 +1586,18 @@ fn verify_invariants(ast: &ContractNode, result: &mut VerificationResult) {
         .collect();
 
     for invariant in &ast.data_semantics.invariants {
-        let inv_text = &invariant.value;
+        let inv_text = &invariant.text;
 
-        // Extract potential field references from invariant text
-        let referenced_fields = extract_identifiers(inv_text);
-
-        // Check that referenced identifiers that look like field names exist in state
-        let mut found_field_ref = false;
-        for ident in &referenced_fields {
-            if state_field_names.contains(ident.as_str()) {
-                found_field_ref = true;
-            }
-        }
+        // Field references: path roots for parsed conditions, text scan for prose
+        let referenced_fields = condition_field_refs(invariant);
+        let found_field_ref = referenced_fields
+            .iter()
+            .any(|ident| state_field_names.contains(ident.as_str()));
 
         // Warn if invariant doesn't reference any state fields
         if !found_field_ref && !state_field_names.is_empty() && !inv_text.is_empty() {
-            result.add_warning(
-                DiagnosticKind::InvariantError,
+            result.report(
+                &lint::INVARIANT_WITHOUT_FIELDS,
                 format!(
                     "invariant '{}' does not reference any declared state fields",
                     inv_text,
@@ -493,15 +1609,174 @@ fn verify_invariants(ast: &ContractNode, result: &mut VerificationResult) {
 
     // Check for duplicate invariants
     let mut seen = BTreeSet::new();
-    for invariant in &ast.data_semantics.invariants {
-        if !seen.insert(&invariant.value) {
-            result.add_warning(
-                DiagnosticKind::InvariantError,
-                format!("duplicate invariant: '{}'", invariant.value),
+    let invariants = &ast.data_semantics.invariants;
+    for (i, invariant) in invariants.iter().enumerate() {
+        if !seen.insert(&invariant.text) {
+            result.report_fix(
+                &lint::DUPLICATE_INVARIANT,
+                format!("duplicate invariant: '{}'", invariant.text),
                 Some(invariant.span.clone()),
+                Some(Fix::safe(
+                    "remove the duplicate",
+                    vec![remove_from_list(&invariants[i - 1].span, &invariant.span)],
+                )),
             );
         }
     }
+
+    verify_invariants_satisfiable(ast, result);
+    verify_initial_state(ast, result);
+}
+
+/// Evaluate every machine-checkable invariant against the initial state,
+/// built from the declared defaults as the executor builds it.
+///
+/// A violated invariant is reported at the invariant and at the default
+/// value of each state field it reads. It is an error when every field it
+/// reads has a declared default; when some start from their type's
+/// default, an operation may be meant to set them first, so it is a
+/// warning.
+fn verify_initial_state(ast: &ContractNode, result: &mut VerificationResult) {
+    let types = ast.type_definitions();
+    let fields: Vec<Field> = ast
+        .data_semantics
+        .state
+        .iter()
+        .map(|f| Field::from_node(f, types))
+        .collect();
+    let state = ExecutionState::from_fields(&fields);
+
+    for invariant in &ast.data_semantics.invariants {
+        let Some(expr) = &invariant.expr else {
+            continue;
+        };
+        let (holds, evaluated) = ExpressionEvaluator::evaluate_condition(
+            &crate::Condition::from(invariant),
+            None,
+            &state,
+            &fields,
+        );
+        if !evaluated || holds {
+            continue;
+        }
+
+        let mut roots = Vec::new();
+        collect_path_roots(expr, &mut roots);
+        let mut read: Vec<&StateFieldNode> = Vec::new();
+        for field in &ast.data_semantics.state {
+            if roots.contains(&field.name.value) {
+                read.push(field);
+            }
+        }
+        let values: Vec<String> = read
+            .iter()
+            .filter_map(|f| Some(format!("{} = {}", f.name.value, state.get(&f.name.value)?)))
+            .collect();
+        let mut message = format!("initial state violates invariant '{}'", invariant.text);
+        if !values.is_empty() {
+            message.push_str(&format!(" ({})", values.join(", ")));
+        }
+        let rule = if read.iter().all(|f| f.default_value.is_some()) {
+            &lint::INITIAL_STATE_VIOLATES_INVARIANT
+        } else {
+            &lint::TYPE_DEFAULT_VIOLATES_INVARIANT
+        };
+        result.report(rule, message, Some(invariant.span.clone()));
+        for field in read {
+            if let Some(default) = &field.default_value {
+                result.report(
+                    rule,
+                    format!(
+                        "default value of '{}' violates invariant '{}'",
+                        field.name.value, invariant.text
+                    ),
+                    Some(literal_span(default)),
+                );
+            }
+        }
+    }
+}
+
+/// Strict mode: every condition must parse into an expression, so that the
+/// executor enforces it rather than skipping it as prose
+fn verify_conditions_checkable(ast: &ContractNode, result: &mut VerificationResult) {
+    let mut conditions: Vec<(String, &ConditionNode, &'static Rule)> = ast
+        .data_semantics
+        .invariants
+        .iter()
+        .map(|c| ("invariant".to_string(), c, &lint::OPAQUE_INVARIANT))
+        .collect();
+    for op in &ast.behavioral_semantics.operations {
+        for (role, condition) in [
+            ("precondition", &op.precondition),
+            ("postcondition", &op.postcondition),
+        ] {
+            let role = format!("{} of '{}'", role, op.name.value);
+            conditions.push((role, condition, &lint::OPAQUE_CONDITION));
+        }
+    }
+
+    for (role, condition, rule) in conditions {
+        let reason = condition.parse_error().or_else(|| {
+            condition
+                .is_opaque()
+                .then(|| "it names no state field or parameter".to_string())
+        });
+        if let Some(reason) = reason {
+            result.report(
+                rule,
+                format!(
+                    "{} '{}' is not machine-checkable: {}",
+                    role, condition.text, reason
+                ),
+                Some(condition.span.clone()),
+            );
+        }
+    }
+}
+
+/// Root field names referenced by a condition.
+///
+/// Parsed conditions yield the first segment of every field path.
+/// Opaque (prose) conditions fall back to scanning the text for identifiers.
+fn condition_field_refs(condition: &ConditionNode) -> Vec<String> {
+    match &condition.expr {
+        Some(expr) => {
+            let mut roots = Vec::new();
+            collect_path_roots(expr, &mut roots);
+            roots
+        }
+        None => extract_identifiers(&condition.text),
+    }
+}
+
+/// Collect the root segment of every field path in an expression
+pub(crate) fn collect_path_roots(expr: &Expr, out: &mut Vec<String>) {
+    match expr {
+        Expr::Literal(_) => {}
+        Expr::Path(segments, _) => out.push(segments[0].clone()),
+        Expr::Unary(_, operand, _)
+        | Expr::IsEmpty(operand, _)
+        | Expr::IsType(operand, _, _)
+        | Expr::Old(operand, _)
+        | Expr::Field(operand, _, _) => collect_path_roots(operand, out),
+        Expr::Binary(_, left, right, _) | Expr::Index(left, right, _) => {
+            collect_path_roots(left, out);
+            collect_path_roots(right, out);
+        }
+        Expr::Quantified(_, var, collection, body, _) => {
+            collect_path_roots(collection, out);
+            // The bound variable is not a field reference
+            let mut body_roots = Vec::new();
+            collect_path_roots(body, &mut body_roots);
+            out.extend(body_roots.into_iter().filter(|root| root != var));
+        }
+        Expr::Call(_, args, _) => {
+            for arg in args {
+                collect_path_roots(arg, out);
+            }
+        }
+    }
 }
 
 /// Extract identifiers (potential field references) from an invariant/condition string
@@ -586,42 +1861,106 @@ fn verify_determinism(ast: &ContractNode, result: &mut VerificationResult) {
     // Patterns that suggest non-determinism
     let nondeterministic_patterns = [
         // Randomness
-        ("random", "randomness usage"),
-        ("rand(", "random function call"),
-        ("Math.random", "random function call"),
-        ("uuid_generate", "runtime UUID generation"),
-        ("generate_id", "runtime ID generation"),
+        ("random", "randomness usage", &lint::RANDOMNESS_IN_CONDITION),
+        (
+            "rand(",
+            "random function call",
+            &lint::RANDOMNESS_IN_CONDITION,
+        ),
+        (
+            "Math.random",
+            "random function call",
+            &lint::RANDOMNESS_IN_CONDITION,
+        ),
+        (
+            "uuid_generate",
+            "runtime UUID generation",
+            &lint::RANDOMNESS_IN_CONDITION,
+        ),
+        (
+            "generate_id",
+            "runtime ID generation",
+            &lint::RANDOMNESS_IN_CONDITION,
+        ),
         // System time
-        ("now()", "system time access"),
-        ("current_time", "system time access"),
-        ("system_time", "system time access"),
-        ("Date.now", "system time access"),
-        ("time.time", "system time access"),
-        ("Instant::now", "system time access"),
+        (
+            "now()",
+            "system time access",
+            &lint::SYSTEM_TIME_IN_CONDITION,
+        ),
+        (
+            "current_time",
+            "system time access",
+            &lint::SYSTEM_TIME_IN_CONDITION,
+        ),
+        (
+            "system_time",
+            "system time access",
+            &lint::SYSTEM_TIME_IN_CONDITION,
+        ),
+        (
+            "Date.now",
+            "system time access",
+            &lint::SYSTEM_TIME_IN_CONDITION,
+        ),
+        (
+            "time.time",
+            "system time access",
+            &lint::SYSTEM_TIME_IN_CONDITION,
+        ),
+        (
+            "Instant::now",
+            "system time access",
+            &lint::SYSTEM_TIME_IN_CONDITION,
+        ),
         // External I/O
-        ("fetch(", "external I/O"),
-        ("http_request", "external I/O"),
-        ("read_file", "external I/O"),
-        ("write_file", "external I/O"),
-        ("network_call", "external I/O"),
-        ("socket", "external I/O"),
+        ("fetch(", "external I/O", &lint::EXTERNAL_IO_IN_CONDITION),
+        (
+            "http_request",
+            "external I/O",
+            &lint::EXTERNAL_IO_IN_CONDITION,
+        ),
+        ("read_file", "external I/O", &lint::EXTERNAL_IO_IN_CONDITION),
+        (
+            "write_file",
+            "external I/O",
+            &lint::EXTERNAL_IO_IN_CONDITION,
+        ),
+        (
+            "network_call",
+            "external I/O",
+            &lint::EXTERNAL_IO_IN_CONDITION,
+        ),
+        ("socket", "external I/O", &lint::EXTERNAL_IO_IN_CONDITION),
         // Hash iteration
-        ("HashMap", "non-deterministic hash iteration"),
-        ("HashSet", "non-deterministic hash iteration"),
-        ("dict_keys", "non-deterministic hash iteration"),
+        (
+            "HashMap",
+            "non-deterministic hash iteration",
+            &lint::HASH_ITERATION_IN_CONDITION,
+        ),
+        (
+            "HashSet",
+            "non-deterministic hash iteration",
+            &lint::HASH_ITERATION_IN_CONDITION,
+        ),
+        (
+            "dict_keys",
+            "non-deterministic hash iteration",
+            &lint::HASH_ITERATION_IN_CONDITION,
+        ),
     ];
 
     // Check operation preconditions, postconditions, side_effects
     for op in &ast.behavioral_semantics.operations {
         check_string_for_nondeterminism(
-            &op.precondition.value,
+            &op.precondition.text,
             &format!("operation '{}' precondition", op.name.value),
             &op.precondition.span,
             &nondeterministic_patterns,
             result,
         );
         check_string_for_nondeterminism(
-            &op.postcondition.value,
+            &op.postcondition.text,
             &format!("operation '{}' postcondition", op.name.value),
             &op.postcondition.span,
             &nondeterministic_patterns,
@@ -648,7 +1987,7 @@ fn verify_determinism(ast: &ContractNode, result: &mut VerificationResult) {
     // Check invariants for non-deterministic patterns
     for inv in &ast.data_semantics.invariants {
         check_string_for_nondeterminism(
-            &inv.value,
+            &inv.text,
             "invariant",
             &inv.span,
             &nondeterministic_patterns,
@@ -662,14 +2001,14 @@ fn check_string_for_nondeterminism(
     text: &str,
     context: &str,
     span: &Span,
-    patterns: &[(&str, &str)],
+    patterns: &[(&str, &str, &'static Rule)],
     result: &mut VerificationResult,
 ) {
     let lower = text.to_lowercase();
-    for &(pattern, description) in patterns {
+    for &(pattern, description, rule) in patterns {
         if lower.contains(&pattern.to_lowercase()) {
-            result.add_error(
-                DiagnosticKind::DeterminismViolation,
+            result.report(
+                rule,
                 format!(
                     "{} detected in {}: text contains '{}'",
                     description, context, pattern,
@@ -699,6 +2038,9 @@ fn verify_coherence(ast: &ContractNode, result: &mut VerificationResult) {
     // Check operations reference valid state fields in pre/postconditions
     verify_operation_field_references(ast, result);
 
+    // Check old(...) only appears where a prior state exists
+    verify_old_references(ast, result);
+
     // Check extension namespace isolation
     verify_extension_namespaces(ast, result);
 }
@@ -708,8 +2050,8 @@ fn verify_unique_operation_names(ast: &ContractNode, result: &mut VerificationRe
     let mut seen = BTreeSet::new();
     for op in &ast.behavioral_semantics.operations {
         if !seen.insert(&op.name.value) {
-            result.add_error(
-                DiagnosticKind::CoherenceError,
+            result.report(
+                &lint::DUPLICATE_OPERATION,
                 format!("duplicate operation name '{}'", op.name.value),
                 Some(op.name.span.clone()),
             );
@@ -722,8 +2064,8 @@ fn verify_unique_state_fields(ast: &ContractNode, result: &mut VerificationResul
     let mut seen = BTreeSet::new();
     for field in &ast.data_semantics.state {
         if !seen.insert(&field.name.value) {
-            result.add_error(
-                DiagnosticKind::CoherenceError,
+            result.report(
+                &lint::DUPLICATE_STATE_FIELD,
                 format!("duplicate state field name '{}'", field.name.value),
                 Some(field.name.span.clone()),
             );
@@ -734,39 +2076,89 @@ fn verify_unique_state_fields(ast: &ContractNode, result: &mut VerificationResul
 /// Verify sandbox_mode is a recognized value
 fn verify_sandbox_mode(ast: &ContractNode, result: &mut VerificationResult) {
     let valid_modes = ["full_isolation", "restricted", "none"];
-    let mode = &ast.execution_constraints.sandbox_mode.value;
-    if !valid_modes.contains(&mode.as_str()) {
-        result.add_warning(
-            DiagnosticKind::CoherenceError,
+    let mode = &ast.execution_constraints.sandbox_mode;
+    if !valid_modes.contains(&mode.value.as_str()) {
+        result.report_fix(
+            &lint::UNKNOWN_SANDBOX_MODE,
             format!(
                 "unrecognized sandbox_mode '{}', expected one of: {}",
-                mode,
+                mode.value,
                 valid_modes.join(", ")
             ),
-            Some(ast.execution_constraints.sandbox_mode.span.clone()),
+            Some(mode.span.clone()),
+            suggest_value(mode, &valid_modes, SANDBOX_MODE_ALIASES),
         );
     }
 }
 
+/// Other names for the recognized sandbox modes
+const SANDBOX_MODE_ALIASES: &[(&str, &str)] = &[
+    ("isolated", "full_isolation"),
+    ("isolation", "full_isolation"),
+    ("sandboxed", "full_isolation"),
+    ("strict", "full_isolation"),
+    ("limited", "restricted"),
+    ("unrestricted", "none"),
+    ("disabled", "none"),
+];
+
+/// Other names for the recognized trigger types
+const TRIGGER_TYPE_ALIASES: &[(&str, &str)] = &[
+    ("api_call", "event_based"),
+    ("api", "event_based"),
+    ("webhook", "event_based"),
+    ("event", "event_based"),
+    ("message", "event_based"),
+    ("cron", "time_based"),
+    ("schedule", "time_based"),
+    ("scheduled", "time_based"),
+    ("timer", "time_based"),
+    ("periodic", "time_based"),
+    ("user", "manual"),
+    ("on_demand", "manual"),
+];
+
+/// Suggest the recognized value a string literal most likely means: the
+/// value an alias stands for, or the closest spelling
+fn suggest_value(
+    literal: &SpannedValue<String>,
+    valid: &[&str],
+    aliases: &[(&str, &str)],
+) -> Option<Fix> {
+    let lower = literal.value.to_lowercase();
+    let value = aliases
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, value)| *value)
+        .or_else(|| valid.iter().copied().find(|v| *v == lower))
+        .or_else(|| fix::closest(&lower, valid.iter().copied()))?;
+    Some(Fix::suggestion(
+        format!("replace '{}' with '{}'", literal.value, value),
+        vec![TextEdit::replace(&literal.span, format!("\"{}\"", value))],
+    ))
+}
+
 /// Verify trigger_types contain recognized values
 fn verify_trigger_types(ast: &ContractNode, result: &mut VerificationResult) {
     let valid_types = ["manual", "time_based", "event_based"];
     for tt in &ast.execution_constraints.trigger_types {
         if !valid_types.contains(&tt.value.as_str()) {
-            result.add_warning(
-                DiagnosticKind::CoherenceError,
+            result.report_fix(
+                &lint::UNKNOWN_TRIGGER_TYPE,
                 format!(
                     "unrecognized trigger_type '{}', expected one of: {}",
                     tt.value,
                     valid_types.join(", ")
                 ),
                 Some(tt.span.clone()),
+                suggest_value(tt, &valid_types, TRIGGER_TYPE_ALIASES),
             );
         }
     }
 }
 
-/// Verify operation pre/postconditions reference valid state fields
+/// Warn about prose pre/postconditions that seem to name unknown fields;
+/// names in parsed conditions are checked with the condition types
 fn verify_operation_field_references(ast: &ContractNode, result: &mut VerificationResult) {
     let state_field_names: BTreeSet<&str> = ast
         .data_semantics
@@ -776,48 +2168,164 @@ fn verify_operation_field_references(ast: &ContractNode, result: &mut Verificati
         .collect();
 
     for op in &ast.behavioral_semantics.operations {
-        // Check precondition references
-        let pre_idents = extract_identifiers(&op.precondition.value);
-        for ident in &pre_idents {
-            if looks_like_field_ref(ident) && !state_field_names.contains(ident.as_str()) {
-                // Only warn — preconditions may reference parameters too
-                let param_names: BTreeSet<&str> = op
-                    .parameters
+        let param_names: BTreeSet<&str> = op
+            .parameters
+            .iter()
+            .map(|p| p.name.value.as_str())
+            .collect();
+
+        for (label, condition) in [
+            ("precondition", &op.precondition),
+            ("postcondition", &op.postcondition),
+        ] {
+            // Names in parsed conditions are checked with their types
+            if !condition.is_opaque() {
+                continue;
+            }
+            for ident in extract_identifiers(&condition.text) {
+                // Prose is only scanned heuristically
+                if !looks_like_field_ref(&ident) {
+                    continue;
+                }
+                // Only warn — conditions may reference parameters too
+                if !state_field_names.contains(ident.as_str())
+                    && !param_names.contains(ident.as_str())
+                {
+                    let fix = verbatim(condition)
+                        .then(|| {
+                            let names = state_field_names.iter().chain(&param_names);
+                            let replacement = fix::closest(&ident, names.copied())?;
+                            let edits = word_spans(&condition.text, &ident)
+                                .into_iter()
+                                .map(|range| {
+                                    let start = condition.text_start().offset;
+                                    let span = Span {
+                                        offset: start + range.start,
+                                        end: start + range.end,
+                                        ..condition.span.clone()
+                                    };
+                                    TextEdit::replace(&span, replacement)
+                                })
+                                .collect();
+                            Some(Fix::suggestion(
+                                format!("replace '{}' with '{}'", ident, replacement),
+                                edits,
+                            ))
+                        })
+                        .flatten();
+                    result.report_fix(
+                        &lint::UNKNOWN_FIELD_REFERENCE,
+                        format!(
+                            "{} of '{}' references unknown field '{}'",
+                            label, op.name.value, ident,
+                        ),
+                        Some(condition.span.clone()),
+                        fix,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Verify `old(...)` is only used in postconditions, is not nested, and
+/// does not wrap parameters that are not also state fields (those have
+/// no prior value)
+fn verify_old_references(ast: &ContractNode, result: &mut VerificationResult) {
+    let mut misplaced = Vec::new();
+    for invariant in &ast.data_semantics.invariants {
+        misplaced.push(("invariants", invariant));
+    }
+    for op in &ast.behavioral_semantics.operations {
+        misplaced.push(("preconditions", &op.precondition));
+    }
+    for (label, condition) in misplaced {
+        let mut uses = Vec::new();
+        if let Some(expr) = &condition.expr {
+            collect_old_uses(expr, false, &mut uses);
+        }
+        for (old, _) in uses {
+            result.report(
+                &lint::MISPLACED_OLD,
+                format!(
+                    "old(...) is only allowed in postconditions, not in {}: '{}'",
+                    label, condition.text
+                ),
+                Some(old.span().clone()),
+            );
+        }
+    }
+
+    for op in &ast.behavioral_semantics.operations {
+        let Some(expr) = &op.postcondition.expr else {
+            continue;
+        };
+        let mut uses = Vec::new();
+        collect_old_uses(expr, false, &mut uses);
+        for (old, nested) in uses {
+            let Expr::Old(operand, span) = old else {
+                continue;
+            };
+            if nested {
+                let fix = verbatim(&op.postcondition).then(|| {
+                    Fix::safe(
+                        "remove the inner old(...)",
+                        unwrap_edits(span, operand.span()),
+                    )
+                });
+                result.report_fix(
+                    &lint::NESTED_OLD,
+                    format!("nested old(...) in postcondition of '{}'", op.name.value),
+                    Some(span.clone()),
+                    fix,
+                );
+                continue;
+            }
+            let mut roots = Vec::new();
+            collect_path_roots(operand, &mut roots);
+            for root in roots {
+                let is_param = op.parameters.iter().any(|p| p.name.value == root);
+                let is_state = ast
+                    .data_semantics
+                    .state
                     .iter()
-                    .map(|p| p.name.value.as_str())
-                    .collect();
-                if !param_names.contains(ident.as_str()) {
-                    result.add_warning(
-                        DiagnosticKind::CoherenceError,
+                    .any(|f| f.name.value == root);
+                if is_param && !is_state {
+                    result.report(
+                        &lint::OLD_OF_PARAMETER,
                         format!(
-                            "precondition of '{}' references unknown field '{}'",
-                            op.name.value, ident,
+                            "old({}) in postcondition of '{}' refers to a parameter, which has no prior value",
+                            root, op.name.value
                         ),
-                        Some(op.precondition.span.clone()),
+                        Some(span.clone()),
                     );
                 }
             }
         }
+    }
+}
 
-        // Check postcondition references
-        let post_idents = extract_identifiers(&op.postcondition.value);
-        for ident in &post_idents {
-            if looks_like_field_ref(ident) && !state_field_names.contains(ident.as_str()) {
-                let param_names: BTreeSet<&str> = op
-                    .parameters
-                    .iter()
-                    .map(|p| p.name.value.as_str())
-                    .collect();
-                if !param_names.contains(ident.as_str()) {
-                    result.add_warning(
-                        DiagnosticKind::CoherenceError,
-                        format!(
-                            "postcondition of '{}' references unknown field '{}'",
-                            op.name.value, ident,
-                        ),
-                        Some(op.postcondition.span.clone()),
-                    );
-                }
+/// Collect every `old(...)` expression, flagged when inside another `old(...)`
+fn collect_old_uses<'a>(expr: &'a Expr, inside: bool, out: &mut Vec<(&'a Expr, bool)>) {
+    match expr {
+        Expr::Literal(_) | Expr::Path(_, _) => {}
+        Expr::Old(operand, _) => {
+            out.push((expr, inside));
+            collect_old_uses(operand, true, out);
+        }
+        Expr::Unary(_, operand, _)
+        | Expr::IsEmpty(operand, _)
+        | Expr::IsType(operand, _, _)
+        | Expr::Field(operand, _, _) => collect_old_uses(operand, inside, out),
+        Expr::Binary(_, left, right, _)
+        | Expr::Index(left, right, _)
+        | Expr::Quantified(_, _, left, right, _) => {
+            collect_old_uses(left, inside, out);
+            collect_old_uses(right, inside, out);
+        }
+        Expr::Call(_, args, _) => {
+            for arg in args {
+                collect_old_uses(arg, inside, out);
             }
         }
     }
@@ -839,8 +2347,8 @@ fn verify_extension_namespaces(ast: &ContractNode, result: &mut VerificationResu
         let mut seen = BTreeSet::new();
         for system in &ext.systems {
             if !seen.insert(&system.name.value) {
-                result.add_error(
-                    DiagnosticKind::CoherenceError,
+                result.report(
+                    &lint::DUPLICATE_EXTENSION_NAMESPACE,
                     format!("duplicate extension namespace '{}'", system.name.value),
                     Some(system.name.span.clone()),
                 );
@@ -849,6 +2357,30 @@ fn verify_extension_namespaces(ast: &ContractNode, result: &mut VerificationResu
     }
 }
 
+// ── Phase 3.5: Integrity ──────────────────────────────────
+
+/// Verify the declared semantic_hash is the canonical hash of the
+/// contract, so the identity block can be trusted to name its content.
+/// Malformed hashes are left to the type checker.
+fn verify_integrity(ast: &ContractNode, result: &mut VerificationResult) {
+    let declared = &ast.identity.semantic_hash;
+    if !declared.value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return;
+    }
+    let computed = computed_hash(ast);
+    if declared.value != computed {
+        result.report_fix(
+            &lint::SEMANTIC_HASH_MISMATCH,
+            format!(
+                "semantic_hash '{}' does not match the contract, expected '{}'",
+                declared.value, computed
+            ),
+            Some(declared.span.clone()),
+            Some(set_semantic_hash(declared, &computed)),
+        );
+    }
+}
+
 // ── Helpers ───────────────────────────────────────────────
 
 /// Human-readable name for a type expression
@@ -861,6 +2393,15 @@ fn type_expr_name(type_expr: &TypeExpression) -> String {
         }
         TypeExpression::Object(_, _) => "Object".to_string(),
         TypeExpression::Enum(_, _) => "Enum".to_string(),
+        TypeExpression::Optional(inner, _) => format!("Optional<{}>", type_expr_name(inner)),
+        TypeExpression::Refined(base, refinements, _) => {
+            let refinements: Vec<String> = refinements
+                .iter()
+                .map(|r| format!("{}: {}", r.name.value, r.value))
+                .collect();
+            format!("{}({})", type_expr_name(base), refinements.join(", "))
+        }
+        TypeExpression::Named(name, _) => name.clone(),
     }
 }
 
@@ -872,6 +2413,7 @@ fn literal_type_name(lit: &LiteralValue) -> String {
         LiteralValue::Float(_, _) => "Float".to_string(),
         LiteralValue::Boolean(_, _) => "Boolean".to_string(),
         LiteralValue::Array(_, _) => "Array".to_string(),
+        LiteralValue::Null(_) => "Null".to_string(),
     }
 }
 
@@ -883,7 +2425,89 @@ fn literal_span(lit: &LiteralValue) -> Span {
         LiteralValue::Float(_, s) => s.clone(),
         LiteralValue::Boolean(_, s) => s.clone(),
         LiteralValue::Array(_, s) => s.clone(),
+        LiteralValue::Null(s) => s.clone(),
+    }
+}
+
+// ── Fixes ─────────────────────────────────────────────────
+
+/// Delete list element `item` together with the separator after the
+/// element before it
+fn remove_from_list(previous: &Span, item: &Span) -> TextEdit {
+    TextEdit::delete(&Span {
+        offset: previous.end,
+        ..item.clone()
+    })
+}
+
+/// Replace wrapper `outer`, like `Optional<...>` or `old(...)`, by the
+/// `inner` text it wraps
+fn unwrap_edits(outer: &Span, inner: &Span) -> Vec<TextEdit> {
+    vec![
+        TextEdit::delete(&Span {
+            end: inner.offset,
+            ..outer.clone()
+        }),
+        TextEdit::delete(&Span {
+            offset: inner.end,
+            ..outer.clone()
+        }),
+    ]
+}
+
+/// Replace misspelled `name` at `span` with the closest candidate
+fn rename<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    span: Option<Span>,
+) -> Option<Fix> {
+    let span = span?;
+    let replacement = fix::closest(name, candidates)?;
+    Some(Fix::suggestion(
+        format!("replace '{}' with '{}'", name, replacement),
+        vec![TextEdit::replace(&span, replacement)],
+    ))
+}
+
+/// Span of segment `i` of a path, when the path is written without
+/// spaces around its dots
+fn segment_span(segments: &[String], i: usize, span: &Span) -> Option<Span> {
+    if span.end.saturating_sub(span.offset) != segments.join(".").len() {
+        return None;
+    }
+    let offset = span.offset + segments[..i].iter().map(|s| s.len() + 1).sum::<usize>();
+    Some(Span {
+        offset,
+        end: offset + segments[i].len(),
+        ..span.clone()
+    })
+}
+
+/// Byte ranges of whole-word occurrences of `word` in `text`, matching
+/// the words `extract_identifiers` yields
+fn word_spans(text: &str, word: &str) -> Vec<std::ops::Range<usize>> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match start {
+            None if c.is_ascii_alphabetic() || c == '_' => start = Some(i),
+            Some(s) if !is_word(c) => {
+                if &text[s..i] == word {
+                    ranges.push(s..i);
+                }
+                start = None;
+            }
+            _ => {}
+        }
     }
+    ranges
+}
+
+/// Whether condition spans map one-to-one onto the source: without
+/// escape sequences the literal is its text plus two quotes
+fn verbatim(condition: &ConditionNode) -> bool {
+    condition.span.end.saturating_sub(condition.span.offset) == condition.text.len() + 2
 }
 
 // ── Tests ─────────────────────────────────────────────────
@@ -896,8 +2520,17 @@ mod tests {
     // ── Helper: parse and verify ──────────────────────────
 
     fn parse_and_verify(input: &str) -> VerificationResult {
-        let ast = parse(input).expect("test input should parse");
-        verify(&ast)
+        verify(&parse_hashed(input))
+    }
+
+    /// Parse a contract whose placeholder semantic_hash is replaced with
+    /// its computed hash, so the integrity check passes
+    fn parse_hashed(input: &str) -> ContractNode {
+        let mut ast = parse(input).expect("test input should parse");
+        if ast.identity.semantic_hash.value == "0000000000000000" {
+            ast.identity.semantic_hash.value = computed_hash(&ast);
+        }
+        ast
     }
 
     // ── Phase 3.1: Type Checker Tests ─────────────────────
@@ -1063,6 +2696,35 @@ mod tests {
         assert!(!result.is_valid(), "Invalid Enum default should fail");
     }
 
+    #[test]
+    fn test_uuid_and_timestamp_defaults_validated() {
+        for field in [
+            "id: UUID = \"550e8400-e29b-41d4-a716-446655440000\"",
+            "at: ISO8601 = \"2026-02-01T00:00:00Z\"",
+        ] {
+            let result = parse_and_verify(&make_contract_with_state(field));
+            assert!(result.is_valid(), "{}: {:?}", field, result.errors());
+        }
+        for (field, expected) in [
+            ("id: UUID = \"1234\"", "not an RFC 4122 UUID"),
+            (
+                "at: ISO8601 = \"2026-13-01T00:00:00Z\"",
+                "not an RFC 3339 timestamp",
+            ),
+        ] {
+            let result = parse_and_verify(&make_contract_with_state(field));
+            assert!(
+                result
+                    .errors()
+                    .iter()
+                    .any(|d| d.rule == &lint::DEFAULT_TYPE_MISMATCH && d.message.contains(expected)),
+                "{}: {:?}",
+                field,
+                result.errors()
+            );
+        }
+    }
+
     #[test]
     fn test_enum_duplicate_variants() {
         let input = make_contract_with_state("status: Enum [\"active\", \"active\", \"inactive\"]");
@@ -1220,73 +2882,636 @@ mod tests {
     }
 
     #[test]
-    fn test_zero_timeout() {
-        let input = make_contract_with_resource_limits(1048576, 0, 1048576);
+    fn test_zero_timeout() {
+        let input = make_contract_with_resource_limits(1048576, 0, 1048576);
+        let result = parse_and_verify(&input);
+        assert!(
+            !result.is_valid(),
+            "zero computation_timeout_ms should fail"
+        );
+    }
+
+    #[test]
+    fn test_valid_stable_id() {
+        let input = make_contract_with_stable_id("ic-test-001");
+        let result = parse_and_verify(&input);
+        assert!(result.is_valid(), "valid stable_id: {:?}", result.errors());
+    }
+
+    #[test]
+    fn test_invalid_stable_id_uppercase() {
+        let input = make_contract_with_stable_id("IC-TEST-001");
+        let result = parse_and_verify(&input);
+        assert!(!result.is_valid(), "uppercase stable_id should fail");
+    }
+
+    #[test]
+    fn test_invalid_stable_id_starts_with_dash() {
+        let input = make_contract_with_stable_id("-invalid");
+        let result = parse_and_verify(&input);
+        assert!(!result.is_valid(), "dash-start stable_id should fail");
+    }
+
+    #[test]
+    fn test_invalid_semantic_hash() {
+        let input = make_contract_with_hash("not-hex-at-all!");
+        let result = parse_and_verify(&input);
+        assert!(!result.is_valid(), "non-hex hash should fail");
+    }
+
+    #[test]
+    fn test_semantic_hash_mismatch() {
+        // A made-up hash is an error naming the computed one
+        let input = make_contract_with_hash("0123456789abcdef");
+        let ast = parse(&input).unwrap();
+        let computed = computed_hash(&ast);
+        let result = verify(&ast);
+        let mismatch: Vec<_> = result
+            .errors()
+            .into_iter()
+            .filter(|d| d.rule == &lint::SEMANTIC_HASH_MISMATCH)
+            .collect();
+        assert_eq!(mismatch.len(), 1, "{:?}", result.diagnostics);
+        assert!(mismatch[0].message.contains(&computed));
+        let edit = &mismatch[0].fix.as_ref().unwrap().edits[0];
+        assert_eq!(&input[edit.span.range()], "\"0123456789abcdef\"");
+
+        // Drafts may downgrade it; it does not stop the other phases
+        let options = VerifyOptions {
+            lint: LintConfig::from_json(r#"{"lint": {"semantic-hash-mismatch": "warning"}}"#)
+                .unwrap(),
+            ..Default::default()
+        };
+        assert!(verify_with_options(&ast, &options).is_valid());
+
+        // The computed hash passes; a malformed one is only ICL-T003
+        let result = verify(&parse(&make_contract_with_hash(&computed)).unwrap());
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let result = parse_and_verify(&make_contract_with_hash("not-hex"));
+        assert!(result
+            .diagnostics
+            .iter()
+            .all(|d| d.rule != &lint::SEMANTIC_HASH_MISMATCH));
+    }
+
+    // ── Named Types Tests ─────────────────────────────────
+
+    #[test]
+    fn test_named_types_valid() {
+        let input = make_contract_with_types(
+            "Amount: Integer, Entry: Object { id: UUID, amount: Amount }",
+            "total: Amount = 0, entries: Array<Entry>, by_id: Map<String, Entry>",
+        );
+        let result = parse_and_verify(&input);
+        assert!(result.is_valid(), "{:?}", result.errors());
+    }
+
+    #[test]
+    fn test_named_type_unknown() {
+        let input = make_contract_with_types("Amount: Integer", "entries: Array<Entry>");
+        let result = parse_and_verify(&input);
+        assert!(
+            result
+                .errors()
+                .iter()
+                .any(|d| d.message == "unknown type 'Entry'"),
+            "{:?}",
+            result.errors()
+        );
+    }
+
+    #[test]
+    fn test_named_type_duplicate() {
+        let input = make_contract_with_types("Amount: Integer, Amount: Float", "total: Amount");
+        let result = parse_and_verify(&input);
+        assert!(result
+            .errors()
+            .iter()
+            .any(|d| d.message == "duplicate type name 'Amount'"));
+    }
+
+    #[test]
+    fn test_named_type_self_recursive() {
+        let input =
+            make_contract_with_types("Node: Object { children: Array<Node> }", "root: Node");
+        let result = parse_and_verify(&input);
+        let errors = result.errors();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(errors[0].message, "recursive type 'Node' (Node -> Node)");
+    }
+
+    #[test]
+    fn test_named_type_mutual_recursion() {
+        let input = make_contract_with_types("A: Array<B>, B: Map<String, A>, C: A", "value: C");
+        let result = parse_and_verify(&input);
+        let messages: Vec<&str> = result.errors().iter().map(|d| d.message.as_str()).collect();
+        // C only refers into the cycle, it is not part of it
+        assert_eq!(
+            messages,
+            vec![
+                "recursive type 'A' (A -> B -> A)",
+                "recursive type 'B' (B -> A -> B)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_named_type_default_checked_against_definition() {
+        let input = make_contract_with_types(
+            "Amount: Integer, Status: Enum[\"on\", \"off\"]",
+            "total: Amount = 0, status: Status = \"on\"",
+        );
+        assert!(parse_and_verify(&input).is_valid());
+
+        let input =
+            make_contract_with_types("Status: Enum[\"on\", \"off\"]", "status: Status = \"dim\"");
+        let result = parse_and_verify(&input);
+        assert!(
+            result
+                .errors()
+                .iter()
+                .any(|d| d.message.contains("expected Status")),
+            "{:?}",
+            result.errors()
+        );
+    }
+
+    #[test]
+    fn test_named_type_float_map_key_rejected() {
+        let input = make_contract_with_types("Ratio: Float", "lookup: Map<Ratio, String>");
+        let result = parse_and_verify(&input);
+        assert!(result
+            .errors()
+            .iter()
+            .any(|d| d.message.contains("Float cannot be used as Map key")));
+    }
+
+    #[test]
+    fn test_optional_defaults() {
+        let input = make_contract_with_types(
+            "Nickname: Optional<String>",
+            "note: Optional<String> = null, age: Optional<Integer> = 42, \
+             nickname: Nickname = null, tags: Array<Optional<String>> = [\"a\", null]",
+        );
+        let result = parse_and_verify(&input);
+        assert!(result.is_valid(), "{:?}", result.errors());
+
+        let input = make_contract_with_state("age: Optional<Integer> = \"old\"");
+        let result = parse_and_verify(&input);
+        assert!(result.errors().iter().any(|d| d
+            .message
+            .contains("has type String, expected Optional<Integer>")));
+    }
+
+    #[test]
+    fn test_null_default_requires_optional() {
+        let input = make_contract_with_state("count: Integer = null");
+        let result = parse_and_verify(&input);
+        assert_eq!(result.errors().len(), 1, "{:?}", result.errors());
+        assert_eq!(
+            result.errors()[0].message,
+            "null default for non-optional field 'count' (declare it as Optional<Integer>)"
+        );
+        let span = result.errors()[0].span.as_ref().unwrap();
+        assert_eq!(&input[span.range()], "null");
+
+        let input = make_contract_with_state("tags: Array<String> = [null]");
+        assert!(!parse_and_verify(&input).is_valid());
+    }
+
+    #[test]
+    fn test_optional_type_checks() {
+        let input = make_contract_with_state("lookup: Map<Optional<String>, Integer>");
+        let result = parse_and_verify(&input);
+        assert!(result.errors().iter().any(|d| d
+            .message
+            .contains("Map key type must be a primitive or Enum")));
+
+        let input = make_contract_with_state("note: Optional<Optional<String>>");
+        let result = parse_and_verify(&input);
+        assert!(result.is_valid());
+        assert!(result
+            .warnings()
+            .iter()
+            .any(|d| d.message == "Optional<Optional<String>> is the same as Optional<String>"));
+    }
+
+    #[test]
+    fn test_refinements_valid() {
+        let input = make_contract_with_types(
+            "Percent: Integer(min: 0, max: 100)",
+            "done: Percent(max: 50) = 10, ratio: Float(min: 0, max: 1.5) = 0.5, \
+             code: String(min_len: 2, pattern: \"^[a-z]+$\") = \"ab\", \
+             tags: Array<String>(max_items: 2) = [\"a\"], \
+             note: Optional<String(max_len: 4)> = null",
+        );
+        let result = parse_and_verify(&input);
+        assert!(result.is_valid(), "{:?}", result.errors());
+    }
+
+    #[test]
+    fn test_refinement_misuse() {
+        let cases = [
+            (
+                "flag: Boolean(min: 0)",
+                "refinement 'min' does not apply to Boolean",
+            ),
+            (
+                "note: Optional<String>(max_len: 2)",
+                "does not apply to Optional<String> (refine the inner type instead)",
+            ),
+            ("n: Integer(least: 0)", "unknown refinement 'least'"),
+            ("n: Integer(min: 0, min: 1)", "duplicate refinement 'min'"),
+            (
+                "n: Integer(min: 0.5)",
+                "refinement 'min' of Integer must be Integer, found Float",
+            ),
+            (
+                "s: String(max_len: -1)",
+                "'max_len' must be a non-negative Integer, found -1",
+            ),
+            (
+                "s: String(pattern: 1)",
+                "'pattern' must be a String, found Integer",
+            ),
+            (
+                "s: String(pattern: \"[a-\")",
+                "invalid pattern \"[a-\": unclosed character class",
+            ),
+            (
+                "n: Integer(min: 5, max: 1)",
+                "refinement 'min' (5) is greater than 'max' (1)",
+            ),
+        ];
+        for (state, expected) in cases {
+            let result = parse_and_verify(&make_contract_with_state(state));
+            assert!(
+                result.errors().iter().any(|d| d.message.contains(expected)),
+                "{}: {:?}",
+                state,
+                result.errors()
+            );
+        }
+    }
+
+    #[test]
+    fn test_refinement_defaults_checked() {
+        let input = make_contract_with_state("count: Integer(min: 0, max: 10) = 11");
+        let result = parse_and_verify(&input);
+        assert_eq!(result.errors().len(), 1, "{:?}", result.errors());
+        assert_eq!(
+            result.errors()[0].message,
+            "default value violates the type of 'count': count is 11, above max 10"
+        );
+        let span = result.errors()[0].span.as_ref().unwrap();
+        assert_eq!(&input[span.range()], "11");
+
+        let input = make_contract_with_types(
+            "Code: String(pattern: \"^[A-Z]+$\")",
+            "codes: Array<Code> = [\"AB\", \"cd\"]",
+        );
+        let result = parse_and_verify(&input);
+        assert!(result.errors().iter().any(|d| d.message
+            == "default value violates the type of 'codes': codes[1] does not match pattern \"^[A-Z]+$\""));
+    }
+
+    // ── Phase 3.2: Invariant Verifier Tests ───────────────
+
+    #[test]
+    fn test_invariant_references_valid_field() {
+        let input = make_contract_with_state_and_invariants("count: Integer = 0", &["count >= 0"]);
+        let result = parse_and_verify(&input);
+        // Should not warn about unreferenced fields
+        assert!(
+            !result
+                .warnings()
+                .iter()
+                .any(|d| d.kind == DiagnosticKind::InvariantError),
+            "Valid field reference should not warn: {:?}",
+            result.warnings()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_invariant_warning() {
+        let input = make_contract_with_state_and_invariants(
+            "count: Integer = 0",
+            &["count >= 0", "count >= 0"],
+        );
+        let result = parse_and_verify(&input);
+        assert!(
+            result
+                .warnings()
+                .iter()
+                .any(|d| d.message.contains("duplicate invariant")),
+            "Should warn about duplicate invariants"
+        );
+    }
+
+    #[test]
+    fn test_contradictory_numeric_invariants() {
+        let input = make_contract_with_state_and_invariants(
+            "count: Integer = 5, ratio: Float = 0.5",
+            &["count >= 5", "ratio <= 1.0", "count < 3", "ratio > 100.0"],
+        );
+        let result = parse_and_verify(&input);
+        let contradictions: Vec<&Diagnostic> = result
+            .errors()
+            .into_iter()
+            .filter(|d| d.message.contains(" contradicts "))
+            .collect();
+        let messages: Vec<&str> = contradictions.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "invariant 'count >= 5' contradicts 'count < 3': no value of 'count' satisfies 'count >= 5' and 'count < 3'",
+                "invariant 'count < 3' contradicts 'count >= 5': no value of 'count' satisfies 'count >= 5' and 'count < 3'",
+                "invariant 'ratio <= 1.0' contradicts 'ratio > 100.0': no value of 'ratio' satisfies 'ratio > 100.0' and 'ratio <= 1.0'",
+                "invariant 'ratio > 100.0' contradicts 'ratio <= 1.0': no value of 'ratio' satisfies 'ratio > 100.0' and 'ratio <= 1.0'",
+            ]
+        );
+
+        // Each invariant involved is reported at its own span
+        let ast = crate::parser::parse(&input).unwrap();
+        let spans: Vec<&Span> = contradictions
+            .iter()
+            .filter_map(|d| d.span.as_ref())
+            .collect();
+        let invariants = &ast.data_semantics.invariants;
+        assert_eq!(
+            spans,
+            [
+                &invariants[0].span,
+                &invariants[2].span,
+                &invariants[1].span,
+                &invariants[3].span
+            ]
+        );
+    }
+
+    #[test]
+    fn test_satisfiable_invariants_not_reported() {
+        for invariants in [
+            &["count >= 0", "count <= 10", "10 >= count"][..],
+            &["count >= 3", "count <= 3"],
+            &["count > 0 and count < 100", "not (count > 100)"],
+            &["count != 3", "count >= 0"],
+        ] {
+            let input = make_contract_with_state_and_invariants("count: Integer", invariants);
+            let result = parse_and_verify(&input);
+            assert!(
+                !result
+                    .errors()
+                    .iter()
+                    .any(|d| d.kind == DiagnosticKind::InvariantError),
+                "{:?}: {:?}",
+                invariants,
+                result.errors()
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsatisfiable_invariant_edge_cases() {
+        let cases: [(&str, &[&str], &str); 5] = [
+            // Integers have no value strictly between 2 and 3
+            (
+                "count: Integer = 0",
+                &["count > 2", "count < 3"],
+                "no value of 'count' satisfies 'count > 2' and 'count < 3'",
+            ),
+            (
+                "count: Integer = 0",
+                &["count > 1 and count < 1"],
+                "invariant 'count > 1 and count < 1' can never hold: no value of 'count' satisfies 'count > 1' and 'count < 1'",
+            ),
+            (
+                "count: Integer = 0",
+                &["count == 4", "count >= 0", "count != 4"],
+                "no value of 'count' satisfies 'count == 4' and 'count == 4' and 'count != 4'",
+            ),
+            (
+                "status: Enum[\"open\", \"closed\", \"paused\"] = \"open\"",
+                &["status in ['open', 'paused']", "status == closed"],
+                "no value of 'status' satisfies 'status in [\"open\", \"paused\"]' and 'status == closed'",
+            ),
+            (
+                "status: Enum[\"open\", \"closed\"] = \"open\"",
+                &["status != 'open'", "not (status in ['closed'])"],
+                "no value of 'status' satisfies 'status != \"open\"' and 'not status in [\"closed\"]'",
+            ),
+        ];
+        for (state, invariants, expected) in cases {
+            let input = make_contract_with_state_and_invariants(state, invariants);
+            let result = parse_and_verify(&input);
+            assert!(
+                result
+                    .errors()
+                    .iter()
+                    .any(|d| d.message.ends_with(expected)),
+                "{:?}: {:?}",
+                invariants,
+                result.errors()
+            );
+        }
+    }
+
+    #[test]
+    fn test_initial_state_violates_invariant() {
+        let input = make_contract_with_state_and_invariants(
+            "count: Integer = -1, limit: Integer = 10",
+            &["count >= 0", "count <= limit"],
+        );
         let result = parse_and_verify(&input);
-        assert!(
-            !result.is_valid(),
-            "zero computation_timeout_ms should fail"
+        let errors = result.errors();
+        let messages: Vec<&str> = errors.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "initial state violates invariant 'count >= 0' (count = -1)",
+                "default value of 'count' violates invariant 'count >= 0'",
+            ]
         );
+
+        // Reported at the invariant and at the default value
+        let ast = crate::parser::parse(&input).unwrap();
+        let default = ast.data_semantics.state[0].default_value.as_ref().unwrap();
+        assert_eq!(
+            errors[0].span.as_ref(),
+            Some(&ast.data_semantics.invariants[0].span)
+        );
+        assert_eq!(errors[1].span.as_ref(), Some(&literal_span(default)));
     }
 
     #[test]
-    fn test_valid_stable_id() {
-        let input = make_contract_with_stable_id("ic-test-001");
+    fn test_initial_state_type_defaults_warn() {
+        // `name` starts as "" unless an operation sets it first
+        let input = make_contract_with_state_and_invariants(
+            "name: String, retries: Integer = 0",
+            &["name is not empty", "retries >= 0"],
+        );
         let result = parse_and_verify(&input);
-        assert!(result.is_valid(), "valid stable_id: {:?}", result.errors());
+        assert!(result.is_valid(), "{:?}", result.errors());
+        let warnings: Vec<&str> = result
+            .warnings()
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(
+            warnings,
+            ["initial state violates invariant 'name is not empty' (name = \"\")"]
+        );
     }
 
-    #[test]
-    fn test_invalid_stable_id_uppercase() {
-        let input = make_contract_with_stable_id("IC-TEST-001");
+    fn preservation_of(
+        state: &str,
+        invariants: &[&str],
+        parameters: &str,
+        pre: &str,
+        post: &str,
+    ) -> Preservation {
+        let input =
+            make_contract_with_invariants_and_operation(state, invariants, parameters, pre, post);
         let result = parse_and_verify(&input);
-        assert!(!result.is_valid(), "uppercase stable_id should fail");
+        assert!(result.is_valid(), "{:?}", result.errors());
+        assert_eq!(result.preservation.len(), 1);
+        result.preservation[0].preservation.clone()
     }
 
     #[test]
-    fn test_invalid_stable_id_starts_with_dash() {
-        let input = make_contract_with_stable_id("-invalid");
-        let result = parse_and_verify(&input);
-        assert!(!result.is_valid(), "dash-start stable_id should fail");
+    fn test_preservation_proved() {
+        let cases = [
+            // Frame: the operation does not write `count`
+            (
+                "count: Integer = 0, label: String = \"a\"",
+                "label: String",
+                "true",
+                "true",
+            ),
+            // Parameter type refinement
+            (
+                "count: Integer = 0",
+                "count: Integer(min: 0)",
+                "true",
+                "true",
+            ),
+            // Postcondition bound
+            (
+                "count: Integer = 0",
+                "count: Integer",
+                "true",
+                "count >= 0 and count < 10",
+            ),
+            // Postcondition relative to the prior state
+            (
+                "count: Integer = 0",
+                "count: Integer",
+                "true",
+                "count == old(count) + 1",
+            ),
+            // Precondition on the parameter, which shadows the field
+            (
+                "count: Integer = 1",
+                "count: Integer",
+                "count >= 1",
+                "count == old(count) - 1",
+            ),
+            // Precondition on a field the operation leaves alone
+            (
+                "count: Integer = 0, limit: Integer = 0",
+                "count: Integer",
+                "limit >= 0",
+                "count == limit",
+            ),
+        ];
+        for (state, params, pre, post) in cases {
+            assert_eq!(
+                preservation_of(state, &["count >= 0"], params, pre, post),
+                Preservation::Proved,
+                "{} / {} / {}",
+                params,
+                pre,
+                post
+            );
+        }
+
+        // Enum parameter narrower than the field; field ordering
+        assert_eq!(
+            preservation_of(
+                "status: Enum[\"open\", \"closed\", \"archived\"] = \"open\", low: Integer = 0, high: Integer = 5",
+                &["status != archived", "low <= 0", "low <= high"],
+                "status: Enum[\"open\", \"closed\"], high: Integer(min: 0)",
+                "true",
+                "true",
+            ),
+            Preservation::Proved
+        );
     }
 
     #[test]
-    fn test_invalid_semantic_hash() {
-        let input = make_contract_with_hash("not-hex-at-all!");
+    fn test_preservation_refuted_with_counterexample() {
+        let input = make_contract_with_invariants_and_operation(
+            "count: Integer = 0",
+            &["count >= 0"],
+            "count: Integer",
+            "true",
+            "count == old(count) - 1",
+        );
         let result = parse_and_verify(&input);
-        assert!(!result.is_valid(), "non-hex hash should fail");
-    }
+        assert!(result.is_valid(), "{:?}", result.errors());
+        assert_eq!(
+            result.preservation[0].preservation,
+            Preservation::Refuted {
+                invariant: "count >= 0".into(),
+                input: serde_json::json!({ "count": -1 }),
+            }
+        );
 
-    // ── Phase 3.2: Invariant Verifier Tests ───────────────
+        let warning = result
+            .warnings()
+            .into_iter()
+            .find(|d| d.message.contains("does not preserve"))
+            .expect("refutation is reported");
+        assert_eq!(
+            warning.message,
+            "operation 'op' does not preserve invariant 'count >= 0': input {\"count\":-1} from the initial state breaks it"
+        );
+        let ast = crate::parser::parse(&input).unwrap();
+        assert_eq!(
+            warning.span.as_ref(),
+            Some(&ast.behavioral_semantics.operations[0].name.span)
+        );
 
-    #[test]
-    fn test_invariant_references_valid_field() {
-        let input = make_contract_with_state_and_invariants("count: Integer = 0", &["count >= 0"]);
-        let result = parse_and_verify(&input);
-        // Should not warn about unreferenced fields
-        assert!(
-            !result
-                .warnings()
-                .iter()
-                .any(|d| d.kind == DiagnosticKind::InvariantError),
-            "Valid field reference should not warn: {:?}",
-            result.warnings()
+        // The counterexample must pass the precondition and postcondition
+        assert_eq!(
+            preservation_of(
+                "ratio: Float = 0.5",
+                &["ratio <= 1.0"],
+                "ratio: Float",
+                "true",
+                "ratio > 0.0",
+            ),
+            Preservation::Refuted {
+                invariant: "ratio <= 1.0".into(),
+                input: serde_json::json!({ "ratio": 1.5 }),
+            }
         );
     }
 
     #[test]
-    fn test_duplicate_invariant_warning() {
-        let input = make_contract_with_state_and_invariants(
-            "count: Integer = 0",
-            &["count >= 0", "count >= 0"],
-        );
-        let result = parse_and_verify(&input);
-        assert!(
-            result
-                .warnings()
-                .iter()
-                .any(|d| d.message.contains("duplicate invariant")),
-            "Should warn about duplicate invariants"
+    fn test_preservation_unknown() {
+        // No candidate input breaks it, but intervals cannot prove it either
+        assert_eq!(
+            preservation_of(
+                "tags: Array<String> = []",
+                &["len(tags) <= 3"],
+                "tags: Array<String>",
+                "true",
+                "true",
+            ),
+            Preservation::Unknown {
+                invariants: vec!["len(tags) <= 3".into()]
+            }
         );
     }
 
@@ -1451,6 +3676,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unknown_trigger_type_suggests_replacement() {
+        let input = make_contract_with_trigger_types(&["cron"]);
+        let result = parse_and_verify(&input);
+        let d = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == &lint::UNKNOWN_TRIGGER_TYPE)
+            .unwrap();
+        let fix = d.fix.as_ref().unwrap();
+        assert!(!fix.safe);
+        assert_eq!(fix.message, "replace 'cron' with 'time_based'");
+        assert_eq!(&input[fix.edits[0].span.range()], "\"cron\"");
+        assert_eq!(fix.edits[0].replacement, "\"time_based\"");
+    }
+
     #[test]
     fn test_valid_trigger_types() {
         let input = make_contract_with_trigger_types(&["manual", "time_based", "event_based"]);
@@ -1465,6 +3706,99 @@ mod tests {
         );
     }
 
+    // ── Lint rules ────────────────────────────────────────
+
+    fn verify_source(input: &str, lint: LintConfig) -> VerificationResult {
+        let ast = parse_hashed(input);
+        let options = VerifyOptions {
+            lint,
+            suppressions: Suppressions::from_source(input),
+            ..Default::default()
+        };
+        verify_with_options(&ast, &options)
+    }
+
+    #[test]
+    fn test_lint_rule_levels_configurable() {
+        let input = make_contract_with_trigger_types(&["cron_job"]);
+        let result = verify_source(&input, LintConfig::new());
+        assert_eq!(result.warnings().len(), 1, "{:?}", result.diagnostics);
+        assert_eq!(result.warnings()[0].rule, &lint::UNKNOWN_TRIGGER_TYPE);
+        assert!(result.warnings()[0]
+            .to_string()
+            .starts_with("warning[ICL-C004] [coherence] at "));
+        let rendered = result.warnings()[0].render(&input, "c.icl");
+        assert!(
+            rendered.starts_with("warning[ICL-C004]: [coherence] unrecognized trigger_type"),
+            "{}",
+            rendered
+        );
+        assert!(rendered.contains("--> c.icl:"), "{}", rendered);
+
+        let mut off = LintConfig::new();
+        off.set("unknown-trigger-type", lint::Level::Off).unwrap();
+        assert!(verify_source(&input, off).diagnostics.is_empty());
+
+        let mut error = LintConfig::new();
+        error.set("ICL-C004", lint::Level::Error).unwrap();
+        let result = verify_source(&input, error);
+        assert!(!result.is_valid());
+        assert_eq!(result.errors()[0].rule.code, "ICL-C004");
+    }
+
+    #[test]
+    fn test_lint_downgraded_errors_keep_contract_valid() {
+        let input = make_contract_with_operation("draw", "the caller picked a random seed", "true");
+        assert!(!verify_source(&input, LintConfig::new()).is_valid());
+
+        let mut config = LintConfig::new();
+        config
+            .set("randomness-in-condition", lint::Level::Warning)
+            .unwrap();
+        let result = verify_source(&input, config);
+        assert!(result.is_valid(), "{:?}", result.diagnostics);
+        assert_eq!(result.warnings()[0].rule, &lint::RANDOMNESS_IN_CONDITION);
+        // A valid contract goes on to the preservation analysis
+        assert_eq!(result.preservation.len(), 1);
+    }
+
+    #[test]
+    fn test_lint_suppression_comments() {
+        let input = make_contract_with_operation("draw", "the caller picked a random seed", "true")
+            .replace(
+                "        precondition:",
+                "        // icl-allow(randomness-in-condition)\n        precondition:",
+            )
+            .replace(
+                "trigger_types: [\"manual\"],",
+                "trigger_types: [\"cron\"], // icl-allow(ICL-C004, no-such-rule)",
+            );
+        let result = verify_source(&input, LintConfig::new());
+        assert!(result.is_valid(), "{:?}", result.diagnostics);
+        assert_eq!(result.diagnostics.len(), 1, "{:?}", result.diagnostics);
+        let unknown = &result.diagnostics[0];
+        assert_eq!(unknown.rule, &lint::UNKNOWN_LINT_RULE);
+        assert_eq!(
+            unknown.message,
+            "icl-allow names unknown lint rule 'no-such-rule'"
+        );
+        assert_eq!(
+            &input[unknown.span.as_ref().unwrap().range()],
+            "no-such-rule"
+        );
+
+        // Without the comments both findings are reported
+        let plain = parse_and_verify(&input.replace("icl-allow", "note"));
+        assert!(plain
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == &lint::RANDOMNESS_IN_CONDITION));
+        assert!(plain
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == &lint::UNKNOWN_TRIGGER_TYPE));
+    }
+
     // ── Conformance Suite ─────────────────────────────────
 
     #[test]
@@ -1597,6 +3931,46 @@ mod tests {
         )
     }
 
+    fn make_contract_with_types(types: &str, state_fields: &str) -> String {
+        make_contract_with_state(state_fields).replace(
+            "  DataSemantics {",
+            &format!("  Types {{ {} }}\n  DataSemantics {{", types),
+        )
+    }
+
+    #[test]
+    fn test_unknown_names_suggest_closest() {
+        let state = "profile: Object { age: Integer }";
+        for (invariant, code, name) in [
+            ("profil.age >= 0", "ICL-T019", "profil"),
+            ("profile.aeg >= 0", "ICL-T020", "aeg"),
+        ] {
+            let input = make_contract_with_state_and_invariants(state, &[invariant]);
+            let result = parse_and_verify(&input);
+            let d = result
+                .diagnostics
+                .iter()
+                .find(|d| d.rule.code == code)
+                .unwrap();
+            let edit = &d.fix.as_ref().unwrap().edits[0];
+            assert_eq!(&input[edit.span.range()], name);
+            assert!(["profile", "age"].contains(&edit.replacement.as_str()));
+        }
+
+        // Escapes shift condition spans off the source: no fix
+        let input = make_contract_with_state_and_invariants(
+            "profile: Object { name: String }",
+            &[r#"profile.nmae != \"\""#],
+        );
+        let result = parse_and_verify(&input);
+        let d = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == &lint::UNKNOWN_FIELD)
+            .unwrap();
+        assert!(d.fix.is_none());
+    }
+
     fn make_contract_with_state_and_invariants(state_fields: &str, invariants: &[&str]) -> String {
         let inv_str = invariants
             .iter()
@@ -1646,6 +4020,32 @@ mod tests {
         )
     }
 
+    /// State, invariants and a single operation "op"
+    fn make_contract_with_invariants_and_operation(
+        state_fields: &str,
+        invariants: &[&str],
+        parameters: &str,
+        precondition: &str,
+        postcondition: &str,
+    ) -> String {
+        make_contract_with_state_and_invariants(state_fields, invariants).replace(
+            "operations: []",
+            &format!(
+                r#"operations: [
+      {{
+        name: "op",
+        precondition: "{}",
+        parameters: {{ {} }},
+        postcondition: "{}",
+        side_effects: [],
+        idempotence: "idempotent"
+      }}
+    ]"#,
+                precondition, parameters, postcondition
+            ),
+        )
+    }
+
     fn make_contract_with_confidence(level: &str) -> String {
         format!(
             r#"Contract {{
@@ -1822,6 +4222,396 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_condition_paths_checked_against_types() {
+        let state = "profile: Object { age: Integer, tags: Array<String> }, \
+                     scores: Array<Integer>, counters: Map<String, Integer>, \
+                     nickname: Optional<Object { first: String }>, count: Integer";
+        let valid = make_contract_with_state_and_invariants(
+            state,
+            &[
+                "profile.age >= 0",
+                "all s in scores: s >= 0",
+                "all c in counters: c >= 0",
+                "all t in profile.tags: t is not empty",
+                "scores is empty or scores[0] >= counters['a']",
+                "nickname is null or nickname.first is not empty",
+            ],
+        );
+        let result = parse_and_verify(&valid);
+        assert!(result.is_valid(), "{:?}", result.errors());
+        // Quantifier variables are not reported as unknown fields
+        assert!(result.warnings().is_empty(), "{:?}", result.warnings());
+
+        let cases = [
+            ("profile.agee >= 0", "'profile' has no field 'agee'"),
+            (
+                "count.value > 0",
+                "cannot access field 'value' of 'count': Integer is not an Object",
+            ),
+            (
+                "count[0] > 0",
+                "cannot index 'count': Integer is not an Array or Map",
+            ),
+            (
+                "scores['a'] > 0",
+                "index into 'scores' must be an Integer, found String",
+            ),
+            (
+                "counters[0] > 0",
+                "index into 'counters' must be a String, found Integer",
+            ),
+            (
+                "all c in count: c > 0",
+                "cannot quantify over 'count': Integer is not an Array or Map",
+            ),
+            (
+                "all s in scores: s.value > 0",
+                "cannot access field 'value' of 's': Integer is not an Object",
+            ),
+        ];
+        for (invariant, expected) in cases {
+            let input = make_contract_with_state_and_invariants(state, &[invariant]);
+            let result = parse_and_verify(&input);
+            assert!(
+                result.errors().iter().any(|d| d.message == expected),
+                "{}: {:?}",
+                invariant,
+                result.errors()
+            );
+        }
+    }
+
+    #[test]
+    fn test_comparison_types_checked() {
+        let state = "count: Integer, max_count: Integer(max: 10), ratio: Float, \
+                     name: String, active: Boolean, \
+                     status: Enum [\"created\", \"shipped\"], \
+                     started_at: ISO8601, ended_at: ISO8601, id: UUID, \
+                     tags: Array<String>, nickname: Optional<String>";
+        let valid = make_contract_with_state_and_invariants(
+            state,
+            &[
+                "count <= max_count",
+                "ratio * 2 > count",
+                "name == 'ada' or active == false",
+                "status == 'created' or status equals shipped",
+                "status in ['created', 'shipped']",
+                "name in tags",
+                "started_at <= ended_at",
+                "started_at > '2026-01-01T00:00:00Z'",
+                "id != '00000000-0000-0000-0000-000000000000'",
+                "nickname == null or nickname != name",
+            ],
+        );
+        let result = parse_and_verify(&valid);
+        assert!(result.is_valid(), "{:?}", result.errors());
+        // Type-derived defaults may not satisfy the invariants yet
+        assert!(
+            result
+                .warnings()
+                .iter()
+                .all(|d| d.message.starts_with("initial state violates")),
+            "{:?}",
+            result.warnings()
+        );
+
+        let cases = [
+            ("count == name", "cannot compare Integer with String in 'count == name'"),
+            ("active != 1", "cannot compare Boolean with Integer in 'active != 1'"),
+            (
+                "name < 'b'",
+                "cannot order String and String in 'name < \"b\"' (only numbers and ISO8601 timestamps are ordered)",
+            ),
+            (
+                "started_at < count",
+                "cannot order ISO8601 and Integer in 'started_at < count' (only numbers and ISO8601 timestamps are ordered)",
+            ),
+            (
+                "started_at < 'yesterday'",
+                "\"yesterday\" is not an ISO8601 timestamp in 'started_at < \"yesterday\"'",
+            ),
+            (
+                "status == 'deleted'",
+                "\"deleted\" is not a variant of 'status' (expected one of: \"created\", \"shipped\")",
+            ),
+            // Only declared variants stand for themselves unquoted
+            ("status != archived", "unknown identifier 'archived'"),
+            ("name == ada", "unknown identifier 'ada'"),
+            ("nmae != name", "unknown identifier 'nmae'"),
+            (
+                "count in ['a', 'b']",
+                "cannot compare Integer with String in 'count in [\"a\", \"b\"]'",
+            ),
+            (
+                "'a' in count",
+                "'in' needs an Array or Map on the right, found Integer in '\"a\" in count'",
+            ),
+            ("name + 1 > 0", "'+' needs numbers, found String in 'name + 1'"),
+        ];
+        for (invariant, expected) in cases {
+            let input = make_contract_with_state_and_invariants(state, &[invariant]);
+            let result = parse_and_verify(&input);
+            assert!(
+                result.errors().iter().any(|d| d.message == expected),
+                "{}: {:?}",
+                invariant,
+                result.errors()
+            );
+        }
+
+        // Comparing a non-Optional value with null is constant, not an error
+        let input = make_contract_with_state_and_invariants(state, &["count != null"]);
+        let result = parse_and_verify(&input);
+        assert!(result.is_valid());
+        assert!(result
+            .warnings()
+            .iter()
+            .any(|d| d.message.contains("'count' is Integer, not Optional")));
+    }
+
+    #[test]
+    fn test_strict_conditions_reject_opaque() {
+        let input = make_contract_with_state_and_invariants(
+            "count: Integer",
+            &["count >= 0", "count is always sensible"],
+        );
+        let ast = parse_hashed(&input);
+        assert!(verify(&ast).is_valid());
+
+        let strict = VerifyOptions {
+            strict_conditions: true,
+            ..Default::default()
+        };
+        let result = verify_with_options(&ast, &strict);
+        let errors = result.errors();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(errors[0].kind, DiagnosticKind::InvariantError);
+        assert!(
+            errors[0]
+                .message
+                .starts_with("invariant 'count is always sensible' is not machine-checkable: "),
+            "{}",
+            errors[0].message
+        );
+        let span = errors[0].span.as_ref().unwrap();
+        assert_eq!(&input[span.range()], "\"count is always sensible\"");
+
+        // Operation conditions are checked too
+        let mut ast = ast;
+        ast.behavioral_semantics.operations.push(OperationNode {
+            name: SpannedValue::new("touch".to_string(), Span::default()),
+            precondition: ConditionNode {
+                text: "whenever it rains".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let result = verify_with_options(&ast, &strict);
+        assert!(result
+            .errors()
+            .iter()
+            .any(|d| d.kind == DiagnosticKind::CoherenceError
+                && d.message
+                    .starts_with("precondition of 'touch' 'whenever it rains'")));
+    }
+
+    #[test]
+    fn test_builtin_calls_checked() {
+        let state = "items: Array<String>, tags: Array<String>, scores: Array<Integer>, \
+                     metadata: Map<String, String>, email: String, count: Integer, \
+                     session_id: UUID, nickname: Optional<String>";
+        let valid = make_contract_with_state_and_invariants(
+            state,
+            &[
+                "len(items) <= 100",
+                "length(items) == len(items)",
+                "contains(tags, 'x') or contains(metadata, email)",
+                "matches(email, '^[^@]+@[^@]+$')",
+                "sum(scores) >= 0 and max(scores) < count * 2",
+                "all k in keys(metadata): len(k) > 0",
+                "is_uuid(session_id) and len(upper(nickname)) < 20",
+            ],
+        );
+        let result = parse_and_verify(&valid);
+        assert!(result.is_valid(), "{:?}", result.errors());
+        // Type-derived defaults may not satisfy the invariants yet
+        assert!(
+            result
+                .warnings()
+                .iter()
+                .all(|d| d.message.starts_with("initial state violates")),
+            "{:?}",
+            result.warnings()
+        );
+
+        let cases = [
+            (
+                "size(items) > 0",
+                "unknown function 'size' in 'size(items)'",
+            ),
+            (
+                "len(items, tags) > 0",
+                "'len' takes 1 argument, found 2 in 'len(items, tags)'",
+            ),
+            (
+                "len(count) > 0",
+                "'len' needs a String, Array or Map, found Integer in 'len(count)'",
+            ),
+            (
+                "contains(tags, count)",
+                "'contains' cannot look for Integer in Array<String> in 'contains(tags, count)'",
+            ),
+            (
+                "sum(items) > 0",
+                "'sum' needs an Array of numbers, found Array<String> in 'sum(items)'",
+            ),
+            (
+                "len(email) == 'x'",
+                "cannot compare Integer with String in 'len(email) == \"x\"'",
+            ),
+            (
+                "matches(email, '(')",
+                "invalid pattern \"(\": unclosed group",
+            ),
+        ];
+        for (invariant, expected) in cases {
+            let input = make_contract_with_state_and_invariants(state, &[invariant]);
+            let result = parse_and_verify(&input);
+            assert!(
+                result.errors().iter().any(|d| d.message == expected),
+                "{}: {:?}",
+                invariant,
+                result.errors()
+            );
+        }
+    }
+
+    #[test]
+    fn test_condition_expressions_typed() {
+        let state = "name: String = \"a\", count: Integer = 0, active: Boolean = true, \
+                     tags: Array<String> = [], status: Enum[\"open\", \"closed\"] = \"open\"";
+        let params = "amount: Integer, label: String";
+
+        // Parameters are in scope in pre/postconditions, bare variants compare with enums
+        let valid = make_contract_with_invariants_and_operation(
+            state,
+            &[
+                "count >= 0",
+                "active or status == closed",
+                "all t in tags: t is not empty",
+            ],
+            params,
+            "amount > 0 and label != name",
+            "count == old(count) + amount and tags[count] is not empty",
+        );
+        let result = parse_and_verify(&valid);
+        assert!(result.is_valid(), "{:?}", result.errors());
+
+        let cases = [
+            ("name >= 5", "cannot order String and Integer in 'name >= 5' (only numbers and ISO8601 timestamps are ordered)"),
+            ("count < max_int", "unknown identifier 'max_int'"),
+            ("amount > 0", "unknown identifier 'amount'"),
+            ("old(missing) == 1", "unknown identifier 'missing'"),
+            ("count", "'count' is Integer, not a Boolean condition"),
+            ("count + 1", "'count + 1' is Integer, not a Boolean condition"),
+            ("active and name", "'name' is String, not a Boolean condition"),
+            ("not 'yes'", "'\"yes\"' is String, not a Boolean condition"),
+            ("any t in tags: t", "'t' is String, not a Boolean condition"),
+            ("count is empty", "'is empty' needs a String, Array or Map, found Integer in 'count is empty'"),
+            ("tags[name] == 'x'", "index into 'tags' must be an Integer, found String"),
+        ];
+        for (invariant, expected) in cases {
+            let input = make_contract_with_state_and_invariants(state, &[invariant]);
+            let result = parse_and_verify(&input);
+            let errors = result.errors();
+            let error = errors.iter().find(|d| d.message == expected);
+            assert!(error.is_some(), "{}: {:?}", invariant, errors);
+            assert_eq!(error.unwrap().kind, DiagnosticKind::TypeError);
+        }
+
+        // The span covers the unknown name
+        let input = make_contract_with_state_and_invariants(state, &["count < max_int"]);
+        let result = parse_and_verify(&input);
+        let span = result.errors()[0].span.clone().unwrap();
+        assert_eq!(&input[span.offset..span.end], "max_int");
+    }
+
+    #[test]
+    fn test_flag_prose_conditions_opaque() {
+        // Bare names that are not in scope, alone or joined by and/or/not,
+        // and `is` with a word that is not empty, a type or a literal are prose
+        let state = "count: Integer = 0, active: Boolean = true";
+        let prose = [
+            "count_positive",
+            "item_added AND count_incremented",
+            "not done or pending",
+            "count is finite",
+        ];
+        let input = make_contract_with_state_and_invariants(state, &prose);
+        let ast = parse_hashed(&input);
+        let result = verify(&ast);
+        assert!(result.is_valid(), "{:?}", result.errors());
+        assert!(ast.data_semantics.invariants[..3]
+            .iter()
+            .all(|i| i.expr.is_some()));
+
+        // Strict mode still reports them as not machine-checkable
+        let strict = VerifyOptions {
+            strict_conditions: true,
+            ..Default::default()
+        };
+        let result = verify_with_options(&ast, &strict);
+        assert!(result.errors().iter().any(|d| d.message
+            == "invariant 'count_positive' is not machine-checkable: it names no state field or parameter"));
+        assert_eq!(result.errors().len(), prose.len(), "{:?}", result.errors());
+
+        // A name in scope makes the others unknown identifiers
+        let input = make_contract_with_state_and_invariants(state, &["active and item_added"]);
+        let result = parse_and_verify(&input);
+        assert!(result
+            .errors()
+            .iter()
+            .any(|d| d.rule == &lint::UNKNOWN_IDENTIFIER
+                && d.message == "unknown identifier 'item_added'"));
+    }
+
+    #[test]
+    fn test_old_only_in_postconditions() {
+        let valid = make_contract_with_operation("bump", "count >= 0", "count == old(count) + 1");
+        let result = parse_and_verify(&valid);
+        assert!(result.is_valid(), "{:?}", result.errors());
+
+        let cases = [
+            (
+                make_contract_with_operation("bump", "old(count) >= 0", "true"),
+                "old(...) is only allowed in postconditions, not in preconditions",
+            ),
+            (
+                make_contract_with_operation("bump", "true", "count == old(old(count))"),
+                "nested old(...) in postcondition of 'bump'",
+            ),
+            (
+                make_contract_with_operation("bump", "true", "count == old(amount)")
+                    .replace("parameters: {}", "parameters: { amount: Integer }"),
+                "old(amount) in postcondition of 'bump' refers to a parameter",
+            ),
+            (
+                valid.replace("invariants: []", "invariants: [\"count >= old(count)\"]"),
+                "old(...) is only allowed in postconditions, not in invariants",
+            ),
+        ];
+        for (input, expected) in cases {
+            let result = parse_and_verify(&input);
+            assert!(
+                result.errors().iter().any(|d| d.message.contains(expected)),
+                "{}: {:?}",
+                expected,
+                result.errors()
+            );
+        }
+    }
+
     fn make_contract_with_operation(name: &str, precondition: &str, postcondition: &str) -> String {
         format!(
             r#"Contract {{
@@ -2036,12 +4826,14 @@ mod tests {
             line: 0,
             column: 0,
             offset: 0,
+            end: 0,
         }
     }
 
     /// Create a minimal valid AST for direct manipulation in tests
     fn make_valid_ast() -> ContractNode {
         ContractNode {
+            imports: vec![],
             identity: IdentityNode {
                 stable_id: SpannedValue::new("ic-test-001".to_string(), dummy_span()),
                 version: SpannedValue::new(1, dummy_span()),
@@ -2059,6 +4851,7 @@ mod tests {
                 confidence_level: SpannedValue::new(1.0, dummy_span()),
                 span: dummy_span(),
             },
+            types: None,
             data_semantics: DataSemanticsNode {
                 state: vec![StateFieldNode {
                     name: SpannedValue::new("value".to_string(), dummy_span()),
@@ -0,0 +1,1007 @@
+//! Language features computed from document text
+//!
+//! Every feature re-parses the document with
+//! [`parse_with_diagnostics`](icl_core::parser::parse_with_diagnostics), so
+//! hover, go-to-definition and symbols keep working on the parts of a
+//! contract that still parse while the user is typing.
+
+use std::path::Path;
+
+use icl_core::builtins::{self, Builtin};
+use icl_core::fix;
+use icl_core::lint::{LintConfig, Suppressions};
+use icl_core::loader::{self, FileLoader, ImportError};
+use icl_core::parser::ast::*;
+use icl_core::parser::tokenizer::{Span, Token, Tokenizer};
+use icl_core::parser::{parse_with_diagnostics, ParseResult};
+use icl_core::verifier::{self, Diagnostic, Severity, VerificationResult, VerifyOptions};
+use serde_json::{json, Value};
+
+use crate::position::LineIndex;
+
+// LSP enum values
+const SEVERITY_ERROR: u8 = 1;
+const SEVERITY_WARNING: u8 = 2;
+const SYMBOL_FUNCTION: u8 = 12;
+const COMPLETION_CLASS: u8 = 7;
+const COMPLETION_FIELD: u8 = 5;
+const COMPLETION_MODULE: u8 = 9;
+const CODE_ACTION_QUICKFIX: &str = "quickfix";
+
+const SECTIONS: &[&str] = &[
+    "Identity",
+    "PurposeStatement",
+    "Types",
+    "DataSemantics",
+    "BehavioralSemantics",
+    "ExecutionConstraints",
+    "HumanMachineContract",
+];
+
+const TYPES: &[&str] = &[
+    "Integer", "Float", "String", "Boolean", "ISO8601", "UUID", "Array", "Map", "Object", "Enum",
+    "Optional",
+];
+
+/// Field names allowed in each block, by the block's label
+fn block_fields(label: &str) -> &'static [&'static str] {
+    match label {
+        "Identity" => &[
+            "stable_id",
+            "version",
+            "created_timestamp",
+            "owner",
+            "semantic_hash",
+        ],
+        "PurposeStatement" => &["narrative", "intent_source", "confidence_level"],
+        "DataSemantics" => &["state", "invariants"],
+        "BehavioralSemantics" => &["operations"],
+        "operation" => &[
+            "name",
+            "precondition",
+            "parameters",
+            "postcondition",
+            "side_effects",
+            "idempotence",
+        ],
+        "ExecutionConstraints" => &[
+            "trigger_types",
+            "resource_limits",
+            "external_permissions",
+            "sandbox_mode",
+        ],
+        "resource_limits" => &[
+            "max_memory_bytes",
+            "computation_timeout_ms",
+            "max_state_size_bytes",
+        ],
+        "HumanMachineContract" => &["system_commitments", "system_refusals", "user_obligations"],
+        _ => &[],
+    }
+}
+
+// ── Diagnostics ───────────────────────────────────────────
+
+/// Parse errors, import errors, or verifier diagnostics once the
+/// document parses and its imports resolve
+///
+/// Imports are read from disk relative to `path`; documents without a
+/// filesystem path (unsaved buffers) cannot resolve imports and use the
+/// default lint levels. `icl-allow` comments apply either way.
+pub fn diagnostics(text: &str, path: Option<&str>) -> Vec<Value> {
+    let index = LineIndex::new(text);
+    match verify(text, path, &index) {
+        Err(diagnostics) => diagnostics,
+        Ok((mut diagnostics, result)) => {
+            diagnostics.extend(
+                result
+                    .diagnostics
+                    .iter()
+                    .map(|d| verifier_diagnostic(&index, d)),
+            );
+            diagnostics
+        }
+    }
+}
+
+/// Verify the document, or return its parse or import errors
+///
+/// Alongside the verification come the errors of a broken `icl.json`.
+fn verify(
+    text: &str,
+    path: Option<&str>,
+    index: &LineIndex,
+) -> Result<(Vec<Value>, VerificationResult), Vec<Value>> {
+    let parsed = parse_with_diagnostics(text);
+
+    if !parsed.is_valid() {
+        // Imported modules are not contracts; only check their imports
+        if let Ok(module) = icl_core::parser::parse_module(text) {
+            return Err(module_diagnostics(index, &module, path));
+        }
+        return Err(parsed
+            .diagnostics
+            .iter()
+            .map(|d| diagnostic(index, &d.span, SEVERITY_ERROR, d.code, &d.message))
+            .collect());
+    }
+
+    let contract = match resolve_imports(parsed.contract, path) {
+        Ok(contract) => contract,
+        Err(e) => {
+            return Err(vec![diagnostic(
+                index,
+                &e.span,
+                SEVERITY_ERROR,
+                e.code,
+                &e.message,
+            )])
+        }
+    };
+
+    // Lint levels come from the project's icl.json; a broken one is
+    // reported and the defaults used
+    let mut diagnostics = Vec::new();
+    let lint = match path.map(|p| LintConfig::for_file(Path::new(p))) {
+        Some(Ok(lint)) => lint,
+        Some(Err(e)) => {
+            let span = Span::default();
+            diagnostics.push(diagnostic(
+                index,
+                &span,
+                SEVERITY_ERROR,
+                "config",
+                &e.to_string(),
+            ));
+            LintConfig::new()
+        }
+        None => LintConfig::new(),
+    };
+    let options = VerifyOptions {
+        lint,
+        suppressions: Suppressions::from_source(text),
+        ..Default::default()
+    };
+
+    let result = verifier::verify_with_options(&contract, &options);
+    Ok((diagnostics, result))
+}
+
+/// Import errors of a module file, checked like the contract importing it
+fn module_diagnostics(index: &LineIndex, module: &ModuleNode, path: Option<&str>) -> Vec<Value> {
+    let contract = ContractNode {
+        imports: module.imports.clone(),
+        ..Default::default()
+    };
+    match resolve_imports(contract, path) {
+        Ok(_) => Vec::new(),
+        Err(e) => vec![diagnostic(
+            index,
+            &e.span,
+            SEVERITY_ERROR,
+            e.code,
+            &e.message,
+        )],
+    }
+}
+
+fn resolve_imports(
+    contract: ContractNode,
+    path: Option<&str>,
+) -> Result<ContractNode, ImportError> {
+    match path {
+        Some(path) => loader::resolve_imports(contract, path, &FileLoader),
+        None => match contract.imports.first() {
+            Some(import) => Err(ImportError {
+                code: loader::UNREADABLE_IMPORT,
+                message: "imports cannot be resolved in an unsaved document".to_string(),
+                span: import.span.clone(),
+            }),
+            None => Ok(contract),
+        },
+    }
+}
+
+fn verifier_diagnostic(index: &LineIndex, d: &Diagnostic) -> Value {
+    let severity = match d.severity {
+        Severity::Error => SEVERITY_ERROR,
+        Severity::Warning => SEVERITY_WARNING,
+    };
+    let span = d.span.clone().unwrap_or_default();
+    diagnostic(index, &span, severity, d.rule.code, &d.message)
+}
+
+fn diagnostic(index: &LineIndex, span: &Span, severity: u8, code: &str, message: &str) -> Value {
+    json!({
+        "range": index.range(span),
+        "severity": severity,
+        "code": code,
+        "source": "icl",
+        "message": message,
+    })
+}
+
+// ── Code actions ──────────────────────────────────────────
+
+/// One quick-fix per verifier diagnostic in `range` that carries a fix
+/// still applicable to `text`; safe fixes are marked preferred
+pub fn code_actions(text: &str, path: Option<&str>, uri: &str, range: &Value) -> Vec<Value> {
+    let index = LineIndex::new(text);
+    let Ok((_, result)) = verify(text, path, &index) else {
+        return Vec::new();
+    };
+    let start = index.offset(&range["start"]);
+    let end = index.offset(&range["end"]);
+
+    result
+        .diagnostics
+        .iter()
+        .filter_map(|d| {
+            let span = d.span.as_ref()?;
+            let fix = d.fix.as_ref()?;
+            if span.offset > end || span.end < start || !fix::applicable(text, fix) {
+                return None;
+            }
+            let edits: Vec<Value> = fix
+                .edits
+                .iter()
+                .map(
+                    |edit| json!({ "range": index.range(&edit.span), "newText": edit.replacement }),
+                )
+                .collect();
+            Some(json!({
+                "title": fix.message,
+                "kind": CODE_ACTION_QUICKFIX,
+                "isPreferred": fix.safe,
+                "diagnostics": [verifier_diagnostic(&index, d)],
+                "edit": { "changes": { uri: edits } },
+            }))
+        })
+        .collect()
+}
+
+// ── Hover ─────────────────────────────────────────────────
+
+/// Markdown hover text and the span it applies to. `path` is used to
+/// resolve imports, which the semantic hash covers.
+pub fn hover(text: &str, path: Option<&str>, offset: usize) -> Option<(String, Span)> {
+    let parsed = parse_with_diagnostics(text);
+    let ast = &parsed.contract;
+
+    if let Some(field) = state_fields(&ast.data_semantics.state)
+        .into_iter()
+        .find(|f| contains(&f.name.span, offset))
+    {
+        return Some((field_hover("state field", field), field.name.span.clone()));
+    }
+
+    for op in &ast.behavioral_semantics.operations {
+        if contains(&op.name.span, offset) {
+            return Some((operation_hover(op), op.name.span.clone()));
+        }
+        if let Some(param) = op
+            .parameters
+            .iter()
+            .find(|p| contains(&p.name.span, offset))
+        {
+            let kind = format!("parameter of `{}`", op.name.value);
+            return Some((field_hover(&kind, param), param.name.span.clone()));
+        }
+    }
+
+    if contains(&ast.identity.semantic_hash.span, offset) {
+        let span = ast.identity.semantic_hash.span.clone();
+        return Some((hash_hover(&parsed, path), span));
+    }
+
+    if let Some((builtin, span)) = builtin_at(ast, offset) {
+        let markdown = format!(
+            "```icl\n{}\n```\n{}",
+            builtin.signature, builtin.description
+        );
+        return Some((markdown, span));
+    }
+
+    let reference = reference_at(ast, offset)?;
+    let (kind, target) = resolve(ast, &reference)?;
+    let mut markdown = field_hover(kind, target);
+    if reference.path.len() > 1 {
+        markdown.push_str(&format!("\n\nPath: `{}`", reference.path.join(".")));
+    }
+    Some((markdown, reference.span))
+}
+
+fn field_hover(kind: &str, field: &StateFieldNode) -> String {
+    let mut markdown = format!(
+        "```icl\n{}: {}\n```\n{}",
+        field.name.value, field.type_expr, kind
+    );
+    if let Some(ref default) = field.default_value {
+        markdown.push_str(&format!(" — default `{}`", literal_text(default)));
+    }
+    markdown
+}
+
+fn operation_hover(op: &OperationNode) -> String {
+    let params: Vec<String> = op
+        .parameters
+        .iter()
+        .map(|p| format!("{}: {}", p.name.value, p.type_expr))
+        .collect();
+    format!(
+        "```icl\noperation {}({})\n```\n**pre:** {}\n\n**post:** {}\n\n**idempotence:** {}",
+        op.name.value,
+        params.join(", "),
+        op.precondition.text,
+        op.postcondition.text,
+        op.idempotence.value
+    )
+}
+
+fn hash_hover(parsed: &ParseResult, path: Option<&str>) -> String {
+    if !parsed.is_valid() {
+        return "Semantic hash (fix parse errors to compute)".to_string();
+    }
+    // The hash covers imported definitions, as in the verifier
+    let Ok(contract) = resolve_imports(parsed.contract.clone(), path) else {
+        return "Semantic hash (fix import errors to compute)".to_string();
+    };
+    let declared = &contract.identity.semantic_hash.value;
+    let normalized = icl_core::normalizer::normalize_ast(contract.clone());
+    let computed = icl_core::normalizer::compute_semantic_hash(&normalized);
+    let status = if computed == *declared {
+        "matches"
+    } else {
+        "differs from the declared value"
+    };
+    format!("Computed semantic hash ({}):\n\n`{}`", status, computed)
+}
+
+fn literal_text(lit: &LiteralValue) -> String {
+    match lit {
+        LiteralValue::String(s, _) => format!("\"{}\"", s),
+        LiteralValue::Integer(n, _) => n.to_string(),
+        LiteralValue::Float(f, _) => f.to_string(),
+        LiteralValue::Boolean(b, _) => b.to_string(),
+        LiteralValue::Null(_) => "null".to_string(),
+        LiteralValue::Array(items, _) => {
+            let items: Vec<String> = items.iter().map(literal_text).collect();
+            format!("[{}]", items.join(", "))
+        }
+    }
+}
+
+// ── Go to definition ──────────────────────────────────────
+
+/// Span of the state field or parameter a condition identifier refers to
+pub fn definition(text: &str, offset: usize) -> Option<Span> {
+    let ast = parse_with_diagnostics(text).contract;
+    let reference = reference_at(&ast, offset)?;
+    resolve(&ast, &reference).map(|(_, field)| field.name.span.clone())
+}
+
+/// A field path used in a condition
+struct Reference {
+    path: Vec<String>,
+    span: Span,
+    /// Index of the operation whose pre/postcondition holds the path
+    operation: Option<usize>,
+}
+
+/// Every condition in the contract, with the index of its operation
+fn conditions(ast: &ContractNode) -> Vec<(&ConditionNode, Option<usize>)> {
+    let mut conditions: Vec<(&ConditionNode, Option<usize>)> = ast
+        .data_semantics
+        .invariants
+        .iter()
+        .map(|c| (c, None))
+        .collect();
+    for (i, op) in ast.behavioral_semantics.operations.iter().enumerate() {
+        conditions.push((&op.precondition, Some(i)));
+        conditions.push((&op.postcondition, Some(i)));
+    }
+    conditions
+}
+
+fn reference_at(ast: &ContractNode, offset: usize) -> Option<Reference> {
+    conditions(ast)
+        .into_iter()
+        .find_map(|(condition, operation)| {
+            let mut paths = Vec::new();
+            collect_paths(condition.expr.as_ref()?, &mut paths);
+            paths
+                .into_iter()
+                .find(|(_, span)| contains(span, offset))
+                .map(|(path, span)| Reference {
+                    path: path.clone(),
+                    span: span.clone(),
+                    operation,
+                })
+        })
+}
+
+/// The built-in function whose name in a call is at `offset`, with the
+/// span of the name
+fn builtin_at(ast: &ContractNode, offset: usize) -> Option<(&'static Builtin, Span)> {
+    conditions(ast).into_iter().find_map(|(condition, _)| {
+        let mut calls = Vec::new();
+        collect_calls(condition.expr.as_ref()?, &mut calls);
+        calls.into_iter().find_map(|(name, span)| {
+            let name_span = Span {
+                end: span.offset + name.len(),
+                ..span.clone()
+            };
+            if !contains(&name_span, offset) {
+                return None;
+            }
+            builtins::lookup(name).map(|builtin| (builtin, name_span))
+        })
+    })
+}
+
+fn collect_calls<'a>(expr: &'a Expr, out: &mut Vec<(&'a str, &'a Span)>) {
+    match expr {
+        Expr::Call(name, args, span) => {
+            out.push((name, span));
+            for arg in args {
+                collect_calls(arg, out);
+            }
+        }
+        Expr::Unary(_, inner, _)
+        | Expr::IsEmpty(inner, _)
+        | Expr::IsType(inner, _, _)
+        | Expr::Old(inner, _)
+        | Expr::Field(inner, _, _) => collect_calls(inner, out),
+        Expr::Binary(_, lhs, rhs, _)
+        | Expr::Index(lhs, rhs, _)
+        | Expr::Quantified(_, _, lhs, rhs, _) => {
+            collect_calls(lhs, out);
+            collect_calls(rhs, out);
+        }
+        Expr::Literal(_) | Expr::Path(_, _) => {}
+    }
+}
+
+fn collect_paths<'a>(expr: &'a Expr, out: &mut Vec<(&'a Vec<String>, &'a Span)>) {
+    match expr {
+        Expr::Path(path, span) => out.push((path, span)),
+        Expr::Unary(_, inner, _)
+        | Expr::IsEmpty(inner, _)
+        | Expr::IsType(inner, _, _)
+        | Expr::Old(inner, _)
+        | Expr::Field(inner, _, _) => collect_paths(inner, out),
+        Expr::Binary(_, lhs, rhs, _) | Expr::Index(lhs, rhs, _) => {
+            collect_paths(lhs, out);
+            collect_paths(rhs, out);
+        }
+        Expr::Quantified(_, var, collection, body, _) => {
+            collect_paths(collection, out);
+            // Paths through the bound variable do not name a field
+            let mut inner = Vec::new();
+            collect_paths(body, &mut inner);
+            out.extend(inner.into_iter().filter(|(path, _)| path[0] != *var));
+        }
+        Expr::Call(_, args, _) => {
+            for arg in args {
+                collect_paths(arg, out);
+            }
+        }
+        Expr::Literal(_) => {}
+    }
+}
+
+/// Parameters of the enclosing operation shadow state fields
+fn resolve<'a>(
+    ast: &'a ContractNode,
+    reference: &Reference,
+) -> Option<(&'static str, &'a StateFieldNode)> {
+    let root = reference.path.first()?;
+    if let Some(op) = reference
+        .operation
+        .and_then(|i| ast.behavioral_semantics.operations.get(i))
+    {
+        if let Some(param) = op.parameters.iter().find(|p| &p.name.value == root) {
+            return Some(("parameter", param));
+        }
+    }
+    ast.data_semantics
+        .state
+        .iter()
+        .find(|f| &f.name.value == root)
+        .map(|f| ("state field", f))
+}
+
+// ── Document symbols ──────────────────────────────────────
+
+/// One `Function` symbol per operation
+pub fn document_symbols(text: &str) -> Vec<Value> {
+    let index = LineIndex::new(text);
+    parse_with_diagnostics(text)
+        .contract
+        .behavioral_semantics
+        .operations
+        .iter()
+        .filter(|op| !op.name.value.is_empty())
+        .map(|op| {
+            json!({
+                "name": op.name.value,
+                "detail": op.idempotence.value,
+                "kind": SYMBOL_FUNCTION,
+                "range": index.range(&op.span),
+                "selectionRange": index.range(&op.name.span),
+            })
+        })
+        .collect()
+}
+
+// ── Completion ────────────────────────────────────────────
+
+/// Section, field or type names valid at `offset`
+pub fn completions(text: &str, offset: usize) -> Vec<Value> {
+    let (tokens, _) = Tokenizer::new(text).tokenize_with_diagnostics();
+    let before: Vec<&Token> = tokens
+        .iter()
+        .filter(|t| t.span.end < offset || (t.span.end == offset && !is_word(&t.token)))
+        .map(|t| &t.token)
+        .collect();
+    let labels = block_labels(&before);
+    let label = labels.last().map(String::as_str);
+
+    let (names, kind): (&[&str], u8) = match (before.last(), label) {
+        // Value position: only types make sense, and only where fields are declared
+        (Some(Token::Colon), Some("state" | "parameters" | "Object" | "Types")) => {
+            return type_completions(text);
+        }
+        (Some(Token::Colon | Token::Equals), _) => (&[], COMPLETION_FIELD),
+        (_, None) => (&["Contract", "Extensions"], COMPLETION_MODULE),
+        (_, Some("Contract")) => (SECTIONS, COMPLETION_MODULE),
+        (_, Some(label)) => (block_fields(label), COMPLETION_FIELD),
+    };
+
+    names
+        .iter()
+        .map(|name| json!({ "label": name, "kind": kind }))
+        .collect()
+}
+
+/// Built-in type names followed by the names defined in `Types`
+fn type_completions(text: &str) -> Vec<Value> {
+    let parsed = parse_with_diagnostics(text);
+    let named = parsed
+        .contract
+        .type_definitions()
+        .iter()
+        .map(|d| d.name.value.as_str());
+    TYPES
+        .iter()
+        .copied()
+        .chain(named)
+        .map(|name| json!({ "label": name, "kind": COMPLETION_CLASS }))
+        .collect()
+}
+
+/// Labels of the `{` / `[` blocks enclosing the end of `tokens`, outermost
+/// first: section keywords, field names (`state`, `resource_limits`),
+/// `Object`, or `operation` for the items of `operations: [...]`.
+fn block_labels(tokens: &[&Token]) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LBrace | Token::LBracket => {
+                let prev = i.checked_sub(1).map(|j| tokens[j]);
+                let field = i.checked_sub(2).map(|j| tokens[j]);
+                let label = match (prev, field) {
+                    (Some(Token::Colon), Some(Token::Identifier(name))) => name.clone(),
+                    (Some(Token::ObjectType), _) => "Object".to_string(),
+                    (Some(Token::LBracket | Token::Comma), _)
+                        if labels.last().map(String::as_str) == Some("operations") =>
+                    {
+                        "operation".to_string()
+                    }
+                    (Some(keyword), _) if is_keyword(keyword) => format!("{:?}", keyword),
+                    _ => String::new(),
+                };
+                labels.push(label);
+            }
+            Token::RBrace | Token::RBracket => {
+                labels.pop();
+            }
+            _ => {}
+        }
+    }
+    labels
+}
+
+fn is_keyword(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Contract
+            | Token::Identity
+            | Token::PurposeStatement
+            | Token::DataSemantics
+            | Token::BehavioralSemantics
+            | Token::ExecutionConstraints
+            | Token::HumanMachineContract
+            | Token::Extensions
+            | Token::Types
+    )
+}
+
+/// Tokens a partially typed word can lex as
+fn is_word(token: &Token) -> bool {
+    is_keyword(token)
+        || matches!(
+            token,
+            Token::Identifier(_)
+                | Token::BooleanLiteral(_)
+                | Token::NullLiteral
+                | Token::IntegerType
+                | Token::FloatType
+                | Token::StringType
+                | Token::BooleanType
+                | Token::Iso8601Type
+                | Token::UuidType
+                | Token::ArrayType
+                | Token::MapType
+                | Token::ObjectType
+                | Token::EnumType
+                | Token::OptionalType
+        )
+}
+
+// ── Formatting ────────────────────────────────────────────
+
+/// Whole-document edit from the formatter, or none if already formatted
+/// or the document does not parse
+pub fn formatting(text: &str) -> Vec<Value> {
+    match icl_core::formatter::format(text) {
+        Ok(formatted) if formatted != text => {
+            let index = LineIndex::new(text);
+            vec![json!({ "range": index.full_range(), "newText": formatted })]
+        }
+        _ => Vec::new(),
+    }
+}
+
+// ── Helpers ───────────────────────────────────────────────
+
+fn contains(span: &Span, offset: usize) -> bool {
+    span.offset <= offset && offset < span.end
+}
+
+/// All state fields, including fields nested in `Object` types
+fn state_fields(fields: &[StateFieldNode]) -> Vec<&StateFieldNode> {
+    let mut out = Vec::new();
+    for field in fields {
+        out.push(field);
+        if let TypeExpression::Object(inner, _) = &field.type_expr {
+            out.extend(state_fields(inner));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = r#"Contract {
+  Identity {
+    stable_id: "ic-lsp-001",
+    version: 1,
+    created_timestamp: 2026-02-01T00:00:00Z,
+    owner: "test",
+    semantic_hash: "307e605193dff0af3afbd32500878d6fb455252cd693f3da0876bfd9fd457659"
+  }
+  PurposeStatement {
+    narrative: "LSP test",
+    intent_source: "test",
+    confidence_level: 1.0
+  }
+  DataSemantics {
+    state: {
+      balance: Integer = 0,
+      profile: Object { age: Integer }
+    },
+    invariants: ["balance >= 0", "profile.age >= 0", "abs(balance) >= 0"]
+  }
+  BehavioralSemantics {
+    operations: [
+      {
+        name: "deposit",
+        precondition: "amount > 0",
+        parameters: { amount: Integer },
+        postcondition: "balance >= amount",
+        side_effects: [],
+        idempotence: "not_idempotent"
+      }
+    ]
+  }
+  ExecutionConstraints {
+    trigger_types: ["manual"],
+    resource_limits: {
+      max_memory_bytes: 1048576,
+      computation_timeout_ms: 100,
+      max_state_size_bytes: 1048576
+    },
+    external_permissions: [],
+    sandbox_mode: "full_isolation"
+  }
+  HumanMachineContract {
+    system_commitments: [],
+    system_refusals: [],
+    user_obligations: []
+  }
+}
+"#;
+
+    /// Offset of the `nth` occurrence of `needle` plus `delta`
+    fn offset_of(needle: &str, nth: usize, delta: usize) -> usize {
+        CONTRACT.match_indices(needle).nth(nth).unwrap().0 + delta
+    }
+
+    /// `text` with its semantic_hash recomputed after an edit
+    fn rehash(text: &str) -> String {
+        let ast = icl_core::parser::parse(text).unwrap();
+        let declared = ast.identity.semantic_hash.value.clone();
+        let hash =
+            icl_core::normalizer::compute_semantic_hash(&icl_core::normalizer::normalize_ast(ast));
+        text.replace(&declared, &hash)
+    }
+
+    #[test]
+    fn test_diagnostics_clean_contract() {
+        assert!(diagnostics(CONTRACT, None).is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_parse_errors_have_ranges() {
+        let text = CONTRACT.replace("version: 1,", "version: \"one\",");
+        let diags = diagnostics(&text, None);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0]["severity"], SEVERITY_ERROR);
+        assert_eq!(diags[0]["code"], "ICL-P005");
+        assert_eq!(
+            diags[0]["range"]["start"],
+            json!({"line": 3, "character": 13})
+        );
+        assert_eq!(
+            diags[0]["range"]["end"],
+            json!({"line": 3, "character": 18})
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_verifier_warning() {
+        let text = rehash(&CONTRACT.replace("balance >= amount", "balance covers missing_field"));
+        let diags = diagnostics(&text, None);
+        assert_eq!(diags.len(), 1, "{:?}", diags);
+        assert_eq!(diags[0]["severity"], SEVERITY_WARNING);
+        assert_eq!(diags[0]["code"], "ICL-C005");
+
+        // An icl-allow comment on the line silences it
+        let text = text.replace(
+            "missing_field\",",
+            "missing_field\", // icl-allow(unknown-field-reference)",
+        );
+        assert!(diagnostics(&text, None).is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_unknown_identifier() {
+        let text = rehash(&CONTRACT.replace("balance >= amount", "balance >= missing_field"));
+        let diags = diagnostics(&text, None);
+        assert_eq!(diags.len(), 1, "{:?}", diags);
+        assert_eq!(diags[0]["severity"], SEVERITY_ERROR);
+        assert_eq!(diags[0]["message"], "unknown identifier 'missing_field'");
+        // Points at the name itself
+        assert_eq!(diags[0]["range"]["start"]["character"], 35);
+        assert_eq!(diags[0]["range"]["end"]["character"], 48);
+    }
+
+    #[test]
+    fn test_code_actions_in_range() {
+        let text = CONTRACT.replace("balance >= amount", "balance >= amuont");
+        let line = text.lines().position(|l| l.contains("amuont")).unwrap();
+        let cursor = json!({ "line": line, "character": 40 });
+        let range = json!({ "start": cursor, "end": cursor });
+        let actions = code_actions(&text, None, "file:///a.icl", &range);
+        assert_eq!(actions.len(), 1, "{:?}", actions);
+        assert_eq!(actions[0]["title"], "replace 'amuont' with 'amount'");
+        assert_eq!(actions[0]["kind"], "quickfix");
+        assert_eq!(actions[0]["isPreferred"], false);
+        assert_eq!(actions[0]["diagnostics"][0]["code"], "ICL-T019");
+        let edits = &actions[0]["edit"]["changes"]["file:///a.icl"];
+        assert_eq!(edits[0]["newText"], "amount");
+        assert_eq!(edits[0]["range"]["start"]["character"], 35);
+        assert_eq!(edits[0]["range"]["end"]["character"], 41);
+
+        // Nothing to fix on another line
+        let top = json!({ "line": 0, "character": 0 });
+        let range = json!({ "start": top, "end": top });
+        assert!(code_actions(&text, None, "file:///a.icl", &range).is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_resolve_imports() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/fixtures/imports");
+        let ledger = format!("{}/ledger.icl", dir);
+        let text = std::fs::read_to_string(&ledger).unwrap();
+        assert!(diagnostics(&text, Some(&ledger)).is_empty());
+
+        // Without a path the imports cannot be read
+        let diags = diagnostics(&text, None);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0]["code"], "ICL-M001");
+        assert_eq!(diags[0]["range"]["start"]["line"], 4);
+
+        let cycle = format!("{}/cycle.icl", dir);
+        let text = std::fs::read_to_string(&cycle).unwrap();
+        let diags = diagnostics(&text, Some(&cycle));
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("import cycle: "));
+
+        // Module files are checked for their imports only
+        let module = format!("{}/cycle/a.icl", dir);
+        let text = std::fs::read_to_string(&module).unwrap();
+        let diags = diagnostics(&text, Some(&module));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0]["code"], "ICL-M003");
+        let module = format!("{}/common/invariants.icl", dir);
+        let text = std::fs::read_to_string(&module).unwrap();
+        assert!(diagnostics(&text, Some(&module)).is_empty());
+    }
+
+    #[test]
+    fn test_hover_state_field_and_parameter() {
+        let (markdown, _) = hover(CONTRACT, None, offset_of("balance: Integer", 0, 2)).unwrap();
+        assert!(markdown.contains("balance: Integer"), "{}", markdown);
+        assert!(markdown.contains("default `0`"), "{}", markdown);
+
+        let (markdown, _) = hover(CONTRACT, None, offset_of("amount: Integer", 0, 0)).unwrap();
+        assert!(markdown.contains("parameter of `deposit`"), "{}", markdown);
+    }
+
+    #[test]
+    fn test_hover_condition_identifier() {
+        // `amount` in the postcondition resolves to the parameter
+        let (markdown, span) = hover(CONTRACT, None, offset_of("amount\"", 0, 1)).unwrap();
+        assert!(markdown.contains("amount: Integer"), "{}", markdown);
+        assert_eq!(&CONTRACT[span.range()], "amount");
+
+        let (markdown, span) = hover(CONTRACT, None, offset_of("profile.age", 0, 9)).unwrap();
+        assert!(markdown.contains("profile: Object"), "{}", markdown);
+        assert_eq!(&CONTRACT[span.range()], "profile.age");
+    }
+
+    #[test]
+    fn test_hover_builtin_function() {
+        let (markdown, span) = hover(CONTRACT, None, offset_of("abs(", 0, 1)).unwrap();
+        assert!(
+            markdown.contains("abs(Integer | Float) -> Integer | Float"),
+            "{}",
+            markdown
+        );
+        assert_eq!(&CONTRACT[span.range()], "abs");
+        // The argument still resolves to its field
+        let (markdown, _) = hover(CONTRACT, None, offset_of("abs(balance", 0, 5)).unwrap();
+        assert!(markdown.contains("balance: Integer"), "{}", markdown);
+    }
+
+    #[test]
+    fn test_hover_semantic_hash_shows_computed_hash() {
+        let offset = offset_of("307e6051", 0, 0);
+        let (markdown, _) = hover(CONTRACT, None, offset).unwrap();
+        assert!(markdown.contains("(matches)"), "{}", markdown);
+
+        let text = CONTRACT.replace("balance: Integer = 0", "balance: Integer = 1");
+        let (markdown, _) = hover(&text, None, offset).unwrap();
+        assert!(
+            markdown.contains("differs from the declared value"),
+            "{}",
+            markdown
+        );
+
+        // Imports are resolved before hashing
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/fixtures/imports");
+        let ledger = format!("{}/ledger.icl", dir);
+        let text = std::fs::read_to_string(&ledger).unwrap();
+        let offset = text.find("semantic_hash: \"").unwrap() + 16;
+        let (markdown, _) = hover(&text, Some(&ledger), offset).unwrap();
+        assert!(markdown.contains("(matches)"), "{}", markdown);
+        let (markdown, _) = hover(&text, None, offset).unwrap();
+        assert!(markdown.contains("fix import errors"), "{}", markdown);
+    }
+
+    #[test]
+    fn test_definition_from_condition() {
+        let target = definition(CONTRACT, offset_of("balance >= 0", 0, 0)).unwrap();
+        assert_eq!(target.offset, offset_of("balance: Integer", 0, 0));
+
+        let target = definition(CONTRACT, offset_of("amount > 0", 0, 0)).unwrap();
+        assert_eq!(target.offset, offset_of("amount: Integer", 0, 0));
+
+        assert!(definition(CONTRACT, offset_of("LSP test", 0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_document_symbols_lists_operations() {
+        let symbols = document_symbols(CONTRACT);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0]["name"], "deposit");
+        assert_eq!(symbols[0]["kind"], SYMBOL_FUNCTION);
+    }
+
+    fn labels(items: Vec<Value>) -> Vec<String> {
+        items
+            .iter()
+            .map(|i| i["label"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_completion_sections_and_fields() {
+        let top = labels(completions(CONTRACT, 0));
+        assert_eq!(top, vec!["Contract", "Extensions"]);
+
+        let sections = labels(completions(CONTRACT, offset_of("  Identity", 0, 2)));
+        assert!(sections.contains(&"PurposeStatement".to_string()));
+
+        // Typing a partial field name inside PurposeStatement
+        let text = CONTRACT.replace("    intent_source", "    inte\n    intent_source");
+        let at = text.find("inte\n").unwrap() + 4;
+        let fields = labels(completions(&text, at));
+        assert!(
+            fields.contains(&"intent_source".to_string()),
+            "{:?}",
+            fields
+        );
+
+        let op_fields = labels(completions(CONTRACT, offset_of("precondition", 0, 0)));
+        assert!(
+            op_fields.contains(&"postcondition".to_string()),
+            "{:?}",
+            op_fields
+        );
+
+        let limits = labels(completions(
+            CONTRACT,
+            offset_of("computation_timeout_ms", 0, 0),
+        ));
+        assert!(limits.contains(&"max_state_size_bytes".to_string()));
+    }
+
+    #[test]
+    fn test_completion_types_in_state() {
+        let types = labels(completions(CONTRACT, offset_of("balance: ", 0, 9)));
+        assert!(types.contains(&"Integer".to_string()), "{:?}", types);
+        assert!(labels(completions(CONTRACT, offset_of("version: ", 0, 9))).is_empty());
+    }
+
+    #[test]
+    fn test_completion_includes_named_types() {
+        let text = CONTRACT.replace(
+            "  DataSemantics {",
+            "  Types { Money: Integer }\n  DataSemantics {",
+        );
+        let at = text.find("balance: ").unwrap() + 9;
+        let types = labels(completions(&text, at));
+        assert!(types.contains(&"Money".to_string()), "{:?}", types);
+
+        let at = text.find("Money: ").unwrap() + 7;
+        assert!(labels(completions(&text, at)).contains(&"Integer".to_string()));
+    }
+
+    #[test]
+    fn test_formatting_edit() {
+        assert!(formatting(CONTRACT).is_empty());
+        let messy = CONTRACT.replace("  Identity {", "Identity{");
+        let edits = formatting(&messy);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0]["newText"], CONTRACT);
+        assert!(formatting("Contract {").is_empty());
+    }
+}
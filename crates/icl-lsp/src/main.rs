@@ -0,0 +1,59 @@
+//! ICL Language Server — LSP over stdio
+//!
+//! Serves live diagnostics, hover, go-to-definition, completion, document
+//! symbols and formatting for `.icl` files, built on `icl-core`'s parser,
+//! verifier, normalizer and formatter.
+//!
+//! # Architecture
+//!
+//! ```text
+//! stdin → protocol::read_message → Server::handle → protocol::write_message → stdout
+//!                                        ↓
+//!                                    analysis (parse / verify / format)
+//! ```
+//!
+//! Logs go to stderr; stdout carries only protocol messages.
+
+mod analysis;
+mod position;
+mod protocol;
+mod server;
+
+use std::io::{self, ErrorKind};
+use std::process;
+
+use server::{Control, Server};
+
+fn main() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut reader = stdin.lock();
+    let mut writer = stdout.lock();
+    let mut server = Server::new();
+
+    loop {
+        let message = match protocol::read_message(&mut reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => process::exit(server.exit_code()),
+            Err(e) if e.kind() == ErrorKind::InvalidData => {
+                eprintln!("icl-lsp: {}", e);
+                continue;
+            }
+            Err(e) => {
+                eprintln!("icl-lsp: {}", e);
+                process::exit(1);
+            }
+        };
+
+        let (outgoing, control) = server.handle(message);
+        for message in &outgoing {
+            if let Err(e) = protocol::write_message(&mut writer, message) {
+                eprintln!("icl-lsp: {}", e);
+                process::exit(1);
+            }
+        }
+        if let Control::Exit(code) = control {
+            process::exit(code);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Conversion between byte offsets and LSP positions
+//!
+//! ICL spans are byte ranges; LSP positions are (line, UTF-16 code unit)
+//! pairs, both zero-based.
+
+use icl_core::parser::tokenizer::Span;
+use serde_json::{json, Value};
+
+/// Line start offsets for one document
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex { text, line_starts }
+    }
+
+    /// LSP position of a byte offset (clamped to the document)
+    pub fn position(&self, offset: usize) -> Value {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character: usize = self.text[self.line_starts[line]..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+        json!({ "line": line, "character": character })
+    }
+
+    /// LSP range of a span
+    pub fn range(&self, span: &Span) -> Value {
+        json!({
+            "start": self.position(span.offset),
+            "end": self.position(span.end.max(span.offset)),
+        })
+    }
+
+    /// LSP range of the whole document
+    pub fn full_range(&self) -> Value {
+        json!({ "start": self.position(0), "end": self.position(self.text.len()) })
+    }
+
+    /// Byte offset of an LSP position (clamped to the line)
+    pub fn offset(&self, position: &Value) -> usize {
+        let line = position["line"].as_u64().unwrap_or(0) as usize;
+        let character = position["character"].as_u64().unwrap_or(0) as usize;
+        let Some(&start) = self.line_starts.get(line) else {
+            return self.text.len();
+        };
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.text.len(), |next| next - 1);
+
+        let mut units = 0;
+        for (i, c) in self.text[start..end].char_indices() {
+            if units >= character {
+                return start + i;
+            }
+            units += c.len_utf16();
+        }
+        end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_roundtrip() {
+        let text = "ab\nc😀d\n";
+        let index = LineIndex::new(text);
+        // 'd' is after a surrogate pair: UTF-16 column 3, byte offset 8
+        let pos = index.position(8);
+        assert_eq!(pos, json!({"line": 1, "character": 3}));
+        assert_eq!(index.offset(&pos), 8);
+        assert_eq!(index.position(0), json!({"line": 0, "character": 0}));
+        assert_eq!(index.position(100), json!({"line": 2, "character": 0}));
+    }
+
+    #[test]
+    fn test_offset_clamps_to_line() {
+        let index = LineIndex::new("ab\ncd");
+        assert_eq!(index.offset(&json!({"line": 0, "character": 10})), 2);
+        assert_eq!(index.offset(&json!({"line": 9, "character": 0})), 5);
+    }
+}
@@ -0,0 +1,91 @@
+//! JSON-RPC message framing for LSP over stdio
+//!
+//! Every message is a `Content-Length: N` header block, a blank line, and
+//! N bytes of JSON. Other headers (`Content-Type`) are accepted and ignored.
+
+use std::io::{self, BufRead, Write};
+
+use serde_json::Value;
+
+/// Read one message. Returns `Ok(None)` at end of input.
+///
+/// # Errors
+/// `InvalidData` for a malformed header or body (the stream stays usable),
+/// any other kind for I/O failures.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length: Option<usize> = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            // Stray blank line between messages
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                content_length = Some(value.trim().parse().map_err(|_| {
+                    invalid_data(format!("invalid Content-Length '{}'", value.trim()))
+                })?);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| invalid_data(format!("invalid JSON message: {}", e)))
+}
+
+/// Write one message with its `Content-Length` header
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_roundtrip() {
+        let message = json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"});
+        let mut buf = Vec::new();
+        write_message(&mut buf, &message).unwrap();
+        write_message(&mut buf, &json!({"ok": "é"})).unwrap();
+
+        let mut reader = io::Cursor::new(buf);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(json!({"ok": "é"})));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_extra_headers_ignored() {
+        let raw = "Content-Type: application/vscode-jsonrpc\r\nContent-Length: 2\r\n\r\n{}";
+        let mut reader = io::Cursor::new(raw.as_bytes());
+        assert_eq!(read_message(&mut reader).unwrap(), Some(json!({})));
+    }
+
+    #[test]
+    fn test_invalid_body() {
+        let raw = "Content-Length: 3\r\n\r\n{x}";
+        let mut reader = io::Cursor::new(raw.as_bytes());
+        let err = read_message(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
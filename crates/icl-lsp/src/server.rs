@@ -0,0 +1,252 @@
+//! LSP request dispatch and open-document state
+//!
+//! The server is a pure state machine: [`Server::handle`] takes one
+//! incoming message and returns the messages to send back, so the stdio
+//! loop in `main` stays trivial and the logic is testable in-process.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use crate::analysis;
+use crate::position::LineIndex;
+
+// JSON-RPC error codes
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// What the stdio loop should do after a message
+#[derive(Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// `exit` received: 0 after a `shutdown` request, 1 otherwise
+    Exit(i32),
+}
+
+#[derive(Default)]
+pub struct Server {
+    /// Open documents by URI (full text sync)
+    documents: BTreeMap<String, String>,
+    shutdown_requested: bool,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Exit code to use if the client closes the stream without `exit`
+    pub fn exit_code(&self) -> i32 {
+        if self.shutdown_requested {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Handle one incoming message, returning responses and notifications
+    pub fn handle(&mut self, message: Value) -> (Vec<Value>, Control) {
+        let method = message["method"].as_str().unwrap_or_default().to_string();
+        let params = &message["params"];
+
+        match message.get("id") {
+            // Responses to server-initiated requests are not used
+            Some(_) if method.is_empty() => (Vec::new(), Control::Continue),
+            Some(id) => {
+                let response = match self.request(&method, params) {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err((code, msg)) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": code, "message": msg },
+                    }),
+                };
+                (vec![response], Control::Continue)
+            }
+            None => self.notification(&method, params),
+        }
+    }
+
+    // ── Requests ──────────────────────────────────────────
+
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": {
+                        "openClose": true,
+                        "change": 1,
+                        "save": { "includeText": true },
+                    },
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "completionProvider": { "triggerCharacters": [] },
+                    "documentSymbolProvider": true,
+                    "documentFormattingProvider": true,
+                    "codeActionProvider": true,
+                },
+                "serverInfo": { "name": "icl-lsp", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => {
+                let (uri, text, offset) = self.document_position(params)?;
+                let path = uri_to_path(uri);
+                Ok(match analysis::hover(text, path.as_deref(), offset) {
+                    Some((markdown, span)) => json!({
+                        "contents": { "kind": "markdown", "value": markdown },
+                        "range": LineIndex::new(text).range(&span),
+                    }),
+                    None => Value::Null,
+                })
+            }
+            "textDocument/definition" => {
+                let (uri, text, offset) = self.document_position(params)?;
+                Ok(match analysis::definition(text, offset) {
+                    Some(span) => json!({ "uri": uri, "range": LineIndex::new(text).range(&span) }),
+                    None => Value::Null,
+                })
+            }
+            "textDocument/completion" => {
+                let (_, text, offset) = self.document_position(params)?;
+                Ok(Value::Array(analysis::completions(text, offset)))
+            }
+            "textDocument/documentSymbol" => {
+                let (_, text) = self.document(params)?;
+                Ok(Value::Array(analysis::document_symbols(text)))
+            }
+            "textDocument/formatting" => {
+                let (_, text) = self.document(params)?;
+                Ok(Value::Array(analysis::formatting(text)))
+            }
+            "textDocument/codeAction" => {
+                let (uri, text) = self.document(params)?;
+                let path = uri_to_path(uri);
+                Ok(Value::Array(analysis::code_actions(
+                    text,
+                    path.as_deref(),
+                    uri,
+                    &params["range"],
+                )))
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("method not found: {}", method))),
+        }
+    }
+
+    fn document<'a>(&'a self, params: &Value) -> Result<(&'a str, &'a str), (i64, String)> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        self.documents
+            .get_key_value(uri)
+            .map(|(uri, text)| (uri.as_str(), text.as_str()))
+            .ok_or_else(|| (INVALID_PARAMS, format!("document not open: {}", uri)))
+    }
+
+    fn document_position<'a>(
+        &'a self,
+        params: &Value,
+    ) -> Result<(&'a str, &'a str, usize), (i64, String)> {
+        let (uri, text) = self.document(params)?;
+        let offset = LineIndex::new(text).offset(&params["position"]);
+        Ok((uri, text, offset))
+    }
+
+    // ── Notifications ─────────────────────────────────────
+
+    fn notification(&mut self, method: &str, params: &Value) -> (Vec<Value>, Control) {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let text = match method {
+            "exit" => return (Vec::new(), Control::Exit(self.exit_code())),
+            "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+            // Full sync: the last change holds the whole document
+            "textDocument/didChange" => params["contentChanges"]
+                .as_array()
+                .and_then(|changes| changes.last())
+                .and_then(|change| change["text"].as_str()),
+            "textDocument/didSave" => params["text"].as_str(),
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return (vec![publish(&uri, Vec::new())], Control::Continue);
+            }
+            _ => return (Vec::new(), Control::Continue),
+        };
+
+        if let Some(text) = text {
+            self.documents.insert(uri.clone(), text.to_string());
+        }
+        match self.documents.get(&uri) {
+            Some(text) => (
+                vec![publish(
+                    &uri,
+                    analysis::diagnostics(text, uri_to_path(&uri).as_deref()),
+                )],
+                Control::Continue,
+            ),
+            None => (Vec::new(), Control::Continue),
+        }
+    }
+}
+
+/// Filesystem path of a `file://` URI, used to resolve imports
+fn uri_to_path(uri: &str) -> Option<String> {
+    let path = uri.strip_prefix("file://")?;
+    // `file:///C:/x` names the Windows path `C:/x`
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => &path[1..],
+        _ => path,
+    };
+
+    // Percent-decode (`%20` and friends)
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        let decoded = match tail {
+            [hi, lo, ..] if b == b'%' => std::str::from_utf8(&[*hi, *lo])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match decoded {
+            Some(byte) => {
+                bytes.push(byte);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(b);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn publish(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/me/my%20contracts/a.icl").as_deref(),
+            Some("/home/me/my contracts/a.icl")
+        );
+        assert_eq!(
+            uri_to_path("file:///C:/work/a.icl").as_deref(),
+            Some("C:/work/a.icl")
+        );
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+}
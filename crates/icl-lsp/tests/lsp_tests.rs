@@ -0,0 +1,253 @@
+//! Integration tests for the ICL language server
+//!
+//! These tests drive the actual icl-lsp binary over stdio with a scripted
+//! client session and check the framed responses and exit code.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+
+// ── Helpers ───────────────────────────────────────────────
+
+const URI: &str = "file:///tmp/contract.icl";
+
+fn fixture_valid(name: &str) -> String {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("../../tests/fixtures/conformance/valid/{}", name));
+    std::fs::read_to_string(path).unwrap()
+}
+
+fn frame(message: &Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+fn read_frames(output: &[u8]) -> Vec<Value> {
+    let mut reader = BufReader::new(output);
+    let mut messages = Vec::new();
+    loop {
+        let mut length = None;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap() == 0 {
+                return messages;
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length:") {
+                length = Some(value.trim().parse::<usize>().unwrap());
+            }
+        }
+        let mut body = vec![0; length.unwrap()];
+        reader.read_exact(&mut body).unwrap();
+        messages.push(serde_json::from_slice(&body).unwrap());
+    }
+}
+
+/// Run a full client session and return (server messages, exit code)
+fn run_session(messages: &[Value]) -> (Vec<Value>, Option<i32>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_icl-lsp"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to execute icl-lsp");
+
+    let mut stdin = child.stdin.take().unwrap();
+    for message in messages {
+        stdin.write_all(frame(message).as_bytes()).unwrap();
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output().unwrap();
+    (read_frames(&output.stdout), output.status.code())
+}
+
+fn request(id: i64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn did_open(text: &str) -> Value {
+    notification(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": URI, "languageId": "icl", "version": 1, "text": text } }),
+    )
+}
+
+fn at(line: usize, character: usize) -> Value {
+    json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+}
+
+fn response(messages: &[Value], id: i64) -> &Value {
+    messages
+        .iter()
+        .find(|m| m["id"] == id)
+        .unwrap_or_else(|| panic!("no response for id {}", id))
+}
+
+fn published(messages: &[Value]) -> Vec<&Value> {
+    messages
+        .iter()
+        .filter(|m| m["method"] == "textDocument/publishDiagnostics")
+        .map(|m| &m["params"]["diagnostics"])
+        .collect()
+}
+
+/// Zero-based (line, character) of the first occurrence of `needle`
+fn locate(text: &str, needle: &str) -> (usize, usize) {
+    let offset = text.find(needle).unwrap();
+    let line = text[..offset].matches('\n').count();
+    let column = offset - text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    (line, column)
+}
+
+// ── Session ───────────────────────────────────────────────
+
+#[test]
+fn test_full_session() {
+    let text = fixture_valid("operations-with-parameters.icl");
+    let (field_line, field_col) = locate(&text, "active is true");
+    let (ident_line, _) = locate(&text, "stable_id");
+    let (trigger_line, trigger_col) = locate(&text, "\"api_call\"");
+    let trigger = at(trigger_line, trigger_col + 1)["position"].clone();
+
+    let (messages, code) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        notification("initialized", json!({})),
+        did_open(&text),
+        request(2, "textDocument/hover", at(field_line, field_col + 1)),
+        request(3, "textDocument/definition", at(field_line, field_col + 1)),
+        request(4, "textDocument/completion", at(ident_line, 4)),
+        request(
+            5,
+            "textDocument/documentSymbol",
+            json!({ "textDocument": { "uri": URI } }),
+        ),
+        request(
+            6,
+            "textDocument/formatting",
+            json!({ "textDocument": { "uri": URI } }),
+        ),
+        request(
+            7,
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": URI },
+                "range": { "start": trigger, "end": trigger },
+                "context": { "diagnostics": [] },
+            }),
+        ),
+        request(8, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+    assert_eq!(code, Some(0));
+
+    let capabilities = &response(&messages, 1)["result"]["capabilities"];
+    assert_eq!(capabilities["hoverProvider"], true);
+    assert_eq!(capabilities["documentFormattingProvider"], true);
+    assert_eq!(capabilities["codeActionProvider"], true);
+
+    // Parses cleanly: only verifier warnings (the fixture's `api_call` trigger)
+    let diagnostics = published(&messages);
+    assert_eq!(diagnostics.len(), 1);
+    for diagnostic in diagnostics[0].as_array().unwrap() {
+        assert_eq!(diagnostic["severity"], 2, "{}", diagnostic);
+    }
+
+    let hover = &response(&messages, 2)["result"]["contents"]["value"];
+    assert!(
+        hover.as_str().unwrap().contains("active: Boolean"),
+        "{}",
+        hover
+    );
+
+    let (state_line, _) = locate(&text, "active: Boolean");
+    let definition = &response(&messages, 3)["result"];
+    assert_eq!(definition["uri"], URI);
+    assert_eq!(definition["range"]["start"]["line"], state_line);
+
+    let labels: Vec<&str> = response(&messages, 4)["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect();
+    assert!(labels.contains(&"semantic_hash"), "{:?}", labels);
+
+    let symbols: Vec<&str> = response(&messages, 5)["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| symbol["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(symbols, vec!["update_profile", "batch_update"]);
+
+    assert!(response(&messages, 6)["result"].is_array());
+
+    let actions = response(&messages, 7)["result"].as_array().unwrap();
+    assert_eq!(actions.len(), 1, "{:?}", actions);
+    assert_eq!(actions[0]["title"], "replace 'api_call' with 'event_based'");
+    let edits = actions[0]["edit"]["changes"][URI].as_array().unwrap();
+    assert_eq!(edits[0]["newText"], "\"event_based\"");
+    assert_eq!(edits[0]["range"]["start"]["line"], trigger_line);
+
+    assert_eq!(response(&messages, 8)["result"], Value::Null);
+}
+
+#[test]
+fn test_diagnostics_follow_edits() {
+    let text = fixture_valid("minimal-contract.icl");
+    let broken = text.replace("version: 1", "version: \"one\"");
+
+    let (messages, code) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        did_open(&text),
+        notification(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "text": broken }],
+            }),
+        ),
+        notification(
+            "textDocument/didClose",
+            json!({ "textDocument": { "uri": URI } }),
+        ),
+        request(2, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+    assert_eq!(code, Some(0));
+
+    let diagnostics = published(&messages);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0], &json!([]));
+    let errors = diagnostics[1].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["source"], "icl");
+    assert_eq!(
+        errors[0]["range"]["start"]["line"],
+        locate(&broken, "\"one\"").0
+    );
+    assert_eq!(diagnostics[2], &json!([]));
+}
+
+#[test]
+fn test_errors_and_exit_without_shutdown() {
+    let (messages, code) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        request(2, "textDocument/hover", at(0, 0)),
+        request(3, "workspace/unknown", json!({})),
+        notification("exit", Value::Null),
+    ]);
+    assert_eq!(code, Some(1));
+    assert_eq!(response(&messages, 2)["error"]["code"], -32602);
+    assert_eq!(response(&messages, 3)["error"]["code"], -32601);
+}